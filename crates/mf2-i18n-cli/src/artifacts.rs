@@ -6,6 +6,7 @@ use std::path::Path;
 use crate::catalog::Catalog;
 use crate::error::CliError;
 use crate::id_map::IdMap;
+use mf2_i18n_core::MessageId;
 
 pub fn write_catalog(path: &Path, catalog: &Catalog) -> Result<(), CliError> {
     let file = fs::File::create(path)?;
@@ -14,15 +15,40 @@ pub fn write_catalog(path: &Path, catalog: &Catalog) -> Result<(), CliError> {
 }
 
 pub fn write_id_map(path: &Path, id_map: &IdMap) -> Result<(), CliError> {
-    let mut entries: BTreeMap<String, u32> = BTreeMap::new();
+    let mut entries: BTreeMap<String, u64> = BTreeMap::new();
     for (key, id) in id_map.entries() {
-        entries.insert(key.to_string(), u32::from(id));
+        entries.insert(key.to_string(), u64::from(id));
     }
     let file = fs::File::create(path)?;
     serde_json::to_writer_pretty(file, &entries)?;
     Ok(())
 }
 
+pub fn write_id_map_perturbations(path: &Path, id_map: &IdMap) -> Result<(), CliError> {
+    let perturbations: BTreeMap<&str, u32> = id_map.perturbations().collect();
+    let file = fs::File::create(path)?;
+    serde_json::to_writer_pretty(file, &perturbations)?;
+    Ok(())
+}
+
+pub fn read_id_map(path: &Path, perturbations_path: &Path) -> Result<IdMap, CliError> {
+    let mut id_map = IdMap::new();
+    if !path.exists() {
+        return Ok(id_map);
+    }
+    let entries: BTreeMap<String, u64> = serde_json::from_str(&fs::read_to_string(path)?)?;
+    let perturbations: BTreeMap<String, u32> = if perturbations_path.exists() {
+        serde_json::from_str(&fs::read_to_string(perturbations_path)?)?
+    } else {
+        BTreeMap::new()
+    };
+    for (key, id) in entries {
+        let perturbation = perturbations.get(&key).copied().unwrap_or(0);
+        id_map.insert_perturbed(key, MessageId::new(id), perturbation)?;
+    }
+    Ok(id_map)
+}
+
 pub fn write_id_map_hash(path: &Path, hash: [u8; 32]) -> Result<(), CliError> {
     let mut file = fs::File::create(path)?;
     writeln!(file, "sha256:{}", hex_encode(hash))?;
@@ -41,7 +67,7 @@ fn hex_encode(bytes: [u8; 32]) -> String {
 mod tests {
     use super::{write_catalog, write_id_map, write_id_map_hash};
     use crate::catalog::{Catalog, CatalogFeatures, CatalogMessage};
-    use crate::id_map::{build_id_map, derive_message_id};
+    use crate::id_map::{IdAssignment, build_id_map, derive_message_id};
     use crate::model::{ArgSpec, ArgType};
     use std::fs;
     use std::path::PathBuf;
@@ -86,19 +112,20 @@ mod tests {
     #[test]
     fn writes_id_map_and_hash() {
         let salt = b"project-salt";
-        let map = build_id_map(vec!["home.title".to_string()], salt).expect("map");
+        let map =
+            build_id_map(vec!["home.title".to_string()], &IdAssignment::new(salt)).expect("map");
         let hash = map.hash().expect("hash");
         let id_path = temp_path("id_map");
         let hash_path = temp_path("id_map_hash");
         write_id_map(&id_path, &map).expect("write id map");
         write_id_map_hash(&hash_path, hash).expect("write hash");
         let contents = fs::read_to_string(&hash_path).expect("read");
-        let expected = derive_message_id("home.title", salt);
+        let expected = derive_message_id("home.title", salt, 0);
         assert!(contents.starts_with("sha256:"));
         assert!(
             fs::read_to_string(&id_path)
                 .unwrap()
-                .contains(&u32::from(expected).to_string())
+                .contains(&u64::from(expected).to_string())
         );
         fs::remove_file(&id_path).ok();
         fs::remove_file(&hash_path).ok();
@@ -0,0 +1,74 @@
+use mf2_i18n_core::{CoreResult, FormatBackend, FormatterOption, PluralCategory};
+
+const SMOKE_CATEGORIES: [PluralCategory; 6] = [
+    PluralCategory::Zero,
+    PluralCategory::One,
+    PluralCategory::Two,
+    PluralCategory::Few,
+    PluralCategory::Many,
+    PluralCategory::Other,
+];
+
+pub(crate) fn smoke_plural_value(category: PluralCategory) -> f64 {
+    let index = SMOKE_CATEGORIES
+        .iter()
+        .position(|candidate| *candidate == category)
+        .unwrap_or(SMOKE_CATEGORIES.len() - 1);
+    index as f64 + 0.5
+}
+
+fn smoke_category(value: f64) -> PluralCategory {
+    if value.fract() != 0.5 || value < 0.0 {
+        return PluralCategory::Other;
+    }
+    SMOKE_CATEGORIES
+        .get(value as usize)
+        .copied()
+        .unwrap_or(PluralCategory::Other)
+}
+
+pub(crate) struct SmokeBackend;
+
+impl FormatBackend for SmokeBackend {
+    fn plural_category(&self, value: f64) -> CoreResult<PluralCategory> {
+        Ok(smoke_category(value))
+    }
+
+    fn ordinal_category(&self, value: f64) -> CoreResult<PluralCategory> {
+        Ok(smoke_category(value))
+    }
+
+    fn format_number(&self, value: f64, _options: &[FormatterOption]) -> CoreResult<String> {
+        Ok(value.to_string())
+    }
+
+    fn format_date(&self, value: i64, _options: &[FormatterOption]) -> CoreResult<String> {
+        Ok(value.to_string())
+    }
+
+    fn format_time(&self, value: i64, _options: &[FormatterOption]) -> CoreResult<String> {
+        Ok(value.to_string())
+    }
+
+    fn format_datetime(&self, value: i64, _options: &[FormatterOption]) -> CoreResult<String> {
+        Ok(value.to_string())
+    }
+
+    fn format_unit(
+        &self,
+        value: f64,
+        _unit_id: u32,
+        _options: &[FormatterOption],
+    ) -> CoreResult<String> {
+        Ok(value.to_string())
+    }
+
+    fn format_currency(
+        &self,
+        value: f64,
+        _code: [u8; 3],
+        _options: &[FormatterOption],
+    ) -> CoreResult<String> {
+        Ok(value.to_string())
+    }
+}
@@ -14,7 +14,7 @@ pub struct Catalog {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CatalogMessage {
     pub key: String,
-    pub id: u32,
+    pub id: u64,
     pub args: Vec<ArgSpec>,
     pub features: CatalogFeatures,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -29,7 +29,7 @@ pub struct CatalogFeatures {
     pub formatters: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct SourceRef {
     pub file: String,
     pub line: u32,
@@ -1,8 +1,10 @@
+use std::collections::BTreeMap;
+
 use thiserror::Error;
 
-use crate::catalog::{Catalog, CatalogFeatures, CatalogMessage};
+use crate::catalog::{Catalog, CatalogFeatures, CatalogMessage, SourceRef};
 use crate::extract::ExtractedMessage;
-use crate::id_map::{IdMap, IdMapError, build_id_map};
+use crate::id_map::{IdAssignment, IdMap, IdMapError, build_id_map};
 
 #[derive(Debug, Error)]
 pub enum CatalogBuildError {
@@ -24,23 +26,27 @@ pub fn build_catalog(
     project: &str,
     default_locale: &str,
     generated_at: &str,
-    salt: &[u8],
+    ids: &IdAssignment,
 ) -> Result<BuildOutput, CatalogBuildError> {
-    let keys: Vec<String> = messages.iter().map(|message| message.key.clone()).collect();
-    let id_map = build_id_map(keys, salt)?;
+    let mut by_key: BTreeMap<&str, Vec<&ExtractedMessage>> = BTreeMap::new();
+    for message in messages {
+        by_key.entry(&message.key).or_default().push(message);
+    }
+    let keys: Vec<String> = by_key.keys().map(|key| key.to_string()).collect();
+    let id_map = build_id_map(keys, ids)?;
     let id_map_hash = id_map.hash()?;
 
-    let mut catalog_messages = Vec::with_capacity(messages.len());
-    for message in messages {
+    let mut catalog_messages = Vec::with_capacity(by_key.len());
+    for (key, occurrences) in by_key {
         let id = id_map
-            .get(&message.key)
-            .ok_or_else(|| CatalogBuildError::MissingKey(message.key.clone()))?;
+            .get(key)
+            .ok_or_else(|| CatalogBuildError::MissingKey(key.to_string()))?;
         catalog_messages.push(CatalogMessage {
-            key: message.key.clone(),
-            id: u32::from(id),
-            args: message.args.clone(),
+            key: key.to_string(),
+            id: u64::from(id),
+            args: occurrences[0].args.clone(),
             features: CatalogFeatures::default(),
-            source_refs: None,
+            source_refs: source_refs(&occurrences),
         });
     }
 
@@ -59,10 +65,27 @@ pub fn build_catalog(
     })
 }
 
+fn source_refs(occurrences: &[&ExtractedMessage]) -> Option<Vec<SourceRef>> {
+    let mut refs: Vec<SourceRef> = occurrences
+        .iter()
+        .filter_map(|message| {
+            Some(SourceRef {
+                file: message.file.clone()?,
+                line: message.span.line,
+                column: message.span.column,
+            })
+        })
+        .collect();
+    refs.sort();
+    refs.dedup();
+    (!refs.is_empty()).then_some(refs)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::extract::ExtractedMessage;
-    use crate::id_map::derive_message_id;
+    use crate::id_map::{IdAssignment, derive_message_id};
+    use crate::lexer::Span;
     use crate::model::{ArgSpec, ArgType};
 
     use super::build_catalog;
@@ -76,12 +99,25 @@ mod tests {
                 arg_type: ArgType::String,
                 required: true,
             }],
+            file: None,
+            span: Span {
+                start: 0,
+                end: 0,
+                line: 1,
+                column: 1,
+            },
         }];
         let salt = b"project-salt";
-        let output =
-            build_catalog(&messages, "demo", "en", "2026-02-01T00:00:00Z", salt).expect("build");
+        let output = build_catalog(
+            &messages,
+            "demo",
+            "en",
+            "2026-02-01T00:00:00Z",
+            &IdAssignment::new(salt),
+        )
+        .expect("build");
 
-        let expected = derive_message_id("home.title", salt);
-        assert_eq!(output.catalog.messages[0].id, u32::from(expected));
+        let expected = derive_message_id("home.title", salt, 0);
+        assert_eq!(output.catalog.messages[0].id, u64::from(expected));
     }
 }
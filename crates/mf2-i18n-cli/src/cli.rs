@@ -1,13 +1,29 @@
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use thiserror::Error;
 
-use crate::command_build::{BuildCommandError, BuildOptions, run_build};
-use crate::command_coverage::{CoverageCommandError, CoverageOptions, run_coverage};
+use crate::command_build::{BuildCommandError, BuildOptions, PackEncoding, run_build};
+use crate::command_check::{CheckCommandError, CheckOptions, run_check};
+use crate::command_coverage::{
+    CoverageCommandError, CoverageFormat, CoverageOptions, run_coverage,
+};
+use crate::command_diff::{DiffCommandError, DiffOptions, render_diff, run_diff};
+use crate::command_emit_id_map::{EmitIdMapCommandError, EmitIdMapOptions, run_emit_id_map};
+use crate::command_export::{ExportCommandError, ExportFormat, ExportOptions, run_export};
 use crate::command_extract::{ExtractCommandError, ExtractOptions, run_extract};
-use crate::command_pseudo::{PseudoCommandError, PseudoOptions, run_pseudo};
+use crate::command_fmt::{FmtCommandError, FmtOptions, run_fmt};
+use crate::command_import_ftl::{ImportFtlCommandError, ImportFtlOptions, run_import_ftl};
+use crate::command_import_po::{ImportPoCommandError, ImportPoOptions, run_import_po};
+use crate::command_lint::{LintCommandError, LintOptions, run_lint};
+use crate::command_pseudo::{PseudoCommandError, PseudoOptions, PseudoStrategy, run_pseudo};
 use crate::command_sign::{SignCommandError, SignOptions, run_sign};
+use crate::command_stats::{StatsCommandError, StatsOptions, run_stats};
 use crate::command_validate::{ValidateCommandError, ValidateOptions, run_validate};
+use crate::command_verify::{VerifyCommandError, VerifyOptions, run_verify};
+use crate::command_watch::{WatchCommandError, WatchOptions, run_watch};
+use crate::diagnostic::{Diagnostic, DiagnosticFormat};
 
 #[derive(Debug, Error)]
 pub enum CliAppError {
@@ -25,6 +41,28 @@ pub enum CliAppError {
     Pseudo(#[from] PseudoCommandError),
     #[error(transparent)]
     Coverage(#[from] CoverageCommandError),
+    #[error(transparent)]
+    ImportPo(#[from] ImportPoCommandError),
+    #[error(transparent)]
+    ImportFtl(#[from] ImportFtlCommandError),
+    #[error(transparent)]
+    Export(#[from] ExportCommandError),
+    #[error(transparent)]
+    Diff(#[from] DiffCommandError),
+    #[error(transparent)]
+    Stats(#[from] StatsCommandError),
+    #[error(transparent)]
+    Watch(#[from] WatchCommandError),
+    #[error(transparent)]
+    EmitIdMap(#[from] EmitIdMapCommandError),
+    #[error(transparent)]
+    Check(#[from] CheckCommandError),
+    #[error(transparent)]
+    Fmt(#[from] FmtCommandError),
+    #[error(transparent)]
+    Lint(#[from] LintCommandError),
+    #[error(transparent)]
+    Verify(#[from] VerifyCommandError),
 }
 
 pub fn run() -> Result<(), CliAppError> {
@@ -33,19 +71,31 @@ pub fn run() -> Result<(), CliAppError> {
     match command.as_str() {
         "extract" => {
             let options = parse_extract_options(args.collect())?;
-            run_extract(&options)?;
+            for diagnostic in run_extract(&options)? {
+                eprintln!("{diagnostic}");
+            }
             Ok(())
         }
         "validate" => {
             let options = parse_validate_options(args.collect())?;
             match run_validate(&options) {
-                Ok(_) => Ok(()),
+                Ok(diagnostics) => {
+                    print_diagnostics(&diagnostics, options.format);
+                    Ok(())
+                }
+                Err(ValidateCommandError::Failed(diagnostics)) => {
+                    print_diagnostics(&diagnostics, options.format);
+                    Err(ValidateCommandError::Failed(diagnostics).into())
+                }
                 Err(err) => Err(err.into()),
             }
         }
         "build" => {
-            let options = parse_build_options(args.collect())?;
-            run_build(&options)?;
+            let options =
+                parse_build_options(args.collect(), std::env::var("SOURCE_DATE_EPOCH").ok())?;
+            for diagnostic in run_build(&options)? {
+                eprintln!("{diagnostic}");
+            }
             Ok(())
         }
         "sign" => {
@@ -53,16 +103,109 @@ pub fn run() -> Result<(), CliAppError> {
             run_sign(&options)?;
             Ok(())
         }
+        "verify" => {
+            let options = parse_verify_options(args.collect())?;
+            run_verify(&options)?;
+            println!("OK");
+            Ok(())
+        }
         "pseudo" => {
             let options = parse_pseudo_options(args.collect())?;
-            run_pseudo(&options)?;
+            for diagnostic in run_pseudo(&options)? {
+                eprintln!("{diagnostic}");
+            }
             Ok(())
         }
         "coverage" => {
             let options = parse_coverage_options(args.collect())?;
-            run_coverage(&options)?;
+            for diagnostic in run_coverage(&options)? {
+                eprintln!("{diagnostic}");
+            }
+            Ok(())
+        }
+        "import-po" => {
+            let options = parse_import_po_options(args.collect())?;
+            run_import_po(&options)?;
+            Ok(())
+        }
+        "import-ftl" => {
+            let options = parse_import_ftl_options(args.collect())?;
+            for diagnostic in run_import_ftl(&options)? {
+                eprintln!("{diagnostic}");
+            }
             Ok(())
         }
+        "export" => {
+            let options = parse_export_options(args.collect())?;
+            for diagnostic in run_export(&options)? {
+                eprintln!("{diagnostic}");
+            }
+            Ok(())
+        }
+        "diff" => {
+            let options = parse_diff_options(args.collect())?;
+            let diff = run_diff(&options)?;
+            print!("{}", render_diff(&diff));
+            Ok(())
+        }
+        "stats" => {
+            let options = parse_stats_options(args.collect())?;
+            run_stats(&options)?;
+            Ok(())
+        }
+        "check" => {
+            let options = parse_check_options(args.collect())?;
+            run_check(&options)?;
+            Ok(())
+        }
+        "watch" => {
+            let options = parse_watch_options(args.collect())?;
+            run_watch(&options)?;
+            Ok(())
+        }
+        "emit-id-map" => {
+            let options = parse_emit_id_map_options(args.collect())?;
+            run_emit_id_map(&options)?;
+            Ok(())
+        }
+        "fmt" => {
+            let options = parse_fmt_options(args.collect())?;
+            for path in run_fmt(&options)? {
+                eprintln!("formatted {}", path.display());
+            }
+            Ok(())
+        }
+        "lint" => {
+            let options = parse_lint_options(args.collect())?;
+            match run_lint(&options) {
+                Ok(diagnostics) => {
+                    print_diagnostics(&diagnostics, options.format);
+                    Ok(())
+                }
+                Err(LintCommandError::Failed(diagnostics)) => {
+                    print_diagnostics(&diagnostics, options.format);
+                    Err(LintCommandError::Failed(diagnostics).into())
+                }
+                Err(err) => Err(err.into()),
+            }
+        }
+        _ => Err(CliAppError::Usage(usage())),
+    }
+}
+
+fn print_diagnostics(diagnostics: &[Diagnostic], format: DiagnosticFormat) {
+    for diagnostic in diagnostics {
+        match format {
+            DiagnosticFormat::Text => eprintln!("{diagnostic}"),
+            DiagnosticFormat::Json => println!("{}", diagnostic.to_json()),
+        }
+    }
+}
+
+fn parse_diagnostic_format(value: &str) -> Result<DiagnosticFormat, CliAppError> {
+    match value {
+        "text" => Ok(DiagnosticFormat::Text),
+        "json" => Ok(DiagnosticFormat::Json),
         _ => Err(CliAppError::Usage(usage())),
     }
 }
@@ -101,19 +244,42 @@ fn parse_extract_options(args: Vec<String>) -> Result<ExtractOptions, CliAppErro
     })
 }
 
+fn parse_watch_options(args: Vec<String>) -> Result<WatchOptions, CliAppError> {
+    let mut debounce = Duration::from_millis(300);
+    let mut extract_args = Vec::new();
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--debounce-ms" {
+            let value = next_value("--debounce-ms", &mut iter)?;
+            let millis = value.parse::<u64>().map_err(|_| {
+                CliAppError::Usage(format!("invalid debounce {value}\n\n{}", usage()))
+            })?;
+            debounce = Duration::from_millis(millis);
+        } else {
+            extract_args.push(arg);
+        }
+    }
+    Ok(WatchOptions {
+        extract: parse_extract_options(extract_args)?,
+        debounce,
+    })
+}
+
 fn next_value(flag: &str, iter: &mut impl Iterator<Item = String>) -> Result<String, CliAppError> {
     iter.next()
         .ok_or_else(|| CliAppError::Usage(format!("{flag} requires a value\n\n{}", usage())))
 }
 
 fn usage() -> String {
-    "usage: mf2-i18n-cli extract --project <id> --root <path> [--root <path>...] --generated-at <rfc3339> [--out <dir>] [--config <path>]\n       mf2-i18n-cli validate --catalog <path> --id-map-hash <path> [--config <path>]\n       mf2-i18n-cli build --catalog <path> --id-map-hash <path> --release-id <id> --generated-at <rfc3339> [--out <dir>] [--config <path>]\n       mf2-i18n-cli sign --manifest <path> --key <path> --key-id <id> [--out <path>]\n       mf2-i18n-cli pseudo --locale <tag> --target <tag> [--out <dir>] [--config <path>]\n       mf2-i18n-cli coverage --catalog <path> --id-map-hash <path> [--out <path>] [--config <path>]".to_string()
+    "usage: mf2-i18n-cli extract --project <id> --root <path> [--root <path>...] --generated-at <rfc3339> [--out <dir>] [--config <path>]\n       mf2-i18n-cli watch --project <id> --root <path> [--root <path>...] --generated-at <rfc3339> [--out <dir>] [--config <path>] [--debounce-ms <ms>]\n       mf2-i18n-cli validate --catalog <path> --id-map-hash <path> [--config <path>] [--execute] [--format <text|json>]\n       mf2-i18n-cli build --catalog <path> --id-map-hash <path> --release-id <id> --generated-at <rfc3339> [--out <dir>] [--config <path>] [--content-encoding <identity|br|gzip>] [--max-pack-bytes <n>] [--max-pack-bytes-locale <tag>=<n>...] [--source-date-epoch <secs>]\n       mf2-i18n-cli sign --manifest <path> --key <path> --key-id <id> [--out <path>] [--append] [--sig-alg <ed25519|ecdsa-p256>]\n       mf2-i18n-cli verify --manifest <path> --pubkey <path> --key-id <id>\n       mf2-i18n-cli pseudo --locale <tag> --target <tag> [--out <dir>] [--config <path>] [--strategy <bracket|accent|expand>] [--expand-percent <n>]\n       mf2-i18n-cli coverage --catalog <path> --id-map-hash <path> [--out <path>] [--config <path>] [--format <json|html|markdown>] [--min-percent <f64>] [--min-percent-locale <tag>=<f64>...]\n       mf2-i18n-cli import-po --po <file> --locale <tag> [--out <dir>]\n       mf2-i18n-cli import-ftl --ftl <file> --locale <tag> [--out <dir>]\n       mf2-i18n-cli export --catalog <path> --id-map-hash <path> --locale <tag> --format <json|csv|xliff> [--out <path>] [--config <path>]\n       mf2-i18n-cli diff --old <catalog> --new <catalog> [--old-id-map-hash <path>] [--new-id-map-hash <path>] [--out <path>]\n       mf2-i18n-cli stats --manifest <path> [--out <path>]\n       mf2-i18n-cli check --manifest <path> --id-map <path> [--out <path>] [--trusted-key <id>=<path>...]\n       mf2-i18n-cli emit-id-map --id-map <path> [--out <path>]\n       mf2-i18n-cli fmt [--check] <path>...\n       mf2-i18n-cli lint [--config <path>] [--deny warnings] [--format <text|json>]".to_string()
 }
 
 fn parse_validate_options(args: Vec<String>) -> Result<ValidateOptions, CliAppError> {
     let mut catalog_path = None;
     let mut id_map_hash_path = None;
     let mut config_path = PathBuf::from("mf2-i18n.toml");
+    let mut execute = false;
+    let mut format = DiagnosticFormat::Text;
     let mut iter = args.into_iter();
     while let Some(arg) = iter.next() {
         match arg.as_str() {
@@ -122,6 +288,8 @@ fn parse_validate_options(args: Vec<String>) -> Result<ValidateOptions, CliAppEr
                 id_map_hash_path = Some(PathBuf::from(next_value("--id-map-hash", &mut iter)?))
             }
             "--config" => config_path = PathBuf::from(next_value("--config", &mut iter)?),
+            "--execute" => execute = true,
+            "--format" => format = parse_diagnostic_format(&next_value("--format", &mut iter)?)?,
             "--help" | "-h" => return Err(CliAppError::Usage(usage())),
             _ => return Err(CliAppError::Usage(usage())),
         }
@@ -132,16 +300,27 @@ fn parse_validate_options(args: Vec<String>) -> Result<ValidateOptions, CliAppEr
         catalog_path,
         id_map_hash_path,
         config_path,
+        execute,
+        format,
     })
 }
 
-fn parse_build_options(args: Vec<String>) -> Result<BuildOptions, CliAppError> {
+fn parse_build_options(
+    args: Vec<String>,
+    env_source_date_epoch: Option<String>,
+) -> Result<BuildOptions, CliAppError> {
     let mut catalog_path = None;
     let mut id_map_hash_path = None;
     let mut release_id = None;
     let mut generated_at = None;
     let mut out_dir = PathBuf::from("i18n-build");
     let mut config_path = PathBuf::from("mf2-i18n.toml");
+    let mut content_encoding = PackEncoding::Identity;
+    let mut max_pack_bytes = None;
+    let mut locale_max_pack_bytes = BTreeMap::new();
+    let mut source_date_epoch = env_source_date_epoch
+        .map(|value| parse_epoch(&value))
+        .transpose()?;
     let mut iter = args.into_iter();
     while let Some(arg) = iter.next() {
         match arg.as_str() {
@@ -153,6 +332,30 @@ fn parse_build_options(args: Vec<String>) -> Result<BuildOptions, CliAppError> {
             "--generated-at" => generated_at = Some(next_value("--generated-at", &mut iter)?),
             "--out" => out_dir = PathBuf::from(next_value("--out", &mut iter)?),
             "--config" => config_path = PathBuf::from(next_value("--config", &mut iter)?),
+            "--content-encoding" => {
+                let value = next_value("--content-encoding", &mut iter)?;
+                content_encoding = PackEncoding::parse(&value).ok_or_else(|| {
+                    CliAppError::Usage(format!("unknown content encoding {value}\n\n{}", usage()))
+                })?;
+            }
+            "--max-pack-bytes" => {
+                let value = next_value("--max-pack-bytes", &mut iter)?;
+                max_pack_bytes = Some(parse_bytes(&value)?);
+            }
+            "--max-pack-bytes-locale" => {
+                let value = next_value("--max-pack-bytes-locale", &mut iter)?;
+                let (locale, bytes) = value.split_once('=').ok_or_else(|| {
+                    CliAppError::Usage(format!(
+                        "--max-pack-bytes-locale expects <tag>=<bytes>\n\n{}",
+                        usage()
+                    ))
+                })?;
+                locale_max_pack_bytes.insert(locale.to_string(), parse_bytes(bytes)?);
+            }
+            "--source-date-epoch" => {
+                let value = next_value("--source-date-epoch", &mut iter)?;
+                source_date_epoch = Some(parse_epoch(&value)?);
+            }
             "--help" | "-h" => return Err(CliAppError::Usage(usage())),
             _ => return Err(CliAppError::Usage(usage())),
         }
@@ -168,6 +371,22 @@ fn parse_build_options(args: Vec<String>) -> Result<BuildOptions, CliAppError> {
         out_dir,
         release_id,
         generated_at,
+        content_encoding,
+        max_pack_bytes,
+        locale_max_pack_bytes,
+        source_date_epoch,
+    })
+}
+
+fn parse_bytes(value: &str) -> Result<u64, CliAppError> {
+    value
+        .parse::<u64>()
+        .map_err(|_| CliAppError::Usage(format!("invalid byte count {value}\n\n{}", usage())))
+}
+
+fn parse_epoch(value: &str) -> Result<u64, CliAppError> {
+    value.parse::<u64>().map_err(|_| {
+        CliAppError::Usage(format!("invalid source date epoch {value}\n\n{}", usage()))
     })
 }
 
@@ -176,6 +395,8 @@ fn parse_sign_options(args: Vec<String>) -> Result<SignOptions, CliAppError> {
     let mut key_path = None;
     let mut key_id = None;
     let mut out_path = None;
+    let mut append = false;
+    let mut sig_alg = None;
     let mut iter = args.into_iter();
     while let Some(arg) = iter.next() {
         match arg.as_str() {
@@ -185,6 +406,8 @@ fn parse_sign_options(args: Vec<String>) -> Result<SignOptions, CliAppError> {
             "--key" => key_path = Some(PathBuf::from(next_value("--key", &mut iter)?)),
             "--key-id" => key_id = Some(next_value("--key-id", &mut iter)?),
             "--out" => out_path = Some(PathBuf::from(next_value("--out", &mut iter)?)),
+            "--append" => append = true,
+            "--sig-alg" => sig_alg = Some(next_value("--sig-alg", &mut iter)?),
             "--help" | "-h" => return Err(CliAppError::Usage(usage())),
             _ => return Err(CliAppError::Usage(usage())),
         }
@@ -197,6 +420,34 @@ fn parse_sign_options(args: Vec<String>) -> Result<SignOptions, CliAppError> {
         key_path,
         key_id,
         out_path,
+        append,
+        sig_alg,
+    })
+}
+
+fn parse_verify_options(args: Vec<String>) -> Result<VerifyOptions, CliAppError> {
+    let mut manifest_path = None;
+    let mut pubkey_path = None;
+    let mut key_id = None;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--manifest" => {
+                manifest_path = Some(PathBuf::from(next_value("--manifest", &mut iter)?))
+            }
+            "--pubkey" => pubkey_path = Some(PathBuf::from(next_value("--pubkey", &mut iter)?)),
+            "--key-id" => key_id = Some(next_value("--key-id", &mut iter)?),
+            "--help" | "-h" => return Err(CliAppError::Usage(usage())),
+            _ => return Err(CliAppError::Usage(usage())),
+        }
+    }
+    let manifest_path = manifest_path.ok_or_else(|| CliAppError::Usage(usage()))?;
+    let pubkey_path = pubkey_path.ok_or_else(|| CliAppError::Usage(usage()))?;
+    let key_id = key_id.ok_or_else(|| CliAppError::Usage(usage()))?;
+    Ok(VerifyOptions {
+        manifest_path,
+        pubkey_path,
+        key_id,
     })
 }
 
@@ -205,6 +456,8 @@ fn parse_pseudo_options(args: Vec<String>) -> Result<PseudoOptions, CliAppError>
     let mut target = None;
     let mut out_dir = PathBuf::from("locales");
     let mut config_path = PathBuf::from("mf2-i18n.toml");
+    let mut strategy = PseudoStrategy::Bracket;
+    let mut expand_percent = None;
     let mut iter = args.into_iter();
     while let Some(arg) = iter.next() {
         match arg.as_str() {
@@ -212,25 +465,44 @@ fn parse_pseudo_options(args: Vec<String>) -> Result<PseudoOptions, CliAppError>
             "--target" => target = Some(next_value("--target", &mut iter)?),
             "--out" => out_dir = PathBuf::from(next_value("--out", &mut iter)?),
             "--config" => config_path = PathBuf::from(next_value("--config", &mut iter)?),
+            "--strategy" => {
+                let value = next_value("--strategy", &mut iter)?;
+                strategy = PseudoStrategy::parse(&value).ok_or_else(|| {
+                    CliAppError::Usage(format!("unknown pseudo strategy {value}\n\n{}", usage()))
+                })?;
+            }
+            "--expand-percent" => {
+                let value = next_value("--expand-percent", &mut iter)?;
+                expand_percent = Some(value.parse::<u32>().map_err(|_| {
+                    CliAppError::Usage(format!("invalid percent {value}\n\n{}", usage()))
+                })?);
+            }
             "--help" | "-h" => return Err(CliAppError::Usage(usage())),
             _ => return Err(CliAppError::Usage(usage())),
         }
     }
     let locale = locale.ok_or_else(|| CliAppError::Usage(usage()))?;
     let target = target.unwrap_or_else(|| "en-xa".to_string());
+    if let (PseudoStrategy::Expand { percent }, Some(value)) = (&mut strategy, expand_percent) {
+        *percent = value;
+    }
     Ok(PseudoOptions {
         locale,
         target,
         out_dir,
         config_path,
+        strategy,
     })
 }
 
 fn parse_coverage_options(args: Vec<String>) -> Result<CoverageOptions, CliAppError> {
     let mut catalog_path = None;
     let mut id_map_hash_path = None;
-    let mut out_path = PathBuf::from("coverage.json");
+    let mut out_path = None;
+    let mut format = CoverageFormat::Json;
     let mut config_path = PathBuf::from("mf2-i18n.toml");
+    let mut min_percent = None;
+    let mut locale_min_percent = BTreeMap::new();
     let mut iter = args.into_iter();
     while let Some(arg) = iter.next() {
         match arg.as_str() {
@@ -238,28 +510,336 @@ fn parse_coverage_options(args: Vec<String>) -> Result<CoverageOptions, CliAppEr
             "--id-map-hash" => {
                 id_map_hash_path = Some(PathBuf::from(next_value("--id-map-hash", &mut iter)?))
             }
-            "--out" => out_path = PathBuf::from(next_value("--out", &mut iter)?),
+            "--out" => out_path = Some(PathBuf::from(next_value("--out", &mut iter)?)),
+            "--format" => {
+                let value = next_value("--format", &mut iter)?;
+                format = CoverageFormat::parse(&value).ok_or_else(|| {
+                    CliAppError::Usage(format!("unknown coverage format {value}\n\n{}", usage()))
+                })?;
+            }
             "--config" => config_path = PathBuf::from(next_value("--config", &mut iter)?),
+            "--min-percent" => {
+                let value = next_value("--min-percent", &mut iter)?;
+                min_percent = Some(parse_percent(&value)?);
+            }
+            "--min-percent-locale" => {
+                let value = next_value("--min-percent-locale", &mut iter)?;
+                let (locale, percent) = value.split_once('=').ok_or_else(|| {
+                    CliAppError::Usage(format!(
+                        "--min-percent-locale expects <tag>=<percent>\n\n{}",
+                        usage()
+                    ))
+                })?;
+                locale_min_percent.insert(locale.to_string(), parse_percent(percent)?);
+            }
             "--help" | "-h" => return Err(CliAppError::Usage(usage())),
             _ => return Err(CliAppError::Usage(usage())),
         }
     }
     let catalog_path = catalog_path.ok_or_else(|| CliAppError::Usage(usage()))?;
     let id_map_hash_path = id_map_hash_path.ok_or_else(|| CliAppError::Usage(usage()))?;
+    let out_path =
+        out_path.unwrap_or_else(|| PathBuf::from(format!("coverage.{}", format.extension())));
     Ok(CoverageOptions {
         catalog_path,
         id_map_hash_path,
         out_path,
         config_path,
+        format,
+        min_percent,
+        locale_min_percent,
     })
 }
 
+fn parse_percent(value: &str) -> Result<f64, CliAppError> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|percent| (0.0..=100.0).contains(percent))
+        .ok_or_else(|| CliAppError::Usage(format!("invalid percent {value}\n\n{}", usage())))
+}
+
+fn parse_import_po_options(args: Vec<String>) -> Result<ImportPoOptions, CliAppError> {
+    let mut po_path = None;
+    let mut locale = None;
+    let mut out_dir = PathBuf::from("locales");
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--po" => po_path = Some(PathBuf::from(next_value("--po", &mut iter)?)),
+            "--locale" => locale = Some(next_value("--locale", &mut iter)?),
+            "--out" => out_dir = PathBuf::from(next_value("--out", &mut iter)?),
+            "--help" | "-h" => return Err(CliAppError::Usage(usage())),
+            _ => return Err(CliAppError::Usage(usage())),
+        }
+    }
+    let po_path = po_path.ok_or_else(|| CliAppError::Usage(usage()))?;
+    let locale = locale.ok_or_else(|| CliAppError::Usage(usage()))?;
+    Ok(ImportPoOptions {
+        po_path,
+        locale,
+        out_dir,
+    })
+}
+
+fn parse_import_ftl_options(args: Vec<String>) -> Result<ImportFtlOptions, CliAppError> {
+    let mut ftl_path = None;
+    let mut locale = None;
+    let mut out_dir = PathBuf::from("locales");
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--ftl" => ftl_path = Some(PathBuf::from(next_value("--ftl", &mut iter)?)),
+            "--locale" => locale = Some(next_value("--locale", &mut iter)?),
+            "--out" => out_dir = PathBuf::from(next_value("--out", &mut iter)?),
+            "--help" | "-h" => return Err(CliAppError::Usage(usage())),
+            _ => return Err(CliAppError::Usage(usage())),
+        }
+    }
+    let ftl_path = ftl_path.ok_or_else(|| CliAppError::Usage(usage()))?;
+    let locale = locale.ok_or_else(|| CliAppError::Usage(usage()))?;
+    Ok(ImportFtlOptions {
+        ftl_path,
+        locale,
+        out_dir,
+    })
+}
+
+fn parse_export_options(args: Vec<String>) -> Result<ExportOptions, CliAppError> {
+    let mut catalog_path = None;
+    let mut id_map_hash_path = None;
+    let mut locale = None;
+    let mut format = None;
+    let mut out_path = None;
+    let mut config_path = PathBuf::from("mf2-i18n.toml");
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--catalog" => catalog_path = Some(PathBuf::from(next_value("--catalog", &mut iter)?)),
+            "--id-map-hash" => {
+                id_map_hash_path = Some(PathBuf::from(next_value("--id-map-hash", &mut iter)?))
+            }
+            "--locale" => locale = Some(next_value("--locale", &mut iter)?),
+            "--format" => {
+                let value = next_value("--format", &mut iter)?;
+                format = Some(ExportFormat::parse(&value).ok_or_else(|| {
+                    CliAppError::Usage(format!("unknown export format {value}\n\n{}", usage()))
+                })?);
+            }
+            "--out" => out_path = Some(PathBuf::from(next_value("--out", &mut iter)?)),
+            "--config" => config_path = PathBuf::from(next_value("--config", &mut iter)?),
+            "--help" | "-h" => return Err(CliAppError::Usage(usage())),
+            _ => return Err(CliAppError::Usage(usage())),
+        }
+    }
+    let catalog_path = catalog_path.ok_or_else(|| CliAppError::Usage(usage()))?;
+    let id_map_hash_path = id_map_hash_path.ok_or_else(|| CliAppError::Usage(usage()))?;
+    let locale = locale.ok_or_else(|| CliAppError::Usage(usage()))?;
+    let format = format.ok_or_else(|| CliAppError::Usage(usage()))?;
+    let out_path =
+        out_path.unwrap_or_else(|| PathBuf::from(format!("{locale}.{}", format.extension())));
+    Ok(ExportOptions {
+        catalog_path,
+        id_map_hash_path,
+        locale,
+        format,
+        out_path,
+        config_path,
+    })
+}
+
+fn parse_diff_options(args: Vec<String>) -> Result<DiffOptions, CliAppError> {
+    let mut old_catalog_path = None;
+    let mut new_catalog_path = None;
+    let mut old_id_map_hash_path = None;
+    let mut new_id_map_hash_path = None;
+    let mut out_path = None;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--old" => old_catalog_path = Some(PathBuf::from(next_value("--old", &mut iter)?)),
+            "--new" => new_catalog_path = Some(PathBuf::from(next_value("--new", &mut iter)?)),
+            "--old-id-map-hash" => {
+                old_id_map_hash_path =
+                    Some(PathBuf::from(next_value("--old-id-map-hash", &mut iter)?))
+            }
+            "--new-id-map-hash" => {
+                new_id_map_hash_path =
+                    Some(PathBuf::from(next_value("--new-id-map-hash", &mut iter)?))
+            }
+            "--out" => out_path = Some(PathBuf::from(next_value("--out", &mut iter)?)),
+            "--help" | "-h" => return Err(CliAppError::Usage(usage())),
+            _ => return Err(CliAppError::Usage(usage())),
+        }
+    }
+    let old_catalog_path = old_catalog_path.ok_or_else(|| CliAppError::Usage(usage()))?;
+    let new_catalog_path = new_catalog_path.ok_or_else(|| CliAppError::Usage(usage()))?;
+    let old_id_map_hash_path =
+        old_id_map_hash_path.unwrap_or_else(|| sibling_id_map_hash(&old_catalog_path));
+    let new_id_map_hash_path =
+        new_id_map_hash_path.unwrap_or_else(|| sibling_id_map_hash(&new_catalog_path));
+    Ok(DiffOptions {
+        old_catalog_path,
+        old_id_map_hash_path,
+        new_catalog_path,
+        new_id_map_hash_path,
+        out_path,
+    })
+}
+
+fn parse_stats_options(args: Vec<String>) -> Result<StatsOptions, CliAppError> {
+    let mut manifest_path = None;
+    let mut out_path = PathBuf::from("stats.json");
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--manifest" => {
+                manifest_path = Some(PathBuf::from(next_value("--manifest", &mut iter)?))
+            }
+            "--out" => out_path = PathBuf::from(next_value("--out", &mut iter)?),
+            "--help" | "-h" => return Err(CliAppError::Usage(usage())),
+            _ => return Err(CliAppError::Usage(usage())),
+        }
+    }
+    let manifest_path = manifest_path.ok_or_else(|| CliAppError::Usage(usage()))?;
+    Ok(StatsOptions {
+        manifest_path,
+        out_path,
+    })
+}
+
+fn parse_check_options(args: Vec<String>) -> Result<CheckOptions, CliAppError> {
+    let mut manifest_path = None;
+    let mut id_map_path = None;
+    let mut out_path = PathBuf::from("check.json");
+    let mut trusted_keys = BTreeMap::new();
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--manifest" => {
+                manifest_path = Some(PathBuf::from(next_value("--manifest", &mut iter)?))
+            }
+            "--id-map" => id_map_path = Some(PathBuf::from(next_value("--id-map", &mut iter)?)),
+            "--out" => out_path = PathBuf::from(next_value("--out", &mut iter)?),
+            "--trusted-key" => {
+                let value = next_value("--trusted-key", &mut iter)?;
+                let (key_id, path) = value.split_once('=').ok_or_else(|| {
+                    CliAppError::Usage(format!("--trusted-key expects <id>=<path>\n\n{}", usage()))
+                })?;
+                trusted_keys.insert(key_id.to_string(), PathBuf::from(path));
+            }
+            "--help" | "-h" => return Err(CliAppError::Usage(usage())),
+            _ => return Err(CliAppError::Usage(usage())),
+        }
+    }
+    let manifest_path = manifest_path.ok_or_else(|| CliAppError::Usage(usage()))?;
+    let id_map_path = id_map_path.ok_or_else(|| CliAppError::Usage(usage()))?;
+    Ok(CheckOptions {
+        manifest_path,
+        id_map_path,
+        out_path,
+        trusted_keys,
+    })
+}
+
+fn parse_emit_id_map_options(args: Vec<String>) -> Result<EmitIdMapOptions, CliAppError> {
+    let mut id_map_path = None;
+    let mut out_path = PathBuf::from("id_map.rs");
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--id-map" => id_map_path = Some(PathBuf::from(next_value("--id-map", &mut iter)?)),
+            "--out" => out_path = PathBuf::from(next_value("--out", &mut iter)?),
+            "--help" | "-h" => return Err(CliAppError::Usage(usage())),
+            _ => return Err(CliAppError::Usage(usage())),
+        }
+    }
+    let id_map_path = id_map_path.ok_or_else(|| CliAppError::Usage(usage()))?;
+    Ok(EmitIdMapOptions {
+        id_map_path,
+        out_path,
+    })
+}
+
+fn parse_fmt_options(args: Vec<String>) -> Result<FmtOptions, CliAppError> {
+    let mut paths = Vec::new();
+    let mut check = false;
+    for arg in args {
+        match arg.as_str() {
+            "--check" => check = true,
+            "--help" | "-h" => return Err(CliAppError::Usage(usage())),
+            flag if flag.starts_with("--") => return Err(CliAppError::Usage(usage())),
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+    if paths.is_empty() {
+        return Err(CliAppError::Usage(usage()));
+    }
+    Ok(FmtOptions { paths, check })
+}
+
+fn parse_lint_options(args: Vec<String>) -> Result<LintOptions, CliAppError> {
+    let mut config_path = PathBuf::from("mf2-i18n.toml");
+    let mut deny_warnings = false;
+    let mut format = DiagnosticFormat::Text;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--config" => config_path = PathBuf::from(next_value("--config", &mut iter)?),
+            "--deny" => match next_value("--deny", &mut iter)?.as_str() {
+                "warnings" => deny_warnings = true,
+                _ => return Err(CliAppError::Usage(usage())),
+            },
+            "--format" => format = parse_diagnostic_format(&next_value("--format", &mut iter)?)?,
+            "--help" | "-h" => return Err(CliAppError::Usage(usage())),
+            _ => return Err(CliAppError::Usage(usage())),
+        }
+    }
+    Ok(LintOptions {
+        config_path,
+        deny_warnings,
+        format,
+    })
+}
+
+fn sibling_id_map_hash(catalog_path: &Path) -> PathBuf {
+    catalog_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("id_map_hash")
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
-        parse_build_options, parse_coverage_options, parse_extract_options, parse_pseudo_options,
-        parse_sign_options, parse_validate_options,
+        ExportFormat, PackEncoding, PseudoStrategy, parse_build_options, parse_check_options,
+        parse_coverage_options, parse_diff_options, parse_export_options, parse_extract_options,
+        parse_fmt_options, parse_import_ftl_options, parse_import_po_options, parse_lint_options,
+        parse_pseudo_options, parse_sign_options, parse_stats_options, parse_validate_options,
+        parse_watch_options,
     };
+    use crate::diagnostic::DiagnosticFormat;
+    use std::path::PathBuf;
+
+    #[test]
+    fn parses_watch_options() {
+        let args = vec![
+            "--project".to_string(),
+            "demo".to_string(),
+            "--root".to_string(),
+            "src".to_string(),
+            "--generated-at".to_string(),
+            "2026-02-01T00:00:00Z".to_string(),
+            "--debounce-ms".to_string(),
+            "750".to_string(),
+        ];
+        let options = parse_watch_options(args).expect("options");
+        assert_eq!(options.debounce, std::time::Duration::from_millis(750));
+        assert_eq!(options.extract.project, "demo");
+        assert!(
+            parse_watch_options(vec!["--debounce-ms".to_string(), "soon".to_string()]).is_err()
+        );
+    }
 
     #[test]
     fn parses_extract_options() {
@@ -284,8 +864,24 @@ mod tests {
             "--id-map-hash".to_string(),
             "id_map_hash".to_string(),
         ];
-        let options = parse_validate_options(args).expect("options");
+        let options = parse_validate_options(args.clone()).expect("options");
         assert!(options.catalog_path.ends_with("i18n.catalog.json"));
+        assert!(!options.execute);
+        assert_eq!(options.format, DiagnosticFormat::Text);
+
+        let mut execute = args.clone();
+        execute.push("--execute".to_string());
+        assert!(parse_validate_options(execute).expect("options").execute);
+
+        let mut json = args.clone();
+        json.extend(["--format".to_string(), "json".to_string()]);
+        assert_eq!(
+            parse_validate_options(json).expect("options").format,
+            DiagnosticFormat::Json
+        );
+        let mut unknown = args;
+        unknown.extend(["--format".to_string(), "sarif".to_string()]);
+        assert!(parse_validate_options(unknown).is_err());
     }
 
     #[test]
@@ -300,8 +896,75 @@ mod tests {
             "--generated-at".to_string(),
             "2026-02-01T00:00:00Z".to_string(),
         ];
-        let options = parse_build_options(args).expect("options");
+        let options = parse_build_options(args, None).expect("options");
         assert_eq!(options.release_id, "r1");
+        assert_eq!(options.content_encoding, PackEncoding::Identity);
+    }
+
+    #[test]
+    fn parses_build_content_encoding() {
+        let args = vec![
+            "--catalog".to_string(),
+            "i18n.catalog.json".to_string(),
+            "--id-map-hash".to_string(),
+            "id_map_hash".to_string(),
+            "--release-id".to_string(),
+            "r1".to_string(),
+            "--generated-at".to_string(),
+            "2026-02-01T00:00:00Z".to_string(),
+            "--content-encoding".to_string(),
+            "br".to_string(),
+        ];
+        let options = parse_build_options(args.clone(), None).expect("options");
+        assert_eq!(options.content_encoding, PackEncoding::Brotli);
+
+        let mut bad = args;
+        bad[9] = "zstd".to_string();
+        assert!(parse_build_options(bad, None).is_err());
+    }
+
+    #[test]
+    fn parses_build_budgets() {
+        let mut args = vec![
+            "--catalog".to_string(),
+            "i18n.catalog.json".to_string(),
+            "--id-map-hash".to_string(),
+            "id_map_hash".to_string(),
+            "--release-id".to_string(),
+            "r1".to_string(),
+            "--generated-at".to_string(),
+            "2026-02-01T00:00:00Z".to_string(),
+            "--max-pack-bytes".to_string(),
+            "4096".to_string(),
+            "--max-pack-bytes-locale".to_string(),
+            "fr=8192".to_string(),
+        ];
+        let options = parse_build_options(args.clone(), None).expect("options");
+        assert_eq!(options.max_pack_bytes, Some(4096));
+        assert_eq!(options.locale_max_pack_bytes.get("fr"), Some(&8192));
+
+        args[11] = "fr".to_string();
+        assert!(parse_build_options(args.clone(), None).is_err());
+
+        args[11] = "fr=8192".to_string();
+        args.extend(["--source-date-epoch".to_string(), "1700000000".to_string()]);
+        let options =
+            parse_build_options(args.clone(), Some("1600000000".to_string())).expect("options");
+        assert_eq!(options.source_date_epoch, Some(1_700_000_000));
+        args[13] = "soon".to_string();
+        assert!(parse_build_options(args.clone(), None).is_err());
+
+        args.truncate(12);
+        let options =
+            parse_build_options(args.clone(), Some("1600000000".to_string())).expect("options");
+        assert_eq!(options.source_date_epoch, Some(1_600_000_000));
+        assert_eq!(
+            parse_build_options(args.clone(), None)
+                .expect("options")
+                .source_date_epoch,
+            None
+        );
+        assert!(parse_build_options(args, Some("soon".to_string())).is_err());
     }
 
     #[test]
@@ -314,8 +977,16 @@ mod tests {
             "--key-id".to_string(),
             "key-1".to_string(),
         ];
-        let options = parse_sign_options(args).expect("options");
+        let options = parse_sign_options(args.clone()).expect("options");
         assert!(options.manifest_path.ends_with("manifest.json"));
+        assert!(!options.append);
+
+        let mut appended = args;
+        appended.push("--append".to_string());
+        appended.extend(["--sig-alg".to_string(), "ecdsa-p256".to_string()]);
+        let options = parse_sign_options(appended).expect("options");
+        assert!(options.append);
+        assert_eq!(options.sig_alg.as_deref(), Some("ecdsa-p256"));
     }
 
     #[test]
@@ -326,9 +997,20 @@ mod tests {
             "--target".to_string(),
             "en-xa".to_string(),
         ];
-        let options = parse_pseudo_options(args).expect("options");
+        let options = parse_pseudo_options(args.clone()).expect("options");
         assert_eq!(options.locale, "en");
         assert_eq!(options.target, "en-xa");
+        assert_eq!(options.strategy, PseudoStrategy::Bracket);
+
+        let mut expand = args;
+        expand.extend([
+            "--strategy".to_string(),
+            "expand".to_string(),
+            "--expand-percent".to_string(),
+            "50".to_string(),
+        ]);
+        let options = parse_pseudo_options(expand).expect("options");
+        assert_eq!(options.strategy, PseudoStrategy::Expand { percent: 50 });
     }
 
     #[test]
@@ -339,7 +1021,155 @@ mod tests {
             "--id-map-hash".to_string(),
             "id_map_hash".to_string(),
         ];
-        let options = parse_coverage_options(args).expect("options");
+        let options = parse_coverage_options(args.clone()).expect("options");
         assert!(options.out_path.ends_with("coverage.json"));
+        assert!(options.min_percent.is_none());
+
+        let mut with_thresholds = args.clone();
+        with_thresholds.extend([
+            "--min-percent".to_string(),
+            "95".to_string(),
+            "--min-percent-locale".to_string(),
+            "fr=80.5".to_string(),
+        ]);
+        with_thresholds.extend(["--format".to_string(), "markdown".to_string()]);
+        let options = parse_coverage_options(with_thresholds).expect("options");
+        assert!(options.out_path.ends_with("coverage.md"));
+        assert_eq!(options.min_percent, Some(95.0));
+        assert_eq!(options.locale_min_percent.get("fr"), Some(&80.5));
+
+        let mut invalid = args;
+        invalid.extend(["--min-percent".to_string(), "high".to_string()]);
+        assert!(parse_coverage_options(invalid).is_err());
+    }
+
+    #[test]
+    fn parses_export_options() {
+        let args = vec![
+            "--catalog".to_string(),
+            "catalog.json".to_string(),
+            "--id-map-hash".to_string(),
+            "id_map_hash".to_string(),
+            "--locale".to_string(),
+            "fr".to_string(),
+            "--format".to_string(),
+            "xliff".to_string(),
+        ];
+        let options = parse_export_options(args.clone()).expect("options");
+        assert_eq!(options.format, ExportFormat::Xliff);
+        assert!(options.out_path.ends_with("fr.xlf"));
+
+        let mut bad = args;
+        bad[7] = "yaml".to_string();
+        assert!(parse_export_options(bad).is_err());
+    }
+
+    #[test]
+    fn parses_diff_options() {
+        let args = vec![
+            "--old".to_string(),
+            "v1/i18n.catalog.json".to_string(),
+            "--new".to_string(),
+            "v2/i18n.catalog.json".to_string(),
+        ];
+        let options = parse_diff_options(args).expect("options");
+        assert!(options.old_id_map_hash_path.ends_with("v1/id_map_hash"));
+        assert!(options.new_id_map_hash_path.ends_with("v2/id_map_hash"));
+        assert!(options.out_path.is_none());
+        assert!(parse_diff_options(vec!["--old".to_string(), "a.json".to_string()]).is_err());
+    }
+
+    #[test]
+    fn parses_stats_options() {
+        let args = vec!["--manifest".to_string(), "out/manifest.json".to_string()];
+        let options = parse_stats_options(args).expect("options");
+        assert!(options.out_path.ends_with("stats.json"));
+        assert!(parse_stats_options(Vec::new()).is_err());
+    }
+
+    #[test]
+    fn parses_fmt_options() {
+        let options = parse_fmt_options(vec![
+            "--check".to_string(),
+            "locales/en".to_string(),
+            "extra.mf2".to_string(),
+        ])
+        .expect("options");
+        assert!(options.check);
+        assert_eq!(options.paths.len(), 2);
+        assert!(parse_fmt_options(vec!["--check".to_string()]).is_err());
+        assert!(parse_fmt_options(vec!["--write".to_string(), "a.mf2".to_string()]).is_err());
+    }
+
+    #[test]
+    fn parses_lint_options() {
+        let options = parse_lint_options(Vec::new()).expect("options");
+        assert!(!options.deny_warnings);
+        assert_eq!(options.config_path, PathBuf::from("mf2-i18n.toml"));
+        let options = parse_lint_options(vec![
+            "--deny".to_string(),
+            "warnings".to_string(),
+            "--config".to_string(),
+            "i18n.toml".to_string(),
+            "--format".to_string(),
+            "json".to_string(),
+        ])
+        .expect("options");
+        assert!(options.deny_warnings);
+        assert_eq!(options.format, DiagnosticFormat::Json);
+        assert_eq!(options.config_path, PathBuf::from("i18n.toml"));
+        assert!(parse_lint_options(vec!["--deny".to_string(), "errors".to_string()]).is_err());
+    }
+
+    #[test]
+    fn parses_check_options() {
+        let mut args = vec![
+            "--manifest".to_string(),
+            "out/manifest.json".to_string(),
+            "--id-map".to_string(),
+            "id_map.json".to_string(),
+        ];
+        let options = parse_check_options(args.clone()).expect("options");
+        assert!(options.out_path.ends_with("check.json"));
+        assert!(options.trusted_keys.is_empty());
+
+        args.extend([
+            "--trusted-key".to_string(),
+            "key-1=keys/key-1.pub".to_string(),
+        ]);
+        let options = parse_check_options(args.clone()).expect("options");
+        assert!(options.trusted_keys["key-1"].ends_with("key-1.pub"));
+
+        args.extend(["--trusted-key".to_string(), "key-2".to_string()]);
+        assert!(parse_check_options(args).is_err());
+    }
+
+    #[test]
+    fn parses_import_po_options() {
+        let args = vec![
+            "--po".to_string(),
+            "fr.po".to_string(),
+            "--locale".to_string(),
+            "fr".to_string(),
+        ];
+        let options = parse_import_po_options(args).expect("options");
+        assert!(options.po_path.ends_with("fr.po"));
+        assert!(options.out_dir.ends_with("locales"));
+        assert!(parse_import_po_options(vec!["--po".to_string()]).is_err());
+    }
+
+    #[test]
+    fn parses_import_ftl_options() {
+        let args = vec![
+            "--ftl".to_string(),
+            "en.ftl".to_string(),
+            "--locale".to_string(),
+            "en".to_string(),
+            "--out".to_string(),
+            "out".to_string(),
+        ];
+        let options = parse_import_ftl_options(args).expect("options");
+        assert!(options.ftl_path.ends_with("en.ftl"));
+        assert!(options.out_dir.ends_with("out"));
     }
 }
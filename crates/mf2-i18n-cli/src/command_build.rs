@@ -1,18 +1,20 @@
 use std::collections::BTreeMap;
 use std::fs;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 
+use mf2_i18n_core::{PackBuildInput, PackEncodeError, encode_pack};
 use thiserror::Error;
 
 use crate::catalog_reader::{CatalogReadError, load_catalog};
 use crate::command_validate::{ValidateCommandError, ValidateOptions, run_validate};
 use crate::compiler::compile_message;
 use crate::config::load_config_or_default;
+use crate::diagnostic::{Diagnostic, DiagnosticFormat};
 use crate::locale_sources::{LocaleSourceError, load_locales};
 use crate::manifest::{Manifest, PackEntry, sha256_hex};
 use crate::micro_locales::{MicroLocaleError, load_micro_locales};
-use crate::pack_encode::{PackBuildInput, encode_pack};
-use crate::parser::parse_message;
+use crate::parser::{parse_message, resolve_selector_kinds};
 
 #[derive(Debug, Error)]
 pub enum BuildCommandError {
@@ -30,6 +32,14 @@ pub enum BuildCommandError {
     MissingMessage(String, String),
     #[error("parse error for {0}: {1}")]
     ParseError(String, String),
+    #[error("invalid bytecode for {0}: {1}")]
+    InvalidProgram(String, mf2_i18n_core::CoreError),
+    #[error(transparent)]
+    Encode(#[from] PackEncodeError),
+    #[error("invalid generated_at timestamp {0}")]
+    InvalidGeneratedAt(String),
+    #[error("packs over budget: {}", .0.join(", "))]
+    OverBudget(Vec<String>),
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
 }
@@ -42,9 +52,87 @@ pub struct BuildOptions {
     pub out_dir: PathBuf,
     pub release_id: String,
     pub generated_at: String,
+    pub content_encoding: PackEncoding,
+    pub max_pack_bytes: Option<u64>,
+    pub locale_max_pack_bytes: BTreeMap<String, u64>,
+    pub source_date_epoch: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackEncoding {
+    Identity,
+    Brotli,
+    Gzip,
 }
 
-pub fn run_build(options: &BuildOptions) -> Result<(), BuildCommandError> {
+impl PackEncoding {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "identity" => Some(Self::Identity),
+            "br" => Some(Self::Brotli),
+            "gzip" => Some(Self::Gzip),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Identity => "identity",
+            Self::Brotli => "br",
+            Self::Gzip => "gzip",
+        }
+    }
+
+    fn file_suffix(&self) -> &'static str {
+        match self {
+            Self::Identity => "",
+            Self::Brotli => ".br",
+            Self::Gzip => ".gz",
+        }
+    }
+
+    fn encode(&self, bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::Identity => Ok(bytes.to_vec()),
+            Self::Brotli => {
+                let mut out = Vec::new();
+                {
+                    let mut writer = brotli::CompressorWriter::new(&mut out, 4096, 11, 22);
+                    writer.write_all(bytes)?;
+                }
+                Ok(out)
+            }
+            Self::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+                encoder.write_all(bytes)?;
+                encoder.finish()
+            }
+        }
+    }
+
+    pub fn decode(&self, bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+        let mut out = Vec::new();
+        match self {
+            Self::Identity => out.extend_from_slice(bytes),
+            Self::Brotli => {
+                brotli::Decompressor::new(bytes, 4096).read_to_end(&mut out)?;
+            }
+            Self::Gzip => {
+                flate2::read::GzDecoder::new(bytes).read_to_end(&mut out)?;
+            }
+        }
+        Ok(out)
+    }
+}
+
+pub fn run_build(options: &BuildOptions) -> Result<Vec<Diagnostic>, BuildCommandError> {
+    let build_epoch_ms = match options.source_date_epoch {
+        Some(seconds) => seconds.saturating_mul(1000),
+        None => mf2_i18n_core::parse_rfc3339(&options.generated_at)
+            .and_then(|millis| u64::try_from(millis).ok())
+            .ok_or_else(|| BuildCommandError::InvalidGeneratedAt(options.generated_at.clone()))?,
+    };
     let config = load_config_or_default(&options.config_path)?;
     let bundle = load_catalog(&options.catalog_path, &options.id_map_hash_path)?;
     let roots: Vec<PathBuf> = config
@@ -53,13 +141,15 @@ pub fn run_build(options: &BuildOptions) -> Result<(), BuildCommandError> {
         .map(|root| resolve_path(&options.config_path, root))
         .collect();
 
-    run_validate(&ValidateOptions {
+    let diagnostics = run_validate(&ValidateOptions {
         catalog_path: options.catalog_path.clone(),
         id_map_hash_path: options.id_map_hash_path.clone(),
         config_path: options.config_path.clone(),
+        execute: false,
+        format: DiagnosticFormat::Text,
     })?;
 
-    let locales = load_locales(&roots)?;
+    let locales = load_locales(&roots, config.duplicate_keys)?;
     let micro_locale_map = load_micro_locales(&resolve_path(
         &options.config_path,
         config
@@ -68,12 +158,36 @@ pub fn run_build(options: &BuildOptions) -> Result<(), BuildCommandError> {
             .unwrap_or("micro-locales.toml"),
     ))?;
 
-    fs::create_dir_all(&options.out_dir)?;
-    let packs_dir = options.out_dir.join("packs");
-    fs::create_dir_all(&packs_dir)?;
-
+    let mut pack_files = Vec::new();
     let mut mf2_packs = BTreeMap::new();
     let mut supported_locales = Vec::new();
+    let mut budgets = BTreeMap::new();
+    let mut over_budget = Vec::new();
+
+    let mut compiled = BTreeMap::new();
+    for locale in &locales {
+        compiled.insert(
+            locale.locale.clone(),
+            compile_locale_messages(
+                locale,
+                &bundle.catalog,
+                micro_locale_map.contains_key(&locale.locale),
+            )?,
+        );
+    }
+    let mut inherited = BTreeMap::new();
+    for (locale, parent) in &micro_locale_map {
+        if let (Some(messages), Some(parent_messages)) =
+            (compiled.get(locale), compiled.get(parent))
+        {
+            let ids: Vec<_> = messages
+                .iter()
+                .filter(|(id, program)| parent_messages.get(id) == Some(program))
+                .map(|(id, _)| *id)
+                .collect();
+            inherited.insert(locale.clone(), ids);
+        }
+    }
 
     for locale in locales {
         let parent = micro_locale_map.get(&locale.locale).cloned();
@@ -82,18 +196,25 @@ pub fn run_build(options: &BuildOptions) -> Result<(), BuildCommandError> {
         } else {
             mf2_i18n_core::PackKind::Base
         };
-        let messages = compile_locale_messages(&locale, &bundle.catalog)?;
+        let mut messages = compiled.remove(&locale.locale).unwrap_or_default();
+        for id in inherited.remove(&locale.locale).unwrap_or_default() {
+            messages.remove(&id);
+        }
         let bytes = encode_pack(&PackBuildInput {
             pack_kind,
             id_map_hash: bundle.id_map_hash,
             locale_tag: locale.locale.clone(),
             parent_tag: parent.clone(),
-            build_epoch_ms: 0,
+            build_epoch_ms,
             messages,
-        });
-        let filename = format!("{}.mf2pack", locale.locale);
-        let path = packs_dir.join(&filename);
-        fs::write(&path, &bytes)?;
+            sort_strings: true,
+        })?;
+        let bytes = options.content_encoding.encode(&bytes)?;
+        let filename = format!(
+            "{}.mf2pack{}",
+            locale.locale,
+            options.content_encoding.file_suffix()
+        );
         let hash = sha256_hex(&bytes);
         let entry = PackEntry {
             kind: match pack_kind {
@@ -104,12 +225,37 @@ pub fn run_build(options: &BuildOptions) -> Result<(), BuildCommandError> {
             url: format!("packs/{filename}"),
             hash,
             size: bytes.len() as u64,
-            content_encoding: "identity".to_string(),
-            pack_schema: 0,
+            content_encoding: options.content_encoding.as_str().to_string(),
+            pack_schema: u32::from(mf2_i18n_core::PACK_SCHEMA_VERSION),
             parent,
         };
+        let budget = options
+            .locale_max_pack_bytes
+            .get(&locale.locale)
+            .copied()
+            .or(options.max_pack_bytes);
+        if let Some(budget) = budget {
+            if entry.size > budget {
+                over_budget.push(format!(
+                    "{} ({} > {budget} bytes)",
+                    locale.locale, entry.size
+                ));
+            }
+            budgets.insert(locale.locale.clone(), budget);
+        }
         mf2_packs.insert(locale.locale.clone(), entry);
         supported_locales.push(locale.locale);
+        pack_files.push((filename, bytes));
+    }
+
+    if !over_budget.is_empty() {
+        return Err(BuildCommandError::OverBudget(over_budget));
+    }
+
+    let packs_dir = options.out_dir.join("packs");
+    fs::create_dir_all(&packs_dir)?;
+    for (filename, bytes) in pack_files {
+        fs::write(packs_dir.join(filename), bytes)?;
     }
 
     supported_locales.sort();
@@ -122,28 +268,38 @@ pub fn run_build(options: &BuildOptions) -> Result<(), BuildCommandError> {
         id_map_hash: format!("sha256:{}", hex::encode(bundle.id_map_hash)),
         mf2_packs,
         icu_packs: None,
-        micro_locales: None,
-        budgets: None,
+        micro_locales: (!micro_locale_map.is_empty()).then_some(micro_locale_map),
+        budgets: (!budgets.is_empty()).then_some(budgets),
         signing: None,
     };
 
     let manifest_path = options.out_dir.join("manifest.json");
     fs::write(&manifest_path, manifest.to_canonical_bytes())?;
-    Ok(())
+    Ok(diagnostics)
 }
 
 fn compile_locale_messages(
     locale: &crate::locale_sources::LocaleBundle,
     catalog: &crate::catalog::Catalog,
+    inherits_missing: bool,
 ) -> Result<BTreeMap<mf2_i18n_core::MessageId, mf2_i18n_core::BytecodeProgram>, BuildCommandError> {
     let mut messages = BTreeMap::new();
     for message in &catalog.messages {
-        let entry = locale.messages.get(&message.key).ok_or_else(|| {
-            BuildCommandError::MissingMessage(message.key.clone(), locale.locale.clone())
-        })?;
-        let parsed = parse_message(&entry.value)
+        let Some(entry) = locale.messages.get(&message.key) else {
+            if inherits_missing {
+                continue;
+            }
+            return Err(BuildCommandError::MissingMessage(
+                message.key.clone(),
+                locale.locale.clone(),
+            ));
+        };
+        let mut parsed = parse_message(&entry.value)
             .map_err(|err| BuildCommandError::ParseError(message.key.clone(), err.message))?;
+        resolve_selector_kinds(&mut parsed, &message.args);
         let compiled = compile_message(&parsed);
+        mf2_i18n_core::verify(&compiled.program)
+            .map_err(|err| BuildCommandError::InvalidProgram(message.key.clone(), err))?;
         messages.insert(mf2_i18n_core::MessageId::new(message.id), compiled.program);
     }
     Ok(messages)
@@ -162,8 +318,10 @@ fn resolve_path(config_path: &Path, value: &str) -> PathBuf {
 
 #[cfg(test)]
 mod tests {
-    use super::{BuildOptions, run_build};
+    use super::{BuildCommandError, BuildOptions, PackEncoding, run_build};
     use crate::catalog::{Catalog, CatalogFeatures, CatalogMessage};
+    use crate::manifest::Manifest;
+    use std::collections::BTreeMap;
     use std::fs;
     use std::path::PathBuf;
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -179,9 +337,7 @@ mod tests {
         path
     }
 
-    #[test]
-    fn builds_manifest_and_pack() {
-        let dir = temp_dir();
+    fn write_project(dir: &std::path::Path) -> (PathBuf, PathBuf, PathBuf) {
         let locales_dir = dir.join("locales").join("en");
         fs::create_dir_all(&locales_dir).expect("locale");
         fs::write(locales_dir.join("messages.mf2"), "home.title = Hi").expect("write");
@@ -214,6 +370,13 @@ mod tests {
             "default_locale = \"en\"\nsource_dirs = [\"locales\"]\nproject_salt_path = \"tools/id_salt.txt\"",
         )
         .expect("config");
+        (catalog_path, hash_path, config_path)
+    }
+
+    #[test]
+    fn builds_manifest_and_pack() {
+        let dir = temp_dir();
+        let (catalog_path, hash_path, config_path) = write_project(&dir);
 
         let out_dir = dir.join("out");
         run_build(&BuildOptions {
@@ -223,6 +386,10 @@ mod tests {
             out_dir: out_dir.clone(),
             release_id: "r1".to_string(),
             generated_at: "2026-02-01T00:00:00Z".to_string(),
+            content_encoding: PackEncoding::Identity,
+            max_pack_bytes: None,
+            locale_max_pack_bytes: BTreeMap::new(),
+            source_date_epoch: None,
         })
         .expect("build");
 
@@ -231,4 +398,299 @@ mod tests {
 
         fs::remove_dir_all(&dir).ok();
     }
+
+    #[test]
+    fn enforces_and_records_pack_budgets() {
+        let dir = temp_dir();
+        let (catalog_path, hash_path, config_path) = write_project(&dir);
+        let mut options = BuildOptions {
+            catalog_path,
+            id_map_hash_path: hash_path,
+            config_path,
+            out_dir: dir.join("out"),
+            release_id: "r1".to_string(),
+            generated_at: "2026-02-01T00:00:00Z".to_string(),
+            content_encoding: PackEncoding::Identity,
+            max_pack_bytes: Some(1 << 20),
+            locale_max_pack_bytes: BTreeMap::from([("en".to_string(), 16)]),
+            source_date_epoch: None,
+        };
+
+        let err = run_build(&options).expect_err("over budget");
+        assert!(matches!(&err, BuildCommandError::OverBudget(failures) if failures.len() == 1));
+        assert!(err.to_string().contains("en ("));
+        assert!(!options.out_dir.join("manifest.json").exists());
+        assert!(!options.out_dir.join("packs/en.mf2pack").exists());
+
+        options.locale_max_pack_bytes.clear();
+        run_build(&options).expect("build");
+        let manifest: Manifest = serde_json::from_slice(
+            &fs::read(options.out_dir.join("manifest.json")).expect("manifest"),
+        )
+        .expect("json");
+        assert_eq!(
+            manifest.budgets,
+            Some(BTreeMap::from([("en".to_string(), 1 << 20)]))
+        );
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn records_micro_locales_for_the_runtime() {
+        let dir = temp_dir();
+        let (catalog_path, hash_path, config_path) = write_project(&dir);
+        let mut catalog: Catalog =
+            serde_json::from_slice(&fs::read(&catalog_path).expect("catalog")).expect("json");
+        catalog.messages.push(CatalogMessage {
+            key: "home.greeting".to_string(),
+            id: 2,
+            args: vec![],
+            features: CatalogFeatures::default(),
+            source_refs: None,
+        });
+        fs::write(&catalog_path, serde_json::to_string(&catalog).unwrap()).expect("catalog");
+        fs::write(
+            dir.join("locales/en/messages.mf2"),
+            "home.title = Hi\n\nhome.greeting = Hello",
+        )
+        .expect("write");
+        let child_dir = dir.join("locales").join("en-x-pirate");
+        fs::create_dir_all(&child_dir).expect("locale");
+        fs::write(child_dir.join("messages.mf2"), "home.title = Ahoy").expect("write");
+        fs::write(
+            dir.join("micro-locales.toml"),
+            "[[locale]]\ntag = \"en-x-pirate\"\nparent = \"en\"\n",
+        )
+        .expect("registry");
+        let id_map_hash = mf2_i18n_core::id_map_hash([
+            ("home.greeting", mf2_i18n_core::MessageId::new(2)),
+            ("home.title", mf2_i18n_core::MessageId::new(1)),
+        ])
+        .expect("hash");
+        fs::write(&hash_path, format!("sha256:{}", hex::encode(id_map_hash))).expect("hash");
+        fs::write(
+            dir.join("id_map.json"),
+            r#"{"home.greeting": 2, "home.title": 1}"#,
+        )
+        .expect("id map");
+
+        let out_dir = dir.join("out");
+        run_build(&BuildOptions {
+            catalog_path,
+            id_map_hash_path: hash_path,
+            config_path,
+            out_dir: out_dir.clone(),
+            release_id: "r1".to_string(),
+            generated_at: "2026-02-01T00:00:00Z".to_string(),
+            content_encoding: PackEncoding::Identity,
+            max_pack_bytes: None,
+            locale_max_pack_bytes: BTreeMap::new(),
+            source_date_epoch: None,
+        })
+        .expect("build");
+
+        let manifest: Manifest =
+            serde_json::from_slice(&fs::read(out_dir.join("manifest.json")).expect("manifest"))
+                .expect("json");
+        assert_eq!(
+            manifest.micro_locales,
+            Some(BTreeMap::from([(
+                "en-x-pirate".to_string(),
+                "en".to_string()
+            )]))
+        );
+
+        let runtime = mf2_i18n_runtime::Runtime::load_from_paths(
+            &out_dir.join("manifest.json"),
+            &dir.join("id_map.json"),
+        )
+        .expect("runtime");
+        let args = mf2_i18n_core::Args::new();
+        assert_eq!(
+            runtime
+                .format("en-x-pirate", "home.title", &args)
+                .expect("child"),
+            "Ahoy"
+        );
+        assert_eq!(
+            runtime.format("en", "home.title", &args).expect("parent"),
+            "Hi"
+        );
+        assert_eq!(
+            runtime
+                .format("en-x-pirate", "home.greeting", &args)
+                .expect("inherited"),
+            "Hello"
+        );
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn overlays_skip_programs_identical_to_their_parent() {
+        let dir = temp_dir();
+        let (catalog_path, hash_path, config_path) = write_project(&dir);
+        let mut catalog: Catalog =
+            serde_json::from_slice(&fs::read(&catalog_path).expect("catalog")).expect("json");
+        catalog.messages.push(CatalogMessage {
+            key: "home.greeting".to_string(),
+            id: 2,
+            args: vec![],
+            features: CatalogFeatures::default(),
+            source_refs: None,
+        });
+        fs::write(&catalog_path, serde_json::to_string(&catalog).unwrap()).expect("catalog");
+        fs::write(
+            dir.join("locales/en/messages.mf2"),
+            "home.title = Hi\n\nhome.greeting = Hello",
+        )
+        .expect("write");
+        let child_dir = dir.join("locales").join("en-x-pirate");
+        fs::create_dir_all(&child_dir).expect("locale");
+        fs::write(
+            child_dir.join("messages.mf2"),
+            "home.title = Hi\n\nhome.greeting = Ahoy",
+        )
+        .expect("write");
+        fs::write(
+            dir.join("micro-locales.toml"),
+            "[[locale]]\ntag = \"en-x-pirate\"\nparent = \"en\"\n",
+        )
+        .expect("registry");
+        let id_map_hash = mf2_i18n_core::id_map_hash([
+            ("home.greeting", mf2_i18n_core::MessageId::new(2)),
+            ("home.title", mf2_i18n_core::MessageId::new(1)),
+        ])
+        .expect("hash");
+        fs::write(&hash_path, format!("sha256:{}", hex::encode(id_map_hash))).expect("hash");
+        fs::write(
+            dir.join("id_map.json"),
+            r#"{"home.greeting": 2, "home.title": 1}"#,
+        )
+        .expect("id map");
+
+        let out_dir = dir.join("out");
+        run_build(&BuildOptions {
+            catalog_path,
+            id_map_hash_path: hash_path,
+            config_path,
+            out_dir: out_dir.clone(),
+            release_id: "r1".to_string(),
+            generated_at: "2026-02-01T00:00:00Z".to_string(),
+            content_encoding: PackEncoding::Identity,
+            max_pack_bytes: None,
+            locale_max_pack_bytes: BTreeMap::new(),
+            source_date_epoch: None,
+        })
+        .expect("build");
+
+        let overlay = mf2_i18n_core::PackCatalog::decode(
+            &fs::read(out_dir.join("packs/en-x-pirate.mf2pack")).expect("pack"),
+            &id_map_hash,
+        )
+        .expect("decode");
+        assert_eq!(
+            overlay.message_ids().collect::<Vec<_>>(),
+            vec![mf2_i18n_core::MessageId::new(2)]
+        );
+
+        let runtime = mf2_i18n_runtime::Runtime::load_from_paths(
+            &out_dir.join("manifest.json"),
+            &dir.join("id_map.json"),
+        )
+        .expect("runtime");
+        let args = mf2_i18n_core::Args::new();
+        assert_eq!(
+            runtime
+                .format("en-x-pirate", "home.title", &args)
+                .expect("title"),
+            "Hi"
+        );
+        assert_eq!(
+            runtime
+                .format("en-x-pirate", "home.greeting", &args)
+                .expect("greeting"),
+            "Ahoy"
+        );
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn stamps_packs_with_a_reproducible_build_epoch() {
+        let dir = temp_dir();
+        let (catalog_path, hash_path, config_path) = write_project(&dir);
+        let mut options = BuildOptions {
+            catalog_path,
+            id_map_hash_path: hash_path,
+            config_path,
+            out_dir: dir.join("first"),
+            release_id: "r1".to_string(),
+            generated_at: "2026-02-01T00:00:00Z".to_string(),
+            content_encoding: PackEncoding::Identity,
+            max_pack_bytes: None,
+            locale_max_pack_bytes: BTreeMap::new(),
+            source_date_epoch: None,
+        };
+        let pack_header = |out_dir: &std::path::Path| {
+            let bytes = fs::read(out_dir.join("packs/en.mf2pack")).expect("pack");
+            let (header, _) = mf2_i18n_core::parse_pack_header(&bytes).expect("header");
+            (bytes, header.build_epoch_ms)
+        };
+
+        run_build(&options).expect("build");
+        let (first, epoch) = pack_header(&options.out_dir);
+        assert_eq!(epoch, 1_769_904_000_000);
+
+        options.out_dir = dir.join("second");
+        run_build(&options).expect("build");
+        assert_eq!(pack_header(&options.out_dir).0, first);
+
+        options.source_date_epoch = Some(1_700_000_000);
+        run_build(&options).expect("build");
+        assert_eq!(pack_header(&options.out_dir).1, 1_700_000_000_000);
+
+        options.source_date_epoch = None;
+        options.generated_at = "last tuesday".to_string();
+        assert!(matches!(
+            run_build(&options),
+            Err(BuildCommandError::InvalidGeneratedAt(_))
+        ));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn builds_compressed_packs() {
+        let dir = temp_dir();
+        let (catalog_path, hash_path, config_path) = write_project(&dir);
+
+        let out_dir = dir.join("out");
+        run_build(&BuildOptions {
+            catalog_path,
+            id_map_hash_path: hash_path,
+            config_path,
+            out_dir: out_dir.clone(),
+            release_id: "r1".to_string(),
+            generated_at: "2026-02-01T00:00:00Z".to_string(),
+            content_encoding: PackEncoding::Gzip,
+            max_pack_bytes: None,
+            locale_max_pack_bytes: BTreeMap::new(),
+            source_date_epoch: None,
+        })
+        .expect("build");
+
+        let manifest: Manifest =
+            serde_json::from_slice(&fs::read(out_dir.join("manifest.json")).expect("manifest"))
+                .expect("json");
+        let entry = &manifest.mf2_packs["en"];
+        assert_eq!(entry.content_encoding, "gzip");
+        assert_eq!(entry.url, "packs/en.mf2pack.gz");
+        let stored = fs::read(out_dir.join(&entry.url)).expect("pack");
+        assert_eq!(stored.len() as u64, entry.size);
+        assert_eq!(&stored[..2], &[0x1f, 0x8b]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
 }
@@ -0,0 +1,398 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use mf2_i18n_core::{IcuDataPack, MessageId, PackCatalog};
+use mf2_i18n_runtime::verify_manifest_signature;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::command_build::PackEncoding;
+use crate::command_sign::{SignCommandError, load_verifying_key};
+use crate::id_map::IdMap;
+use crate::manifest::{Manifest, PackEntry, sha256_hex};
+
+#[derive(Debug, Error)]
+pub enum CheckCommandError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Key(#[from] SignCommandError),
+    #[error("release check failed: {}", .0.join(", "))]
+    Failed(Vec<String>),
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckOptions {
+    pub manifest_path: PathBuf,
+    pub id_map_path: PathBuf,
+    pub out_path: PathBuf,
+    pub trusted_keys: BTreeMap<String, PathBuf>,
+}
+
+#[derive(Debug, Serialize)]
+struct CheckReport {
+    release_id: String,
+    id_map: CheckStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signature: Option<CheckStatus>,
+    packs: BTreeMap<String, CheckStatus>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    icu_packs: BTreeMap<String, CheckStatus>,
+}
+
+#[derive(Debug, Serialize)]
+struct CheckStatus {
+    ok: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    problems: Vec<String>,
+}
+
+impl CheckStatus {
+    fn from_problems(problems: Vec<String>) -> Self {
+        Self {
+            ok: problems.is_empty(),
+            problems,
+        }
+    }
+}
+
+pub fn run_check(options: &CheckOptions) -> Result<(), CheckCommandError> {
+    let manifest_bytes = fs::read(&options.manifest_path)?;
+    let manifest: Manifest = serde_json::from_slice(&manifest_bytes)?;
+    let base_dir = options
+        .manifest_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."));
+    let expected_hash = parse_id_map_hash(&manifest.id_map_hash);
+
+    let id_map = CheckStatus::from_problems(check_id_map(
+        &options.id_map_path,
+        &manifest.id_map_hash,
+        expected_hash,
+    ));
+
+    let signature = if options.trusted_keys.is_empty() {
+        None
+    } else {
+        let mut keyring = BTreeMap::new();
+        for (key_id, path) in &options.trusted_keys {
+            keyring.insert(key_id.clone(), load_verifying_key(path)?);
+        }
+        let runtime_manifest: mf2_i18n_runtime::Manifest = serde_json::from_slice(&manifest_bytes)?;
+        let problems = if runtime_manifest.signing.is_none() {
+            vec!["manifest is not signed".to_string()]
+        } else {
+            match verify_manifest_signature(&runtime_manifest, &keyring) {
+                Ok(()) => Vec::new(),
+                Err(err) => vec![err.to_string()],
+            }
+        };
+        Some(CheckStatus::from_problems(problems))
+    };
+
+    let mut packs = BTreeMap::new();
+    for (locale, entry) in &manifest.mf2_packs {
+        let mut problems = Vec::new();
+        if let Some(parent) = &entry.parent
+            && !manifest.mf2_packs.contains_key(parent)
+        {
+            problems.push(format!("parent {parent} has no pack"));
+        }
+        if let Some(bytes) = read_pack(base_dir, entry, &mut problems) {
+            match expected_hash {
+                Some(hash) => {
+                    if let Err(err) = PackCatalog::decode(&bytes, &hash) {
+                        problems.push(format!("decode failed: {err:?}"));
+                    }
+                }
+                None => problems.push("cannot decode without a valid id map hash".to_string()),
+            }
+        }
+        packs.insert(locale.clone(), CheckStatus::from_problems(problems));
+    }
+
+    let mut icu_packs = BTreeMap::new();
+    for (locale, entry) in manifest.icu_packs.iter().flatten() {
+        let mut problems = Vec::new();
+        if let Some(bytes) = read_pack(base_dir, entry, &mut problems)
+            && let Err(err) = IcuDataPack::decode(&bytes)
+        {
+            problems.push(format!("decode failed: {err:?}"));
+        }
+        icu_packs.insert(locale.clone(), CheckStatus::from_problems(problems));
+    }
+
+    let report = CheckReport {
+        release_id: manifest.release_id.clone(),
+        id_map,
+        signature,
+        packs,
+        icu_packs,
+    };
+    fs::write(&options.out_path, serde_json::to_string_pretty(&report)?)?;
+
+    let mut failures = Vec::new();
+    let mut collect = |scope: &str, status: &CheckStatus| {
+        for problem in &status.problems {
+            failures.push(format!("{scope}: {problem}"));
+        }
+    };
+    collect("id map", &report.id_map);
+    if let Some(signature) = &report.signature {
+        collect("signature", signature);
+    }
+    for (locale, status) in &report.packs {
+        collect(locale, status);
+    }
+    for (locale, status) in &report.icu_packs {
+        collect(&format!("icu {locale}"), status);
+    }
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(CheckCommandError::Failed(failures))
+    }
+}
+
+fn check_id_map(path: &Path, declared: &str, expected: Option<[u8; 32]>) -> Vec<String> {
+    let Some(expected) = expected else {
+        return vec![format!("invalid manifest id map hash {declared}")];
+    };
+    let entries: BTreeMap<String, u64> = match fs::read(path)
+        .map_err(|err| err.to_string())
+        .and_then(|bytes| serde_json::from_slice(&bytes).map_err(|err| err.to_string()))
+    {
+        Ok(entries) => entries,
+        Err(err) => return vec![format!("cannot read id map: {err}")],
+    };
+    let mut id_map = IdMap::new();
+    for (key, id) in entries {
+        if let Err(err) = id_map.insert(key, MessageId::new(id)) {
+            return vec![err.to_string()];
+        }
+    }
+    match id_map.hash() {
+        Ok(actual) if actual == expected => Vec::new(),
+        Ok(actual) => vec![format!(
+            "hash sha256:{} does not match manifest {declared}",
+            hex::encode(actual)
+        )],
+        Err(err) => vec![err.to_string()],
+    }
+}
+
+fn read_pack(base_dir: &Path, entry: &PackEntry, problems: &mut Vec<String>) -> Option<Vec<u8>> {
+    let stored = match fs::read(base_dir.join(&entry.url)) {
+        Ok(stored) => stored,
+        Err(err) => {
+            problems.push(format!("cannot read {}: {err}", entry.url));
+            return None;
+        }
+    };
+    if stored.len() as u64 != entry.size {
+        problems.push(format!(
+            "size {} does not match manifest {}",
+            stored.len(),
+            entry.size
+        ));
+    }
+    let hash = sha256_hex(&stored);
+    if hash != entry.hash {
+        problems.push(format!(
+            "hash {hash} does not match manifest {}",
+            entry.hash
+        ));
+    }
+    let Some(encoding) = PackEncoding::parse(&entry.content_encoding) else {
+        problems.push(format!(
+            "unsupported content encoding {}",
+            entry.content_encoding
+        ));
+        return None;
+    };
+    match encoding.decode(&stored) {
+        Ok(bytes) => Some(bytes),
+        Err(err) => {
+            problems.push(format!("cannot decode {}: {err}", entry.content_encoding));
+            None
+        }
+    }
+}
+
+fn parse_id_map_hash(value: &str) -> Option<[u8; 32]> {
+    let hex = value.strip_prefix("sha256:").unwrap_or(value);
+    hex::decode(hex).ok()?.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CheckCommandError, CheckOptions, run_check};
+    use crate::catalog::{Catalog, CatalogFeatures, CatalogMessage};
+    use crate::command_build::{BuildOptions, PackEncoding, run_build};
+    use crate::command_sign::{SignOptions, run_sign};
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_dir() -> PathBuf {
+        let mut path = std::env::temp_dir();
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        path.push(format!("mf2_i18n_check_{nanos}"));
+        fs::create_dir_all(&path).expect("dir");
+        path
+    }
+
+    fn build_release(dir: &std::path::Path) -> PathBuf {
+        for (locale, text) in [("en", "Hi"), ("fr", "Salut")] {
+            let locale_dir = dir.join("locales").join(locale);
+            fs::create_dir_all(&locale_dir).expect("locale");
+            fs::write(
+                locale_dir.join("messages.mf2"),
+                format!("home.title = {text}"),
+            )
+            .expect("write");
+        }
+        let catalog = Catalog {
+            schema: 1,
+            project: "demo".to_string(),
+            generated_at: "2026-02-01T00:00:00Z".to_string(),
+            default_locale: "en".to_string(),
+            messages: vec![CatalogMessage {
+                key: "home.title".to_string(),
+                id: 1,
+                args: vec![],
+                features: CatalogFeatures::default(),
+                source_refs: None,
+            }],
+        };
+        let catalog_path = dir.join("i18n.catalog.json");
+        fs::write(&catalog_path, serde_json::to_string(&catalog).unwrap()).expect("catalog");
+        let id_map_hash =
+            mf2_i18n_core::id_map_hash([("home.title", mf2_i18n_core::MessageId::new(1))])
+                .expect("hash");
+        let hash_path = dir.join("id_map_hash");
+        fs::write(&hash_path, format!("sha256:{}", hex::encode(id_map_hash))).expect("hash");
+        fs::write(dir.join("id_map.json"), r#"{"home.title": 1}"#).expect("id map");
+        let config_path = dir.join("mf2-i18n.toml");
+        fs::write(
+            &config_path,
+            "default_locale = \"en\"\nsource_dirs = [\"locales\"]\nproject_salt_path = \"tools/id_salt.txt\"",
+        )
+        .expect("config");
+
+        let out_dir = dir.join("out");
+        run_build(&BuildOptions {
+            catalog_path,
+            id_map_hash_path: hash_path,
+            config_path,
+            out_dir: out_dir.clone(),
+            release_id: "r1".to_string(),
+            generated_at: "2026-02-01T00:00:00Z".to_string(),
+            content_encoding: PackEncoding::Identity,
+            max_pack_bytes: None,
+            locale_max_pack_bytes: BTreeMap::new(),
+            source_date_epoch: None,
+        })
+        .expect("build");
+        out_dir
+    }
+
+    #[test]
+    fn passes_a_clean_release() {
+        let dir = temp_dir();
+        let out_dir = build_release(&dir);
+        run_check(&CheckOptions {
+            manifest_path: out_dir.join("manifest.json"),
+            id_map_path: dir.join("id_map.json"),
+            out_path: dir.join("check.json"),
+            trusted_keys: BTreeMap::new(),
+        })
+        .expect("check");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn reports_every_problem_in_one_run() {
+        let dir = temp_dir();
+        let out_dir = build_release(&dir);
+        let pack_path = out_dir.join("packs/fr.mf2pack");
+        let mut pack = fs::read(&pack_path).expect("pack");
+        let last = pack.len() - 1;
+        pack[last] ^= 0xff;
+        fs::write(&pack_path, pack).expect("corrupt");
+        fs::write(dir.join("id_map.json"), r#"{"home.title": 2}"#).expect("id map");
+
+        let options = CheckOptions {
+            manifest_path: out_dir.join("manifest.json"),
+            id_map_path: dir.join("id_map.json"),
+            out_path: dir.join("check.json"),
+            trusted_keys: BTreeMap::new(),
+        };
+        let err = run_check(&options).expect_err("check should fail");
+        let CheckCommandError::Failed(failures) = &err else {
+            panic!("unexpected error {err}");
+        };
+        assert!(
+            failures
+                .iter()
+                .any(|failure| failure.starts_with("id map: "))
+        );
+        assert!(
+            failures
+                .iter()
+                .any(|failure| failure.starts_with("fr: hash "))
+        );
+        assert!(!failures.iter().any(|failure| failure.starts_with("en: ")));
+
+        let report: serde_json::Value =
+            serde_json::from_slice(&fs::read(&options.out_path).expect("report")).expect("json");
+        assert_eq!(report["id_map"]["ok"], false);
+        assert_eq!(report["packs"]["en"]["ok"], true);
+        assert_eq!(report["packs"]["fr"]["ok"], false);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn verifies_signatures_with_trusted_keys() {
+        let dir = temp_dir();
+        let out_dir = build_release(&dir);
+        let pubkey_path = dir.join("release.pub");
+        let verifying_key = ed25519_dalek::SigningKey::from_bytes(&[3u8; 32]).verifying_key();
+        fs::write(&pubkey_path, hex::encode(verifying_key.to_bytes())).expect("write");
+        let options = CheckOptions {
+            manifest_path: out_dir.join("manifest.json"),
+            id_map_path: dir.join("id_map.json"),
+            out_path: dir.join("check.json"),
+            trusted_keys: BTreeMap::from([("release".to_string(), pubkey_path)]),
+        };
+        let err = run_check(&options).expect_err("unsigned");
+        assert!(
+            err.to_string()
+                .contains("signature: manifest is not signed")
+        );
+
+        let key_path = dir.join("release.key");
+        fs::write(&key_path, hex::encode([3u8; 32])).expect("write");
+        run_sign(&SignOptions {
+            manifest_path: options.manifest_path.clone(),
+            key_path,
+            key_id: "release".to_string(),
+            out_path: None,
+            append: false,
+            sig_alg: None,
+        })
+        .expect("sign");
+        run_check(&options).expect("check");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -6,7 +6,9 @@ use serde::Serialize;
 use thiserror::Error;
 
 use crate::catalog_reader::{CatalogReadError, load_catalog};
+use crate::command_export::xml_escape;
 use crate::config::load_config_or_default;
+use crate::diagnostic::Diagnostic;
 use crate::error::CliError;
 use crate::locale_sources::{LocaleSourceError, load_locales};
 
@@ -22,6 +24,34 @@ pub enum CoverageCommandError {
     Io(#[from] std::io::Error),
     #[error("json error: {0}")]
     Json(#[from] serde_json::Error),
+    #[error("coverage below threshold: {}", .0.join(", "))]
+    BelowThreshold(Vec<String>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoverageFormat {
+    Json,
+    Html,
+    Markdown,
+}
+
+impl CoverageFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "json" => Some(Self::Json),
+            "html" => Some(Self::Html),
+            "markdown" | "md" => Some(Self::Markdown),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Html => "html",
+            Self::Markdown => "md",
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -30,6 +60,9 @@ pub struct CoverageOptions {
     pub id_map_hash_path: PathBuf,
     pub out_path: PathBuf,
     pub config_path: PathBuf,
+    pub format: CoverageFormat,
+    pub min_percent: Option<f64>,
+    pub locale_min_percent: BTreeMap<String, f64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -47,7 +80,7 @@ struct LocaleCoverage {
     missing_keys: Vec<String>,
 }
 
-pub fn run_coverage(options: &CoverageOptions) -> Result<(), CoverageCommandError> {
+pub fn run_coverage(options: &CoverageOptions) -> Result<Vec<Diagnostic>, CoverageCommandError> {
     let config = load_config_or_default(&options.config_path)?;
     let base_dir = options
         .config_path
@@ -60,7 +93,7 @@ pub fn run_coverage(options: &CoverageOptions) -> Result<(), CoverageCommandErro
         .collect();
 
     let catalog = load_catalog(&options.catalog_path, &options.id_map_hash_path)?;
-    let locales = load_locales(&roots)?;
+    let locales = load_locales(&roots, config.duplicate_keys)?;
 
     let mut specs = BTreeSet::new();
     for key in catalog.message_specs.keys() {
@@ -69,8 +102,10 @@ pub fn run_coverage(options: &CoverageOptions) -> Result<(), CoverageCommandErro
 
     let total = specs.len();
     let mut report_locales = BTreeMap::new();
+    let mut diagnostics = Vec::new();
 
     for locale in locales {
+        diagnostics.extend(locale.diagnostics);
         let mut missing = Vec::new();
         let mut present = 0usize;
         let mut extra = 0usize;
@@ -107,18 +142,112 @@ pub fn run_coverage(options: &CoverageOptions) -> Result<(), CoverageCommandErro
         total_messages: total,
         locales: report_locales,
     };
-    let json = serde_json::to_string_pretty(&report)?;
-    fs::write(&options.out_path, json)?;
-    Ok(())
+    let contents = match options.format {
+        CoverageFormat::Json => serde_json::to_string_pretty(&report)?,
+        CoverageFormat::Html => render_html(&report),
+        CoverageFormat::Markdown => render_markdown(&report),
+    };
+    fs::write(&options.out_path, contents)?;
+
+    let mut failures = Vec::new();
+    for (locale, coverage) in &report.locales {
+        let threshold = options
+            .locale_min_percent
+            .get(locale)
+            .copied()
+            .or(options.min_percent);
+        if let Some(threshold) = threshold
+            && coverage.percent < threshold
+        {
+            failures.push(format!(
+                "{locale} ({:.1}% < {threshold:.1}%)",
+                coverage.percent
+            ));
+        }
+    }
+    if !failures.is_empty() {
+        return Err(CoverageCommandError::BelowThreshold(failures));
+    }
+    Ok(diagnostics)
+}
+
+fn render_markdown(report: &CoverageReport) -> String {
+    let mut out = format!(
+        "# Translation coverage\n\nTotal messages: {}\n\n| Locale | Coverage | Present | Missing | Extra |\n| --- | ---: | ---: | ---: | ---: |\n",
+        report.total_messages
+    );
+    for (locale, coverage) in &report.locales {
+        out.push_str(&format!(
+            "| {} | {:.1}% | {} | {} | {} |\n",
+            locale.replace('|', "\\|"),
+            coverage.percent,
+            coverage.present,
+            coverage.missing,
+            coverage.extra
+        ));
+    }
+    out
+}
+
+const HTML_SORT_SCRIPT: &str = "document.querySelectorAll('th[data-col]').forEach(function (th) {
+  th.addEventListener('click', function () {
+    var body = th.closest('table').tBodies[0];
+    var col = Number(th.dataset.col);
+    var asc = th.dataset.dir !== 'asc';
+    th.dataset.dir = asc ? 'asc' : 'desc';
+    Array.from(body.rows).sort(function (a, b) {
+      var x = a.cells[col].dataset.sort, y = b.cells[col].dataset.sort;
+      var cmp = isNaN(x) ? x.localeCompare(y) : Number(x) - Number(y);
+      return asc ? cmp : -cmp;
+    }).forEach(function (row) { body.appendChild(row); });
+  });
+});";
+
+fn render_html(report: &CoverageReport) -> String {
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Translation coverage</title>\n</head>\n<body>\n",
+    );
+    out.push_str(&format!(
+        "<h1>Translation coverage</h1>\n<p>Total messages: {}</p>\n",
+        report.total_messages
+    ));
+    out.push_str("<table>\n<thead>\n<tr><th data-col=\"0\">Locale</th><th data-col=\"1\">Coverage</th><th data-col=\"2\">Missing</th><th>Missing keys</th></tr>\n</thead>\n<tbody>\n");
+    for (locale, coverage) in &report.locales {
+        let locale = xml_escape(locale);
+        out.push_str(&format!(
+            "<tr><td data-sort=\"{locale}\">{locale}</td><td data-sort=\"{percent}\">{percent:.1}%</td><td data-sort=\"{missing}\">{missing}</td><td>",
+            percent = coverage.percent,
+            missing = coverage.missing
+        ));
+        if !coverage.missing_keys.is_empty() {
+            out.push_str(&format!(
+                "<details><summary>{} keys</summary><ul>",
+                coverage.missing_keys.len()
+            ));
+            for key in &coverage.missing_keys {
+                out.push_str(&format!("<li>{}</li>", xml_escape(key)));
+            }
+            out.push_str("</ul></details>");
+        }
+        out.push_str("</td></tr>\n");
+    }
+    out.push_str("</tbody>\n</table>\n<script>\n");
+    out.push_str(HTML_SORT_SCRIPT);
+    out.push_str("\n</script>\n</body>\n</html>\n");
+    out
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{CoverageOptions, run_coverage};
+    use super::{
+        CoverageCommandError, CoverageFormat, CoverageOptions, CoverageReport, LocaleCoverage,
+        render_html, render_markdown, run_coverage,
+    };
     use crate::catalog::{Catalog, CatalogFeatures, CatalogMessage};
     use crate::model::{ArgSpec, ArgType};
+    use std::collections::BTreeMap;
     use std::fs;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
     use std::time::{SystemTime, UNIX_EPOCH};
 
     fn temp_dir(name: &str) -> PathBuf {
@@ -132,12 +261,10 @@ mod tests {
         path
     }
 
-    #[test]
-    fn writes_coverage_report() {
-        let root = temp_dir("coverage_root");
+    fn write_project(root: &Path, source: &str, keys: &[&str]) -> CoverageOptions {
         let locale_dir = root.join("en");
         fs::create_dir_all(&locale_dir).expect("locale");
-        fs::write(locale_dir.join("messages.mf2"), "home.title = Hello").expect("write");
+        fs::write(locale_dir.join("messages.mf2"), source).expect("write");
 
         let config_path = root.join("mf2-i18n.toml");
         fs::write(
@@ -151,17 +278,21 @@ mod tests {
             project: "demo".to_string(),
             generated_at: "2026-02-01T00:00:00Z".to_string(),
             default_locale: "en".to_string(),
-            messages: vec![CatalogMessage {
-                key: "home.title".to_string(),
-                id: 1,
-                args: vec![ArgSpec {
-                    name: "name".to_string(),
-                    arg_type: ArgType::String,
-                    required: false,
-                }],
-                features: CatalogFeatures::default(),
-                source_refs: None,
-            }],
+            messages: keys
+                .iter()
+                .enumerate()
+                .map(|(idx, key)| CatalogMessage {
+                    key: key.to_string(),
+                    id: idx as u64 + 1,
+                    args: vec![ArgSpec {
+                        name: "name".to_string(),
+                        arg_type: ArgType::String,
+                        required: false,
+                    }],
+                    features: CatalogFeatures::default(),
+                    source_refs: None,
+                })
+                .collect(),
         };
         let catalog_path = root.join("catalog.json");
         fs::write(
@@ -176,18 +307,89 @@ mod tests {
         )
         .expect("write hash");
 
-        let out_path = root.join("coverage.json");
-        let options = CoverageOptions {
+        CoverageOptions {
             catalog_path,
             id_map_hash_path: hash_path,
-            out_path: out_path.clone(),
+            out_path: root.join("coverage.json"),
             config_path,
-        };
+            format: CoverageFormat::Json,
+            min_percent: None,
+            locale_min_percent: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn writes_coverage_report() {
+        let root = temp_dir("coverage_root");
+        let options = write_project(&root, "home.title = Hello", &["home.title"]);
         run_coverage(&options).expect("run");
-        let contents = fs::read_to_string(&out_path).expect("read");
+        let contents = fs::read_to_string(&options.out_path).expect("read");
         assert!(contents.contains("\"total_messages\""));
         assert!(contents.contains("\"present\""));
 
         fs::remove_dir_all(&root).ok();
     }
+
+    #[test]
+    fn fails_locales_below_threshold_after_writing_report() {
+        let root = temp_dir("coverage_threshold");
+        let mut options = write_project(&root, "home.title = Hello", &["home.title", "home.body"]);
+        options.min_percent = Some(90.0);
+        let err = run_coverage(&options).expect_err("below threshold");
+        match err {
+            CoverageCommandError::BelowThreshold(locales) => {
+                assert_eq!(locales, vec!["en (50.0% < 90.0%)".to_string()]);
+            }
+            other => panic!("unexpected error {other}"),
+        }
+        assert!(options.out_path.exists());
+
+        options.locale_min_percent.insert("en".to_string(), 50.0);
+        run_coverage(&options).expect("override");
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    fn sample_report() -> CoverageReport {
+        let mut locales = BTreeMap::new();
+        locales.insert(
+            "en".to_string(),
+            LocaleCoverage {
+                present: 2,
+                missing: 0,
+                extra: 0,
+                percent: 100.0,
+                missing_keys: Vec::new(),
+            },
+        );
+        locales.insert(
+            "fr".to_string(),
+            LocaleCoverage {
+                present: 1,
+                missing: 1,
+                extra: 0,
+                percent: 50.0,
+                missing_keys: vec!["<b>.title".to_string()],
+            },
+        );
+        CoverageReport {
+            total_messages: 2,
+            locales,
+        }
+    }
+
+    #[test]
+    fn renders_markdown_row_per_locale() {
+        let markdown = render_markdown(&sample_report());
+        assert!(markdown.contains("| en | 100.0% | 2 | 0 | 0 |\n"));
+        assert!(markdown.contains("| fr | 50.0% | 1 | 1 | 0 |\n"));
+    }
+
+    #[test]
+    fn renders_html_with_escaped_keys() {
+        let html = render_html(&sample_report());
+        assert!(html.contains("<li>&lt;b&gt;.title</li>"));
+        assert!(!html.contains("<b>.title"));
+        assert!(html.contains("<td data-sort=\"50\">50.0%</td>"));
+    }
 }
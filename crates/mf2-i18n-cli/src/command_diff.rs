@@ -0,0 +1,259 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::catalog_reader::{CatalogReadError, load_catalog};
+use crate::model::{ArgSpec, MessageSpec};
+
+#[derive(Debug, Error)]
+pub enum DiffCommandError {
+    #[error(transparent)]
+    Catalog(#[from] CatalogReadError),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Debug, Clone)]
+pub struct DiffOptions {
+    pub old_catalog_path: PathBuf,
+    pub old_id_map_hash_path: PathBuf,
+    pub new_catalog_path: PathBuf,
+    pub new_id_map_hash_path: PathBuf,
+    pub out_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Serialize, PartialEq)]
+pub struct CatalogDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<ChangedMessage>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ChangedMessage {
+    pub key: String,
+    pub args: Vec<ArgChange>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ArgChange {
+    pub name: String,
+    pub old: Option<ArgSpec>,
+    pub new: Option<ArgSpec>,
+}
+
+pub fn run_diff(options: &DiffOptions) -> Result<CatalogDiff, DiffCommandError> {
+    let old = load_catalog(&options.old_catalog_path, &options.old_id_map_hash_path)?;
+    let new = load_catalog(&options.new_catalog_path, &options.new_id_map_hash_path)?;
+    let diff = diff_specs(&old.message_specs, &new.message_specs);
+    if let Some(out_path) = &options.out_path {
+        let json = serde_json::to_string_pretty(&diff)?;
+        fs::write(out_path, json)?;
+    }
+    Ok(diff)
+}
+
+pub fn render_diff(diff: &CatalogDiff) -> String {
+    let mut out = String::new();
+    render_keys(&mut out, "added", &diff.added);
+    render_keys(&mut out, "removed", &diff.removed);
+    out.push_str(&format!("changed ({}):\n", diff.changed.len()));
+    for message in &diff.changed {
+        out.push_str(&format!("  {}\n", message.key));
+        for arg in &message.args {
+            out.push_str(&format!(
+                "    ${}: {} -> {}\n",
+                arg.name,
+                describe_arg(arg.old.as_ref()),
+                describe_arg(arg.new.as_ref())
+            ));
+        }
+    }
+    out
+}
+
+fn render_keys(out: &mut String, label: &str, keys: &[String]) {
+    out.push_str(&format!("{label} ({}):\n", keys.len()));
+    for key in keys {
+        out.push_str(&format!("  {key}\n"));
+    }
+}
+
+fn describe_arg(spec: Option<&ArgSpec>) -> String {
+    match spec {
+        Some(spec) if spec.required => spec.arg_type.as_str().to_string(),
+        Some(spec) => format!("{} (optional)", spec.arg_type.as_str()),
+        None => "none".to_string(),
+    }
+}
+
+fn diff_specs(
+    old: &BTreeMap<String, MessageSpec>,
+    new: &BTreeMap<String, MessageSpec>,
+) -> CatalogDiff {
+    let mut diff = CatalogDiff::default();
+    for (key, new_spec) in new {
+        match old.get(key) {
+            None => diff.added.push(key.clone()),
+            Some(old_spec) => {
+                let args = diff_args(&old_spec.args, &new_spec.args);
+                if !args.is_empty() {
+                    diff.changed.push(ChangedMessage {
+                        key: key.clone(),
+                        args,
+                    });
+                }
+            }
+        }
+    }
+    for key in old.keys() {
+        if !new.contains_key(key) {
+            diff.removed.push(key.clone());
+        }
+    }
+    diff
+}
+
+fn diff_args(old: &[ArgSpec], new: &[ArgSpec]) -> Vec<ArgChange> {
+    let old_by_name: BTreeMap<&str, &ArgSpec> =
+        old.iter().map(|arg| (arg.name.as_str(), arg)).collect();
+    let new_by_name: BTreeMap<&str, &ArgSpec> =
+        new.iter().map(|arg| (arg.name.as_str(), arg)).collect();
+    let mut names: Vec<&str> = old_by_name
+        .keys()
+        .chain(new_by_name.keys())
+        .copied()
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+
+    let mut changes = Vec::new();
+    for name in names {
+        let old_arg = old_by_name.get(name).copied();
+        let new_arg = new_by_name.get(name).copied();
+        if old_arg != new_arg {
+            changes.push(ArgChange {
+                name: name.to_string(),
+                old: old_arg.cloned(),
+                new: new_arg.cloned(),
+            });
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DiffOptions, render_diff, run_diff};
+    use crate::catalog::{Catalog, CatalogFeatures, CatalogMessage};
+    use crate::model::{ArgSpec, ArgType};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        path.push(format!("mf2_i18n_{name}_{nanos}"));
+        fs::create_dir_all(&path).expect("dir");
+        path
+    }
+
+    fn message(key: &str, id: u64, args: Vec<ArgSpec>) -> CatalogMessage {
+        CatalogMessage {
+            key: key.to_string(),
+            id,
+            args,
+            features: CatalogFeatures::default(),
+            source_refs: None,
+        }
+    }
+
+    fn arg(name: &str, arg_type: ArgType) -> ArgSpec {
+        ArgSpec {
+            name: name.to_string(),
+            arg_type,
+            required: true,
+        }
+    }
+
+    fn write_catalog(dir: &Path, messages: Vec<CatalogMessage>) {
+        fs::create_dir_all(dir).expect("dir");
+        let catalog = Catalog {
+            schema: 1,
+            project: "demo".to_string(),
+            generated_at: "2026-02-01T00:00:00Z".to_string(),
+            default_locale: "en".to_string(),
+            messages,
+        };
+        fs::write(
+            dir.join("i18n.catalog.json"),
+            serde_json::to_string_pretty(&catalog).expect("json"),
+        )
+        .expect("write catalog");
+        fs::write(
+            dir.join("id_map_hash"),
+            "sha256:000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+        )
+        .expect("write hash");
+    }
+
+    #[test]
+    fn detects_added_removed_and_changed_args() {
+        let root = temp_dir("diff_root");
+        let old_dir = root.join("old");
+        let new_dir = root.join("new");
+        write_catalog(
+            &old_dir,
+            vec![
+                message("cart.count", 1, vec![arg("count", ArgType::String)]),
+                message("home.legacy", 2, Vec::new()),
+            ],
+        );
+        write_catalog(
+            &new_dir,
+            vec![
+                message("cart.count", 1, vec![arg("count", ArgType::Number)]),
+                message("home.title", 3, Vec::new()),
+            ],
+        );
+
+        let out_path = root.join("diff.json");
+        let diff = run_diff(&DiffOptions {
+            old_catalog_path: old_dir.join("i18n.catalog.json"),
+            old_id_map_hash_path: old_dir.join("id_map_hash"),
+            new_catalog_path: new_dir.join("i18n.catalog.json"),
+            new_id_map_hash_path: new_dir.join("id_map_hash"),
+            out_path: Some(out_path.clone()),
+        })
+        .expect("diff");
+
+        assert_eq!(diff.added, vec!["home.title".to_string()]);
+        assert_eq!(diff.removed, vec!["home.legacy".to_string()]);
+        assert_eq!(diff.changed.len(), 1);
+        let change = &diff.changed[0].args[0];
+        assert_eq!(change.name, "count");
+        assert_eq!(
+            change.old.as_ref().map(|arg| &arg.arg_type),
+            Some(&ArgType::String)
+        );
+        assert_eq!(
+            change.new.as_ref().map(|arg| &arg.arg_type),
+            Some(&ArgType::Number)
+        );
+        assert!(render_diff(&diff).contains("$count: string -> number"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(out_path).expect("read")).expect("json");
+        assert_eq!(json["changed"][0]["args"][0]["new"]["type"], "number");
+        fs::remove_dir_all(&root).ok();
+    }
+}
@@ -0,0 +1,95 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+
+use mf2_i18n_core::MessageId;
+use thiserror::Error;
+
+use crate::id_map::{IdMap, IdMapError};
+
+#[derive(Debug, Error)]
+pub enum EmitIdMapCommandError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    IdMap(#[from] IdMapError),
+}
+
+#[derive(Debug, Clone)]
+pub struct EmitIdMapOptions {
+    pub id_map_path: PathBuf,
+    pub out_path: PathBuf,
+}
+
+pub fn run_emit_id_map(options: &EmitIdMapOptions) -> Result<(), EmitIdMapCommandError> {
+    let entries: BTreeMap<String, u64> =
+        serde_json::from_str(&fs::read_to_string(&options.id_map_path)?)?;
+    let mut id_map = IdMap::new();
+    for (key, id) in entries {
+        id_map.insert(key, MessageId::new(id))?;
+    }
+    fs::write(&options.out_path, render_id_map_source(&id_map)?)?;
+    Ok(())
+}
+
+fn render_id_map_source(id_map: &IdMap) -> Result<String, IdMapError> {
+    let hash = id_map.hash()?;
+    let mut out = String::from("// @generated by mf2-i18n-cli emit-id-map. Do not edit.\n\n");
+    out.push_str("pub static ID_MAP: &[(&str, u64)] = &[\n");
+    for (key, id) in id_map.entries() {
+        let _ = writeln!(out, "    ({key:?}, {}),", u64::from(id));
+    }
+    out.push_str("];\n\npub const ID_MAP_HASH: [u8; 32] = [");
+    for (index, byte) in hash.iter().enumerate() {
+        if index % 8 == 0 {
+            out.push_str("\n   ");
+        }
+        let _ = write!(out, " 0x{byte:02x},");
+    }
+    out.push_str("\n];\n");
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EmitIdMapOptions, run_emit_id_map};
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        path.push(format!("mf2_i18n_{name}_{nanos}"));
+        fs::create_dir_all(&path).expect("dir");
+        path
+    }
+
+    #[test]
+    fn emits_sorted_static_id_map() {
+        let dir = temp_dir("emit_id_map");
+        let id_map_path = dir.join("id_map.json");
+        fs::write(&id_map_path, r#"{"home.title": 7, "cart.\"count\"": 3}"#).expect("write");
+        let out_path = dir.join("id_map.rs");
+        run_emit_id_map(&EmitIdMapOptions {
+            id_map_path,
+            out_path: out_path.clone(),
+        })
+        .expect("emit");
+
+        let source = fs::read_to_string(out_path).expect("read");
+        let cart = source.find("(\"cart.\\\"count\\\"\", 3),").expect("cart");
+        let home = source.find("(\"home.title\", 7),").expect("home");
+        assert!(cart < home);
+        assert!(source.contains("pub const ID_MAP_HASH: [u8; 32] = ["));
+        assert_eq!(source.matches(" 0x").count(), 32);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,295 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::catalog_reader::{CatalogReadError, load_catalog};
+use crate::config::load_config_or_default;
+use crate::diagnostic::Diagnostic;
+use crate::error::CliError;
+use crate::locale_sources::{LocaleBundle, LocaleSourceError, load_locales};
+
+#[derive(Debug, Error)]
+pub enum ExportCommandError {
+    #[error("config error: {0}")]
+    Config(#[from] CliError),
+    #[error(transparent)]
+    Catalog(#[from] CatalogReadError),
+    #[error(transparent)]
+    Sources(#[from] LocaleSourceError),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+    Xliff,
+}
+
+impl ExportFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            "xliff" => Some(Self::Xliff),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Csv => "csv",
+            Self::Xliff => "xlf",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    pub catalog_path: PathBuf,
+    pub id_map_hash_path: PathBuf,
+    pub locale: String,
+    pub format: ExportFormat,
+    pub out_path: PathBuf,
+    pub config_path: PathBuf,
+}
+
+struct ExportRow {
+    key: String,
+    source: String,
+    translation: String,
+}
+
+pub fn run_export(options: &ExportOptions) -> Result<Vec<Diagnostic>, ExportCommandError> {
+    let config = load_config_or_default(&options.config_path)?;
+    let base_dir = options
+        .config_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."));
+    let roots: Vec<PathBuf> = config
+        .source_dirs
+        .iter()
+        .map(|dir| base_dir.join(dir))
+        .collect();
+
+    let bundle = load_catalog(&options.catalog_path, &options.id_map_hash_path)?;
+    let locales = load_locales(&roots, config.duplicate_keys)?;
+    let source_locale = bundle.catalog.default_locale.clone();
+    let source = find_locale(&locales, &source_locale);
+    let target = find_locale(&locales, &options.locale);
+
+    let rows: Vec<ExportRow> = bundle
+        .message_specs
+        .keys()
+        .map(|key| ExportRow {
+            key: key.clone(),
+            source: message_value(source, key),
+            translation: message_value(target, key),
+        })
+        .collect();
+
+    let contents = match options.format {
+        ExportFormat::Json => render_json(&rows)?,
+        ExportFormat::Csv => render_csv(&rows),
+        ExportFormat::Xliff => render_xliff(&rows, &source_locale, &options.locale),
+    };
+    fs::write(&options.out_path, contents)?;
+    Ok(locales
+        .into_iter()
+        .flat_map(|locale| locale.diagnostics)
+        .collect())
+}
+
+fn find_locale<'a>(locales: &'a [LocaleBundle], locale: &str) -> Option<&'a LocaleBundle> {
+    locales.iter().find(|bundle| bundle.locale == locale)
+}
+
+fn message_value(bundle: Option<&LocaleBundle>, key: &str) -> String {
+    bundle
+        .and_then(|bundle| bundle.messages.get(key))
+        .map(|message| message.value.clone())
+        .unwrap_or_default()
+}
+
+fn render_json(rows: &[ExportRow]) -> Result<String, serde_json::Error> {
+    let map: BTreeMap<&str, &str> = rows
+        .iter()
+        .map(|row| (row.key.as_str(), row.translation.as_str()))
+        .collect();
+    serde_json::to_string_pretty(&map)
+}
+
+fn render_csv(rows: &[ExportRow]) -> String {
+    let mut out = String::from("key,source,translation\n");
+    for row in rows {
+        out.push_str(&csv_field(&row.key));
+        out.push(',');
+        out.push_str(&csv_field(&row.source));
+        out.push(',');
+        out.push_str(&csv_field(&row.translation));
+        out.push('\n');
+    }
+    out
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn render_xliff(rows: &[ExportRow], source_locale: &str, target_locale: &str) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!(
+        "<xliff xmlns=\"urn:oasis:names:tc:xliff:document:2.0\" version=\"2.0\" srcLang=\"{}\" trgLang=\"{}\">\n",
+        xml_escape(source_locale),
+        xml_escape(target_locale)
+    ));
+    out.push_str("  <file id=\"messages\">\n");
+    for row in rows {
+        out.push_str(&format!("    <unit id=\"{}\">\n", xml_escape(&row.key)));
+        out.push_str("      <segment>\n");
+        out.push_str(&format!(
+            "        <source>{}</source>\n",
+            xml_escape(&row.source)
+        ));
+        out.push_str(&format!(
+            "        <target>{}</target>\n",
+            xml_escape(&row.translation)
+        ));
+        out.push_str("      </segment>\n");
+        out.push_str("    </unit>\n");
+    }
+    out.push_str("  </file>\n</xliff>\n");
+    out
+}
+
+pub(crate) fn xml_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExportFormat, ExportOptions, run_export};
+    use crate::catalog::{Catalog, CatalogFeatures, CatalogMessage};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        path.push(format!("mf2_i18n_{name}_{nanos}"));
+        fs::create_dir_all(&path).expect("dir");
+        path
+    }
+
+    fn export(root: &Path, format: ExportFormat) -> String {
+        let out_path = root.join("export.out");
+        run_export(&ExportOptions {
+            catalog_path: root.join("catalog.json"),
+            id_map_hash_path: root.join("id_map_hash"),
+            locale: "fr".to_string(),
+            format,
+            out_path: out_path.clone(),
+            config_path: root.join("mf2-i18n.toml"),
+        })
+        .expect("export");
+        fs::read_to_string(out_path).expect("read")
+    }
+
+    fn write_project(root: &Path) {
+        fs::create_dir_all(root.join("en")).expect("en");
+        fs::create_dir_all(root.join("fr")).expect("fr");
+        fs::write(
+            root.join("en").join("messages.mf2"),
+            "home.title = Hello, { $name }\n\nabout.body = Say \"hi\"\n\ncart.count = 2 < 3",
+        )
+        .expect("write en");
+        fs::write(root.join("fr").join("messages.mf2"), "home.title = Bonjour").expect("write fr");
+        fs::write(
+            root.join("mf2-i18n.toml"),
+            "default_locale = \"en\"\nsource_dirs = [\".\"]\nmicro_locales_registry = \"micro-locales.toml\"\nproject_salt_path = \"tools/id_salt.txt\"\n",
+        )
+        .expect("write config");
+        let messages = ["home.title", "cart.count", "about.body"]
+            .iter()
+            .enumerate()
+            .map(|(idx, key)| CatalogMessage {
+                key: key.to_string(),
+                id: idx as u64,
+                args: Vec::new(),
+                features: CatalogFeatures::default(),
+                source_refs: None,
+            })
+            .collect();
+        let catalog = Catalog {
+            schema: 1,
+            project: "demo".to_string(),
+            generated_at: "2026-02-01T00:00:00Z".to_string(),
+            default_locale: "en".to_string(),
+            messages,
+        };
+        fs::write(
+            root.join("catalog.json"),
+            serde_json::to_string_pretty(&catalog).expect("json"),
+        )
+        .expect("write catalog");
+        fs::write(
+            root.join("id_map_hash"),
+            "sha256:000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+        )
+        .expect("write hash");
+    }
+
+    #[test]
+    fn exports_csv_sorted_by_key() {
+        let root = temp_dir("export_csv");
+        write_project(&root);
+        let csv = export(&root, ExportFormat::Csv);
+        assert_eq!(
+            csv,
+            "key,source,translation\nabout.body,\"Say \"\"hi\"\"\",\ncart.count,2 < 3,\nhome.title,\"Hello, { $name }\",Bonjour\n"
+        );
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn exports_json_and_xliff_with_empty_targets() {
+        let root = temp_dir("export_formats");
+        write_project(&root);
+        let json: serde_json::Value =
+            serde_json::from_str(&export(&root, ExportFormat::Json)).expect("json");
+        assert_eq!(json["home.title"], "Bonjour");
+        assert_eq!(json["cart.count"], "");
+
+        let xliff = export(&root, ExportFormat::Xliff);
+        assert!(xliff.contains("srcLang=\"en\" trgLang=\"fr\""));
+        assert!(xliff.contains(
+            "<unit id=\"cart.count\">\n      <segment>\n        <source>2 &lt; 3</source>\n        <target></target>"
+        ));
+        fs::remove_dir_all(&root).ok();
+    }
+}
@@ -3,9 +3,13 @@ use std::path::{Path, PathBuf};
 
 use thiserror::Error;
 
-use crate::artifacts::{write_catalog, write_id_map, write_id_map_hash};
+use crate::artifacts::{
+    read_id_map, write_catalog, write_id_map, write_id_map_hash, write_id_map_perturbations,
+};
 use crate::config::load_config_or_default;
-use crate::extract_pipeline::{ExtractPipelineError, extract_from_sources};
+use crate::diagnostic::Diagnostic;
+use crate::extract_pipeline::{ExtractPipelineError, ExtractedCatalog, extract_from_sources};
+use crate::id_map::IdAssignment;
 
 #[derive(Debug, Error)]
 pub enum ExtractCommandError {
@@ -26,25 +30,48 @@ pub struct ExtractOptions {
     pub generated_at: String,
 }
 
-pub fn run_extract(options: &ExtractOptions) -> Result<(), ExtractCommandError> {
+pub fn run_extract(options: &ExtractOptions) -> Result<Vec<Diagnostic>, ExtractCommandError> {
+    Ok(extract_artifacts(options)?.warnings)
+}
+
+pub fn extract_artifacts(
+    options: &ExtractOptions,
+) -> Result<ExtractedCatalog, ExtractCommandError> {
     let config = load_config_or_default(&options.config_path)?;
     let salt_path = resolve_path(&options.config_path, &config.project_salt_path);
     let salt = fs::read_to_string(&salt_path)?;
     let salt_bytes = salt.trim_end().as_bytes().to_vec();
-
-    let output = extract_from_sources(
+    let id_map_path = options.out_dir.join("id_map.json");
+    let perturbations_path = options.out_dir.join("id_map_perturbations.json");
+    let previous = if config.rehash_id_collisions {
+        Some(read_id_map(&id_map_path, &perturbations_path)?)
+    } else {
+        None
+    };
+    let ids = match &previous {
+        Some(previous) => IdAssignment::new(&salt_bytes).rehashing(previous),
+        None => IdAssignment::new(&salt_bytes),
+    };
+
+    let extracted = extract_from_sources(
         &options.roots,
         &options.project,
         &config.default_locale,
         &options.generated_at,
-        &salt_bytes,
+        &ids,
+        &config.extract_macros,
+        Some(&options.out_dir.join("extract_cache.json")),
     )?;
 
+    let output = &extracted.output;
     fs::create_dir_all(&options.out_dir)?;
     write_catalog(&options.out_dir.join("i18n.catalog.json"), &output.catalog)?;
     write_id_map_hash(&options.out_dir.join("id_map_hash"), output.id_map_hash)?;
-    write_id_map(&options.out_dir.join("id_map.json"), &output.id_map)?;
-    Ok(())
+    write_id_map(&id_map_path, &output.id_map)?;
+    if config.rehash_id_collisions {
+        write_id_map_perturbations(&perturbations_path, &output.id_map)?;
+    }
+    Ok(extracted)
 }
 
 fn resolve_path(config_path: &Path, value: &str) -> PathBuf {
@@ -61,6 +88,7 @@ fn resolve_path(config_path: &Path, value: &str) -> PathBuf {
 #[cfg(test)]
 mod tests {
     use super::{ExtractOptions, run_extract};
+    use crate::id_map::derive_message_id;
     use std::fs;
     use std::path::PathBuf;
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -109,4 +137,58 @@ mod tests {
 
         fs::remove_dir_all(&dir).ok();
     }
+
+    #[test]
+    fn rehashing_keeps_recorded_perturbations() {
+        let dir = temp_dir();
+        let src_dir = dir.join("src");
+        fs::create_dir_all(&src_dir).expect("src dir");
+        fs::write(
+            src_dir.join("lib.rs"),
+            "let _ = t!(\"home.title\"); let _ = t!(\"home.subtitle\");",
+        )
+        .expect("src");
+        fs::write(dir.join("id_salt.txt"), "salt").expect("salt");
+        let config_path = dir.join("mf2-i18n.toml");
+        fs::write(
+            &config_path,
+            "default_locale = \"en\"\nsource_dirs = [\"locales\"]\nproject_salt_path = \"id_salt.txt\"\nrehash_id_collisions = true\n",
+        )
+        .expect("config");
+
+        let out_dir = dir.join("out");
+        fs::create_dir_all(&out_dir).expect("out dir");
+        fs::write(out_dir.join("id_map.json"), r#"{"home.title": 1}"#).expect("id map");
+        fs::write(
+            out_dir.join("id_map_perturbations.json"),
+            r#"{"home.title": 2}"#,
+        )
+        .expect("perturbations");
+
+        let options = ExtractOptions {
+            project: "demo".to_string(),
+            roots: vec![src_dir],
+            out_dir: out_dir.clone(),
+            config_path,
+            generated_at: "2026-02-01T00:00:00Z".to_string(),
+        };
+        run_extract(&options).expect("run");
+        let id_map = fs::read_to_string(out_dir.join("id_map.json")).expect("id map");
+        let title = derive_message_id("home.title", b"salt", 2).get();
+        let subtitle = derive_message_id("home.subtitle", b"salt", 0).get();
+        assert!(id_map.contains(&format!("\"home.title\": {title}")));
+        assert!(id_map.contains(&format!("\"home.subtitle\": {subtitle}")));
+        let perturbations =
+            fs::read_to_string(out_dir.join("id_map_perturbations.json")).expect("read");
+        assert!(perturbations.contains("\"home.title\": 2"));
+        assert!(!perturbations.contains("home.subtitle"));
+
+        run_extract(&options).expect("rerun");
+        assert_eq!(
+            fs::read_to_string(out_dir.join("id_map.json")).expect("id map"),
+            id_map
+        );
+
+        fs::remove_dir_all(&dir).ok();
+    }
 }
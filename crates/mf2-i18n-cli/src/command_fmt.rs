@@ -0,0 +1,175 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::mf2_source::{
+    parse_mf2_document, push_source_comments, push_source_entry, serialize_message,
+};
+use crate::parser::{Expr, Message, Segment, parse_message};
+
+#[derive(Debug, Error)]
+pub enum FmtCommandError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("{}:{line}: {message}", .path.display())]
+    Parse {
+        path: PathBuf,
+        line: u32,
+        message: String,
+    },
+    #[error("not formatted: {}", .0.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join(", "))]
+    Unformatted(Vec<PathBuf>),
+}
+
+#[derive(Debug, Clone)]
+pub struct FmtOptions {
+    pub paths: Vec<PathBuf>,
+    pub check: bool,
+}
+
+pub fn run_fmt(options: &FmtOptions) -> Result<Vec<PathBuf>, FmtCommandError> {
+    let mut files = Vec::new();
+    for path in &options.paths {
+        collect_sources(path, &mut files)?;
+    }
+
+    let mut changed = Vec::new();
+    for path in files {
+        let input = fs::read_to_string(&path)?;
+        let output = format_source(&input).map_err(|(line, message)| FmtCommandError::Parse {
+            path: path.clone(),
+            line,
+            message,
+        })?;
+        if output == input {
+            continue;
+        }
+        if !options.check {
+            fs::write(&path, &output)?;
+        }
+        changed.push(path);
+    }
+
+    if options.check && !changed.is_empty() {
+        return Err(FmtCommandError::Unformatted(changed));
+    }
+    Ok(changed)
+}
+
+fn collect_sources(path: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    if !path.is_dir() {
+        files.push(path.to_path_buf());
+        return Ok(());
+    }
+    let mut entries: Vec<PathBuf> = fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<_, _>>()?;
+    entries.sort();
+    for entry in entries {
+        if entry.is_dir() {
+            collect_sources(&entry, files)?;
+        } else if entry.extension().is_some_and(|ext| ext == "mf2") {
+            files.push(entry);
+        }
+    }
+    Ok(())
+}
+
+fn format_source(input: &str) -> Result<String, (u32, String)> {
+    let (entries, trailing_comments) =
+        parse_mf2_document(input).map_err(|err| (err.line, err.message))?;
+    let mut out = String::new();
+    for entry in &entries {
+        let value = if entry.value.is_empty() {
+            String::new()
+        } else {
+            let mut message = parse_message(&entry.value).map_err(|err| {
+                (
+                    entry.line + err.span.line.saturating_sub(1),
+                    format!("{}: {}", entry.key, err.message),
+                )
+            })?;
+            order_cases(&mut message);
+            serialize_message(&message)
+        };
+        push_source_entry(&mut out, &entry.comments, &entry.key, &value);
+    }
+    push_source_comments(&mut out, &trailing_comments);
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+fn order_cases(message: &mut Message) {
+    for segment in &mut message.segments {
+        let Segment::Expr(Expr::Select(select)) = segment else {
+            continue;
+        };
+        select.cases.sort_by_key(|case| case.is_default);
+        for case in &mut select.cases {
+            order_cases(&mut case.value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FmtCommandError, FmtOptions, format_source, run_fmt};
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_dir() -> PathBuf {
+        let mut path = std::env::temp_dir();
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        path.push(format!("mf2_i18n_fmt_{nanos}"));
+        fs::create_dir_all(&path).expect("dir");
+        path
+    }
+
+    const MESSY: &str = "// greeting\nhome.title   =   Hi {$name}!\n\n\ncart.items = {$count :plural -> *[other]{# items}   [one]   {# item}}\n\n\n# keep me\n";
+    const CANONICAL: &str = "# greeting\nhome.title = Hi { $name }!\n\ncart.items = { $count :plural -> [one] {# item} *[other] {# items} }\n\n# keep me\n";
+
+    #[test]
+    fn formats_messy_messages_canonically() {
+        let formatted = format_source(MESSY).expect("format");
+        assert_eq!(formatted, CANONICAL);
+        assert_eq!(format_source(&formatted).expect("reformat"), formatted);
+    }
+
+    #[test]
+    fn check_fails_without_rewriting() {
+        let dir = temp_dir();
+        let messy = dir.join("locales/en/messages.mf2");
+        fs::create_dir_all(messy.parent().expect("parent")).expect("locale dir");
+        fs::write(&messy, MESSY).expect("write");
+        fs::write(dir.join("locales/en/clean.mf2"), CANONICAL).expect("write");
+        fs::write(dir.join("locales/en/notes.txt"), MESSY).expect("write");
+
+        let check = FmtOptions {
+            paths: vec![dir.join("locales")],
+            check: true,
+        };
+        let err = run_fmt(&check).expect_err("check should fail");
+        assert!(
+            matches!(&err, FmtCommandError::Unformatted(paths) if paths == std::slice::from_ref(&messy))
+        );
+        assert_eq!(fs::read_to_string(&messy).expect("read"), MESSY);
+
+        let changed = run_fmt(&FmtOptions {
+            paths: vec![dir.join("locales")],
+            check: false,
+        })
+        .expect("fmt");
+        assert_eq!(changed, vec![messy.clone()]);
+        assert_eq!(fs::read_to_string(&messy).expect("read"), CANONICAL);
+        assert!(run_fmt(&check).expect("clean").is_empty());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,688 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+use crate::diagnostic::Diagnostic;
+use crate::mf2_source::push_source_entry;
+use crate::parser::parse_message;
+
+#[derive(Debug, Error)]
+pub enum ImportFtlCommandError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("ftl parse error: {file}:{line} {message}")]
+    Parse {
+        file: String,
+        line: u32,
+        message: String,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportFtlOptions {
+    pub ftl_path: PathBuf,
+    pub locale: String,
+    pub out_dir: PathBuf,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct FtlEntry {
+    id: String,
+    comments: Vec<String>,
+    lines: Vec<String>,
+    attribute_lines: Vec<u32>,
+    line: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FtlError {
+    line: u32,
+    message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PatternError {
+    Syntax(String),
+    Unsupported(&'static str, &'static str),
+}
+
+struct ImportedMessage {
+    comments: Vec<String>,
+    value: String,
+}
+
+struct Conversion {
+    messages: BTreeMap<String, ImportedMessage>,
+    unsupported: Vec<(u32, &'static str, &'static str)>,
+}
+
+pub fn run_import_ftl(
+    options: &ImportFtlOptions,
+) -> Result<Vec<Diagnostic>, ImportFtlCommandError> {
+    let contents = fs::read_to_string(&options.ftl_path)?;
+    let file = options.ftl_path.display().to_string();
+    let conversion = parse_ftl(&contents)
+        .and_then(|entries| convert_entries(&entries))
+        .map_err(|err| ImportFtlCommandError::Parse {
+            file: file.clone(),
+            line: err.line,
+            message: err.message,
+        })?;
+
+    let output_dir = options.out_dir.join(&options.locale);
+    fs::create_dir_all(&output_dir)?;
+    let mut out = String::new();
+    for (key, message) in &conversion.messages {
+        push_source_entry(&mut out, &message.comments, key, &message.value);
+    }
+    fs::write(output_dir.join("messages.mf2"), out)?;
+
+    Ok(conversion
+        .unsupported
+        .into_iter()
+        .map(|(line, code, message)| {
+            Diagnostic::new(code, message).with_span(file.clone(), line, 1)
+        })
+        .collect())
+}
+
+fn parse_ftl(input: &str) -> Result<Vec<FtlEntry>, FtlError> {
+    let mut entries = Vec::new();
+    let mut current: Option<FtlEntry> = None;
+    let mut comments = Vec::new();
+    let mut pending_blank = 0usize;
+    let mut in_attribute = false;
+
+    for (idx, raw_line) in input.lines().enumerate() {
+        let line_no = (idx + 1) as u32;
+        let line = raw_line.trim_end();
+        let indented = line.starts_with([' ', '\t'])
+            || (current.is_some() && line.starts_with(['}', '[', '*']));
+        if line.is_empty() {
+            if current.is_some() {
+                pending_blank += 1;
+            } else {
+                comments.clear();
+            }
+            continue;
+        }
+        if indented {
+            let Some(entry) = current.as_mut() else {
+                return Err(ftl_error(line_no, "unexpected indented line"));
+            };
+            let trimmed = line.trim_start();
+            if trimmed.starts_with('.') {
+                entry.attribute_lines.push(line_no);
+                in_attribute = true;
+            } else if !in_attribute {
+                for _ in 0..pending_blank {
+                    entry.lines.push(String::new());
+                }
+                entry.lines.push(line.to_string());
+            }
+            pending_blank = 0;
+            continue;
+        }
+
+        if let Some(entry) = current.take() {
+            entries.push(entry);
+        }
+        pending_blank = 0;
+        in_attribute = false;
+        if let Some(comment) = line.strip_prefix('#') {
+            if comment.starts_with('#') {
+                comments.clear();
+            } else {
+                comments.push(comment.trim().to_string());
+            }
+            continue;
+        }
+        let (id, value) = line
+            .split_once('=')
+            .ok_or_else(|| ftl_error(line_no, "expected '=' after message id"))?;
+        let id = id.trim();
+        if !is_ftl_identifier(id.strip_prefix('-').unwrap_or(id)) {
+            return Err(ftl_error(line_no, "invalid message id"));
+        }
+        current = Some(FtlEntry {
+            id: id.to_string(),
+            comments: std::mem::take(&mut comments),
+            lines: vec![value.trim_start().to_string()],
+            attribute_lines: Vec::new(),
+            line: line_no,
+        });
+    }
+    if let Some(entry) = current {
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+fn convert_entries(entries: &[FtlEntry]) -> Result<Conversion, FtlError> {
+    let mut conversion = Conversion {
+        messages: BTreeMap::new(),
+        unsupported: Vec::new(),
+    };
+    for entry in entries {
+        if entry.id.starts_with('-') {
+            conversion
+                .unsupported
+                .push((entry.line, "MF2E200", "fluent terms are not supported"));
+            continue;
+        }
+        for line in &entry.attribute_lines {
+            conversion
+                .unsupported
+                .push((*line, "MF2E201", "fluent attributes are not supported"));
+        }
+        let source = pattern_source(&entry.lines);
+        if source.is_empty() {
+            continue;
+        }
+        let mut parser = PatternParser {
+            chars: source.chars().collect(),
+            pos: 0,
+        };
+        let value = match parser.parse_pattern(false) {
+            Ok(value) => value.trim().to_string(),
+            Err(PatternError::Unsupported(code, message)) => {
+                conversion.unsupported.push((entry.line, code, message));
+                continue;
+            }
+            Err(PatternError::Syntax(message)) => return Err(ftl_error(entry.line, &message)),
+        };
+        if value.lines().skip(1).any(|line| line.trim().is_empty()) {
+            return Err(ftl_error(
+                entry.line,
+                "blank lines cannot be represented in mf2 sources",
+            ));
+        }
+        parse_message(&value).map_err(|err| {
+            ftl_error(
+                entry.line,
+                &format!("converted message is not valid MF2: {}", err.message),
+            )
+        })?;
+        let key = entry.id.to_ascii_lowercase();
+        if conversion.messages.contains_key(&key) {
+            return Err(ftl_error(entry.line, "duplicate message id"));
+        }
+        conversion.messages.insert(
+            key,
+            ImportedMessage {
+                comments: entry.comments.clone(),
+                value,
+            },
+        );
+    }
+    Ok(conversion)
+}
+
+fn pattern_source(lines: &[String]) -> String {
+    let indent = lines
+        .iter()
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let mut out = lines.first().cloned().unwrap_or_default();
+    for line in lines.iter().skip(1) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(line.get(indent..).unwrap_or(""));
+    }
+    out.trim_end().to_string()
+}
+
+struct PatternParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl PatternParser {
+    fn parse_pattern(&mut self, in_variant: bool) -> Result<String, PatternError> {
+        let mut out = String::new();
+        while let Some(ch) = self.peek() {
+            match ch {
+                '{' => {
+                    self.pos += 1;
+                    out.push_str(&self.parse_placeable()?);
+                    continue;
+                }
+                '}' if in_variant => break,
+                '}' => return Err(syntax("unbalanced closing brace")),
+                '\n' if in_variant && self.next_line_starts_variant() => break,
+                _ => out.push(ch),
+            }
+            self.pos += 1;
+        }
+        Ok(out)
+    }
+
+    fn parse_placeable(&mut self) -> Result<String, PatternError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('$') => {
+                self.pos += 1;
+                let name = self.parse_identifier()?;
+                self.finish_expression(name, None, Vec::new())
+            }
+            Some('"') => {
+                let literal = self.parse_string_literal()?;
+                self.expect_closing()?;
+                if literal.contains(['{', '}']) {
+                    return Err(PatternError::Unsupported(
+                        "MF2E203",
+                        "braces in string literals are not supported",
+                    ));
+                }
+                Ok(literal)
+            }
+            Some(ch) if ch.is_ascii_digit() => {
+                let number = self.parse_number()?;
+                self.expect_closing()?;
+                Ok(number)
+            }
+            Some('-') => Err(PatternError::Unsupported(
+                "MF2E200",
+                "fluent terms are not supported",
+            )),
+            Some(ch) if ch.is_ascii_alphabetic() => {
+                let name = self.parse_identifier()?;
+                if self.peek() != Some('(') {
+                    return Err(PatternError::Unsupported(
+                        "MF2E202",
+                        "fluent message references are not supported",
+                    ));
+                }
+                let formatter = match name.as_str() {
+                    "NUMBER" => "number",
+                    "DATETIME" => "datetime",
+                    _ => {
+                        return Err(PatternError::Unsupported(
+                            "MF2E203",
+                            "unsupported fluent function",
+                        ));
+                    }
+                };
+                let (variable, options) = self.parse_call_arguments()?;
+                self.finish_expression(variable, Some(formatter), options)
+            }
+            _ => Err(syntax("expected placeable expression")),
+        }
+    }
+
+    fn finish_expression(
+        &mut self,
+        variable: String,
+        formatter: Option<&str>,
+        options: Vec<(String, String)>,
+    ) -> Result<String, PatternError> {
+        self.skip_whitespace();
+        if self.peek() == Some('-') && self.chars.get(self.pos + 1) == Some(&'>') {
+            self.pos += 2;
+            let ordinal = options
+                .iter()
+                .any(|(name, value)| name == "type" && value == "ordinal");
+            return self.parse_select(&variable, formatter.is_some(), ordinal);
+        }
+        self.expect_closing()?;
+        let mut out = format!("{{ ${variable}");
+        if let Some(formatter) = formatter {
+            out.push_str(&format!(" :{formatter}"));
+            for (name, value) in options {
+                out.push_str(&format!(" {name}={value}"));
+            }
+        }
+        out.push_str(" }");
+        Ok(out)
+    }
+
+    fn parse_call_arguments(&mut self) -> Result<(String, Vec<(String, String)>), PatternError> {
+        self.pos += 1;
+        let mut variable = None;
+        let mut options = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(')') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(',') => self.pos += 1,
+                Some('$') if variable.is_none() => {
+                    self.pos += 1;
+                    variable = Some(self.parse_identifier()?);
+                }
+                Some(ch) if ch.is_ascii_alphabetic() => {
+                    let name = self.parse_identifier()?;
+                    self.skip_whitespace();
+                    if self.peek() != Some(':') {
+                        return Err(syntax("expected ':' after argument name"));
+                    }
+                    self.pos += 1;
+                    self.skip_whitespace();
+                    let value = match self.peek() {
+                        Some('"') => self.parse_string_literal()?,
+                        Some(ch) if ch.is_ascii_digit() => self.parse_number()?,
+                        _ => return Err(syntax("expected argument value")),
+                    };
+                    if !value
+                        .chars()
+                        .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-' | '.'))
+                    {
+                        return Err(PatternError::Unsupported(
+                            "MF2E203",
+                            "unsupported fluent function argument",
+                        ));
+                    }
+                    options.push((name, value));
+                }
+                _ => {
+                    return Err(PatternError::Unsupported(
+                        "MF2E203",
+                        "unsupported fluent function argument",
+                    ));
+                }
+            }
+        }
+        let variable = variable.ok_or(PatternError::Unsupported(
+            "MF2E203",
+            "fluent functions need a variable argument",
+        ))?;
+        Ok((variable, options))
+    }
+
+    fn parse_select(
+        &mut self,
+        variable: &str,
+        numeric: bool,
+        ordinal: bool,
+    ) -> Result<String, PatternError> {
+        let mut variants = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('}') => {
+                    self.pos += 1;
+                    break;
+                }
+                None => return Err(syntax("unterminated select expression")),
+                _ => {}
+            }
+            let is_default = self.peek() == Some('*');
+            if is_default {
+                self.pos += 1;
+            }
+            if self.peek() != Some('[') {
+                return Err(syntax("expected variant key"));
+            }
+            self.pos += 1;
+            self.skip_whitespace();
+            let key = match self.peek() {
+                Some(ch) if ch.is_ascii_digit() => self.parse_number()?,
+                _ => self.parse_identifier()?,
+            };
+            self.skip_whitespace();
+            if self.peek() != Some(']') {
+                return Err(syntax("expected ']' after variant key"));
+            }
+            self.pos += 1;
+            let value = self.parse_pattern(true)?;
+            variants.push((key, is_default, value.trim().to_string()));
+        }
+        if variants
+            .iter()
+            .filter(|(_, is_default, _)| *is_default)
+            .count()
+            != 1
+        {
+            return Err(syntax("select needs exactly one default variant"));
+        }
+
+        let plural = numeric
+            || variants.iter().any(|(key, _, _)| {
+                key.starts_with(|ch: char| ch.is_ascii_digit())
+                    || matches!(key.as_str(), "zero" | "one" | "two" | "few" | "many")
+            });
+        let mut out = format!("{{ ${variable}");
+        if ordinal {
+            out.push_str(" :ordinal");
+        } else if plural {
+            out.push_str(" :plural");
+        }
+        out.push_str(" ->");
+        for (key, is_default, value) in variants {
+            out.push(' ');
+            if is_default {
+                out.push('*');
+            }
+            if key.starts_with(|ch: char| ch.is_ascii_digit()) {
+                if key.parse::<u32>().is_err() {
+                    return Err(PatternError::Unsupported(
+                        "MF2E203",
+                        "non-integer variant keys are not supported",
+                    ));
+                }
+                out.push_str(&format!("[={key}] {{{value}}}"));
+            } else {
+                out.push_str(&format!("[{key}] {{{value}}}"));
+            }
+        }
+        out.push_str(" }");
+        Ok(out)
+    }
+
+    fn parse_identifier(&mut self) -> Result<String, PatternError> {
+        let start = self.pos;
+        while let Some(ch) = self.peek() {
+            let valid = if self.pos == start {
+                ch.is_ascii_alphabetic()
+            } else {
+                ch.is_ascii_alphanumeric() || ch == '_' || ch == '-'
+            };
+            if !valid {
+                break;
+            }
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return Err(syntax("expected identifier"));
+        }
+        Ok(self.chars[start..self.pos].iter().collect())
+    }
+
+    fn parse_number(&mut self) -> Result<String, PatternError> {
+        let start = self.pos;
+        while let Some(ch) = self.peek() {
+            if !(ch.is_ascii_digit() || ch == '.') {
+                break;
+            }
+            self.pos += 1;
+        }
+        Ok(self.chars[start..self.pos].iter().collect())
+    }
+
+    fn parse_string_literal(&mut self) -> Result<String, PatternError> {
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            match self.peek() {
+                Some('"') => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                Some('\\') => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some('"') => out.push('"'),
+                        Some('\\') => out.push('\\'),
+                        _ => return Err(syntax("invalid escape sequence")),
+                    }
+                }
+                Some('\n') | None => return Err(syntax("unterminated string literal")),
+                Some(ch) => out.push(ch),
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn expect_closing(&mut self) -> Result<(), PatternError> {
+        self.skip_whitespace();
+        if self.peek() != Some('}') {
+            return Err(syntax("expected '}'"));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn next_line_starts_variant(&self) -> bool {
+        self.chars[self.pos + 1..]
+            .iter()
+            .find(|ch| !ch.is_whitespace())
+            .is_some_and(|ch| matches!(ch, '[' | '*' | '}'))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+}
+
+fn is_ftl_identifier(value: &str) -> bool {
+    let mut chars = value.chars();
+    chars.next().is_some_and(|ch| ch.is_ascii_alphabetic())
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-')
+}
+
+fn syntax(message: &str) -> PatternError {
+    PatternError::Syntax(message.to_string())
+}
+
+fn ftl_error(line: u32, message: &str) -> FtlError {
+    FtlError {
+        line,
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ImportFtlOptions, convert_entries, parse_ftl, run_import_ftl};
+    use crate::mf2_source::parse_mf2_source;
+    use crate::parser::{CaseKey, Expr, Segment, SelectKind, parse_message};
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        path.push(format!("mf2_i18n_{name}_{nanos}"));
+        fs::create_dir_all(&path).expect("dir");
+        path
+    }
+
+    const SAMPLE: &str = "\
+## Inbox
+# Shown in the header
+inbox-title = Inbox for { $user }
+
+emails = { $count ->
+    [0] No new emails.
+    [one] One new email.
+   *[other] { $count } new emails.
+}
+
+-brand = Firefox
+about = About { -brand }
+
+login = Sign in
+    .placeholder = Email
+";
+
+    #[test]
+    fn converts_count_selector_into_plural() {
+        let entries = parse_ftl(SAMPLE).expect("parse");
+        let conversion = convert_entries(&entries).expect("convert");
+        let value = &conversion.messages["emails"].value;
+        assert_eq!(
+            value,
+            "{ $count :plural -> [=0] {No new emails.} [one] {One new email.} *[other] {{ $count } new emails.} }"
+        );
+        let message = parse_message(value).expect("mf2");
+        match &message.segments[0] {
+            Segment::Expr(Expr::Select(select)) => {
+                assert_eq!(select.selectors[0].name, "count");
+                assert_eq!(select.selectors[0].kind, SelectKind::Plural);
+                assert_eq!(select.cases[0].keys[0], CaseKey::Exact(0));
+                assert!(select.cases[2].is_default);
+            }
+            _ => panic!("expected select"),
+        }
+    }
+
+    #[test]
+    fn flags_terms_and_attributes() {
+        let entries = parse_ftl(SAMPLE).expect("parse");
+        let conversion = convert_entries(&entries).expect("convert");
+        let keys: Vec<&str> = conversion.messages.keys().map(String::as_str).collect();
+        assert_eq!(keys, vec!["emails", "inbox-title", "login"]);
+        let codes: Vec<(u32, &str)> = conversion
+            .unsupported
+            .iter()
+            .map(|(line, code, _)| (*line, *code))
+            .collect();
+        assert_eq!(
+            codes,
+            vec![(11, "MF2E200"), (12, "MF2E200"), (15, "MF2E201")]
+        );
+    }
+
+    #[test]
+    fn converts_number_function_options() {
+        let entries =
+            parse_ftl("price = { NUMBER($amount, minimumFractionDigits: 2) }").expect("parse");
+        let conversion = convert_entries(&entries).expect("convert");
+        assert_eq!(
+            conversion.messages["price"].value,
+            "{ $amount :number minimumFractionDigits=2 }"
+        );
+    }
+
+    #[test]
+    fn import_command_writes_locale_file() {
+        let root = temp_dir("import_ftl");
+        let ftl_path = root.join("en.ftl");
+        fs::write(&ftl_path, SAMPLE).expect("write");
+        let out_dir = root.join("locales");
+        let diagnostics = run_import_ftl(&ImportFtlOptions {
+            ftl_path,
+            locale: "en".to_string(),
+            out_dir: out_dir.clone(),
+        })
+        .expect("import");
+        assert_eq!(diagnostics.len(), 3);
+
+        let contents = fs::read_to_string(out_dir.join("en").join("messages.mf2")).expect("read");
+        let entries = parse_mf2_source(&contents).expect("mf2 source");
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[1].key, "inbox-title");
+        assert_eq!(entries[1].value, "Inbox for { $user }");
+        assert!(contents.contains("# Shown in the header\ninbox-title"));
+
+        fs::remove_dir_all(&root).ok();
+    }
+}
@@ -0,0 +1,758 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use mf2_i18n_core::{CldrPluralBackend, FormatBackend, LanguageTag, PluralCategory};
+use thiserror::Error;
+
+use crate::backend::SmokeBackend;
+use crate::mf2_source::{is_valid_key, push_source_entry};
+use crate::parser::parse_message;
+
+#[derive(Debug, Error)]
+pub enum ImportPoCommandError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("po parse error: {file}:{line} {message}")]
+    Parse {
+        file: String,
+        line: u32,
+        message: String,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportPoOptions {
+    pub po_path: PathBuf,
+    pub locale: String,
+    pub out_dir: PathBuf,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct PoEntry {
+    comments: Vec<String>,
+    context: Option<String>,
+    id: String,
+    id_plural: Option<String>,
+    strings: Vec<String>,
+    line: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PoError {
+    line: u32,
+    message: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Field {
+    Context,
+    Id,
+    IdPlural,
+    Str(usize),
+}
+
+struct ImportedMessage {
+    comments: Vec<String>,
+    value: String,
+}
+
+pub fn run_import_po(options: &ImportPoOptions) -> Result<(), ImportPoCommandError> {
+    let contents = fs::read_to_string(&options.po_path)?;
+    let parse_error = |err: PoError| ImportPoCommandError::Parse {
+        file: options.po_path.display().to_string(),
+        line: err.line,
+        message: err.message,
+    };
+    let entries = parse_po(&contents).map_err(parse_error)?;
+    let messages = convert_entries(&entries, &options.locale).map_err(parse_error)?;
+
+    let output_dir = options.out_dir.join(&options.locale);
+    fs::create_dir_all(&output_dir)?;
+    let mut out = String::new();
+    for (key, message) in &messages {
+        push_source_entry(&mut out, &message.comments, key, &message.value);
+    }
+    fs::write(output_dir.join("messages.mf2"), out)?;
+    Ok(())
+}
+
+fn parse_po(input: &str) -> Result<Vec<PoEntry>, PoError> {
+    let mut entries = Vec::new();
+    let mut current = PoEntry::default();
+    let mut field: Option<Field> = None;
+    let mut saw_str = false;
+
+    for (idx, raw_line) in input.lines().enumerate() {
+        let line_no = (idx + 1) as u32;
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with("#~") {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#') {
+            if saw_str {
+                flush_entry(&mut entries, &mut current, &mut field, &mut saw_str);
+            }
+            current.comments.push(comment.trim().to_string());
+            continue;
+        }
+        if line.starts_with('"') {
+            let value = parse_quoted(line, line_no)?;
+            match field {
+                Some(Field::Context) => current
+                    .context
+                    .get_or_insert_with(String::new)
+                    .push_str(&value),
+                Some(Field::Id) => current.id.push_str(&value),
+                Some(Field::IdPlural) => current
+                    .id_plural
+                    .get_or_insert_with(String::new)
+                    .push_str(&value),
+                Some(Field::Str(index)) => current.strings[index].push_str(&value),
+                None => return Err(po_error(line_no, "string without keyword")),
+            }
+            continue;
+        }
+
+        let (keyword, rest) = line
+            .split_once(char::is_whitespace)
+            .ok_or_else(|| po_error(line_no, "expected keyword and string"))?;
+        let value = parse_quoted(rest.trim(), line_no)?;
+        let next = match keyword {
+            "msgctxt" => Field::Context,
+            "msgid" => Field::Id,
+            "msgid_plural" => Field::IdPlural,
+            "msgstr" => Field::Str(0),
+            _ => match keyword
+                .strip_prefix("msgstr[")
+                .and_then(|rest| rest.strip_suffix(']'))
+            {
+                Some(index) => Field::Str(
+                    index
+                        .parse::<usize>()
+                        .map_err(|_| po_error(line_no, "invalid plural index"))?,
+                ),
+                None => return Err(po_error(line_no, "unknown keyword")),
+            },
+        };
+        if saw_str && matches!(next, Field::Context | Field::Id) {
+            flush_entry(&mut entries, &mut current, &mut field, &mut saw_str);
+        }
+        if current.line == 0 {
+            current.line = line_no;
+        }
+        match next {
+            Field::Context => current.context = Some(value),
+            Field::Id => current.id = value,
+            Field::IdPlural => current.id_plural = Some(value),
+            Field::Str(index) => {
+                if current.strings.len() <= index {
+                    current.strings.resize(index + 1, String::new());
+                }
+                current.strings[index] = value;
+                saw_str = true;
+            }
+        }
+        field = Some(next);
+    }
+    if saw_str {
+        flush_entry(&mut entries, &mut current, &mut field, &mut saw_str);
+    }
+    Ok(entries)
+}
+
+fn flush_entry(
+    entries: &mut Vec<PoEntry>,
+    current: &mut PoEntry,
+    field: &mut Option<Field>,
+    saw_str: &mut bool,
+) {
+    entries.push(std::mem::take(current));
+    *field = None;
+    *saw_str = false;
+}
+
+fn parse_quoted(input: &str, line: u32) -> Result<String, PoError> {
+    let inner = input
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .ok_or_else(|| po_error(line, "expected quoted string"))?;
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some('r') => out.push('\r'),
+                Some('"') => out.push('"'),
+                Some('\\') => out.push('\\'),
+                _ => return Err(po_error(line, "invalid escape sequence")),
+            },
+            '"' => return Err(po_error(line, "unescaped quote in string")),
+            _ => out.push(ch),
+        }
+    }
+    Ok(out)
+}
+
+fn convert_entries(
+    entries: &[PoEntry],
+    locale: &str,
+) -> Result<BTreeMap<String, ImportedMessage>, PoError> {
+    let mut plural_forms = None;
+    let mut messages = BTreeMap::new();
+    for entry in entries {
+        if entry.id.is_empty() && entry.context.is_none() {
+            if let Some(header) = entry.strings.first() {
+                plural_forms = parse_plural_forms_header(header)
+                    .map_err(|message| po_error(entry.line, message))?;
+            }
+            continue;
+        }
+        if is_fuzzy(entry) || entry.strings.iter().all(String::is_empty) {
+            continue;
+        }
+        let value = if entry.id_plural.is_some() {
+            plural_value(&entry.strings, plural_forms.as_ref(), locale)
+        } else {
+            escape_text(&entry.strings[0])
+        };
+        if value.lines().skip(1).any(|line| line.trim().is_empty()) {
+            return Err(po_error(
+                entry.line,
+                "blank lines cannot be represented in mf2 sources",
+            ));
+        }
+        parse_message(&value).map_err(|err| {
+            po_error(
+                entry.line,
+                &format!("translation is not valid MF2: {}", err.message),
+            )
+        })?;
+        let key = unique_key(&messages, &message_key(entry));
+        messages.insert(
+            key,
+            ImportedMessage {
+                comments: entry.comments.clone(),
+                value,
+            },
+        );
+    }
+    Ok(messages)
+}
+
+fn is_fuzzy(entry: &PoEntry) -> bool {
+    entry.comments.iter().any(|comment| {
+        comment
+            .strip_prefix(',')
+            .is_some_and(|flags| flags.split(',').any(|flag| flag.trim() == "fuzzy"))
+    })
+}
+
+fn escape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for ch in value.chars() {
+        if matches!(ch, '\\' | '{' | '}') {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+    out
+}
+
+fn plural_value(forms: &[String], plural_forms: Option<&PluralForms>, locale: &str) -> String {
+    let categories = plural_forms
+        .filter(|plural_forms| plural_forms.count == forms.len())
+        .and_then(|plural_forms| form_categories(plural_forms, locale))
+        .unwrap_or_else(|| positional_categories(forms.len()));
+    let default = categories
+        .iter()
+        .position(|category| *category == PluralCategory::Other)
+        .unwrap_or(forms.len() - 1);
+    let mut seen = Vec::new();
+    let mut out = String::from("{ $n :plural ->");
+    for (idx, (form, category)) in forms.iter().zip(&categories).enumerate() {
+        if idx == default || seen.contains(category) {
+            continue;
+        }
+        seen.push(*category);
+        out.push_str(&format!(
+            " [{}] {{{}}}",
+            category_name(*category),
+            escape_text(form)
+        ));
+    }
+    out.push_str(&format!(
+        " *[other] {{{}}} }}",
+        escape_text(&forms[default])
+    ));
+    out
+}
+
+fn positional_categories(count: usize) -> Vec<PluralCategory> {
+    use PluralCategory::{Few, Many, One, Other, Two, Zero};
+    let categories: &[PluralCategory] = match count {
+        1 => &[Other],
+        2 => &[One, Other],
+        3 => &[One, Few, Other],
+        4 => &[One, Two, Few, Other],
+        5 => &[One, Two, Few, Many, Other],
+        _ => &[Zero, One, Two, Few, Many, Other],
+    };
+    let mut out: Vec<PluralCategory> = categories.iter().copied().take(count).collect();
+    out.resize(count, Other);
+    if let Some(last) = out.last_mut() {
+        *last = Other;
+    }
+    out
+}
+
+fn form_categories(plural_forms: &PluralForms, locale: &str) -> Option<Vec<PluralCategory>> {
+    let tag = LanguageTag::parse(locale).ok()?;
+    if !CldrPluralBackend::<SmokeBackend>::supports(&tag) {
+        return None;
+    }
+    let backend = CldrPluralBackend::new(&tag, SmokeBackend);
+    let mut counts = vec![Vec::<(PluralCategory, u32)>::new(); plural_forms.count];
+    for n in 0..=PLURAL_SAMPLE_MAX {
+        let index = usize::try_from(plural_forms.expr.eval(n)?).ok()?;
+        let category = backend.plural_category(n as f64).ok()?;
+        let tally = counts.get_mut(index)?;
+        match tally.iter_mut().find(|(seen, _)| *seen == category) {
+            Some((_, count)) => *count += 1,
+            None => tally.push((category, 1)),
+        }
+    }
+    counts
+        .into_iter()
+        .map(|tally| {
+            tally
+                .into_iter()
+                .rev()
+                .max_by_key(|(_, count)| *count)
+                .map(|(category, _)| category)
+        })
+        .collect()
+}
+
+fn category_name(category: PluralCategory) -> &'static str {
+    match category {
+        PluralCategory::Zero => "zero",
+        PluralCategory::One => "one",
+        PluralCategory::Two => "two",
+        PluralCategory::Few => "few",
+        PluralCategory::Many => "many",
+        PluralCategory::Other => "other",
+    }
+}
+
+const PLURAL_SAMPLE_MAX: i64 = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PluralForms {
+    count: usize,
+    expr: PluralExpr,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PluralExpr {
+    N,
+    Number(i64),
+    Not(Box<PluralExpr>),
+    Binary(String, Box<PluralExpr>, Box<PluralExpr>),
+    Conditional(Box<PluralExpr>, Box<PluralExpr>, Box<PluralExpr>),
+}
+
+impl PluralExpr {
+    fn eval(&self, n: i64) -> Option<i64> {
+        Some(match self {
+            PluralExpr::N => n,
+            PluralExpr::Number(value) => *value,
+            PluralExpr::Not(inner) => i64::from(inner.eval(n)? == 0),
+            PluralExpr::Conditional(test, then, otherwise) => {
+                if test.eval(n)? != 0 {
+                    then.eval(n)?
+                } else {
+                    otherwise.eval(n)?
+                }
+            }
+            PluralExpr::Binary(op, left, right) => {
+                let left = left.eval(n)?;
+                let right = right.eval(n)?;
+                match op.as_str() {
+                    "||" => i64::from(left != 0 || right != 0),
+                    "&&" => i64::from(left != 0 && right != 0),
+                    "==" => i64::from(left == right),
+                    "!=" => i64::from(left != right),
+                    "<" => i64::from(left < right),
+                    "<=" => i64::from(left <= right),
+                    ">" => i64::from(left > right),
+                    ">=" => i64::from(left >= right),
+                    "+" => left.checked_add(right)?,
+                    "-" => left.checked_sub(right)?,
+                    "*" => left.checked_mul(right)?,
+                    "/" => left.checked_div(right)?,
+                    "%" => left.checked_rem(right)?,
+                    _ => return None,
+                }
+            }
+        })
+    }
+}
+
+fn parse_plural_forms_header(header: &str) -> Result<Option<PluralForms>, &'static str> {
+    let Some(value) = header.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix("Plural-Forms:")
+            .map(|value| value.trim())
+    }) else {
+        return Ok(None);
+    };
+    let mut count = None;
+    let mut expr = None;
+    for part in value.split(';') {
+        match part.trim().split_once('=') {
+            Some(("nplurals", value)) => count = value.trim().parse::<usize>().ok(),
+            Some(("plural", value)) => expr = Some(parse_plural_expr(value)?),
+            _ => {}
+        }
+    }
+    match (count, expr) {
+        (Some(count), Some(expr)) if count > 0 => Ok(Some(PluralForms { count, expr })),
+        _ => Err("invalid Plural-Forms header"),
+    }
+}
+
+fn parse_plural_expr(input: &str) -> Result<PluralExpr, &'static str> {
+    let tokens = tokenize_plural_expr(input)?;
+    let mut cursor = 0;
+    let expr = parse_conditional(&tokens, &mut cursor)?;
+    if cursor != tokens.len() {
+        return Err("invalid Plural-Forms expression");
+    }
+    Ok(expr)
+}
+
+fn tokenize_plural_expr(input: &str) -> Result<Vec<String>, &'static str> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            ch if ch.is_whitespace() => {}
+            '0'..='9' => {
+                let mut number = ch.to_string();
+                while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                    number.push(digit);
+                }
+                tokens.push(number);
+            }
+            'n' | '?' | ':' | '(' | ')' | '+' | '-' | '*' | '/' | '%' => {
+                tokens.push(ch.to_string())
+            }
+            '|' | '&' | '=' | '!' | '<' | '>' => {
+                let mut op = ch.to_string();
+                if let Some(next) = chars.next_if(|next| matches!(next, '|' | '&' | '=')) {
+                    op.push(next);
+                }
+                if matches!(op.as_str(), "|" | "&" | "=") {
+                    return Err("invalid Plural-Forms expression");
+                }
+                tokens.push(op);
+            }
+            _ => return Err("invalid Plural-Forms expression"),
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_conditional(tokens: &[String], cursor: &mut usize) -> Result<PluralExpr, &'static str> {
+    let test = parse_binary(tokens, cursor, 0)?;
+    if tokens.get(*cursor).map(String::as_str) != Some("?") {
+        return Ok(test);
+    }
+    *cursor += 1;
+    let then = parse_conditional(tokens, cursor)?;
+    if tokens.get(*cursor).map(String::as_str) != Some(":") {
+        return Err("invalid Plural-Forms expression");
+    }
+    *cursor += 1;
+    let otherwise = parse_conditional(tokens, cursor)?;
+    Ok(PluralExpr::Conditional(
+        Box::new(test),
+        Box::new(then),
+        Box::new(otherwise),
+    ))
+}
+
+fn binary_precedence(op: &str) -> Option<u8> {
+    match op {
+        "||" => Some(0),
+        "&&" => Some(1),
+        "==" | "!=" => Some(2),
+        "<" | "<=" | ">" | ">=" => Some(3),
+        "+" | "-" => Some(4),
+        "*" | "/" | "%" => Some(5),
+        _ => None,
+    }
+}
+
+fn parse_binary(
+    tokens: &[String],
+    cursor: &mut usize,
+    min_precedence: u8,
+) -> Result<PluralExpr, &'static str> {
+    let mut left = parse_unary(tokens, cursor)?;
+    while let Some(op) = tokens.get(*cursor)
+        && let Some(precedence) = binary_precedence(op)
+        && precedence >= min_precedence
+    {
+        *cursor += 1;
+        let right = parse_binary(tokens, cursor, precedence + 1)?;
+        left = PluralExpr::Binary(op.clone(), Box::new(left), Box::new(right));
+    }
+    Ok(left)
+}
+
+fn parse_unary(tokens: &[String], cursor: &mut usize) -> Result<PluralExpr, &'static str> {
+    let token = tokens
+        .get(*cursor)
+        .ok_or("invalid Plural-Forms expression")?;
+    *cursor += 1;
+    match token.as_str() {
+        "n" => Ok(PluralExpr::N),
+        "!" => Ok(PluralExpr::Not(Box::new(parse_unary(tokens, cursor)?))),
+        "(" => {
+            let inner = parse_conditional(tokens, cursor)?;
+            if tokens.get(*cursor).map(String::as_str) != Some(")") {
+                return Err("invalid Plural-Forms expression");
+            }
+            *cursor += 1;
+            Ok(inner)
+        }
+        number => number
+            .parse::<i64>()
+            .map(PluralExpr::Number)
+            .map_err(|_| "invalid Plural-Forms expression"),
+    }
+}
+
+fn message_key(entry: &PoEntry) -> String {
+    if let Some(context) = &entry.context
+        && !context.is_empty()
+        && is_valid_key(context)
+    {
+        return context.clone();
+    }
+    let mut key = String::new();
+    for ch in entry.id.chars() {
+        if ch.is_ascii_alphanumeric() {
+            key.push(ch.to_ascii_lowercase());
+        } else if !key.is_empty() && !key.ends_with('_') {
+            key.push('_');
+        }
+    }
+    let key = key.trim_end_matches('_');
+    if key.is_empty() {
+        "message".to_string()
+    } else {
+        key.to_string()
+    }
+}
+
+fn unique_key(messages: &BTreeMap<String, ImportedMessage>, base: &str) -> String {
+    let mut key = base.to_string();
+    let mut suffix = 2;
+    while messages.contains_key(&key) {
+        key = format!("{base}_{suffix}");
+        suffix += 1;
+    }
+    key
+}
+
+fn po_error(line: u32, message: &str) -> PoError {
+    PoError {
+        line,
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ImportPoOptions, convert_entries, parse_po, run_import_po};
+    use crate::mf2_source::parse_mf2_source;
+    use crate::parser::{Expr, Segment, SelectKind, parse_message};
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        path.push(format!("mf2_i18n_{name}_{nanos}"));
+        fs::create_dir_all(&path).expect("dir");
+        path
+    }
+
+    const SAMPLE: &str = r#"msgid ""
+msgstr ""
+"Plural-Forms: nplurals=2; plural=(n != 1);\n"
+
+# Greeting on the home page
+#: src/home.rs:10
+msgctxt "home.title"
+msgid "Welcome"
+msgstr "Bienvenue"
+
+msgid "Say \"hi\"\n"
+"to everyone"
+msgstr "Dites \"salut\"\n"
+"à tous"
+
+msgid "One file"
+msgid_plural "Many files"
+msgstr[0] "Un fichier"
+msgstr[1] "Plusieurs fichiers"
+
+msgid "Untranslated"
+msgstr ""
+"#;
+
+    #[test]
+    fn parses_singular_multiline_and_plural_entries() {
+        let entries = parse_po(SAMPLE).expect("parse");
+        assert_eq!(entries.len(), 5);
+        assert_eq!(entries[1].context.as_deref(), Some("home.title"));
+        assert_eq!(
+            entries[1].comments,
+            vec!["Greeting on the home page", ": src/home.rs:10"]
+        );
+        assert_eq!(entries[2].id, "Say \"hi\"\nto everyone");
+        assert_eq!(entries[2].strings[0], "Dites \"salut\"\nà tous");
+        assert_eq!(entries[3].strings.len(), 2);
+
+        let messages = convert_entries(&entries, "fr").expect("convert");
+        let keys: Vec<&str> = messages.keys().map(String::as_str).collect();
+        assert_eq!(keys, vec!["home.title", "one_file", "say_hi_to_everyone"]);
+    }
+
+    #[test]
+    fn converts_plural_forms_to_mf2_plural() {
+        let entries = parse_po(SAMPLE).expect("parse");
+        let messages = convert_entries(&entries, "fr").expect("convert");
+        let value = &messages["one_file"].value;
+        assert_eq!(
+            value,
+            "{ $n :plural -> [one] {Un fichier} *[other] {Plusieurs fichiers} }"
+        );
+        let message = parse_message(value).expect("mf2");
+        match &message.segments[0] {
+            Segment::Expr(Expr::Select(select)) => {
+                assert_eq!(select.selectors[0].kind, SelectKind::Plural);
+                assert_eq!(select.cases.len(), 2);
+            }
+            _ => panic!("expected select"),
+        }
+    }
+
+    #[test]
+    fn escapes_literal_braces() {
+        let entries =
+            parse_po("msgid \"Use {name}\"\nmsgstr \"Utilisez {nom} \\\\ {x}\"").expect("parse");
+        let messages = convert_entries(&entries, "fr").expect("convert");
+        let value = &messages["use_name"].value;
+        assert_eq!(value, "Utilisez \\{nom\\} \\\\ \\{x\\}");
+        let message = parse_message(value).expect("mf2");
+        match &message.segments[..] {
+            [Segment::Text { value, .. }] => assert_eq!(value, "Utilisez {nom} \\ {x}"),
+            other => panic!("expected text, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn maps_plural_forms_through_the_header_expression() {
+        let input = r#"msgid ""
+msgstr "Plural-Forms: nplurals=3; plural=(n%10==1 && n%100!=11 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2);\n"
+
+msgid "One file"
+msgid_plural "Many files"
+msgstr[0] "{n} файл"
+msgstr[1] "файла"
+msgstr[2] "файлов"
+"#;
+        let entries = parse_po(input).expect("parse");
+        let messages = convert_entries(&entries, "ru").expect("convert");
+        assert_eq!(
+            messages["one_file"].value,
+            "{ $n :plural -> [one] {\\{n\\} файл} [few] {файла} *[other] {файлов} }"
+        );
+
+        let reversed = r#"msgid ""
+msgstr "Plural-Forms: nplurals=2; plural=n == 1 ? 1 : 0;\n"
+
+msgid "One file"
+msgid_plural "Many files"
+msgstr[0] "Files"
+msgstr[1] "File"
+"#;
+        let entries = parse_po(reversed).expect("parse");
+        let messages = convert_entries(&entries, "en").expect("convert");
+        assert_eq!(
+            messages["one_file"].value,
+            "{ $n :plural -> [one] {File} *[other] {Files} }"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_plural_forms_header() {
+        let entries =
+            parse_po("msgid \"\"\nmsgstr \"Plural-Forms: nplurals=2; plural=n ?? 1;\\n\"")
+                .expect("parse");
+        let err = convert_entries(&entries, "fr").err().expect("header");
+        assert_eq!(err.line, 1);
+        assert_eq!(err.message, "invalid Plural-Forms expression");
+    }
+
+    #[test]
+    fn skips_fuzzy_entries() {
+        let input = "#, fuzzy, c-format\nmsgid \"Draft\"\nmsgstr \"Brouillon\"\n\n#, c-format\nmsgid \"Done\"\nmsgstr \"Fini\"\n";
+        let entries = parse_po(input).expect("parse");
+        let messages = convert_entries(&entries, "fr").expect("convert");
+        let keys: Vec<&str> = messages.keys().map(String::as_str).collect();
+        assert_eq!(keys, vec!["done"]);
+    }
+
+    #[test]
+    fn rejects_invalid_escape() {
+        let err = parse_po("msgid \"a\\q\"\nmsgstr \"b\"").expect_err("escape");
+        assert_eq!(err.line, 1);
+        assert_eq!(err.message, "invalid escape sequence");
+    }
+
+    #[test]
+    fn import_command_writes_locale_file() {
+        let root = temp_dir("import_po");
+        let po_path = root.join("fr.po");
+        fs::write(&po_path, SAMPLE).expect("write");
+        let out_dir = root.join("locales");
+        run_import_po(&ImportPoOptions {
+            po_path,
+            locale: "fr".to_string(),
+            out_dir: out_dir.clone(),
+        })
+        .expect("import");
+
+        let contents = fs::read_to_string(out_dir.join("fr").join("messages.mf2")).expect("read");
+        assert!(contents.starts_with("# Greeting on the home page\n"));
+        let entries = parse_mf2_source(&contents).expect("mf2 source");
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].key, "home.title");
+        assert_eq!(entries[0].value, "Bienvenue");
+        assert_eq!(entries[2].value, "Dites \"salut\"\nà tous");
+
+        fs::remove_dir_all(&root).ok();
+    }
+}
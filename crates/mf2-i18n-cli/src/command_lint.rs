@@ -0,0 +1,160 @@
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+use crate::command_validate::resolve_path;
+use crate::config::load_config_or_default;
+use crate::diagnostic::{Diagnostic, DiagnosticFormat, Severity};
+use crate::linter::{has_plural_rules, lint_message};
+use crate::locale_sources::{LocaleBundle, LocaleSourceError, load_locales};
+use crate::parser::parse_message;
+
+#[derive(Debug, Error)]
+pub enum LintCommandError {
+    #[error("config error: {0}")]
+    Config(#[from] crate::error::CliError),
+    #[error(transparent)]
+    Source(#[from] LocaleSourceError),
+    #[error("lint failed with {} diagnostics", .0.len())]
+    Failed(Vec<Diagnostic>),
+}
+
+#[derive(Debug, Clone)]
+pub struct LintOptions {
+    pub config_path: PathBuf,
+    pub deny_warnings: bool,
+    pub format: DiagnosticFormat,
+}
+
+pub fn run_lint(options: &LintOptions) -> Result<Vec<Diagnostic>, LintCommandError> {
+    let config = load_config_or_default(&options.config_path)?;
+    let roots: Vec<PathBuf> = config
+        .source_dirs
+        .iter()
+        .map(|root| resolve_path(&options.config_path, root))
+        .collect();
+    let locales = load_locales(&roots, config.duplicate_keys)?;
+
+    let mut diagnostics = Vec::new();
+    for locale in &locales {
+        diagnostics.extend(lint_locale(locale));
+    }
+
+    let failed = diagnostics.iter().any(|diag| {
+        diag.severity == Severity::Error
+            || (options.deny_warnings && diag.severity == Severity::Warning)
+    });
+    if failed {
+        Err(LintCommandError::Failed(diagnostics))
+    } else {
+        Ok(diagnostics)
+    }
+}
+
+fn lint_locale(locale: &LocaleBundle) -> Vec<Diagnostic> {
+    let mut diagnostics = locale.diagnostics.clone();
+    if !has_plural_rules(&locale.locale) {
+        diagnostics.push(
+            Diagnostic::new(
+                "MF2I103",
+                format!(
+                    "no plural rules for {}; one case check skipped",
+                    locale.locale
+                ),
+            )
+            .with_severity(Severity::Info)
+            .with_span(format!("locale:{}", locale.locale), 1, 1),
+        );
+    }
+    for (key, entry) in &locale.messages {
+        let message =
+            match parse_message(&entry.value) {
+                Ok(message) => message,
+                Err(err) => {
+                    diagnostics.push(
+                        Diagnostic::new("MF2E001", format!("parse error: {}", err.message))
+                            .with_span(entry.file.clone(), entry.line, 1),
+                    );
+                    continue;
+                }
+            };
+        for mut diag in lint_message(key, &entry.value, &message, &locale.locale) {
+            diag.message = format!("{key}: {}", diag.message);
+            diag.file = Some(entry.file.clone());
+            diag.line = Some(entry.line + diag.line.unwrap_or(1) - 1);
+            diagnostics.push(diag);
+        }
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LintCommandError, LintOptions, run_lint};
+    use crate::diagnostic::{DiagnosticFormat, Severity};
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_dir() -> PathBuf {
+        let mut path = std::env::temp_dir();
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        path.push(format!("mf2_i18n_lint_{nanos}"));
+        fs::create_dir_all(&path).expect("dir");
+        path
+    }
+
+    #[test]
+    fn warnings_fail_only_when_denied() {
+        let dir = temp_dir();
+        let locale_dir = dir.join("locales/en");
+        fs::create_dir_all(&locale_dir).expect("locale dir");
+        fs::write(
+            locale_dir.join("messages.mf2"),
+            "home.title = Welcome\n\ncart.items = { $count :plural -> [=0] {none} *[other] {# items} }\n",
+        )
+        .expect("write");
+        let config_path = dir.join("mf2-i18n.toml");
+        fs::write(
+            &config_path,
+            "default_locale = \"en\"\nsource_dirs = [\"locales\"]\nproject_salt_path = \"salt.txt\"\n",
+        )
+        .expect("config");
+
+        let mut options = LintOptions {
+            config_path,
+            deny_warnings: false,
+            format: DiagnosticFormat::Text,
+        };
+        let diagnostics = run_lint(&options).expect("lint");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "MF2W103");
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(diagnostics[0].line, Some(3));
+        assert_eq!(
+            diagnostics[0].message,
+            "cart.items: plural is missing a one case"
+        );
+
+        options.deny_warnings = true;
+        let err = run_lint(&options).expect_err("denied warnings");
+        assert!(matches!(err, LintCommandError::Failed(diagnostics) if diagnostics.len() == 1));
+
+        fs::create_dir_all(dir.join("locales/tlh")).expect("locale dir");
+        fs::write(
+            dir.join("locales/tlh/messages.mf2"),
+            "home.title = nuqneH\n",
+        )
+        .expect("write");
+        options.deny_warnings = false;
+        let diagnostics = run_lint(&options).expect("info does not fail");
+        assert!(diagnostics.iter().any(|diag| diag.code == "MF2I103"
+            && diag.severity == Severity::Info
+            && diag.file.as_deref() == Some("locale:tlh")));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
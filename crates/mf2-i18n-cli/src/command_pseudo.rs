@@ -5,8 +5,11 @@ use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 use crate::config::load_config_or_default;
+use crate::diagnostic::Diagnostic;
 use crate::error::CliError;
 use crate::locale_sources::{LocaleSourceError, load_locales};
+use crate::mf2_source::{serialize_message, serialize_mf2_source};
+use crate::parser::{Expr, Message, ParseError, Segment, parse_message};
 
 #[derive(Debug, Error)]
 pub enum PseudoCommandError {
@@ -16,6 +19,8 @@ pub enum PseudoCommandError {
     Sources(#[from] LocaleSourceError),
     #[error("unknown locale {0}")]
     UnknownLocale(String),
+    #[error("parse error for {0}: {1}")]
+    ParseError(String, String),
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
 }
@@ -26,9 +31,32 @@ pub struct PseudoOptions {
     pub target: String,
     pub out_dir: PathBuf,
     pub config_path: PathBuf,
+    pub strategy: PseudoStrategy,
 }
 
-pub fn run_pseudo(options: &PseudoOptions) -> Result<(), PseudoCommandError> {
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PseudoStrategy {
+    Bracket,
+    Accent,
+    Expand { percent: u32 },
+}
+
+pub const DEFAULT_EXPAND_PERCENT: u32 = 30;
+
+impl PseudoStrategy {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "bracket" => Some(Self::Bracket),
+            "accent" => Some(Self::Accent),
+            "expand" => Some(Self::Expand {
+                percent: DEFAULT_EXPAND_PERCENT,
+            }),
+            _ => None,
+        }
+    }
+}
+
+pub fn run_pseudo(options: &PseudoOptions) -> Result<Vec<Diagnostic>, PseudoCommandError> {
     let config = load_config_or_default(&options.config_path)?;
     let base_dir = options
         .config_path
@@ -39,7 +67,7 @@ pub fn run_pseudo(options: &PseudoOptions) -> Result<(), PseudoCommandError> {
         .iter()
         .map(|dir| base_dir.join(dir))
         .collect();
-    let locales = load_locales(&roots)?;
+    let locales = load_locales(&roots, config.duplicate_keys)?;
     let source = locales
         .into_iter()
         .find(|bundle| bundle.locale == options.locale)
@@ -50,67 +78,62 @@ pub fn run_pseudo(options: &PseudoOptions) -> Result<(), PseudoCommandError> {
 
     let mut entries = BTreeMap::new();
     for (key, message) in source.messages {
-        entries.insert(key, pseudolocalize_message(&message.value));
+        let value = pseudolocalize_message(&message.value, options.strategy)
+            .map_err(|err| PseudoCommandError::ParseError(key.clone(), err.message))?;
+        entries.insert(key, value);
     }
 
     let out_path = output_dir.join("messages.mf2");
-    let contents = serialize_entries(&entries);
+    let contents = serialize_mf2_source(&entries);
     fs::write(out_path, contents)?;
-    Ok(())
+    Ok(source.diagnostics)
 }
 
-fn serialize_entries(entries: &BTreeMap<String, String>) -> String {
-    let mut out = String::new();
-    for (idx, (key, value)) in entries.iter().enumerate() {
-        if idx > 0 {
-            out.push_str("\n\n");
-        }
-        let mut lines = value.lines();
-        if let Some(first) = lines.next() {
-            out.push_str(key);
-            out.push_str(" = ");
-            out.push_str(first);
-        } else {
-            out.push_str(key);
-            out.push_str(" = ");
-        }
-        for line in lines {
-            out.push('\n');
-            out.push_str(line);
-        }
-    }
-    out
-}
+const ACCENTED_LOWER: &str = "áƀçđéƒĝĥíĵķĺɱñóƥɋŕšţúṽŵẋýž";
+const ACCENTED_UPPER: &str = "ÁƁÇĐÉƑĜĤÍĴĶĹṀÑÓƤɊŔŠŢÚṼŴẊÝŽ";
 
-fn pseudolocalize_message(input: &str) -> String {
+fn pseudolocalize_message(input: &str, strategy: PseudoStrategy) -> Result<String, ParseError> {
     if input.is_empty() {
-        return String::new();
+        return Ok(String::new());
     }
-    let mut output = String::from("[[");
-    let mut depth = 0u32;
-    for ch in input.chars() {
-        match ch {
-            '{' => {
-                depth += 1;
-                output.push(ch);
+    let mut message = parse_message(input)?;
+    let text_len = pseudolocalize_segments(&mut message, strategy);
+    let mut output = serialize_message(&message);
+    Ok(match strategy {
+        PseudoStrategy::Bracket => format!("[[{output}]]"),
+        PseudoStrategy::Accent => output,
+        PseudoStrategy::Expand { percent } => {
+            let padding = (text_len * percent as usize).div_ceil(100);
+            if padding > 0 {
+                output.push(' ');
+                output.extend(std::iter::repeat_n('~', padding.saturating_sub(1)));
             }
-            '}' => {
-                if depth > 0 {
-                    depth -= 1;
-                }
-                output.push(ch);
+            output
+        }
+    })
+}
+
+fn pseudolocalize_segments(message: &mut Message, strategy: PseudoStrategy) -> usize {
+    let mut text_len = 0usize;
+    for segment in &mut message.segments {
+        match segment {
+            Segment::Text { value, .. } => {
+                text_len += value.chars().count();
+                *value = match strategy {
+                    PseudoStrategy::Bracket => value.chars().map(pseudo_char).collect(),
+                    PseudoStrategy::Accent => value.chars().map(accent_char).collect(),
+                    PseudoStrategy::Expand { .. } => continue,
+                };
             }
-            _ => {
-                if depth > 0 {
-                    output.push(ch);
-                } else {
-                    output.push_str(&pseudo_char(ch));
+            Segment::Expr(Expr::Select(select)) => {
+                for case in &mut select.cases {
+                    text_len += pseudolocalize_segments(&mut case.value, strategy);
                 }
             }
+            Segment::Pound { .. } | Segment::Expr(_) => {}
         }
     }
-    output.push_str("]]");
-    output
+    text_len
 }
 
 fn pseudo_char(ch: char) -> String {
@@ -118,10 +141,7 @@ fn pseudo_char(ch: char) -> String {
         return ch.to_string();
     }
     let lower = ch.to_ascii_lowercase();
-    let doubled = match lower {
-        'a' | 'e' | 'i' | 'o' | 'u' => true,
-        _ => false,
-    };
+    let doubled = matches!(lower, 'a' | 'e' | 'i' | 'o' | 'u');
     if doubled {
         let mut out = String::new();
         out.push(ch);
@@ -135,9 +155,22 @@ fn pseudo_char(ch: char) -> String {
     }
 }
 
+fn accent_char(ch: char) -> char {
+    let table = if ch.is_ascii_lowercase() {
+        ACCENTED_LOWER
+    } else if ch.is_ascii_uppercase() {
+        ACCENTED_UPPER
+    } else {
+        return ch;
+    };
+    let index = (ch.to_ascii_lowercase() as u8 - b'a') as usize;
+    table.chars().nth(index).unwrap_or(ch)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{PseudoOptions, pseudolocalize_message, run_pseudo};
+    use super::{PseudoOptions, PseudoStrategy, pseudolocalize_message, run_pseudo};
+    use crate::parser::{CaseKey, Expr, Segment, parse_message};
     use std::fs;
     use std::path::PathBuf;
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -156,11 +189,52 @@ mod tests {
     #[test]
     fn pseudo_preserves_expressions() {
         let input = "Hello { $name }";
-        let out = pseudolocalize_message(input);
+        let out = pseudolocalize_message(input, PseudoStrategy::Bracket).expect("pseudo");
         assert!(out.contains("{ $name }"));
         assert!(out.starts_with("[["));
     }
 
+    #[test]
+    fn pseudo_keeps_plural_keys_parseable() {
+        let input = "{ $count :plural -> [one] {# file} *[other] {# files} }";
+        let out = pseudolocalize_message(input, PseudoStrategy::Bracket).expect("pseudo");
+        assert_eq!(
+            out,
+            "[[{ $count :plural -> [one] {# f~iil~ee} *[other] {# f~iil~ees~} }]]"
+        );
+        let parsed = parse_message(&out).expect("reparse");
+        let Segment::Expr(Expr::Select(select)) = &parsed.segments[1] else {
+            panic!("expected select");
+        };
+        assert_eq!(
+            select.cases[0].keys,
+            vec![CaseKey::Ident("one".to_string())]
+        );
+        assert!(select.cases[1].is_default);
+        assert_eq!(select.cases[1].keys, vec![CaseKey::Other]);
+    }
+
+    #[test]
+    fn accent_preserves_expressions() {
+        let out =
+            pseudolocalize_message("Hello { $name }", PseudoStrategy::Accent).expect("pseudo");
+        assert_eq!(out, "Ĥéĺĺó { $name }");
+    }
+
+    #[test]
+    fn expand_grows_text_by_target_ratio() {
+        let input = "Save changes";
+        let out =
+            pseudolocalize_message(input, PseudoStrategy::Expand { percent: 30 }).expect("pseudo");
+        assert!(out.starts_with(input));
+        let ratio = out.chars().count() as f64 / input.chars().count() as f64;
+        assert!((1.25..=1.4).contains(&ratio), "ratio {ratio}");
+
+        let out = pseudolocalize_message("Hi { $name }", PseudoStrategy::Expand { percent: 100 })
+            .expect("pseudo");
+        assert_eq!(out, "Hi { $name } ~~");
+    }
+
     #[test]
     fn pseudo_command_writes_locale_file() {
         let root = temp_dir("pseudo_root");
@@ -181,6 +255,7 @@ mod tests {
             target: "en-xa".to_string(),
             out_dir: out_dir.clone(),
             config_path,
+            strategy: PseudoStrategy::Bracket,
         };
         run_pseudo(&options).expect("run");
 
@@ -191,4 +266,38 @@ mod tests {
         fs::remove_dir_all(&root).ok();
         fs::remove_dir_all(&out_dir).ok();
     }
+
+    #[test]
+    fn pseudo_command_returns_duplicate_key_warnings() {
+        let root = temp_dir("pseudo_dup_root");
+        let locale_dir = root.join("en");
+        fs::create_dir_all(&locale_dir).expect("locale");
+        fs::write(locale_dir.join("a.mf2"), "home.title = Hello").expect("write");
+        fs::write(locale_dir.join("b.mf2"), "home.title = Hi").expect("write");
+
+        let config_path = root.join("mf2-i18n.toml");
+        fs::write(
+            &config_path,
+            "default_locale = \"en\"\nsource_dirs = [\".\"]\nproject_salt_path = \"tools/id_salt.txt\"\nduplicate_keys = \"last-wins\"\n",
+        )
+        .expect("write config");
+
+        let out_dir = temp_dir("pseudo_dup_out");
+        let diagnostics = run_pseudo(&PseudoOptions {
+            locale: "en".to_string(),
+            target: "en-xa".to_string(),
+            out_dir: out_dir.clone(),
+            config_path,
+            strategy: PseudoStrategy::Bracket,
+        })
+        .expect("run");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "MF2W002");
+        let contents =
+            fs::read_to_string(out_dir.join("en-xa").join("messages.mf2")).expect("read");
+        assert!(contents.contains("[[H~ii]]"), "{contents}");
+
+        fs::remove_dir_all(&root).ok();
+        fs::remove_dir_all(&out_dir).ok();
+    }
 }
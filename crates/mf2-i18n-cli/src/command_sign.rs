@@ -1,10 +1,12 @@
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use ed25519_dalek::{Signer, SigningKey};
+use ed25519_dalek::Signer;
+use mf2_i18n_runtime::{ManifestKey, RuntimeError, load_manifest, verify_manifest_signature};
 use thiserror::Error;
 
-use crate::manifest::{Manifest, ManifestSigning};
+use crate::manifest::{Manifest, ManifestSignatures, ManifestSigning};
 
 #[derive(Debug, Error)]
 pub enum SignCommandError {
@@ -12,10 +14,16 @@ pub enum SignCommandError {
     Io(#[from] std::io::Error),
     #[error("json error: {0}")]
     Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Runtime(#[from] RuntimeError),
     #[error("invalid signing key")]
     InvalidKey,
     #[error("invalid key length {0}")]
     InvalidKeyLength(usize),
+    #[error("unsupported signature algorithm {0}")]
+    UnsupportedAlgorithm(String),
+    #[error("key file is for {0}, not {1}")]
+    AlgorithmMismatch(String, String),
 }
 
 #[derive(Debug, Clone)]
@@ -24,51 +32,128 @@ pub struct SignOptions {
     pub key_path: PathBuf,
     pub key_id: String,
     pub out_path: Option<PathBuf>,
+    pub append: bool,
+    pub sig_alg: Option<String>,
+}
+
+enum SigningKey {
+    Ed25519(ed25519_dalek::SigningKey),
+    EcdsaP256(p256::ecdsa::SigningKey),
 }
 
 pub fn run_sign(options: &SignOptions) -> Result<(), SignCommandError> {
     let manifest_contents = fs::read_to_string(&options.manifest_path)?;
     let mut manifest: Manifest = serde_json::from_str(&manifest_contents)?;
-    let signing_key = load_signing_key(&options.key_path)?;
+    let signing_key = load_signing_key(&options.key_path, options.sig_alg.as_deref())?;
 
     let signature = sign_manifest(&manifest, &signing_key, &options.key_id);
-    manifest.signing = Some(signature);
+    match &mut manifest.signing {
+        Some(signing) if options.append => signing.push(signature),
+        _ => manifest.signing = Some(ManifestSignatures::Single(signature)),
+    }
 
     let out_path = options.out_path.as_ref().unwrap_or(&options.manifest_path);
     let json = serde_json::to_string_pretty(&manifest)?;
     fs::write(out_path, json)?;
+
+    let written = load_manifest(out_path)?;
+    let keyring = BTreeMap::from([(options.key_id.clone(), signing_key.manifest_key())]);
+    verify_manifest_signature(&written, &keyring)?;
     Ok(())
 }
 
+impl SigningKey {
+    fn manifest_key(&self) -> ManifestKey {
+        match self {
+            SigningKey::Ed25519(key) => key.verifying_key().into(),
+            SigningKey::EcdsaP256(key) => (*key.verifying_key()).into(),
+        }
+    }
+}
+
 fn sign_manifest(manifest: &Manifest, key: &SigningKey, key_id: &str) -> ManifestSigning {
     let bytes = manifest.to_signing_bytes();
-    let signature = key.sign(&bytes);
+    let (sig_alg, signature) = match key {
+        SigningKey::Ed25519(key) => ("ed25519", key.sign(&bytes).to_bytes().to_vec()),
+        SigningKey::EcdsaP256(key) => {
+            let signature: p256::ecdsa::Signature = key.sign(&bytes);
+            ("ecdsa-p256", signature.to_bytes().to_vec())
+        }
+    };
     ManifestSigning {
-        sig_alg: "ed25519".to_string(),
+        sig_alg: sig_alg.to_string(),
         key_id: key_id.to_string(),
-        manifest_sig: format!("hex:{}", hex::encode(signature.to_bytes())),
+        manifest_sig: format!("hex:{}", hex::encode(signature)),
     }
 }
 
-fn load_signing_key(path: &Path) -> Result<SigningKey, SignCommandError> {
+fn load_signing_key(path: &Path, sig_alg: Option<&str>) -> Result<SigningKey, SignCommandError> {
     let contents = fs::read_to_string(path)?;
     let trimmed = contents.trim();
-    let hex_text = trimmed.strip_prefix("hex:").unwrap_or(trimmed);
+    let (prefix, key_text) = match trimmed.split_once(':') {
+        Some((alg @ ("ed25519" | "ecdsa-p256"), rest)) => (Some(alg), rest),
+        _ => (None, trimmed),
+    };
+    let sig_alg = match (sig_alg, prefix) {
+        (Some(requested), Some(found)) if requested != found => {
+            return Err(SignCommandError::AlgorithmMismatch(
+                found.to_string(),
+                requested.to_string(),
+            ));
+        }
+        (Some(alg), _) | (None, Some(alg)) => alg,
+        (None, None) => "ed25519",
+    };
+    let hex_text = key_text.strip_prefix("hex:").unwrap_or(key_text);
     let bytes = hex::decode(hex_text).map_err(|_| SignCommandError::InvalidKey)?;
     if bytes.len() != 32 {
         return Err(SignCommandError::InvalidKeyLength(bytes.len()));
     }
-    let mut key_bytes = [0u8; 32];
-    key_bytes.copy_from_slice(&bytes);
-    Ok(SigningKey::from_bytes(&key_bytes))
+    match sig_alg {
+        "ed25519" => {
+            let mut key_bytes = [0u8; 32];
+            key_bytes.copy_from_slice(&bytes);
+            Ok(SigningKey::Ed25519(ed25519_dalek::SigningKey::from_bytes(
+                &key_bytes,
+            )))
+        }
+        "ecdsa-p256" => p256::ecdsa::SigningKey::from_slice(&bytes)
+            .map(SigningKey::EcdsaP256)
+            .map_err(|_| SignCommandError::InvalidKey),
+        other => Err(SignCommandError::UnsupportedAlgorithm(other.to_string())),
+    }
+}
+
+pub fn load_verifying_key(path: &Path) -> Result<ManifestKey, SignCommandError> {
+    let contents = fs::read_to_string(path)?;
+    let trimmed = contents.trim();
+    let (prefix, key_text) = match trimmed.split_once(':') {
+        Some((alg @ ("ed25519" | "ecdsa-p256"), rest)) => (Some(alg), rest),
+        _ => (None, trimmed),
+    };
+    let hex_text = key_text.strip_prefix("hex:").unwrap_or(key_text);
+    let bytes = hex::decode(hex_text).map_err(|_| SignCommandError::InvalidKey)?;
+    match (prefix, bytes.len()) {
+        (Some("ed25519") | None, 32) => {
+            let mut key_bytes = [0u8; 32];
+            key_bytes.copy_from_slice(&bytes);
+            ed25519_dalek::VerifyingKey::from_bytes(&key_bytes)
+                .map(ManifestKey::Ed25519)
+                .map_err(|_| SignCommandError::InvalidKey)
+        }
+        (Some("ed25519"), len) => Err(SignCommandError::InvalidKeyLength(len)),
+        _ => p256::ecdsa::VerifyingKey::from_sec1_bytes(&bytes)
+            .map(ManifestKey::EcdsaP256)
+            .map_err(|_| SignCommandError::InvalidKey),
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{SignOptions, load_signing_key, sign_manifest};
+    use super::{SignCommandError, SignOptions, SigningKey, load_signing_key, sign_manifest};
     use crate::command_sign::run_sign;
-    use crate::manifest::{Manifest, PackEntry};
-    use ed25519_dalek::SigningKey;
+    use crate::manifest::{Manifest, ManifestSignatures};
+    use mf2_i18n_test_support::ManifestBuilder;
     use std::collections::BTreeMap;
     use std::fs;
     use std::path::PathBuf;
@@ -84,33 +169,20 @@ mod tests {
         path
     }
 
+    const PINNED_SIGNING_BYTES: &str = concat!(
+        r#"{"default_locale":"en","generated_at":"2026-02-01T00:00:00Z","id_map_hash":"#,
+        r#""sha256:0000000000000000000000000000000000000000000000000000000000000000","#,
+        r#""mf2_packs":{"en":{"content_encoding":"identity","hash":"#,
+        r#""sha256:4862f447f2c7f272fa2f4aaf89dadb3b1ac09105bd5864f8d1a0c9452bb0a226","#,
+        r#""kind":"base","pack_schema":0,"size":4,"url":"packs/en.mf2pack"}},"#,
+        r#""release_id":"r1","schema":1,"supported_locales":["en","fr"]}"#,
+    );
+
     fn sample_manifest() -> Manifest {
-        let mut mf2_packs = BTreeMap::new();
-        mf2_packs.insert(
-            "en".to_string(),
-            PackEntry {
-                kind: "base".to_string(),
-                url: "packs/en.mf2pack".to_string(),
-                hash: "sha256:abc".to_string(),
-                size: 12,
-                content_encoding: "identity".to_string(),
-                pack_schema: 0,
-                parent: None,
-            },
-        );
-        Manifest {
-            schema: 1,
-            release_id: "r1".to_string(),
-            generated_at: "2026-02-01T00:00:00Z".to_string(),
-            default_locale: "en".to_string(),
-            supported_locales: vec!["en".to_string()],
-            id_map_hash: "sha256:dead".to_string(),
-            mf2_packs,
-            icu_packs: None,
-            micro_locales: None,
-            budgets: None,
-            signing: None,
-        }
+        let manifest = ManifestBuilder::new([0; 32])
+            .pack("en", None, b"pack")
+            .build();
+        serde_json::from_value(manifest).expect("manifest")
     }
 
     #[test]
@@ -121,18 +193,33 @@ mod tests {
             "hex:000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
         )
         .expect("write");
-        let key = load_signing_key(&path).expect("key");
-        let bytes = key.to_bytes();
-        assert_eq!(bytes[0], 0);
+        let key = load_signing_key(&path, None).expect("key");
+        assert!(matches!(key, SigningKey::Ed25519(key) if key.to_bytes()[0] == 0));
+
+        fs::write(&path, format!("ecdsa-p256:hex:{}", hex::encode([7u8; 32]))).expect("write");
+        let key = load_signing_key(&path, None).expect("key");
+        assert!(matches!(key, SigningKey::EcdsaP256(_)));
+        assert!(matches!(
+            load_signing_key(&path, Some("ed25519")),
+            Err(SignCommandError::AlgorithmMismatch(_, _))
+        ));
+
+        fs::write(&path, hex::encode([7u8; 32])).expect("write");
+        let key = load_signing_key(&path, Some("ecdsa-p256")).expect("key");
+        assert!(matches!(key, SigningKey::EcdsaP256(_)));
+        assert!(matches!(
+            load_signing_key(&path, Some("rsa")),
+            Err(SignCommandError::UnsupportedAlgorithm(_))
+        ));
         fs::remove_file(&path).ok();
     }
 
     #[test]
     fn signs_and_verifies_manifest() {
-        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
         let verifying_key = signing_key.verifying_key();
         let manifest = sample_manifest();
-        let signing = sign_manifest(&manifest, &signing_key, "demo");
+        let signing = sign_manifest(&manifest, &SigningKey::Ed25519(signing_key), "demo");
         let signature_bytes =
             hex::decode(signing.manifest_sig.trim_start_matches("hex:")).expect("hex");
         let signature = ed25519_dalek::Signature::from_slice(&signature_bytes).expect("sig");
@@ -141,6 +228,42 @@ mod tests {
             .expect("verify");
     }
 
+    #[test]
+    fn signing_bytes_format_is_pinned() {
+        let mut manifest = sample_manifest();
+        manifest.supported_locales = vec!["fr".to_string(), "en".to_string()];
+        let unsigned = manifest.to_signing_bytes();
+        assert_eq!(
+            String::from_utf8(unsigned.clone()).expect("utf8"),
+            PINNED_SIGNING_BYTES
+        );
+        let runtime: mf2_i18n_runtime::Manifest =
+            serde_json::from_slice(&manifest.to_canonical_bytes()).expect("runtime manifest");
+        assert_eq!(runtime.to_signing_bytes().expect("bytes"), unsigned);
+
+        manifest.signing = Some(ManifestSignatures::Single(sign_manifest(
+            &manifest,
+            &SigningKey::Ed25519(ed25519_dalek::SigningKey::from_bytes(&[7u8; 32])),
+            "demo",
+        )));
+        assert_eq!(manifest.to_signing_bytes(), unsigned);
+    }
+
+    #[test]
+    fn signs_ecdsa_p256_for_runtime_verification() {
+        let signing_key = p256::ecdsa::SigningKey::from_slice(&[7u8; 32]).expect("key");
+        let verifying_key = *signing_key.verifying_key();
+        let mut manifest = sample_manifest();
+        let signing = sign_manifest(&manifest, &SigningKey::EcdsaP256(signing_key), "pki");
+        assert_eq!(signing.sig_alg, "ecdsa-p256");
+        manifest.signing = Some(ManifestSignatures::Single(signing));
+
+        let runtime: mf2_i18n_runtime::Manifest =
+            serde_json::from_slice(&manifest.to_canonical_bytes()).expect("runtime manifest");
+        let keyring = BTreeMap::from([("pki".to_string(), verifying_key.into())]);
+        mf2_i18n_runtime::verify_manifest_signature(&runtime, &keyring).expect("verify");
+    }
+
     #[test]
     fn run_sign_writes_signature() {
         let manifest_path = temp_path("manifest");
@@ -160,12 +283,70 @@ mod tests {
             key_path,
             key_id: "key-1".to_string(),
             out_path: Some(out_path.clone()),
+            append: false,
+            sig_alg: None,
         };
         run_sign(&options).expect("sign");
         let signed_contents = fs::read_to_string(&out_path).expect("read");
         assert!(signed_contents.contains("\"signing\""));
+        let signed = mf2_i18n_runtime::load_manifest(&out_path).expect("runtime manifest");
+        let key = load_signing_key(&options.key_path, None).expect("key");
+        let keyring = BTreeMap::from([("key-1".to_string(), key.manifest_key())]);
+        mf2_i18n_runtime::verify_manifest_signature(&signed, &keyring).expect("verify");
 
         fs::remove_file(&manifest_path).ok();
         fs::remove_file(&out_path).ok();
     }
+
+    #[test]
+    fn run_sign_appends_signatures() {
+        let manifest_path = temp_path("manifest");
+        let old_key_path = temp_path("old_key");
+        let new_key_path = temp_path("new_key");
+        fs::write(
+            &manifest_path,
+            serde_json::to_string_pretty(&sample_manifest()).expect("json"),
+        )
+        .expect("write");
+        fs::write(&old_key_path, hex::encode([3u8; 32])).expect("write");
+        fs::write(&new_key_path, hex::encode([4u8; 32])).expect("write");
+
+        let mut options = SignOptions {
+            manifest_path: manifest_path.clone(),
+            key_path: old_key_path.clone(),
+            key_id: "old".to_string(),
+            out_path: None,
+            append: true,
+            sig_alg: None,
+        };
+        run_sign(&options).expect("sign old");
+        options.key_path = new_key_path.clone();
+        options.key_id = "new".to_string();
+        run_sign(&options).expect("sign new");
+
+        let manifest: Manifest =
+            serde_json::from_str(&fs::read_to_string(&manifest_path).expect("read")).expect("json");
+        let signatures = manifest.signing.as_ref().expect("signing").as_slice();
+        let key_ids: Vec<&str> = signatures.iter().map(|s| s.key_id.as_str()).collect();
+        assert_eq!(key_ids, ["old", "new"]);
+        assert_eq!(
+            signatures[0].manifest_sig,
+            sign_manifest(
+                &manifest,
+                &SigningKey::Ed25519(ed25519_dalek::SigningKey::from_bytes(&[3u8; 32])),
+                "old"
+            )
+            .manifest_sig
+        );
+
+        options.append = false;
+        run_sign(&options).expect("overwrite");
+        let manifest: Manifest =
+            serde_json::from_str(&fs::read_to_string(&manifest_path).expect("read")).expect("json");
+        assert_eq!(manifest.signing.expect("signing").as_slice().len(), 1);
+
+        fs::remove_file(&manifest_path).ok();
+        fs::remove_file(&old_key_path).ok();
+        fs::remove_file(&new_key_path).ok();
+    }
 }
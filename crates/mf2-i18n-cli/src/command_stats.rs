@@ -0,0 +1,232 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use mf2_i18n_core::{BytecodeProgram, Catalog, Opcode, PackCatalog};
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::command_build::PackEncoding;
+use crate::manifest::Manifest;
+
+const SECTION_STRING_POOL: u8 = 1;
+
+#[derive(Debug, Error)]
+pub enum StatsCommandError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("invalid id map hash")]
+    InvalidHash,
+    #[error("unsupported content encoding {1} for locale {0}")]
+    UnsupportedEncoding(String, String),
+    #[error("pack error for locale {0}: {1}")]
+    Pack(String, String),
+}
+
+#[derive(Debug, Clone)]
+pub struct StatsOptions {
+    pub manifest_path: PathBuf,
+    pub out_path: PathBuf,
+}
+
+#[derive(Debug, Serialize)]
+struct StatsReport {
+    release_id: String,
+    total_messages: usize,
+    locales: BTreeMap<String, LocaleStats>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct LocaleStats {
+    messages: usize,
+    plural_messages: usize,
+    select_messages: usize,
+    opcodes: usize,
+    average_opcodes: f64,
+    case_tables: usize,
+    string_pool_bytes: u32,
+    pack_bytes: u64,
+}
+
+pub fn run_stats(options: &StatsOptions) -> Result<(), StatsCommandError> {
+    let manifest: Manifest = serde_json::from_slice(&fs::read(&options.manifest_path)?)?;
+    let id_map_hash = parse_id_map_hash(&manifest.id_map_hash)?;
+    let base_dir = options
+        .manifest_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."));
+
+    let mut locales = BTreeMap::new();
+    for (locale, entry) in &manifest.mf2_packs {
+        let encoding = PackEncoding::parse(&entry.content_encoding).ok_or_else(|| {
+            StatsCommandError::UnsupportedEncoding(locale.clone(), entry.content_encoding.clone())
+        })?;
+        let stored = fs::read(base_dir.join(&entry.url))?;
+        let bytes = encoding.decode(&stored)?;
+        let pack = PackCatalog::decode(&bytes, &id_map_hash)
+            .map_err(|err| StatsCommandError::Pack(locale.clone(), format!("{err:?}")))?;
+        let mut stats = pack_stats(&pack);
+        stats.pack_bytes = entry.size;
+        locales.insert(locale.clone(), stats);
+    }
+
+    let report = StatsReport {
+        release_id: manifest.release_id.clone(),
+        total_messages: locales
+            .get(&manifest.default_locale)
+            .map(|stats| stats.messages)
+            .unwrap_or_default(),
+        locales,
+    };
+    let json = serde_json::to_string_pretty(&report)?;
+    fs::write(&options.out_path, json)?;
+    Ok(())
+}
+
+fn pack_stats(pack: &PackCatalog) -> LocaleStats {
+    let mut stats = LocaleStats {
+        case_tables: pack.case_table_count(),
+        string_pool_bytes: pack
+            .sections()
+            .iter()
+            .find(|section| section.section_type == SECTION_STRING_POOL)
+            .map(|section| section.length)
+            .unwrap_or_default(),
+        ..LocaleStats::default()
+    };
+    for id in pack.message_ids() {
+        let Some(program) = pack.lookup(id) else {
+            continue;
+        };
+        stats.messages += 1;
+        stats.opcodes += program.opcodes.len();
+        if has_plural(program) {
+            stats.plural_messages += 1;
+        }
+        if program
+            .opcodes
+            .iter()
+            .any(|opcode| matches!(opcode, Opcode::Select { .. }))
+        {
+            stats.select_messages += 1;
+        }
+    }
+    if stats.messages > 0 {
+        stats.average_opcodes = stats.opcodes as f64 / stats.messages as f64;
+    }
+    stats
+}
+
+fn has_plural(program: &BytecodeProgram) -> bool {
+    program.opcodes.iter().any(|opcode| match opcode {
+        Opcode::SelectPlural { .. } => true,
+        Opcode::SelectMulti { table } => program
+            .multi_case_tables
+            .get(*table as usize)
+            .is_some_and(|table| {
+                table
+                    .selectors
+                    .iter()
+                    .any(|selector| selector.ruleset.is_some())
+            }),
+        _ => false,
+    })
+}
+
+fn parse_id_map_hash(value: &str) -> Result<[u8; 32], StatsCommandError> {
+    let hex = value.strip_prefix("sha256:").unwrap_or(value);
+    let bytes = hex::decode(hex).map_err(|_| StatsCommandError::InvalidHash)?;
+    bytes.try_into().map_err(|_| StatsCommandError::InvalidHash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StatsOptions, run_stats};
+    use crate::catalog::{Catalog, CatalogFeatures, CatalogMessage};
+    use crate::command_build::{BuildOptions, PackEncoding, run_build};
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        path.push(format!("mf2_i18n_{name}_{nanos}"));
+        fs::create_dir_all(&path).expect("dir");
+        path
+    }
+
+    #[test]
+    fn reports_opcodes_and_pool_size_for_sample_pack() {
+        let dir = temp_dir("stats_root");
+        let locales_dir = dir.join("locales").join("en");
+        fs::create_dir_all(&locales_dir).expect("locale");
+        fs::write(locales_dir.join("messages.mf2"), "home.title = Hi").expect("write");
+        let catalog = Catalog {
+            schema: 1,
+            project: "demo".to_string(),
+            generated_at: "2026-02-01T00:00:00Z".to_string(),
+            default_locale: "en".to_string(),
+            messages: vec![CatalogMessage {
+                key: "home.title".to_string(),
+                id: 1,
+                args: vec![],
+                features: CatalogFeatures::default(),
+                source_refs: None,
+            }],
+        };
+        let catalog_path = dir.join("i18n.catalog.json");
+        fs::write(&catalog_path, serde_json::to_string(&catalog).unwrap()).expect("catalog");
+        let hash_path = dir.join("id_map_hash");
+        fs::write(
+            &hash_path,
+            "sha256:000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+        )
+        .expect("hash");
+        let config_path = dir.join("mf2-i18n.toml");
+        fs::write(
+            &config_path,
+            "default_locale = \"en\"\nsource_dirs = [\"locales\"]\nproject_salt_path = \"tools/id_salt.txt\"",
+        )
+        .expect("config");
+
+        let out_dir = dir.join("out");
+        run_build(&BuildOptions {
+            catalog_path,
+            id_map_hash_path: hash_path,
+            config_path,
+            out_dir: out_dir.clone(),
+            release_id: "r1".to_string(),
+            generated_at: "2026-02-01T00:00:00Z".to_string(),
+            content_encoding: PackEncoding::Gzip,
+            max_pack_bytes: None,
+            locale_max_pack_bytes: BTreeMap::new(),
+            source_date_epoch: None,
+        })
+        .expect("build");
+
+        let out_path = dir.join("stats.json");
+        run_stats(&StatsOptions {
+            manifest_path: out_dir.join("manifest.json"),
+            out_path: out_path.clone(),
+        })
+        .expect("stats");
+
+        let report: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(out_path).expect("read")).expect("json");
+        let en = &report["locales"]["en"];
+        assert_eq!(report["total_messages"], 1);
+        assert_eq!(en["messages"], 1);
+        assert!(en["opcodes"].as_u64().expect("opcodes") > 0);
+        assert!(en["string_pool_bytes"].as_u64().expect("pool") > 0);
+        assert_eq!(en["plural_messages"], 0);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
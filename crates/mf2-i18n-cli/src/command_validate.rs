@@ -2,11 +2,17 @@ use std::path::{Path, PathBuf};
 
 use thiserror::Error;
 
+use mf2_i18n_core::{Args, BytecodeProgram, CaseKey, Opcode, PluralCategory, Value, execute};
+
+use crate::backend::{SmokeBackend, smoke_plural_value};
 use crate::catalog_reader::{CatalogReadError, load_catalog};
+use crate::compiler::compile_message;
 use crate::config::load_config_or_default;
-use crate::diagnostic::Diagnostic;
+use crate::diagnostic::{Diagnostic, DiagnosticFormat, Severity};
 use crate::locale_sources::{LocaleBundle, LocaleSourceError, load_locales};
-use crate::parser::parse_message;
+use crate::micro_locales::{MicroLocaleError, load_micro_locales};
+use crate::model::{ArgType, MessageSpec};
+use crate::parser::{parse_message, resolve_selector_kinds};
 use crate::validator::validate_message;
 
 #[derive(Debug, Error)]
@@ -17,8 +23,10 @@ pub enum ValidateCommandError {
     Catalog(#[from] CatalogReadError),
     #[error(transparent)]
     Source(#[from] LocaleSourceError),
-    #[error("validation failed with {0} diagnostics")]
-    Failed(usize),
+    #[error(transparent)]
+    MicroLocales(#[from] MicroLocaleError),
+    #[error("validation failed with {} diagnostics", .0.len())]
+    Failed(Vec<Diagnostic>),
 }
 
 #[derive(Debug, Clone)]
@@ -26,6 +34,8 @@ pub struct ValidateOptions {
     pub catalog_path: PathBuf,
     pub id_map_hash_path: PathBuf,
     pub config_path: PathBuf,
+    pub execute: bool,
+    pub format: DiagnosticFormat,
 }
 
 pub fn run_validate(options: &ValidateOptions) -> Result<Vec<Diagnostic>, ValidateCommandError> {
@@ -36,21 +46,38 @@ pub fn run_validate(options: &ValidateOptions) -> Result<Vec<Diagnostic>, Valida
         .iter()
         .map(|root| resolve_path(&options.config_path, root))
         .collect();
-    let locales = load_locales(&roots)?;
+    let locales = load_locales(&roots, config.duplicate_keys)?;
+    let micro_locale_map = load_micro_locales(&resolve_path(
+        &options.config_path,
+        config
+            .micro_locales_registry
+            .as_deref()
+            .unwrap_or("micro-locales.toml"),
+    ))?;
 
     let mut diagnostics = Vec::new();
     for locale in locales {
-        diagnostics.extend(validate_locale(&locale, &bundle.message_specs));
+        diagnostics.extend(locale.diagnostics.iter().cloned());
+        diagnostics.extend(validate_locale(
+            &locale,
+            &bundle.message_specs,
+            &config.formatter_options,
+            options.execute,
+            micro_locale_map.contains_key(&locale.locale),
+        ));
     }
 
-    if diagnostics.is_empty() {
-        Ok(diagnostics)
+    if diagnostics
+        .iter()
+        .any(|diag| diag.severity == Severity::Error)
+    {
+        Err(ValidateCommandError::Failed(diagnostics))
     } else {
-        Err(ValidateCommandError::Failed(diagnostics.len()))
+        Ok(diagnostics)
     }
 }
 
-fn resolve_path(config_path: &Path, value: &str) -> PathBuf {
+pub(crate) fn resolve_path(config_path: &Path, value: &str) -> PathBuf {
     let path = PathBuf::from(value);
     if path.is_absolute() {
         return path;
@@ -63,37 +90,49 @@ fn resolve_path(config_path: &Path, value: &str) -> PathBuf {
 
 fn validate_locale(
     locale: &LocaleBundle,
-    specs: &std::collections::BTreeMap<String, crate::model::MessageSpec>,
+    specs: &std::collections::BTreeMap<String, MessageSpec>,
+    formatter_options: &std::collections::BTreeMap<String, Vec<String>>,
+    execute: bool,
+    inherits_missing: bool,
 ) -> Vec<Diagnostic> {
     let mut diagnostics = Vec::new();
 
     for (key, spec) in specs {
         if !locale.messages.contains_key(key) {
+            if inherits_missing {
+                continue;
+            }
             diagnostics.push(Diagnostic::new("MF2E100", "missing key").with_span(
                 format!("locale:{}", locale.locale),
                 1,
                 1,
             ));
-        } else {
-            if let Some(entry) = locale.messages.get(key) {
-                match parse_message(&entry.value) {
-                    Ok(message) => {
-                        for mut diag in validate_message(&message, spec) {
-                            let line = entry.line + diag.line.unwrap_or(1) - 1;
-                            let column = diag.column.unwrap_or(1);
-                            diag.file = Some(entry.file.clone());
-                            diag.line = Some(line);
-                            diag.column = Some(column);
-                            diagnostics.push(diag);
+        } else if let Some(entry) = locale.messages.get(key) {
+            match parse_message(&entry.value) {
+                Ok(mut message) => {
+                    resolve_selector_kinds(&mut message, &spec.args);
+                    let message_diagnostics = validate_message(&message, spec, formatter_options);
+                    if execute && message_diagnostics.is_empty() {
+                        let program = compile_message(&message).program;
+                        if let Some(diag) = smoke_execute(&program, spec) {
+                            diagnostics.push(diag.with_span(entry.file.clone(), entry.line, 1));
                         }
                     }
-                    Err(err) => {
-                        diagnostics.push(
-                            Diagnostic::new("MF2E001", format!("parse error: {}", err.message))
-                                .with_span(entry.file.clone(), entry.line, 1),
-                        );
+                    for mut diag in message_diagnostics {
+                        let line = entry.line + diag.line.unwrap_or(1) - 1;
+                        let column = diag.column.unwrap_or(1);
+                        diag.file = Some(entry.file.clone());
+                        diag.line = Some(line);
+                        diag.column = Some(column);
+                        diagnostics.push(diag);
                     }
                 }
+                Err(err) => {
+                    diagnostics.push(
+                        Diagnostic::new("MF2E001", format!("parse error: {}", err.message))
+                            .with_span(entry.file.clone(), entry.line, 1),
+                    );
+                }
             }
         }
     }
@@ -111,11 +150,187 @@ fn validate_locale(
     diagnostics
 }
 
+fn smoke_execute(program: &BytecodeProgram, spec: &MessageSpec) -> Option<Diagnostic> {
+    let selectors = selector_candidates(program);
+    let base: Vec<(&str, Value)> = program
+        .arg_names
+        .iter()
+        .filter(|name| !selectors.iter().any(|(selected, _)| selected == name))
+        .map(|name| {
+            let arg_type = spec
+                .args
+                .iter()
+                .find(|arg| &arg.name == name)
+                .map_or(&ArgType::Any, |arg| &arg.arg_type);
+            (name.as_str(), placeholder_value(name, arg_type))
+        })
+        .collect();
+    for choice in smoke_choices(&selectors) {
+        let mut args = Args::new();
+        for (name, value) in &base {
+            args.insert(*name, value.clone());
+        }
+        for ((name, values), idx) in selectors.iter().zip(choice) {
+            args.insert(*name, values[idx].clone());
+        }
+        if let Err(err) = execute(program, &args, &SmokeBackend) {
+            return Some(Diagnostic::new(
+                "MF2E060",
+                format!("execution error: {err}"),
+            ));
+        }
+    }
+    None
+}
+
+const MAX_SMOKE_RUNS: usize = 256;
+
+fn smoke_choices(selectors: &[(&str, Vec<Value>)]) -> Vec<Vec<usize>> {
+    let total = selectors
+        .iter()
+        .try_fold(1usize, |total, (_, values)| total.checked_mul(values.len()));
+    if total.is_some_and(|total| total <= MAX_SMOKE_RUNS) {
+        let mut choices = vec![Vec::new()];
+        for (_, values) in selectors {
+            choices = choices
+                .into_iter()
+                .flat_map(|choice| {
+                    (0..values.len()).map(move |idx| {
+                        let mut next = choice.clone();
+                        next.push(idx);
+                        next
+                    })
+                })
+                .collect();
+        }
+        return choices;
+    }
+    let mut choices = vec![vec![0; selectors.len()]];
+    for (position, (_, values)) in selectors.iter().enumerate() {
+        for idx in 1..values.len() {
+            let mut choice = vec![0; selectors.len()];
+            choice[position] = idx;
+            choices.push(choice);
+        }
+    }
+    choices
+}
+
+fn selector_candidates(program: &BytecodeProgram) -> Vec<(&str, Vec<Value>)> {
+    let mut candidates: Vec<(&str, Vec<Value>)> = Vec::new();
+    let mut push = |aidx: u32, value: Value| {
+        let Some(name) = program.arg_name(aidx) else {
+            return;
+        };
+        let index = match candidates.iter().position(|(seen, _)| *seen == name) {
+            Some(index) => index,
+            None => {
+                candidates.push((name, Vec::new()));
+                candidates.len() - 1
+            }
+        };
+        let values = &mut candidates[index].1;
+        if !values.contains(&value) {
+            values.push(value);
+        }
+    };
+    for opcode in &program.opcodes {
+        match *opcode {
+            Opcode::Select { aidx, table } => {
+                for key in program
+                    .case_tables
+                    .get(table as usize)
+                    .into_iter()
+                    .flat_map(|table| &table.entries)
+                    .map(|entry| &entry.key)
+                {
+                    push(aidx, string_candidate(program, key));
+                }
+            }
+            Opcode::SelectPlural {
+                aidx,
+                table,
+                offset,
+                ..
+            } => {
+                for key in program
+                    .case_tables
+                    .get(table as usize)
+                    .into_iter()
+                    .flat_map(|table| &table.entries)
+                    .map(|entry| &entry.key)
+                {
+                    push(aidx, Value::Num(plural_candidate(key) + offset));
+                }
+            }
+            Opcode::SelectMulti { table } => {
+                let Some(table) = program.multi_case_tables.get(table as usize) else {
+                    continue;
+                };
+                for entry in &table.entries {
+                    for (selector, key) in table.selectors.iter().zip(&entry.keys) {
+                        let value = match selector.ruleset {
+                            Some(_) => Value::Num(plural_candidate(key)),
+                            None => string_candidate(program, key),
+                        };
+                        push(selector.aidx, value);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    candidates
+}
+
+fn string_candidate(program: &BytecodeProgram, key: &CaseKey) -> Value {
+    match key {
+        CaseKey::String(sidx) => Value::Str(
+            program
+                .string_pool
+                .get(*sidx)
+                .unwrap_or_default()
+                .to_string(),
+        ),
+        _ => Value::Str("\u{0}".to_string()),
+    }
+}
+
+fn plural_candidate(key: &CaseKey) -> f64 {
+    let category = match key {
+        CaseKey::Exact(value) => return *value as f64,
+        CaseKey::Category(category) => *category,
+        CaseKey::String(_) | CaseKey::Other => PluralCategory::Other,
+    };
+    smoke_plural_value(category)
+}
+
+fn placeholder_value(name: &str, arg_type: &ArgType) -> Value {
+    match arg_type {
+        ArgType::String | ArgType::Any => Value::Str(name.to_string()),
+        ArgType::Number => Value::Num(1.0),
+        ArgType::Bool => Value::Bool(true),
+        ArgType::DateTime => Value::DateTime(0),
+        ArgType::Unit => Value::Unit {
+            value: 1.0,
+            unit_id: 0,
+        },
+        ArgType::Currency => Value::Currency {
+            value: 1.0,
+            code: *b"USD",
+        },
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{ValidateOptions, run_validate};
+    use super::{ValidateOptions, run_validate, smoke_execute};
     use crate::catalog::{Catalog, CatalogFeatures, CatalogMessage};
-    use crate::model::{ArgSpec, ArgType};
+    use crate::compiler::compile_message;
+    use crate::diagnostic::{DiagnosticFormat, Severity};
+    use crate::model::{ArgSpec, ArgType, MessageSpec};
+    use crate::parser::parse_message;
+    use mf2_i18n_core::{CaseKey, Opcode};
     use std::fs;
     use std::path::PathBuf;
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -185,13 +400,82 @@ mod tests {
             catalog_path,
             id_map_hash_path: hash_path,
             config_path,
+            execute: true,
+            format: DiagnosticFormat::Text,
         };
         let err = run_validate(&options).expect_err("validate should fail");
         match err {
-            super::ValidateCommandError::Failed(count) => assert!(count > 0),
+            super::ValidateCommandError::Failed(diagnostics) => {
+                assert!(!diagnostics.is_empty());
+                assert!(
+                    diagnostics
+                        .iter()
+                        .all(|diag| diag.severity == Severity::Error)
+                );
+            }
             _ => panic!("unexpected error"),
         }
 
         fs::remove_dir_all(&dir).ok();
     }
+
+    #[test]
+    fn smoke_execution_catches_stack_underflow() {
+        let spec = MessageSpec {
+            key: "home.title".to_string(),
+            args: vec![ArgSpec {
+                name: "name".to_string(),
+                arg_type: ArgType::String,
+                required: true,
+            }],
+        };
+        let message = parse_message("Hi {$name}").expect("parse");
+        let mut program = compile_message(&message).program;
+        assert_eq!(smoke_execute(&program, &spec), None);
+
+        program.opcodes.insert(0, Opcode::EmitStack);
+        let diag = smoke_execute(&program, &spec).expect("diagnostic");
+        assert_eq!(diag.code, "MF2E060");
+        assert!(diag.message.contains("stack underflow"));
+    }
+
+    #[test]
+    fn smoke_execution_covers_every_branch() {
+        let spec = MessageSpec {
+            key: "inbox".to_string(),
+            args: vec![
+                ArgSpec {
+                    name: "count".to_string(),
+                    arg_type: ArgType::Number,
+                    required: true,
+                },
+                ArgSpec {
+                    name: "kind".to_string(),
+                    arg_type: ArgType::String,
+                    required: true,
+                },
+            ],
+        };
+        let message = parse_message(
+            "{ $count :plural -> [=0] {none} [one] {one} *[other] {{ $kind -> [mail] {mail} *[other] {items} }} }",
+        )
+        .expect("parse");
+        let program = compile_message(&message).program;
+        assert_eq!(smoke_execute(&program, &spec), None);
+
+        let targets: Vec<u32> = program
+            .case_tables
+            .iter()
+            .flat_map(|table| &table.entries)
+            .filter(|entry| entry.key != CaseKey::Other)
+            .map(|entry| entry.target)
+            .collect();
+        assert_eq!(targets.len(), 3);
+        for target in targets {
+            let mut broken = compile_message(&message).program;
+            broken.opcodes[target as usize] = Opcode::EmitStack;
+            let diag = smoke_execute(&broken, &spec).expect("diagnostic");
+            assert!(diag.message.contains("stack underflow"), "{target}");
+        }
+    }
 }
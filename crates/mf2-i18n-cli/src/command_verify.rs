@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use mf2_i18n_runtime::{RuntimeError, load_manifest, verify_manifest_signature};
+use thiserror::Error;
+
+use crate::command_sign::{SignCommandError, load_verifying_key};
+
+#[derive(Debug, Error)]
+pub enum VerifyCommandError {
+    #[error(transparent)]
+    Key(#[from] SignCommandError),
+    #[error(transparent)]
+    Runtime(#[from] RuntimeError),
+    #[error("manifest is not signed")]
+    Unsigned,
+}
+
+#[derive(Debug, Clone)]
+pub struct VerifyOptions {
+    pub manifest_path: PathBuf,
+    pub pubkey_path: PathBuf,
+    pub key_id: String,
+}
+
+pub fn run_verify(options: &VerifyOptions) -> Result<(), VerifyCommandError> {
+    let manifest = load_manifest(&options.manifest_path)?;
+    if manifest.signing.is_none() {
+        return Err(VerifyCommandError::Unsigned);
+    }
+    let key = load_verifying_key(&options.pubkey_path)?;
+    let keyring = BTreeMap::from([(options.key_id.clone(), key)]);
+    verify_manifest_signature(&manifest, &keyring)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{VerifyCommandError, VerifyOptions, run_verify};
+    use crate::command_sign::{SignOptions, run_sign};
+    use crate::manifest::Manifest;
+    use mf2_i18n_runtime::RuntimeError;
+    use mf2_i18n_test_support::ManifestBuilder;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_path(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        path.push(format!("mf2_i18n_verify_{name}_{nanos}.json"));
+        path
+    }
+
+    fn sample_manifest() -> Manifest {
+        let manifest = ManifestBuilder::new([0; 32])
+            .pack("en", None, b"pack")
+            .build();
+        serde_json::from_value(manifest).expect("manifest")
+    }
+
+    #[test]
+    fn verifies_signed_manifest_and_rejects_tampering() {
+        let manifest_path = temp_path("manifest");
+        let key_path = temp_path("signing_key");
+        let pubkey_path = temp_path("pubkey");
+        fs::write(
+            &manifest_path,
+            serde_json::to_string_pretty(&sample_manifest()).expect("json"),
+        )
+        .expect("write");
+        fs::write(&key_path, hex::encode([3u8; 32])).expect("write");
+        let verifying_key = ed25519_dalek::SigningKey::from_bytes(&[3u8; 32]).verifying_key();
+        fs::write(&pubkey_path, hex::encode(verifying_key.to_bytes())).expect("write");
+
+        let options = VerifyOptions {
+            manifest_path: manifest_path.clone(),
+            pubkey_path: pubkey_path.clone(),
+            key_id: "key-1".to_string(),
+        };
+        assert!(matches!(
+            run_verify(&options),
+            Err(VerifyCommandError::Unsigned)
+        ));
+
+        run_sign(&SignOptions {
+            manifest_path: manifest_path.clone(),
+            key_path: key_path.clone(),
+            key_id: "key-1".to_string(),
+            out_path: None,
+            append: false,
+            sig_alg: None,
+        })
+        .expect("sign");
+        run_verify(&options).expect("verify");
+
+        let mut tampered: Manifest =
+            serde_json::from_str(&fs::read_to_string(&manifest_path).expect("read")).expect("json");
+        tampered.release_id = "r2".to_string();
+        fs::write(
+            &manifest_path,
+            serde_json::to_string_pretty(&tampered).expect("json"),
+        )
+        .expect("write");
+        assert!(matches!(
+            run_verify(&options),
+            Err(VerifyCommandError::Runtime(RuntimeError::SignatureFailed))
+        ));
+
+        fs::remove_file(&manifest_path).ok();
+        fs::remove_file(&key_path).ok();
+        fs::remove_file(&pubkey_path).ok();
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use thiserror::Error;
+
+use crate::command_extract::{ExtractOptions, extract_artifacts};
+use crate::extract_pipeline::should_skip_dir;
+
+#[derive(Debug, Error)]
+pub enum WatchCommandError {
+    #[error("watch error: {0}")]
+    Notify(#[from] notify::Error),
+}
+
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    pub extract: ExtractOptions,
+    pub debounce: Duration,
+}
+
+pub fn run_watch(options: &WatchOptions) -> Result<(), WatchCommandError> {
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)?;
+    let roots: Vec<PathBuf> = options
+        .extract
+        .roots
+        .iter()
+        .map(|root| root.canonicalize().unwrap_or_else(|_| root.clone()))
+        .collect();
+    for root in &roots {
+        watcher.watch(root, RecursiveMode::Recursive)?;
+    }
+
+    let mut keys = run_cycle(&options.extract, &BTreeSet::new()).unwrap_or_default();
+    let mut pending_since: Option<Instant> = None;
+    loop {
+        let event = match pending_since {
+            Some(since) => receiver.recv_timeout(options.debounce.saturating_sub(since.elapsed())),
+            None => receiver
+                .recv()
+                .map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+        };
+        match event {
+            Ok(Ok(event)) => {
+                if is_relevant_event(&roots, &event) {
+                    pending_since = Some(Instant::now());
+                }
+                continue;
+            }
+            Ok(Err(err)) => {
+                eprintln!("watch error: {err}");
+                continue;
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(()),
+        }
+        pending_since = None;
+        if let Some(next_keys) = run_cycle(&options.extract, &keys) {
+            keys = next_keys;
+        }
+    }
+}
+
+fn is_relevant_event(roots: &[PathBuf], event: &Event) -> bool {
+    !matches!(event.kind, EventKind::Access(_))
+        && event.paths.iter().any(|path| is_watched_path(roots, path))
+}
+
+fn run_cycle(options: &ExtractOptions, previous: &BTreeSet<String>) -> Option<BTreeSet<String>> {
+    match extract_artifacts(options) {
+        Ok(extracted) => {
+            for warning in &extracted.warnings {
+                eprintln!("{warning}");
+            }
+            let keys: BTreeSet<String> = extracted
+                .output
+                .catalog
+                .messages
+                .iter()
+                .map(|message| message.key.clone())
+                .collect();
+            println!("{}", summarize(previous, &keys));
+            Some(keys)
+        }
+        Err(err) => {
+            eprintln!("extract failed: {err}");
+            None
+        }
+    }
+}
+
+fn summarize(previous: &BTreeSet<String>, current: &BTreeSet<String>) -> String {
+    let added: Vec<&str> = current.difference(previous).map(String::as_str).collect();
+    let removed: Vec<&str> = previous.difference(current).map(String::as_str).collect();
+    let mut out = format!(
+        "extracted {} keys (+{} -{})",
+        current.len(),
+        added.len(),
+        removed.len()
+    );
+    for key in added {
+        out.push_str(&format!("\n  + {key}"));
+    }
+    for key in removed {
+        out.push_str(&format!("\n  - {key}"));
+    }
+    out
+}
+
+fn is_watched_path(roots: &[PathBuf], path: &Path) -> bool {
+    if path.extension().and_then(|ext| ext.to_str()) != Some("rs") {
+        return false;
+    }
+    roots.iter().any(|root| {
+        if path == root {
+            return true;
+        }
+        let Ok(relative) = path.strip_prefix(root) else {
+            return false;
+        };
+        relative
+            .parent()
+            .is_some_and(|parent| !parent.ancestors().any(should_skip_dir))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_relevant_event, is_watched_path, summarize};
+    use notify::event::{AccessKind, CreateKind, ModifyKind};
+    use notify::{Event, EventKind};
+    use std::collections::BTreeSet;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn change_filter_ignores_target_and_non_rust_files() {
+        let roots = vec![PathBuf::from("/work/app")];
+        assert!(is_watched_path(&roots, Path::new("/work/app/src/lib.rs")));
+        assert!(!is_watched_path(
+            &roots,
+            Path::new("/work/app/target/debug/build/out.rs")
+        ));
+        assert!(!is_watched_path(
+            &roots,
+            Path::new("/work/app/.git/hooks/x.rs")
+        ));
+        assert!(!is_watched_path(
+            &roots,
+            Path::new("/work/app/src/notes.md")
+        ));
+        assert!(!is_watched_path(&roots, Path::new("/elsewhere/src/lib.rs")));
+    }
+
+    #[test]
+    fn change_filter_only_checks_components_below_the_root() {
+        let roots = vec![PathBuf::from("/home/me/target/app")];
+        assert!(is_watched_path(
+            &roots,
+            Path::new("/home/me/target/app/src/lib.rs")
+        ));
+        assert!(!is_watched_path(
+            &roots,
+            Path::new("/home/me/target/app/target/out.rs")
+        ));
+        let file_root = vec![PathBuf::from("/home/me/target/main.rs")];
+        assert!(is_watched_path(
+            &file_root,
+            Path::new("/home/me/target/main.rs")
+        ));
+    }
+
+    #[test]
+    fn ignores_access_events() {
+        let roots = vec![PathBuf::from("/work/app")];
+        let path = PathBuf::from("/work/app/src/lib.rs");
+        let modified = Event::new(EventKind::Modify(ModifyKind::Any)).add_path(path.clone());
+        assert!(is_relevant_event(&roots, &modified));
+        let created = Event::new(EventKind::Create(CreateKind::File)).add_path(path.clone());
+        assert!(is_relevant_event(&roots, &created));
+        let accessed = Event::new(EventKind::Access(AccessKind::Any)).add_path(path);
+        assert!(!is_relevant_event(&roots, &accessed));
+    }
+
+    #[test]
+    fn summarizes_added_and_removed_keys() {
+        let previous: BTreeSet<String> = ["home.title".to_string()].into_iter().collect();
+        let current: BTreeSet<String> = ["cart.count".to_string()].into_iter().collect();
+        assert_eq!(
+            summarize(&previous, &current),
+            "extracted 1 keys (+1 -1)\n  + cart.count\n  - home.title"
+        );
+    }
+}
@@ -1,10 +1,14 @@
 use std::collections::BTreeMap;
 
 use mf2_i18n_core::{
-    BytecodeProgram, CaseEntry, CaseKey, CaseTable, FormatterId, Opcode, PluralRuleset,
+    BytecodeProgram, CaseEntry, CaseKey, CaseTable, FormatterId, MultiCaseEntry, MultiCaseTable,
+    MultiSelector, Opcode, PluralCategory, PluralRuleset,
 };
 
-use crate::parser::{CaseKey as AstCaseKey, Expr, Message, Segment, SelectKind, VarExpr};
+use crate::parser::{
+    CaseKey as AstCaseKey, Declaration, Expr, FormatOption, Literal, LiteralExpr, MarkupExpr,
+    MarkupKind, Message, OptionValue, Segment, SelectExpr, SelectKind, VarExpr,
+};
 
 pub struct CompileResult {
     pub program: BytecodeProgram,
@@ -12,6 +16,7 @@ pub struct CompileResult {
 
 pub fn compile_message(message: &Message) -> CompileResult {
     let mut compiler = Compiler::new();
+    compiler.compile_declarations(&message.declarations);
     compiler.compile_message(message);
     compiler.program.opcodes.push(Opcode::End);
     CompileResult {
@@ -22,6 +27,8 @@ pub fn compile_message(message: &Message) -> CompileResult {
 struct Compiler {
     program: BytecodeProgram,
     arg_indices: BTreeMap<String, u32>,
+    locals: BTreeMap<String, Expr>,
+    plural_scopes: Vec<(u32, f64)>,
 }
 
 impl Compiler {
@@ -29,6 +36,8 @@ impl Compiler {
         Self {
             program: BytecodeProgram::new(),
             arg_indices: BTreeMap::new(),
+            locals: BTreeMap::new(),
+            plural_scopes: Vec::new(),
         }
     }
 
@@ -39,51 +48,256 @@ impl Compiler {
                     let sidx = self.program.string_pool.push(value.clone());
                     self.program.opcodes.push(Opcode::EmitText { sidx });
                 }
+                Segment::Pound { .. } => self.compile_pound(),
                 Segment::Expr(expr) => match expr {
-                    Expr::Variable(var) => self.compile_var(var),
+                    Expr::Variable(_) | Expr::Literal(_) => self.compile_value(expr),
                     Expr::Select(select) => self.compile_select(select),
+                    Expr::Markup(markup) => self.compile_markup(markup),
                 },
             }
         }
     }
 
-    fn compile_var(&mut self, var: &VarExpr) {
-        let aidx = self.arg_index(&var.name);
-        self.program.opcodes.push(Opcode::PushArg { aidx });
-        if let Some(formatter) = &var.formatter {
+    fn compile_declarations(&mut self, declarations: &[Declaration]) {
+        for declaration in declarations {
+            let value = self.resolve(&declaration.value);
+            self.locals.insert(declaration.name.clone(), value);
+        }
+    }
+
+    fn resolve(&self, expr: &Expr) -> Expr {
+        let Expr::Variable(var) = expr else {
+            return expr.clone();
+        };
+        match (self.locals.get(&var.name), &var.formatter) {
+            (Some(local), None) => local.clone(),
+            (Some(Expr::Variable(source)), Some(_)) => Expr::Variable(VarExpr {
+                name: source.name.clone(),
+                ..var.clone()
+            }),
+            (Some(Expr::Literal(literal)), Some(_)) => Expr::Literal(LiteralExpr {
+                value: literal.value.clone(),
+                formatter: var.formatter.clone(),
+                options: var.options.clone(),
+                span: var.span.clone(),
+            }),
+            _ => expr.clone(),
+        }
+    }
+
+    fn compile_value(&mut self, expr: &Expr) {
+        let value = self.resolve(expr);
+        self.push_operand(&value);
+        let (formatter, options) = match &value {
+            Expr::Variable(var) => (&var.formatter, &var.options),
+            Expr::Literal(literal) => (&literal.formatter, &literal.options),
+            _ => return,
+        };
+        if let Some(formatter) = formatter {
             let fid = formatter_id(formatter);
+            for option in options {
+                self.compile_option(option);
+            }
+            let opt_count = options.len().min(u8::MAX as usize) as u8;
             self.program
                 .opcodes
-                .push(Opcode::CallFmt { fid, opt_count: 0 });
+                .push(Opcode::CallFmt { fid, opt_count });
         }
         self.program.opcodes.push(Opcode::EmitStack);
     }
 
-    fn compile_select(&mut self, select: &crate::parser::SelectExpr) {
-        let aidx = self.arg_index(&select.selector);
+    fn push_operand(&mut self, value: &Expr) {
+        let opcode = match value {
+            Expr::Variable(var) => Opcode::PushArg {
+                aidx: self.arg_index(&var.name),
+            },
+            Expr::Literal(literal) => self.literal_opcode(literal),
+            _ => return,
+        };
+        self.program.opcodes.push(opcode);
+    }
+
+    fn literal_opcode(&mut self, literal: &LiteralExpr) -> Opcode {
+        let number = match (&literal.value, literal.formatter.as_deref()) {
+            (Literal::Number(raw), _) | (Literal::Quoted(raw), Some("number")) => {
+                raw.parse::<f64>().ok()
+            }
+            (Literal::Quoted(_), _) => None,
+        };
+        match (number, &literal.value) {
+            (Some(number), _) => {
+                let nidx = self.program.number_pool.len() as u32;
+                self.program.number_pool.push(number);
+                Opcode::PushNum { nidx }
+            }
+            (None, Literal::Number(raw) | Literal::Quoted(raw)) => Opcode::PushStr {
+                sidx: self.program.string_pool.push(raw.clone()),
+            },
+        }
+    }
+
+    fn compile_option(&mut self, option: &FormatOption) {
+        let sidx = self.program.string_pool.push(option.name.clone());
+        self.program.opcodes.push(Opcode::PushStr { sidx });
+        let opcode = match &option.value {
+            OptionValue::Variable(name) => match self.locals.get(name).cloned() {
+                Some(local) => {
+                    self.push_operand(&local);
+                    return;
+                }
+                None => Opcode::PushArg {
+                    aidx: self.arg_index(name),
+                },
+            },
+            OptionValue::Number(raw) => match raw.parse::<f64>() {
+                Ok(number) => {
+                    let nidx = self.program.number_pool.len() as u32;
+                    self.program.number_pool.push(number);
+                    Opcode::PushNum { nidx }
+                }
+                Err(_) => Opcode::PushStr {
+                    sidx: self.program.string_pool.push(raw.clone()),
+                },
+            },
+            OptionValue::Literal(raw) => Opcode::PushStr {
+                sidx: self.program.string_pool.push(raw.clone()),
+            },
+        };
+        self.program.opcodes.push(opcode);
+    }
+
+    fn compile_markup(&mut self, markup: &MarkupExpr) {
+        for option in &markup.options {
+            self.compile_option(option);
+        }
+        let sidx = self.program.string_pool.push(markup.name.clone());
+        let opt_count = markup.options.len().min(u8::MAX as usize) as u8;
+        let opcode = match markup.kind {
+            MarkupKind::Open => Opcode::OpenMarkup { sidx, opt_count },
+            MarkupKind::Close => Opcode::CloseMarkup { sidx, opt_count },
+            MarkupKind::Standalone => Opcode::StandaloneMarkup { sidx, opt_count },
+        };
+        self.program.opcodes.push(opcode);
+    }
+
+    fn compile_pound(&mut self) {
+        let Some((aidx, offset)) = self.plural_scopes.last().copied() else {
+            let sidx = self.program.string_pool.push("#");
+            self.program.opcodes.push(Opcode::EmitText { sidx });
+            return;
+        };
+        self.program
+            .opcodes
+            .push(Opcode::PushPluralArg { aidx, offset });
+        self.program.opcodes.push(Opcode::CallFmt {
+            fid: FormatterId::Number,
+            opt_count: 0,
+        });
+        self.program.opcodes.push(Opcode::EmitStack);
+    }
+
+    fn compile_select(&mut self, select: &SelectExpr) {
+        if select.selectors.len() > 1 {
+            self.compile_multi_select(select);
+            return;
+        }
+        let selector = &select.selectors[0];
+        let aidx = self.selector_index(&selector.name);
         let table_idx = self.program.case_tables.len() as u32;
-        let select_pos = self.program.opcodes.len();
-        let opcode = match select.kind {
-            SelectKind::Plural => Opcode::SelectPlural {
+        self.program.case_tables.push(CaseTable {
+            entries: Vec::new(),
+        });
+        let offset = select.offset.map(f64::from).unwrap_or(0.0);
+        let opcode = match plural_ruleset(&selector.kind) {
+            Some(ruleset) => Opcode::SelectPlural {
                 aidx,
-                ruleset: PluralRuleset::Cardinal,
+                ruleset,
                 table: table_idx,
+                offset,
             },
-            SelectKind::Select => Opcode::Select {
+            None => Opcode::Select {
                 aidx,
                 table: table_idx,
             },
         };
         self.program.opcodes.push(opcode);
 
+        let is_plural = selector.kind != SelectKind::Select;
+        if is_plural {
+            self.plural_scopes.push((aidx, offset));
+        }
         let mut entries = Vec::with_capacity(select.cases.len());
-        let mut jumps = Vec::new();
-        for case in &select.cases {
-            let start = self.program.opcodes.len() as u32;
+        let targets = self.compile_cases(select);
+        for (case, target) in select.cases.iter().zip(targets) {
             entries.push(CaseEntry {
-                key: compile_case_key(&mut self.program, &case.key, case.is_default),
-                target: start,
+                key: compile_case_key(
+                    &mut self.program,
+                    &case.keys[0],
+                    case.is_default,
+                    &selector.kind,
+                ),
+                target,
             });
+        }
+        if is_plural {
+            self.plural_scopes.pop();
+        }
+
+        let mut table = CaseTable { entries };
+        table.sort_entries(&self.program.string_pool);
+        self.program.case_tables[table_idx as usize] = table;
+    }
+
+    fn compile_multi_select(&mut self, select: &SelectExpr) {
+        let selectors: Vec<MultiSelector> = select
+            .selectors
+            .iter()
+            .map(|selector| MultiSelector {
+                aidx: self.selector_index(&selector.name),
+                ruleset: plural_ruleset(&selector.kind),
+            })
+            .collect();
+        let table_idx = self.program.multi_case_tables.len() as u32;
+        self.program.multi_case_tables.push(MultiCaseTable {
+            selectors: selectors.clone(),
+            entries: Vec::new(),
+        });
+        self.program
+            .opcodes
+            .push(Opcode::SelectMulti { table: table_idx });
+
+        let plural_scope = selectors
+            .iter()
+            .find(|selector| selector.ruleset.is_some())
+            .map(|selector| (selector.aidx, 0.0));
+        if let Some(scope) = plural_scope {
+            self.plural_scopes.push(scope);
+        }
+        let mut entries = Vec::with_capacity(select.cases.len());
+        let targets = self.compile_cases(select);
+        for (case, target) in select.cases.iter().zip(targets) {
+            let keys = case
+                .keys
+                .iter()
+                .zip(&select.selectors)
+                .map(|(key, selector)| {
+                    compile_case_key(&mut self.program, key, case.is_default, &selector.kind)
+                })
+                .collect();
+            entries.push(MultiCaseEntry { keys, target });
+        }
+        if plural_scope.is_some() {
+            self.plural_scopes.pop();
+        }
+
+        self.program.multi_case_tables[table_idx as usize].entries = entries;
+    }
+
+    fn compile_cases(&mut self, select: &SelectExpr) -> Vec<u32> {
+        let mut targets = Vec::with_capacity(select.cases.len());
+        let mut jumps = Vec::new();
+        for case in &select.cases {
+            targets.push(self.program.opcodes.len() as u32);
             self.compile_message(&case.value);
             let jump_pos = self.program.opcodes.len();
             self.program.opcodes.push(Opcode::Jump { rel: 0 });
@@ -96,22 +310,17 @@ impl Compiler {
                 *rel = end - jump_pos as i32;
             }
         }
+        targets
+    }
 
-        if let Some(opcode) = self.program.opcodes.get_mut(select_pos) {
-            *opcode = match select.kind {
-                SelectKind::Plural => Opcode::SelectPlural {
-                    aidx,
-                    ruleset: PluralRuleset::Cardinal,
-                    table: table_idx,
-                },
-                SelectKind::Select => Opcode::Select {
-                    aidx,
-                    table: table_idx,
-                },
-            };
+    fn selector_index(&mut self, name: &str) -> u32 {
+        match self.locals.get(name) {
+            Some(Expr::Variable(source)) => {
+                let source = source.name.clone();
+                self.arg_index(&source)
+            }
+            _ => self.arg_index(name),
         }
-
-        self.program.case_tables.push(CaseTable { entries });
     }
 
     fn arg_index(&mut self, name: &str) -> u32 {
@@ -136,7 +345,20 @@ fn formatter_id(name: &str) -> FormatterId {
     }
 }
 
-fn compile_case_key(program: &mut BytecodeProgram, key: &AstCaseKey, is_default: bool) -> CaseKey {
+fn plural_ruleset(kind: &SelectKind) -> Option<PluralRuleset> {
+    match kind {
+        SelectKind::Plural => Some(PluralRuleset::Cardinal),
+        SelectKind::Ordinal => Some(PluralRuleset::Ordinal),
+        SelectKind::Select => None,
+    }
+}
+
+fn compile_case_key(
+    program: &mut BytecodeProgram,
+    key: &AstCaseKey,
+    is_default: bool,
+    kind: &SelectKind,
+) -> CaseKey {
     if is_default {
         return CaseKey::Other;
     }
@@ -144,18 +366,121 @@ fn compile_case_key(program: &mut BytecodeProgram, key: &AstCaseKey, is_default:
         AstCaseKey::Other => CaseKey::Other,
         AstCaseKey::Exact(value) => CaseKey::Exact(*value),
         AstCaseKey::Ident(value) => {
+            if !matches!(kind, SelectKind::Select)
+                && let Some(category) = plural_category(value)
+            {
+                return CaseKey::Category(category);
+            }
             let sidx = program.string_pool.push(value.clone());
             CaseKey::String(sidx)
         }
     }
 }
 
+fn plural_category(name: &str) -> Option<PluralCategory> {
+    match name {
+        "zero" => Some(PluralCategory::Zero),
+        "one" => Some(PluralCategory::One),
+        "two" => Some(PluralCategory::Two),
+        "few" => Some(PluralCategory::Few),
+        "many" => Some(PluralCategory::Many),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
+    use mf2_i18n_core::{
+        Args, BytecodeProgram, CaseKey, Catalog, CoreError, CoreResult, FormatBackend, FormatterId,
+        FormatterOption, MessageId, Opcode, PackBuildInput, PackCatalog, PackKind, PluralCategory,
+        PluralRuleset, Value, encode_pack, execute,
+    };
+
     use crate::parser::parse_message;
 
     use super::compile_message;
 
+    struct NumberOnlyBackend;
+
+    impl FormatBackend for NumberOnlyBackend {
+        fn plural_category(&self, _value: f64) -> CoreResult<PluralCategory> {
+            Ok(PluralCategory::Other)
+        }
+
+        fn format_number(&self, value: f64, options: &[FormatterOption]) -> CoreResult<String> {
+            Ok(format!("number({value}, {} options)", options.len()))
+        }
+
+        fn format_date(&self, _value: i64, _options: &[FormatterOption]) -> CoreResult<String> {
+            Err(CoreError::Unsupported("date"))
+        }
+
+        fn format_time(&self, _value: i64, _options: &[FormatterOption]) -> CoreResult<String> {
+            Err(CoreError::Unsupported("time"))
+        }
+
+        fn format_datetime(&self, _value: i64, _options: &[FormatterOption]) -> CoreResult<String> {
+            Err(CoreError::Unsupported("datetime"))
+        }
+
+        fn format_unit(
+            &self,
+            _value: f64,
+            _unit_id: u32,
+            _options: &[FormatterOption],
+        ) -> CoreResult<String> {
+            Err(CoreError::Unsupported("unit"))
+        }
+
+        fn format_currency(
+            &self,
+            _value: f64,
+            _code: [u8; 3],
+            _options: &[FormatterOption],
+        ) -> CoreResult<String> {
+            Err(CoreError::Unsupported("currency"))
+        }
+    }
+
+    #[test]
+    fn formats_literal_operands() {
+        let message =
+            parse_message("{ 42 :number useGrouping=false } and { |7.5| :number } { |x| }")
+                .expect("parse");
+        let program = compile_message(&message).program;
+        assert_eq!(program.opcodes[0], Opcode::PushNum { nidx: 0 });
+        assert_eq!(program.number_pool, vec![42.0, 7.5]);
+        let out = execute(&program, &Args::new(), &NumberOnlyBackend).expect("format");
+        assert_eq!(out, "number(42, 1 options) and number(7.5, 0 options) x");
+    }
+
+    #[test]
+    fn declared_locals_render_consistently() {
+        let message = parse_message(
+            ".local $n = { $count :number }\nYou have { $n } items, { $n } in total.",
+        )
+        .expect("parse");
+        let program = compile_message(&message).program;
+        assert_eq!(program.arg_names, vec!["count".to_string()]);
+        let mut args = Args::new();
+        args.insert("count", Value::Num(3.0));
+        let out = execute(&program, &args, &NumberOnlyBackend).expect("format");
+        assert_eq!(
+            out,
+            "You have number(3, 0 options) items, number(3, 0 options) in total."
+        );
+
+        let message = parse_message(
+            ".input { $count :number }\n{ $count } / { $count :number minimumFractionDigits=2 }",
+        )
+        .expect("parse");
+        let program = compile_message(&message).program;
+        let out = execute(&program, &args, &NumberOnlyBackend).expect("format");
+        assert_eq!(out, "number(3, 0 options) / number(3, 1 options)");
+    }
+
     #[test]
     fn compiles_simple_message() {
         let message = parse_message("Hello { $name }").expect("parse");
@@ -163,10 +488,465 @@ mod tests {
         assert!(!compiled.program.opcodes.is_empty());
     }
 
+    #[test]
+    fn emits_unescaped_text() {
+        let message = parse_message(r"Hello \{world\}").expect("parse");
+        let program = compile_message(&message).program;
+        let Opcode::EmitText { sidx } = program.opcodes[0] else {
+            panic!("expected text");
+        };
+        assert_eq!(program.string_pool.get(sidx), Some("Hello {world}"));
+    }
+
+    #[test]
+    fn compiles_formatter_options() {
+        let message = parse_message("{ $n :number maximumFractionDigits=2 useGrouping=false }")
+            .expect("parse");
+        let program = compile_message(&message).program;
+        assert_eq!(program.number_pool, vec![2.0]);
+        assert!(matches!(program.opcodes[1], Opcode::PushStr { .. }));
+        assert_eq!(program.opcodes[2], Opcode::PushNum { nidx: 0 });
+        assert!(matches!(program.opcodes[4], Opcode::PushStr { .. }));
+        assert_eq!(
+            program.opcodes[5],
+            Opcode::CallFmt {
+                fid: FormatterId::Number,
+                opt_count: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn compiles_markup_with_variable_options() {
+        let message = parse_message("{#link href=$url}go{/link}").expect("parse");
+        let program = compile_message(&message).program;
+        assert!(matches!(program.opcodes[0], Opcode::PushStr { .. }));
+        assert_eq!(program.opcodes[1], Opcode::PushArg { aidx: 0 });
+        assert!(matches!(
+            program.opcodes[2],
+            Opcode::OpenMarkup { opt_count: 1, .. }
+        ));
+        assert!(matches!(
+            program.opcodes[4],
+            Opcode::CloseMarkup { opt_count: 0, .. }
+        ));
+        assert_eq!(program.arg_names, vec!["url".to_string()]);
+    }
+
     #[test]
     fn compiles_select_message() {
         let message = parse_message("{ $count -> [one] {1} *[other] {n} }").expect("parse");
         let compiled = compile_message(&message);
         assert!(!compiled.program.case_tables.is_empty());
     }
+
+    #[test]
+    fn compiles_ordinal_message() {
+        let message = parse_message("{ $place :ordinal -> [one] {st} [two] {nd} *[other] {th} }")
+            .expect("parse");
+        let compiled = compile_message(&message);
+        assert_eq!(
+            compiled.program.opcodes[0],
+            Opcode::SelectPlural {
+                aidx: 0,
+                ruleset: PluralRuleset::Ordinal,
+                table: 0,
+                offset: 0.0,
+            }
+        );
+        assert_eq!(
+            compiled.program.case_tables[0].entries[0].key,
+            CaseKey::Category(PluralCategory::One)
+        );
+    }
+
+    #[test]
+    fn compiles_plural_offset_and_pound() {
+        let message =
+            parse_message("{ $count -> offset:1 [=0] {just you} *[other] {you and # others} }")
+                .expect("parse");
+        let compiled = compile_message(&message);
+        assert_eq!(
+            compiled.program.opcodes[0],
+            Opcode::SelectPlural {
+                aidx: 0,
+                ruleset: PluralRuleset::Cardinal,
+                table: 0,
+                offset: 1.0,
+            }
+        );
+        assert!(compiled.program.opcodes.contains(&Opcode::PushPluralArg {
+            aidx: 0,
+            offset: 1.0,
+        }));
+    }
+
+    #[test]
+    fn pound_renders_the_plural_selector() {
+        struct EnglishBackend;
+
+        impl FormatBackend for EnglishBackend {
+            fn plural_category(&self, value: f64) -> CoreResult<PluralCategory> {
+                Ok(if value == 1.0 {
+                    PluralCategory::One
+                } else {
+                    PluralCategory::Other
+                })
+            }
+
+            fn format_number(
+                &self,
+                value: f64,
+                _options: &[FormatterOption],
+            ) -> CoreResult<String> {
+                Ok(value.to_string())
+            }
+
+            fn format_date(&self, _value: i64, _options: &[FormatterOption]) -> CoreResult<String> {
+                Err(CoreError::Unsupported("date"))
+            }
+
+            fn format_time(&self, _value: i64, _options: &[FormatterOption]) -> CoreResult<String> {
+                Err(CoreError::Unsupported("time"))
+            }
+
+            fn format_datetime(
+                &self,
+                _value: i64,
+                _options: &[FormatterOption],
+            ) -> CoreResult<String> {
+                Err(CoreError::Unsupported("datetime"))
+            }
+
+            fn format_unit(
+                &self,
+                _value: f64,
+                _unit_id: u32,
+                _options: &[FormatterOption],
+            ) -> CoreResult<String> {
+                Err(CoreError::Unsupported("unit"))
+            }
+
+            fn format_currency(
+                &self,
+                _value: f64,
+                _code: [u8; 3],
+                _options: &[FormatterOption],
+            ) -> CoreResult<String> {
+                Err(CoreError::Unsupported("currency"))
+            }
+        }
+
+        let message = parse_message("{ $n -> [one] {# item} *[other] {# items} }").expect("parse");
+        let program = compile_message(&message).program;
+        assert!(matches!(
+            program.opcodes[0],
+            Opcode::SelectPlural {
+                ruleset: PluralRuleset::Cardinal,
+                ..
+            }
+        ));
+        for (count, expected) in [(1, "1 item"), (2, "2 items")] {
+            let mut args = Args::new();
+            args.with_int("n", count);
+            let out = execute(&program, &args, &EnglishBackend).expect("format");
+            assert_eq!(out, expected);
+        }
+
+        let message = parse_message("{ $kind -> [one] {# first} [red] {red} *[other] {rest} }")
+            .expect("parse");
+        assert!(!matches!(
+            compile_message(&message).program.opcodes[0],
+            Opcode::SelectPlural { .. }
+        ));
+        let message =
+            parse_message("{ $kind -> [one] {one} [two] {two} *[other] {rest} }").expect("parse");
+        assert!(matches!(
+            compile_message(&message).program.opcodes[0],
+            Opcode::Select { .. }
+        ));
+    }
+
+    #[test]
+    fn nested_selects_reference_their_own_tables() {
+        let message = parse_message(
+            "{ $kind -> [a] {{ $count -> [=1] {one} *[other] {many} }} *[other] {none} }",
+        )
+        .expect("parse");
+        let compiled = compile_message(&message);
+        assert_eq!(
+            compiled.program.opcodes[0],
+            Opcode::Select { aidx: 0, table: 0 }
+        );
+        assert_eq!(compiled.program.case_tables[0].entries.len(), 2);
+        assert_eq!(compiled.program.case_tables[1].entries.len(), 2);
+    }
+
+    #[test]
+    fn compiles_multi_selector_message() {
+        let message = parse_message(
+            "{ $count :plural $gender -> [one female] {a} [* male] {b} *[other other] {c} }",
+        )
+        .expect("parse");
+        let compiled = compile_message(&message);
+        assert_eq!(
+            compiled.program.opcodes[0],
+            Opcode::SelectMulti { table: 0 }
+        );
+        assert!(compiled.program.case_tables.is_empty());
+        let table = &compiled.program.multi_case_tables[0];
+        assert_eq!(table.selectors[0].ruleset, Some(PluralRuleset::Cardinal));
+        assert_eq!(table.selectors[1].ruleset, None);
+        assert_eq!(
+            table.entries[0].keys[0],
+            CaseKey::Category(PluralCategory::One)
+        );
+        assert_eq!(table.entries[1].keys[0], CaseKey::Other);
+        assert_eq!(table.entries[2].keys, vec![CaseKey::Other, CaseKey::Other]);
+    }
+
+    #[test]
+    fn dedupes_identical_case_tables() {
+        let mut messages = BTreeMap::new();
+        let mut sources = BTreeMap::new();
+        for id in 0..40u64 {
+            let source =
+                format!("{{ $count :plural -> [one] {{# item{id}}} *[other] {{# items{id}}} }}");
+            let message = parse_message(&source).expect("parse");
+            sources.insert(MessageId::new(id), compile_message(&message).program);
+            messages.insert(MessageId::new(id), compile_message(&message).program);
+        }
+
+        let bytes = encode_pack(&PackBuildInput {
+            pack_kind: PackKind::Base,
+            id_map_hash: [7u8; 32],
+            locale_tag: "en".to_string(),
+            parent_tag: None,
+            build_epoch_ms: 0,
+            messages,
+            sort_strings: false,
+        })
+        .expect("encode");
+
+        let catalog = PackCatalog::decode(&bytes, &[7u8; 32]).expect("decode");
+        for (id, source) in &sources {
+            let program = catalog.lookup(*id).expect("program");
+            assert_eq!(program.case_tables.len(), 1);
+            assert_eq!(program.opcodes.len(), source.opcodes.len());
+            for (decoded, original) in program.opcodes.iter().zip(&source.opcodes) {
+                match (*decoded, *original) {
+                    (
+                        Opcode::SelectPlural { table: decoded, .. },
+                        Opcode::SelectPlural {
+                            table: original, ..
+                        },
+                    ) => assert_eq!(
+                        program.case_tables[decoded as usize],
+                        source.case_tables[original as usize]
+                    ),
+                    (Opcode::EmitText { sidx: decoded }, Opcode::EmitText { sidx: original }) => {
+                        assert_eq!(
+                            program.string_pool.get(decoded),
+                            source.string_pool.get(original)
+                        )
+                    }
+                    _ => assert_eq!(decoded, original),
+                }
+            }
+        }
+    }
+
+    fn describe(program: &BytecodeProgram) -> Vec<String> {
+        let key = |key: &CaseKey| match key {
+            CaseKey::String(sidx) => format!("{:?}", program.string_pool.get(*sidx)),
+            other => format!("{other:?}"),
+        };
+        program
+            .opcodes
+            .iter()
+            .map(|opcode| match *opcode {
+                Opcode::EmitText { sidx } => format!("text {:?}", program.string_pool.get(sidx)),
+                Opcode::Select { table, .. } | Opcode::SelectPlural { table, .. } => {
+                    let entries: Vec<String> = program.case_tables[table as usize]
+                        .entries
+                        .iter()
+                        .map(|entry| format!("{}->{}", key(&entry.key), entry.target))
+                        .collect();
+                    format!("select {entries:?}")
+                }
+                other => format!("{other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sorted_string_pool_is_deterministic_and_resolves() {
+        let sources = [
+            "{ $kind -> [zebra] {Zoo} [apple] {Fruit} *[other] {Thing} }",
+            "Welcome back, { $name }",
+            "{ $count :plural -> [one] {# apple} *[other] {# apples} }",
+        ];
+        let build = |sort_strings| {
+            let mut messages = BTreeMap::new();
+            for (id, source) in sources.iter().enumerate() {
+                let message = parse_message(source).expect("parse");
+                messages.insert(MessageId::new(id as u64), compile_message(&message).program);
+            }
+            encode_pack(&PackBuildInput {
+                pack_kind: PackKind::Overlay,
+                id_map_hash: [7u8; 32],
+                locale_tag: "en-x-test".to_string(),
+                parent_tag: Some("en".to_string()),
+                build_epoch_ms: 0,
+                messages,
+                sort_strings,
+            })
+            .expect("encode")
+        };
+
+        let sorted = build(true);
+        assert_eq!(sorted, build(true));
+        let unsorted = build(false);
+        assert_ne!(sorted, unsorted);
+
+        let sorted = PackCatalog::decode(&sorted, &[7u8; 32]).expect("decode");
+        let unsorted = PackCatalog::decode(&unsorted, &[7u8; 32]).expect("decode");
+        assert_eq!(sorted.parent_tag(), Some("en"));
+        let program = sorted.lookup(MessageId::new(0)).expect("program");
+        let pool: Vec<&str> = (0..program.string_pool.len() as u32)
+            .filter_map(|idx| program.string_pool.get(idx))
+            .collect();
+        assert!(pool.windows(2).all(|pair| pair[0] < pair[1]));
+        for id in 0..sources.len() as u64 {
+            let id = MessageId::new(id);
+            assert_eq!(
+                describe(sorted.lookup(id).expect("sorted")),
+                describe(unsorted.lookup(id).expect("unsorted"))
+            );
+        }
+    }
+
+    #[test]
+    fn round_trips_wide_exact_keys() {
+        let message = parse_message(
+            "{ $delta :plural -> [=1000000000000] {big} [=-1] {behind} [=2] {two} *[other] {other} }",
+        )
+        .expect("parse");
+        let program = compile_message(&message).program;
+        let keys: Vec<CaseKey> = program.case_tables[0]
+            .entries
+            .iter()
+            .map(|entry| entry.key.clone())
+            .collect();
+        let mut messages = BTreeMap::new();
+        messages.insert(MessageId::new(1), program);
+
+        let bytes = encode_pack(&PackBuildInput {
+            pack_kind: PackKind::Base,
+            id_map_hash: [7u8; 32],
+            locale_tag: "en".to_string(),
+            parent_tag: None,
+            build_epoch_ms: 0,
+            messages,
+            sort_strings: false,
+        })
+        .expect("encode");
+
+        let catalog = PackCatalog::decode(&bytes, &[7u8; 32]).expect("decode");
+        let program = catalog.lookup(MessageId::new(1)).expect("program");
+        let decoded: Vec<CaseKey> = program.case_tables[0]
+            .entries
+            .iter()
+            .map(|entry| entry.key.clone())
+            .collect();
+        assert_eq!(decoded, keys);
+        assert!(decoded.contains(&CaseKey::Exact(1_000_000_000_000)));
+        assert!(decoded.contains(&CaseKey::Exact(-1)));
+    }
+
+    #[test]
+    fn stores_case_entries_sorted() {
+        let cases: String = (0..500)
+            .rev()
+            .map(|index| format!("[c{index:03}] {{country {index}}} "))
+            .collect();
+        let source = format!("{{ $country :select -> {cases}*[other] {{elsewhere}} }}");
+        let encode = |reverse: bool| {
+            let mut program = compile_message(&parse_message(&source).expect("parse")).program;
+            if reverse {
+                program.case_tables[0].entries.reverse();
+            }
+            let mut messages = BTreeMap::new();
+            messages.insert(MessageId::new(1), program);
+            encode_pack(&PackBuildInput {
+                pack_kind: PackKind::Base,
+                id_map_hash: [7u8; 32],
+                locale_tag: "en".to_string(),
+                parent_tag: None,
+                build_epoch_ms: 0,
+                messages,
+                sort_strings: false,
+            })
+            .expect("encode")
+        };
+
+        let bytes = encode(true);
+        assert_eq!(bytes, encode(false));
+        let catalog = PackCatalog::decode(&bytes, &[7u8; 32]).expect("decode");
+        let program = catalog.lookup(MessageId::new(1)).expect("program");
+        let keys: Vec<&str> = program.case_tables[0]
+            .entries
+            .iter()
+            .filter_map(|entry| match entry.key {
+                CaseKey::String(sidx) => program.string_pool.get(sidx),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(keys.len(), 500);
+        assert!(keys.is_sorted());
+        assert_eq!(
+            program.case_tables[0]
+                .entries
+                .last()
+                .map(|entry| &entry.key),
+            Some(&CaseKey::Other)
+        );
+    }
+
+    #[test]
+    fn round_trips_markup_opcodes() {
+        let message = parse_message("{#link href=$url}docs{/link}{#br/}").expect("parse");
+        let program = compile_message(&message).program;
+
+        let mut messages = BTreeMap::new();
+        messages.insert(MessageId::new(1), program);
+
+        let bytes = encode_pack(&PackBuildInput {
+            pack_kind: PackKind::Base,
+            id_map_hash: [7u8; 32],
+            locale_tag: "en".to_string(),
+            parent_tag: None,
+            build_epoch_ms: 0,
+            messages,
+            sort_strings: false,
+        })
+        .expect("encode");
+
+        let catalog = PackCatalog::decode(&bytes, &[7u8; 32]).expect("decode");
+        let program = catalog.lookup(MessageId::new(1)).expect("program");
+        let Opcode::OpenMarkup { sidx, opt_count } = program.opcodes[2] else {
+            panic!("expected open markup");
+        };
+        assert_eq!(program.string_pool.get(sidx), Some("link"));
+        assert_eq!(opt_count, 1);
+        let Opcode::CloseMarkup { sidx, opt_count } = program.opcodes[4] else {
+            panic!("expected close markup");
+        };
+        assert_eq!(program.string_pool.get(sidx), Some("link"));
+        assert_eq!(opt_count, 0);
+        let Opcode::StandaloneMarkup { sidx, .. } = program.opcodes[5] else {
+            panic!("expected standalone markup");
+        };
+        assert_eq!(program.string_pool.get(sidx), Some("br"));
+    }
 }
@@ -1,9 +1,11 @@
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
 use serde::Deserialize;
 
 use crate::error::CliError;
+use crate::extract::{is_ident_continue, is_ident_start};
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct CliConfig {
@@ -11,6 +13,27 @@ pub struct CliConfig {
     pub source_dirs: Vec<String>,
     pub micro_locales_registry: Option<String>,
     pub project_salt_path: String,
+    #[serde(default = "default_extract_macros")]
+    pub extract_macros: Vec<String>,
+    #[serde(default)]
+    pub formatter_options: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    pub rehash_id_collisions: bool,
+    #[serde(default)]
+    pub duplicate_keys: DuplicateKeyPolicy,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DuplicateKeyPolicy {
+    #[default]
+    Error,
+    FirstWins,
+    LastWins,
+}
+
+fn default_extract_macros() -> Vec<String> {
+    vec!["t".to_string()]
 }
 
 impl Default for CliConfig {
@@ -20,16 +43,32 @@ impl Default for CliConfig {
             source_dirs: vec!["locales".to_string()],
             micro_locales_registry: Some("micro-locales.toml".to_string()),
             project_salt_path: "tools/id_salt.txt".to_string(),
+            extract_macros: default_extract_macros(),
+            formatter_options: BTreeMap::new(),
+            rehash_id_collisions: false,
+            duplicate_keys: DuplicateKeyPolicy::Error,
         }
     }
 }
 
 pub fn load_config(path: &Path) -> Result<CliConfig, CliError> {
     let contents = fs::read_to_string(path)?;
-    let config = toml::from_str(&contents)?;
+    let config: CliConfig = toml::from_str(&contents)?;
+    if let Some(name) = config
+        .extract_macros
+        .iter()
+        .find(|name| !is_macro_name(name))
+    {
+        return Err(CliError::InvalidExtractMacro(name.clone()));
+    }
     Ok(config)
 }
 
+fn is_macro_name(name: &str) -> bool {
+    let mut bytes = name.bytes();
+    name != "_" && bytes.next().is_some_and(is_ident_start) && bytes.all(is_ident_continue)
+}
+
 pub fn load_config_or_default(path: &Path) -> Result<CliConfig, CliError> {
     if path.exists() {
         load_config(path)
@@ -40,7 +79,8 @@ pub fn load_config_or_default(path: &Path) -> Result<CliConfig, CliError> {
 
 #[cfg(test)]
 mod tests {
-    use super::{CliConfig, load_config_or_default};
+    use super::{CliConfig, DuplicateKeyPolicy, load_config_or_default};
+    use crate::error::CliError;
     use std::fs;
     use std::path::PathBuf;
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -74,6 +114,91 @@ project_salt_path = "tools/id_salt.txt"
         fs::write(&path, contents).expect("write");
         let config = load_config_or_default(&path).expect("config");
         assert_eq!(config.default_locale, "fr");
+        assert_eq!(config.extract_macros, vec!["t".to_string()]);
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn loads_extract_macros() {
+        let path = temp_path("macros");
+        let contents = r#"
+default_locale = "en"
+source_dirs = ["locales"]
+project_salt_path = "tools/id_salt.txt"
+extract_macros = ["t", "tr"]
+"#;
+        fs::write(&path, contents).expect("write");
+        let config = load_config_or_default(&path).expect("config");
+        assert_eq!(
+            config.extract_macros,
+            vec!["t".to_string(), "tr".to_string()]
+        );
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn rejects_invalid_extract_macros() {
+        for (name, macros) in [
+            ("empty_macro", r#"["t", ""]"#),
+            ("bang_macro", r#"["t!"]"#),
+            ("path_macro", r#"["i18n::t"]"#),
+            ("digit_macro", r#"["1t"]"#),
+            ("underscore_macro", r#"["_"]"#),
+        ] {
+            let path = temp_path(name);
+            let contents = format!(
+                "default_locale = \"en\"\nsource_dirs = [\"locales\"]\nproject_salt_path = \"tools/id_salt.txt\"\nextract_macros = {macros}\n"
+            );
+            fs::write(&path, contents).expect("write");
+            let err = load_config_or_default(&path).expect_err(name);
+            assert!(
+                matches!(err, CliError::InvalidExtractMacro(_)),
+                "{name}: {err}"
+            );
+            fs::remove_file(&path).ok();
+        }
+    }
+
+    #[test]
+    fn loads_formatter_option_allow_lists() {
+        let path = temp_path("formatter_options");
+        let contents = r#"
+default_locale = "en"
+source_dirs = ["locales"]
+project_salt_path = "tools/id_salt.txt"
+
+[formatter_options]
+number = ["minimumFractionDigits", "maximumFractionDigits"]
+"#;
+        fs::write(&path, contents).expect("write");
+        let config = load_config_or_default(&path).expect("config");
+        assert_eq!(
+            config.formatter_options["number"],
+            vec![
+                "minimumFractionDigits".to_string(),
+                "maximumFractionDigits".to_string()
+            ]
+        );
+        assert!(CliConfig::default().formatter_options.is_empty());
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn loads_duplicate_key_policy() {
+        let path = temp_path("duplicate_keys");
+        let contents = r#"
+default_locale = "en"
+source_dirs = ["locales"]
+project_salt_path = "tools/id_salt.txt"
+duplicate_keys = "last-wins"
+"#;
+        fs::write(&path, contents).expect("write");
+        let config = load_config_or_default(&path).expect("config");
+        assert_eq!(config.duplicate_keys, DuplicateKeyPolicy::LastWins);
+        assert_eq!(
+            CliConfig::default().duplicate_keys,
+            DuplicateKeyPolicy::Error
+        );
         fs::remove_file(&path).ok();
     }
 
@@ -1,20 +1,40 @@
-#[derive(Debug, Clone, PartialEq, Eq)]
+use std::fmt;
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticFormat {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct Diagnostic {
-    pub code: String,
-    pub message: String,
     pub file: Option<String>,
     pub line: Option<u32>,
     pub column: Option<u32>,
+    pub code: String,
+    pub severity: Severity,
+    pub message: String,
 }
 
 impl Diagnostic {
     pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
         Self {
-            code: code.into(),
-            message: message.into(),
             file: None,
             line: None,
             column: None,
+            code: code.into(),
+            severity: Severity::Error,
+            message: message.into(),
         }
     }
 
@@ -24,4 +44,51 @@ impl Diagnostic {
         self.column = Some(column);
         self
     }
+
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("diagnostic serializes")
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}:{} {}",
+            self.code,
+            self.file.as_deref().unwrap_or_default(),
+            self.line.unwrap_or(1),
+            self.message
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Diagnostic, Severity};
+
+    #[test]
+    fn serializes_mixed_severities_as_json_lines() {
+        let diagnostics = [
+            Diagnostic::new("MF2E100", "missing key").with_span("locale:fr", 1, 1),
+            Diagnostic::new("MF2W103", "plural is missing a one case")
+                .with_severity(Severity::Warning)
+                .with_span("locales/en/messages.mf2", 3, 14),
+            Diagnostic::new("MF2I103", "no plural rules for tlh").with_severity(Severity::Info),
+        ];
+        let lines: Vec<String> = diagnostics.iter().map(Diagnostic::to_json).collect();
+        assert_eq!(
+            lines,
+            vec![
+                r#"{"file":"locale:fr","line":1,"column":1,"code":"MF2E100","severity":"error","message":"missing key"}"#,
+                r#"{"file":"locales/en/messages.mf2","line":3,"column":14,"code":"MF2W103","severity":"warning","message":"plural is missing a one case"}"#,
+                r#"{"file":null,"line":null,"column":null,"code":"MF2I103","severity":"info","message":"no plural rules for tlh"}"#,
+            ]
+        );
+    }
 }
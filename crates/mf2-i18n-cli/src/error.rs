@@ -8,4 +8,8 @@ pub enum CliError {
     Json(#[from] serde_json::Error),
     #[error("toml error: {0}")]
     Toml(#[from] toml::de::Error),
+    #[error(transparent)]
+    IdMap(#[from] crate::id_map::IdMapError),
+    #[error("invalid extract macro name {0:?}")]
+    InvalidExtractMacro(String),
 }
@@ -1,11 +1,26 @@
 use crate::lexer::Span;
 use crate::model::{ArgSpec, ArgType};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExtractedMessage {
     pub key: String,
     pub args: Vec<ArgSpec>,
+    pub file: Option<String>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtractWarning {
+    pub message: String,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExtractOutput {
+    pub messages: Vec<ExtractedMessage>,
+    pub warnings: Vec<ExtractWarning>,
 }
 
 #[derive(Debug, Clone, Error)]
@@ -15,9 +30,9 @@ pub struct ExtractError {
     pub span: Span,
 }
 
-pub fn extract_messages(input: &str) -> Result<Vec<ExtractedMessage>, ExtractError> {
+pub fn extract_messages(input: &str, macros: &[String]) -> Result<ExtractOutput, ExtractError> {
     let mut scanner = Scanner::new(input);
-    let mut messages = Vec::new();
+    let mut output = ExtractOutput::default();
     while let Some(byte) = scanner.peek() {
         if scanner.starts_line_comment() {
             scanner.skip_line_comment();
@@ -35,14 +50,21 @@ pub fn extract_messages(input: &str) -> Result<Vec<ExtractedMessage>, ExtractErr
             scanner.skip_string()?;
             continue;
         }
-        if scanner.starts_t_macro() {
-            let message = scanner.parse_t_macro()?;
-            messages.push(message);
+        if let Some(name) = scanner.starts_macro(macros) {
+            match scanner.parse_t_macro(name)? {
+                MacroKey::Literal(message) => output.messages.push(message),
+                MacroKey::NonLiteral(warning) => output.warnings.push(warning),
+            }
             continue;
         }
         scanner.bump();
     }
-    Ok(messages)
+    Ok(output)
+}
+
+enum MacroKey {
+    Literal(ExtractedMessage),
+    NonLiteral(ExtractWarning),
 }
 
 struct Scanner<'a> {
@@ -117,18 +139,18 @@ impl<'a> Scanner<'a> {
         self.input.get(idx) == Some(&b'"')
     }
 
-    fn starts_t_macro(&self) -> bool {
-        if self.peek() != Some(b't') || self.peek_next() != Some(b'!') {
-            return false;
+    fn starts_macro<'m>(&self, macros: &'m [String]) -> Option<&'m str> {
+        if self.index > 0
+            && let Some(prev) = self.input.get(self.index - 1).copied()
+            && is_ident_continue(prev)
+        {
+            return None;
         }
-        if self.index > 0 {
-            if let Some(prev) = self.input.get(self.index - 1).copied() {
-                if is_ident_continue(prev) {
-                    return false;
-                }
-            }
-        }
-        true
+        let rest = &self.input[self.index..];
+        macros
+            .iter()
+            .map(String::as_str)
+            .find(|name| rest.starts_with(name.as_bytes()) && rest.get(name.len()) == Some(&b'!'))
     }
 
     fn skip_line_comment(&mut self) {
@@ -205,20 +227,21 @@ impl<'a> Scanner<'a> {
         }
     }
 
-    fn parse_t_macro(&mut self) -> Result<ExtractedMessage, ExtractError> {
+    fn parse_t_macro(&mut self, name: &str) -> Result<MacroKey, ExtractError> {
         let start = self.index;
         let line = self.line;
         let column = self.column;
-        self.bump();
-        self.bump();
+        for _ in 0..=name.len() {
+            self.bump();
+        }
         self.skip_ws();
         if self.peek() != Some(b'(') {
-            return Err(self.error("expected '(' after t!", start, line, column));
+            return Err(self.error(&format!("expected '(' after {name}!"), start, line, column));
         }
         self.bump();
         self.skip_ws();
         if self.peek() != Some(b'"') {
-            return Err(self.error("expected string literal key", start, line, column));
+            return self.skip_non_literal_key(name, start, line, column);
         }
         let key = self.parse_string_value()?;
         self.skip_ws();
@@ -232,6 +255,7 @@ impl<'a> Scanner<'a> {
                 }
                 let name = self.parse_ident()?;
                 self.skip_ws();
+                let mut optional = self.bump_if(b'?');
                 if self.peek() != Some(b':') {
                     return Err(self.error(
                         "expected ':' after argument name",
@@ -243,10 +267,12 @@ impl<'a> Scanner<'a> {
                 self.bump();
                 self.skip_ws();
                 let arg_type = self.parse_arg_type()?;
+                self.skip_ws();
+                optional |= self.bump_if(b'?');
                 args.push(ArgSpec {
                     name,
                     arg_type,
-                    required: true,
+                    required: !optional,
                 });
                 self.skip_ws();
                 match self.peek() {
@@ -267,10 +293,68 @@ impl<'a> Scanner<'a> {
         }
         self.skip_ws();
         if self.peek() != Some(b')') {
-            return Err(self.error("expected ')' to close t! macro", start, line, column));
+            return Err(self.error(
+                &format!("expected ')' to close {name}! macro"),
+                start,
+                line,
+                column,
+            ));
         }
         self.bump();
-        Ok(ExtractedMessage { key, args })
+        Ok(MacroKey::Literal(ExtractedMessage {
+            key,
+            args,
+            file: None,
+            span: self.span(start, self.index, line, column),
+        }))
+    }
+
+    fn skip_non_literal_key(
+        &mut self,
+        name: &str,
+        start: usize,
+        line: u32,
+        column: u32,
+    ) -> Result<MacroKey, ExtractError> {
+        let key_start = self.index;
+        let key_line = self.line;
+        let key_column = self.column;
+        let mut key_end = None;
+        let mut depth = 0u32;
+        loop {
+            match self.peek() {
+                None => {
+                    return Err(self.error(
+                        &format!("expected ')' to close {name}! macro"),
+                        start,
+                        line,
+                        column,
+                    ));
+                }
+                Some(b'"') => {
+                    self.skip_string()?;
+                    continue;
+                }
+                Some(b'(' | b'[' | b'{') => depth += 1,
+                Some(b')') if depth == 0 => break,
+                Some(b')' | b']' | b'}') => depth = depth.saturating_sub(1),
+                Some(b',') if depth == 0 && key_end.is_none() => key_end = Some(self.index),
+                _ => {}
+            }
+            self.bump();
+        }
+        let key_end = key_end.unwrap_or(self.index);
+        let expr = String::from_utf8_lossy(&self.input[key_start..key_end])
+            .trim()
+            .to_string();
+        if expr.is_empty() {
+            return Err(self.error("expected string literal key", start, line, column));
+        }
+        self.bump();
+        Ok(MacroKey::NonLiteral(ExtractWarning {
+            message: format!("{name}! key `{expr}` is not a string literal and was not extracted"),
+            span: self.span(key_start, key_end, key_line, key_column),
+        }))
     }
 
     fn parse_string_value(&mut self) -> Result<String, ExtractError> {
@@ -336,6 +420,15 @@ impl<'a> Scanner<'a> {
         }
     }
 
+    fn bump_if(&mut self, expected: u8) -> bool {
+        if self.peek() == Some(expected) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
     fn skip_ws(&mut self) {
         while let Some(byte) = self.peek() {
             if byte.is_ascii_whitespace() {
@@ -347,17 +440,22 @@ impl<'a> Scanner<'a> {
     }
 }
 
-fn is_ident_start(byte: u8) -> bool {
+pub(crate) fn is_ident_start(byte: u8) -> bool {
     byte.is_ascii_alphabetic() || byte == b'_'
 }
 
-fn is_ident_continue(byte: u8) -> bool {
+pub(crate) fn is_ident_continue(byte: u8) -> bool {
     is_ident_start(byte) || byte.is_ascii_digit()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::extract_messages;
+    use super::{ExtractError, ExtractedMessage};
+    use crate::model::ArgType;
+
+    fn extract_messages(input: &str) -> Result<Vec<ExtractedMessage>, ExtractError> {
+        super::extract_messages(input, &["t".to_string()]).map(|output| output.messages)
+    }
 
     #[test]
     fn extracts_simple_key() {
@@ -384,6 +482,15 @@ mod tests {
         assert_eq!(messages[0].args[0].name, "count");
     }
 
+    #[test]
+    fn extracts_optional_args() {
+        let input = r#"let _ = t!("k", name: string?, count?: number, total: number);"#;
+        let messages = extract_messages(input).expect("extract");
+        let required: Vec<bool> = messages[0].args.iter().map(|arg| arg.required).collect();
+        assert_eq!(required, vec![false, false, true]);
+        assert_eq!(messages[0].args[0].arg_type, ArgType::String);
+    }
+
     #[test]
     fn skips_comments_and_strings() {
         let input = r#"
@@ -395,4 +502,36 @@ mod tests {
         assert_eq!(messages.len(), 1);
         assert_eq!(messages[0].key, "ok");
     }
+
+    #[test]
+    fn extracts_configured_macro_names() {
+        let input = r#"
+        let _ = t!("home.title");
+        let _ = tr!("cart.items", count: number);
+        let _ = attr!("ignored");
+        let _ = str!("ignored");
+        "#;
+        let macros = vec!["t".to_string(), "tr".to_string()];
+        let output = super::extract_messages(input, &macros).expect("extract");
+        let keys: Vec<&str> = output
+            .messages
+            .iter()
+            .map(|message| message.key.as_str())
+            .collect();
+        assert_eq!(keys, vec!["home.title", "cart.items"]);
+    }
+
+    #[test]
+    fn warns_on_non_literal_keys_without_aborting() {
+        let input = "let _ = t!(KEYS::HOME_TITLE, name: string);\nlet _ = t!(\"cart.items\");\nlet _ = t!(key_for(\"a,b\"));";
+        let output = super::extract_messages(input, &["t".to_string()]).expect("extract");
+        assert_eq!(output.messages.len(), 1);
+        assert_eq!(output.messages[0].key, "cart.items");
+        assert_eq!(output.warnings.len(), 2);
+        assert!(output.warnings[0].message.contains("`KEYS::HOME_TITLE`"));
+        assert_eq!(output.warnings[0].span.line, 1);
+        assert_eq!(output.warnings[0].span.column, 12);
+        assert!(output.warnings[1].message.contains("`key_for(\"a,b\")`"));
+        assert_eq!(output.warnings[1].span.line, 3);
+    }
 }
@@ -0,0 +1,61 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::extract::ExtractOutput;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ExtractCache {
+    macros: Vec<String>,
+    files: BTreeMap<String, CachedFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedFile {
+    hash: String,
+    output: ExtractOutput,
+}
+
+impl ExtractCache {
+    pub fn new(macros: &[String]) -> Self {
+        Self {
+            macros: macros.to_vec(),
+            files: BTreeMap::new(),
+        }
+    }
+
+    pub fn load(path: &Path, macros: &[String]) -> Self {
+        fs::read(path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<Self>(&bytes).ok())
+            .filter(|cache| cache.macros == macros)
+            .unwrap_or_else(|| Self::new(macros))
+    }
+
+    pub fn get(&self, path: &str, hash: &str) -> Option<&ExtractOutput> {
+        self.files
+            .get(path)
+            .filter(|cached| cached.hash == hash)
+            .map(|cached| &cached.output)
+    }
+
+    pub fn insert(&mut self, path: String, hash: String, output: ExtractOutput) {
+        self.files.insert(path, CachedFile { hash, output });
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_vec(self)?)
+    }
+}
+
+pub fn content_hash(contents: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(contents.as_bytes());
+    hex::encode(hasher.finalize())
+}
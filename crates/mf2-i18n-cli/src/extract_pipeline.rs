@@ -5,7 +5,11 @@ use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 use crate::catalog_builder::{BuildOutput, CatalogBuildError, build_catalog};
-use crate::extract::{ExtractError, ExtractedMessage, extract_messages};
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::extract::{ExtractError, ExtractOutput, ExtractedMessage, extract_messages};
+use crate::extract_cache::{ExtractCache, content_hash};
+use crate::id_map::IdAssignment;
+use crate::model::ArgSpec;
 
 #[derive(Debug, Error)]
 pub enum ExtractPipelineError {
@@ -15,8 +19,25 @@ pub enum ExtractPipelineError {
     Extract(#[from] ExtractError),
     #[error(transparent)]
     Build(#[from] CatalogBuildError),
-    #[error("conflicting argument specs for key {0}")]
-    ConflictingArgs(String),
+    #[cfg(feature = "rayon")]
+    #[error("extract worker panicked: {0}")]
+    WorkerPanicked(String),
+    #[error(
+        "conflicting argument specs for key {key}: {first} has ({first_args}) but {second} has ({second_args})"
+    )]
+    ConflictingArgs {
+        key: String,
+        first: String,
+        first_args: String,
+        second: String,
+        second_args: String,
+    },
+}
+
+#[derive(Debug)]
+pub struct ExtractedCatalog {
+    pub output: BuildOutput,
+    pub warnings: Vec<Diagnostic>,
 }
 
 pub fn collect_rust_files(roots: &[PathBuf]) -> Result<Vec<PathBuf>, ExtractPipelineError> {
@@ -32,10 +53,20 @@ pub fn extract_from_sources(
     project: &str,
     default_locale: &str,
     generated_at: &str,
-    salt: &[u8],
-) -> Result<BuildOutput, ExtractPipelineError> {
+    ids: &IdAssignment,
+    macros: &[String],
+    cache_path: Option<&Path>,
+) -> Result<ExtractedCatalog, ExtractPipelineError> {
     let files = collect_rust_files(roots)?;
-    extract_from_files(&files, project, default_locale, generated_at, salt)
+    extract_from_files(
+        &files,
+        project,
+        default_locale,
+        generated_at,
+        ids,
+        macros,
+        cache_path,
+    )
 }
 
 pub fn extract_from_files(
@@ -43,30 +74,113 @@ pub fn extract_from_files(
     project: &str,
     default_locale: &str,
     generated_at: &str,
-    salt: &[u8],
-) -> Result<BuildOutput, ExtractPipelineError> {
-    let mut by_key: BTreeMap<String, ExtractedMessage> = BTreeMap::new();
-    for path in files {
-        let contents = fs::read_to_string(path)?;
-        let extracted = extract_messages(&contents)?;
-        for message in extracted {
-            if let Some(existing) = by_key.get(&message.key) {
+    ids: &IdAssignment,
+    macros: &[String],
+    cache_path: Option<&Path>,
+) -> Result<ExtractedCatalog, ExtractPipelineError> {
+    let cache = match cache_path {
+        Some(path) => ExtractCache::load(path, macros),
+        None => ExtractCache::new(macros),
+    };
+    let mut next_cache = ExtractCache::new(macros);
+    let mut first_by_key: BTreeMap<String, usize> = BTreeMap::new();
+    let mut messages: Vec<ExtractedMessage> = Vec::new();
+    let mut warnings = Vec::new();
+    let scanned = scan_files(files, |path| scan_file(path, macros, &cache))?;
+    for (path, scanned) in files.iter().zip(scanned) {
+        let (hash, extracted) = scanned?;
+        next_cache.insert(path.display().to_string(), hash, extracted.clone());
+        for warning in extracted.warnings {
+            warnings.push(
+                Diagnostic::new("MF2W001", warning.message)
+                    .with_severity(Severity::Warning)
+                    .with_span(
+                        path.display().to_string(),
+                        warning.span.line,
+                        warning.span.column,
+                    ),
+            );
+        }
+        for mut message in extracted.messages {
+            message.file = Some(path.display().to_string());
+            if let Some(&idx) = first_by_key.get(&message.key) {
+                let existing = &messages[idx];
                 if existing.args != message.args {
-                    return Err(ExtractPipelineError::ConflictingArgs(message.key));
+                    return Err(ExtractPipelineError::ConflictingArgs {
+                        first: location(existing),
+                        first_args: describe_args(&existing.args),
+                        second: location(&message),
+                        second_args: describe_args(&message.args),
+                        key: message.key,
+                    });
                 }
-                continue;
+            } else {
+                first_by_key.insert(message.key.clone(), messages.len());
             }
-            by_key.insert(message.key.clone(), message);
+            messages.push(message);
         }
     }
-    let messages: Vec<ExtractedMessage> = by_key.into_values().collect();
-    Ok(build_catalog(
-        &messages,
-        project,
-        default_locale,
-        generated_at,
-        salt,
-    )?)
+    if let Some(path) = cache_path {
+        next_cache.save(path)?;
+    }
+    let output = build_catalog(&messages, project, default_locale, generated_at, ids)?;
+    Ok(ExtractedCatalog { output, warnings })
+}
+
+fn location(message: &ExtractedMessage) -> String {
+    format!(
+        "{}:{}:{}",
+        message.file.as_deref().unwrap_or("<unknown>"),
+        message.span.line,
+        message.span.column
+    )
+}
+
+fn describe_args(args: &[ArgSpec]) -> String {
+    args.iter()
+        .map(|arg| format!("{}: {}", arg.name, arg.arg_type.as_str()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+type Scanned = Result<(String, ExtractOutput), ExtractPipelineError>;
+
+#[cfg(not(feature = "rayon"))]
+fn scan_files(
+    files: &[PathBuf],
+    scan: impl Fn(&Path) -> Scanned,
+) -> Result<Vec<Scanned>, ExtractPipelineError> {
+    Ok(files.iter().map(|path| scan(path)).collect())
+}
+
+// `collect` on an indexed parallel iterator keeps input order, so results
+// are merged in file order exactly as the serial scan would.
+#[cfg(feature = "rayon")]
+fn scan_files(
+    files: &[PathBuf],
+    scan: impl Fn(&Path) -> Scanned + Sync,
+) -> Result<Vec<Scanned>, ExtractPipelineError> {
+    use rayon::prelude::*;
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        files.par_iter().map(|path| scan(path)).collect()
+    }))
+    .map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        ExtractPipelineError::WorkerPanicked(message)
+    })
+}
+
+fn scan_file(path: &Path, macros: &[String], cache: &ExtractCache) -> Scanned {
+    let contents = fs::read_to_string(path)?;
+    let hash = content_hash(&contents);
+    if let Some(cached) = cache.get(&path.display().to_string(), &hash) {
+        return Ok((hash, cached.clone()));
+    }
+    Ok((hash, extract_messages(&contents, macros)?))
 }
 
 fn collect_rust_files_inner(
@@ -94,7 +208,7 @@ fn collect_rust_files_inner(
     Ok(())
 }
 
-fn should_skip_dir(path: &Path) -> bool {
+pub(crate) fn should_skip_dir(path: &Path) -> bool {
     matches!(
         path.file_name().and_then(|name| name.to_str()),
         Some(".git") | Some("target") | Some("node_modules")
@@ -103,10 +217,11 @@ fn should_skip_dir(path: &Path) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::extract_from_files;
-    use crate::id_map::derive_message_id;
+    use super::{ExtractPipelineError, extract_from_files};
+    use crate::diagnostic::Severity;
+    use crate::id_map::{IdAssignment, derive_message_id};
     use std::fs;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
     use std::time::{SystemTime, UNIX_EPOCH};
 
     fn temp_dir() -> PathBuf {
@@ -120,6 +235,25 @@ mod tests {
         path
     }
 
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn worker_panics_become_errors() {
+        let files: Vec<_> = (0..8)
+            .map(|idx| PathBuf::from(format!("f{idx}.rs")))
+            .collect();
+        let err = super::scan_files(&files, |path| {
+            if path == Path::new("f5.rs") {
+                panic!("scanner exploded");
+            }
+            Ok((String::new(), Default::default()))
+        })
+        .expect_err("panic");
+        assert!(matches!(
+            err,
+            ExtractPipelineError::WorkerPanicked(ref message) if message == "scanner exploded"
+        ));
+    }
+
     #[test]
     fn extracts_from_multiple_files() {
         let dir = temp_dir();
@@ -128,24 +262,219 @@ mod tests {
         fs::write(&file_a, "let _ = t!(\"home.title\");").expect("write");
         fs::write(&file_b, "let _ = t!(\"cart.items\");").expect("write");
 
-        let output = extract_from_files(
+        let extracted = extract_from_files(
             &[file_a, file_b],
             "demo",
             "en",
             "2026-02-01T00:00:00Z",
-            b"salt",
+            &IdAssignment::new(b"salt"),
+            &["t".to_string()],
+            None,
         )
         .expect("extract");
 
-        let expected = derive_message_id("home.title", b"salt");
+        let expected = derive_message_id("home.title", b"salt", 0);
+        assert!(extracted.warnings.is_empty());
         assert!(
-            output
+            extracted
+                .output
                 .catalog
                 .messages
                 .iter()
-                .any(|message| message.id == u32::from(expected))
+                .any(|message| message.id == u64::from(expected))
+        );
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn records_every_call_site_as_a_source_ref() {
+        let dir = temp_dir();
+        let file_a = dir.join("a.rs");
+        let file_b = dir.join("b.rs");
+        fs::write(&file_a, "let _ = t!(\"home.title\");").expect("write");
+        fs::write(
+            &file_b,
+            "fn f() {}\nlet _ = t!(\"home.title\");\nlet _ = t!(\"cart.items\");",
+        )
+        .expect("write");
+
+        let extracted = extract_from_files(
+            &[file_b.clone(), file_a.clone()],
+            "demo",
+            "en",
+            "2026-02-01T00:00:00Z",
+            &IdAssignment::new(b"salt"),
+            &["t".to_string()],
+            None,
+        )
+        .expect("extract");
+
+        let messages = &extracted.output.catalog.messages;
+        assert_eq!(messages.len(), 2);
+        let title = messages
+            .iter()
+            .find(|message| message.key == "home.title")
+            .expect("title");
+        let refs: Vec<(String, u32)> = title
+            .source_refs
+            .as_ref()
+            .expect("refs")
+            .iter()
+            .map(|source| (source.file.clone(), source.line))
+            .collect();
+        assert_eq!(
+            refs,
+            vec![
+                (file_a.display().to_string(), 1),
+                (file_b.display().to_string(), 2)
+            ]
         );
 
         fs::remove_dir_all(&dir).ok();
     }
+
+    #[test]
+    fn cached_runs_match_a_cold_extract() {
+        let dir = temp_dir();
+        let cache_path = dir.join("out").join("extract_cache.json");
+        let files: Vec<PathBuf> = ["a.rs", "b.rs", "c.rs"]
+            .iter()
+            .map(|name| dir.join(name))
+            .collect();
+        fs::write(&files[0], "let _ = t!(\"home.title\");").expect("write");
+        fs::write(&files[1], "let _ = t!(\"cart.items\", count: number);").expect("write");
+        fs::write(&files[2], "let _ = t!(keys::DYNAMIC);").expect("write");
+        let run = |cache: Option<&Path>| {
+            extract_from_files(
+                &files,
+                "demo",
+                "en",
+                "2026-02-01T00:00:00Z",
+                &IdAssignment::new(b"salt"),
+                &["t".to_string()],
+                cache,
+            )
+            .expect("extract")
+        };
+
+        run(Some(&cache_path));
+        let cache: serde_json::Value =
+            serde_json::from_slice(&fs::read(&cache_path).expect("cache")).expect("json");
+        assert_eq!(cache["files"].as_object().map(|files| files.len()), Some(3));
+
+        fs::write(
+            &files[1],
+            "let _ = t!(\"cart.items\", count: number);\nlet _ = t!(\"cart.empty\");",
+        )
+        .expect("write");
+        let warm = run(Some(&cache_path));
+        let cold = run(None);
+        assert_eq!(warm.output.catalog.messages.len(), 3);
+        assert_eq!(warm.output.id_map_hash, cold.output.id_map_hash);
+        assert_eq!(
+            serde_json::to_value(&warm.output.catalog).expect("json"),
+            serde_json::to_value(&cold.output.catalog).expect("json")
+        );
+        assert_eq!(warm.warnings, cold.warnings);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn non_literal_keys_warn_without_aborting_the_file() {
+        let dir = temp_dir();
+        let file = dir.join("lib.rs");
+        fs::write(
+            &file,
+            "let _ = t!(keys::HOME_TITLE);\nlet _ = t!(\"cart.items\");",
+        )
+        .expect("write");
+
+        let extracted = extract_from_files(
+            std::slice::from_ref(&file),
+            "demo",
+            "en",
+            "2026-02-01T00:00:00Z",
+            &IdAssignment::new(b"salt"),
+            &["t".to_string()],
+            None,
+        )
+        .expect("extract");
+
+        assert_eq!(extracted.output.catalog.messages.len(), 1);
+        assert_eq!(extracted.output.catalog.messages[0].key, "cart.items");
+        assert_eq!(extracted.warnings.len(), 1);
+        assert_eq!(extracted.warnings[0].code, "MF2W001");
+        assert_eq!(extracted.warnings[0].severity, Severity::Warning);
+        assert_eq!(extracted.warnings[0].line, Some(1));
+        assert_eq!(
+            extracted.warnings[0].file.as_deref(),
+            Some(file.display().to_string().as_str())
+        );
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn reports_first_conflict_in_file_order_across_workers() {
+        let dir = temp_dir();
+        let mut files = Vec::new();
+        for idx in 0..32 {
+            let file = dir.join(format!("f{idx:02}.rs"));
+            let source = match idx {
+                3 => "let _ = t!(\"cart.items\", count: number);".to_string(),
+                20 => "let _ = t!(\"cart.items\", count: string);".to_string(),
+                5 => "let _ = t!(\"home.title\", name: string);".to_string(),
+                27 => "let _ = t!(\"home.title\", name: number);".to_string(),
+                _ => format!("let _ = t!(\"page.{idx}\");"),
+            };
+            fs::write(&file, source).expect("write");
+            files.push(file);
+        }
+
+        for _ in 0..4 {
+            let err = extract_from_files(
+                &files,
+                "demo",
+                "en",
+                "2026-02-01T00:00:00Z",
+                &IdAssignment::new(b"salt"),
+                &["t".to_string()],
+                None,
+            )
+            .expect_err("conflict");
+            assert!(
+                matches!(&err, ExtractPipelineError::ConflictingArgs { key, .. } if key == "cart.items"),
+                "unexpected error {err}"
+            );
+        }
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn conflicting_args_name_both_locations() {
+        let dir = temp_dir();
+        let file_a = dir.join("a.rs");
+        let file_b = dir.join("b.rs");
+        fs::write(&file_a, "let _ = t!(\"cart.items\", count: number);").expect("write");
+        fs::write(&file_b, "\n  let _ = t!(\"cart.items\", count: string);").expect("write");
+
+        let err = extract_from_files(
+            &[file_a.clone(), file_b.clone()],
+            "demo",
+            "en",
+            "2026-02-01T00:00:00Z",
+            &IdAssignment::new(b"salt"),
+            &["t".to_string()],
+            None,
+        )
+        .expect_err("conflict");
+        let message = err.to_string();
+        assert!(message.contains(&format!("{}:1:9 has (count: number)", file_a.display())));
+        assert!(message.contains(&format!("{}:2:11 has (count: string)", file_b.display())));
+
+        fs::remove_dir_all(&dir).ok();
+    }
 }
@@ -17,10 +17,37 @@ pub enum IdMapError {
     KeyTooLong { len: usize },
 }
 
+const MAX_PERTURBATION: u32 = 64;
+
 #[derive(Debug, Clone)]
 pub struct IdMap {
     entries: BTreeMap<String, MessageId>,
     reverse: BTreeMap<MessageId, String>,
+    perturbations: BTreeMap<String, u32>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct IdAssignment<'a> {
+    pub salt: &'a [u8],
+    pub id_bits: u32,
+    pub rehash: Option<&'a IdMap>,
+}
+
+impl<'a> IdAssignment<'a> {
+    pub fn new(salt: &'a [u8]) -> Self {
+        Self {
+            salt,
+            id_bits: 64,
+            rehash: None,
+        }
+    }
+
+    pub fn rehashing(self, previous: &'a IdMap) -> Self {
+        Self {
+            rehash: Some(previous),
+            ..self
+        }
+    }
 }
 
 impl IdMap {
@@ -28,18 +55,33 @@ impl IdMap {
         Self {
             entries: BTreeMap::new(),
             reverse: BTreeMap::new(),
+            perturbations: BTreeMap::new(),
         }
     }
 
     pub fn insert(&mut self, key: String, id: MessageId) -> Result<(), IdMapError> {
-        if let Some(existing) = self.reverse.get(&id) {
-            if existing != &key {
-                return Err(IdMapError::Collision {
-                    id,
-                    existing: existing.clone(),
-                    incoming: key,
-                });
-            }
+        self.insert_perturbed(key, id, 0)
+    }
+
+    pub fn insert_perturbed(
+        &mut self,
+        key: String,
+        id: MessageId,
+        perturbation: u32,
+    ) -> Result<(), IdMapError> {
+        if let Some(existing) = self.reverse.get(&id)
+            && existing != &key
+        {
+            return Err(IdMapError::Collision {
+                id,
+                existing: existing.clone(),
+                incoming: key,
+            });
+        }
+        if perturbation == 0 {
+            self.perturbations.remove(&key);
+        } else {
+            self.perturbations.insert(key.clone(), perturbation);
         }
         self.entries.insert(key.clone(), id);
         self.reverse.insert(id, key);
@@ -50,10 +92,18 @@ impl IdMap {
         self.entries.get(key).copied()
     }
 
+    pub fn perturbation(&self, key: &str) -> Option<u32> {
+        self.perturbations.get(key).copied()
+    }
+
     pub fn entries(&self) -> impl Iterator<Item = (&str, MessageId)> {
         self.entries.iter().map(|(k, v)| (k.as_str(), *v))
     }
 
+    pub fn perturbations(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.perturbations.iter().map(|(k, v)| (k.as_str(), *v))
+    }
+
     pub fn hash(&self) -> Result<[u8; 32], IdMapError> {
         let mut hasher = Sha256::new();
         for (key, id) in &self.entries {
@@ -63,7 +113,7 @@ impl IdMap {
                 .map_err(|_| IdMapError::KeyTooLong { len: key.len() })?;
             hasher.update(len.to_le_bytes());
             hasher.update(key.as_bytes());
-            hasher.update(u32::from(*id).to_le_bytes());
+            hasher.update(u64::from(*id).to_le_bytes());
         }
         Ok(hasher.finalize().into())
     }
@@ -75,51 +125,133 @@ impl Default for IdMap {
     }
 }
 
-pub fn derive_message_id(key: &str, salt: &[u8]) -> MessageId {
+pub fn derive_message_id(key: &str, salt: &[u8], perturbation: u32) -> MessageId {
     let mut hasher = Hasher::new();
     hasher.update(salt);
     hasher.update(key.as_bytes());
+    if perturbation != 0 {
+        hasher.update(&[0]);
+        hasher.update(&perturbation.to_le_bytes());
+    }
     let hash = hasher.finalize();
     let bytes = hash.as_bytes();
-    MessageId::new(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    MessageId::new(u64::from_le_bytes([
+        bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
+    ]))
 }
 
-pub fn build_id_map<I>(keys: I, salt: &[u8]) -> Result<IdMap, IdMapError>
+pub fn build_id_map<I>(keys: I, assignment: &IdAssignment) -> Result<IdMap, IdMapError>
 where
     I: IntoIterator<Item = String>,
 {
+    let mut keys: Vec<String> = keys.into_iter().collect();
+    if let Some(previous) = assignment.rehash {
+        keys.sort_by_key(|key| previous.get(key).is_none());
+    }
+    let mask = u64::MAX >> (64 - assignment.id_bits.clamp(1, 64));
     let mut map = IdMap::new();
     for key in keys {
-        let id = derive_message_id(&key, salt);
-        map.insert(key, id)?;
+        let mut perturbation = assignment
+            .rehash
+            .and_then(|previous| previous.perturbation(&key))
+            .unwrap_or(0);
+        loop {
+            let id = derive_message_id(&key, assignment.salt, perturbation).get() & mask;
+            match map.insert_perturbed(key.clone(), MessageId::new(id), perturbation) {
+                Err(IdMapError::Collision { .. })
+                    if assignment.rehash.is_some() && perturbation < MAX_PERTURBATION =>
+                {
+                    perturbation += 1;
+                }
+                result => {
+                    result?;
+                    break;
+                }
+            }
+        }
     }
     Ok(map)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{IdMap, IdMapError, build_id_map, derive_message_id};
+    use super::{IdAssignment, IdMap, IdMapError, build_id_map, derive_message_id};
     use mf2_i18n_core::MessageId;
 
+    const COLLIDING_32: [&str; 2] = ["page.key83531", "page.key118977"];
+
+    fn keys(keys: &[&str]) -> Vec<String> {
+        keys.iter().map(|key| key.to_string()).collect()
+    }
+
     #[test]
     fn derives_message_id_deterministically() {
         let salt = b"project-salt";
-        let id_a = derive_message_id("home.title", salt);
-        let id_b = derive_message_id("home.title", salt);
+        let id_a = derive_message_id("home.title", salt, 0);
+        let id_b = derive_message_id("home.title", salt, 0);
         assert_eq!(id_a, id_b);
+        assert_ne!(id_a, derive_message_id("home.title", salt, 1));
     }
 
     #[test]
     fn builds_id_map_and_hashes_stably() {
         let salt = b"project-salt";
-        let keys = vec!["b".to_string(), "a".to_string()];
-        let map = build_id_map(keys, salt).expect("map");
+        let map = build_id_map(keys(&["b", "a"]), &IdAssignment::new(salt)).expect("map");
         assert!(map.get("a").is_some());
         let hash_a = map.hash().expect("hash");
         let hash_b = map.hash().expect("hash");
         assert_eq!(hash_a, hash_b);
     }
 
+    #[test]
+    fn keys_colliding_in_32_bits_stay_distinct() {
+        let salt = b"project-salt";
+        let id_a = derive_message_id(COLLIDING_32[0], salt, 0).get();
+        let id_b = derive_message_id(COLLIDING_32[1], salt, 0).get();
+        assert_eq!(id_a as u32, id_b as u32);
+        assert_ne!(id_a, id_b);
+        assert!(build_id_map(keys(&COLLIDING_32), &IdAssignment::new(salt)).is_ok());
+
+        let narrow = IdAssignment {
+            id_bits: 32,
+            ..IdAssignment::new(salt)
+        };
+        let err = build_id_map(keys(&COLLIDING_32), &narrow).expect_err("collision");
+        assert!(matches!(err, IdMapError::Collision { .. }));
+    }
+
+    #[test]
+    fn rehashing_moves_only_the_new_colliding_key() {
+        let salt = b"project-salt";
+        let narrow = IdAssignment {
+            id_bits: 32,
+            ..IdAssignment::new(salt)
+        };
+        let before = keys(&["home.title", COLLIDING_32[0]]);
+        let previous = build_id_map(before, &narrow.rehashing(&IdMap::new())).expect("map");
+        assert_eq!(previous.perturbations().count(), 0);
+
+        let after = keys(&["home.title", COLLIDING_32[0], COLLIDING_32[1]]);
+        let map = build_id_map(after.clone(), &narrow.rehashing(&previous)).expect("rehash");
+        for (key, id) in previous.entries() {
+            assert_eq!(map.get(key), Some(id));
+        }
+        assert_eq!(map.perturbation(COLLIDING_32[1]), Some(1));
+        assert_eq!(
+            map.get(COLLIDING_32[1]),
+            Some(MessageId::new(
+                derive_message_id(COLLIDING_32[1], salt, 1).get() & u64::from(u32::MAX)
+            ))
+        );
+
+        let rebuilt = build_id_map(after, &narrow.rehashing(&map)).expect("rebuild");
+        assert_eq!(rebuilt.hash().expect("hash"), map.hash().expect("hash"));
+        assert_eq!(
+            rebuilt.perturbations().collect::<Vec<_>>(),
+            vec![(COLLIDING_32[1], 1)]
+        );
+    }
+
     #[test]
     fn detects_message_id_collisions() {
         let mut map = IdMap::new();
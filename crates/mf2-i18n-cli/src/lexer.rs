@@ -1,4 +1,6 @@
-#[derive(Debug, Clone, PartialEq, Eq)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Span {
     pub start: usize,
     pub end: usize,
@@ -19,8 +21,12 @@ pub enum TokenKind {
     LBracket,
     RBracket,
     Star,
+    Hash,
+    Slash,
     Ident(String),
     Number(String),
+    Quoted(String),
+    Keyword(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -64,6 +70,7 @@ impl<'a> Lexer<'a> {
 
     pub fn lex_all(mut self) -> Result<Vec<Token>, LexError> {
         let mut tokens = Vec::new();
+        self.lex_declarations(&mut tokens)?;
         while self.offset < self.bytes.len() {
             if self.is_expr_mode() {
                 self.lex_expr_token(&mut tokens)?;
@@ -83,21 +90,90 @@ impl<'a> Lexer<'a> {
         Ok(tokens)
     }
 
+    fn lex_declarations(&mut self, tokens: &mut Vec<Token>) -> Result<(), LexError> {
+        loop {
+            let (offset, line, column) = (self.offset, self.line, self.column);
+            self.skip_whitespace();
+            let rest = &self.input[self.offset..];
+            let Some(keyword) = [".local", ".input"].into_iter().find(|keyword| {
+                rest.starts_with(keyword)
+                    && !rest[keyword.len()..]
+                        .bytes()
+                        .next()
+                        .is_some_and(is_ident_continue)
+            }) else {
+                if tokens.is_empty() {
+                    self.offset = offset;
+                    self.line = line;
+                    self.column = column;
+                }
+                return Ok(());
+            };
+            tokens.push(Token {
+                kind: TokenKind::Keyword(keyword[1..].to_string()),
+                span: Span {
+                    start: self.offset,
+                    end: self.offset + keyword.len(),
+                    line: self.line,
+                    column: self.column,
+                },
+            });
+            for _ in 0..keyword.len() {
+                self.advance_byte();
+            }
+            self.lex_declaration_body(tokens)?;
+        }
+    }
+
+    fn lex_declaration_body(&mut self, tokens: &mut Vec<Token>) -> Result<(), LexError> {
+        loop {
+            self.skip_whitespace();
+            let span = self.single_span(self.offset, self.line, self.column);
+            match self.bytes.get(self.offset) {
+                None => return Err(self.error("expected declaration expression", span)),
+                Some(b'}') => return Err(self.error("unbalanced brace", span)),
+                Some(b'{') => break,
+                Some(_) => self.lex_expr_token(tokens)?,
+            }
+        }
+        self.lex_text_token(tokens)?;
+        while self.mode_stack.len() > 1 && self.offset < self.bytes.len() {
+            if self.is_expr_mode() {
+                self.lex_expr_token(tokens)?;
+            } else {
+                self.lex_text_token(tokens)?;
+            }
+        }
+        Ok(())
+    }
+
     fn lex_text_token(&mut self, tokens: &mut Vec<Token>) -> Result<(), LexError> {
         let start = self.offset;
         let line = self.line;
         let column = self.column;
+        let mut text = String::new();
+        let mut run_start = start;
         while self.offset < self.bytes.len() {
             let byte = self.bytes[self.offset];
             if byte == b'{' || byte == b'}' {
                 break;
             }
+            if byte == b'\\'
+                && let Some(escaped @ (b'{' | b'}' | b'\\')) = self.peek_byte()
+            {
+                text.push_str(&self.input[run_start..self.offset]);
+                text.push(escaped as char);
+                self.advance_byte();
+                self.advance_byte();
+                run_start = self.offset;
+                continue;
+            }
             self.advance_byte();
         }
         if self.offset > start {
-            let text = &self.input[start..self.offset];
+            text.push_str(&self.input[run_start..self.offset]);
             tokens.push(Token {
-                kind: TokenKind::Text(text.to_string()),
+                kind: TokenKind::Text(text),
                 span: Span {
                     start,
                     end: self.offset,
@@ -199,6 +275,20 @@ impl<'a> Lexer<'a> {
                 });
                 self.advance_byte();
             }
+            b'#' => {
+                tokens.push(Token {
+                    kind: TokenKind::Hash,
+                    span,
+                });
+                self.advance_byte();
+            }
+            b'/' => {
+                tokens.push(Token {
+                    kind: TokenKind::Slash,
+                    span,
+                });
+                self.advance_byte();
+            }
             b'=' => {
                 tokens.push(Token {
                     kind: TokenKind::Equals,
@@ -236,6 +326,10 @@ impl<'a> Lexer<'a> {
                 let token = self.lex_number()?;
                 tokens.push(token);
             }
+            b'|' => {
+                let token = self.lex_quoted()?;
+                tokens.push(token);
+            }
             _ => {
                 if is_ident_start(byte) {
                     let token = self.lex_ident()?;
@@ -318,6 +412,47 @@ impl<'a> Lexer<'a> {
         })
     }
 
+    fn lex_quoted(&mut self) -> Result<Token, LexError> {
+        let start = self.offset;
+        let line = self.line;
+        let column = self.column;
+        self.advance_byte();
+        let mut value = String::new();
+        let mut run_start = self.offset;
+        loop {
+            let Some(&byte) = self.bytes.get(self.offset) else {
+                let span = Span {
+                    start,
+                    end: self.offset,
+                    line,
+                    column,
+                };
+                return Err(self.error("unterminated literal", span));
+            };
+            match byte {
+                b'|' => break,
+                b'\\' if matches!(self.peek_byte(), Some(b'|' | b'\\')) => {
+                    value.push_str(&self.input[run_start..self.offset]);
+                    self.advance_byte();
+                    run_start = self.offset;
+                    self.advance_byte();
+                }
+                _ => self.advance_byte(),
+            }
+        }
+        value.push_str(&self.input[run_start..self.offset]);
+        self.advance_byte();
+        Ok(Token {
+            kind: TokenKind::Quoted(value),
+            span: Span {
+                start,
+                end: self.offset,
+                line,
+                column,
+            },
+        })
+    }
+
     fn skip_whitespace(&mut self) {
         while self.offset < self.bytes.len() {
             let byte = self.bytes[self.offset];
@@ -392,6 +527,18 @@ mod tests {
         assert_eq!(tokens[4].kind, TokenKind::RBrace);
     }
 
+    #[test]
+    fn lexes_escaped_braces_and_backslashes_as_text() {
+        let input = r"Hello \{world\} \\ C:\temp { $name }";
+        let tokens = Lexer::new(input).lex_all().expect("lex");
+        assert_eq!(
+            tokens[0].kind,
+            TokenKind::Text(r"Hello {world} \ C:\temp ".to_string())
+        );
+        assert_eq!(tokens[0].span.end, input.len() - "{ $name }".len());
+        assert_eq!(tokens[1].kind, TokenKind::LBrace);
+    }
+
     #[test]
     fn lexes_numbers_and_equals() {
         let input = "{ =0 {zero} }";
@@ -404,6 +551,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn lexes_quoted_literals() {
+        let input = r"{ |a \| b \\ c| :identity }";
+        let tokens = Lexer::new(input).lex_all().expect("lex");
+        assert_eq!(tokens[1].kind, TokenKind::Quoted(r"a | b \ c".to_string()));
+        assert_eq!(tokens[2].kind, TokenKind::Colon);
+
+        let err = Lexer::new("{ |open }").lex_all().expect_err("unterminated");
+        assert_eq!(err.message, "unterminated literal");
+    }
+
+    #[test]
+    fn lexes_markup_tokens() {
+        let input = "{#bold}hi{/bold}{#br/}";
+        let kinds: Vec<TokenKind> = Lexer::new(input)
+            .lex_all()
+            .expect("lex")
+            .into_iter()
+            .map(|token| token.kind)
+            .collect();
+        assert_eq!(kinds[1], TokenKind::Hash);
+        assert_eq!(kinds[6], TokenKind::Slash);
+        assert_eq!(kinds[12], TokenKind::Slash);
+        assert_eq!(kinds[13], TokenKind::RBrace);
+    }
+
     #[test]
     fn lexes_colon_and_ident() {
         let input = "{ $value :number }";
@@ -0,0 +1,198 @@
+use std::collections::BTreeSet;
+
+use mf2_i18n_core::{CldrPluralBackend, FormatBackend, LanguageTag, PluralCategory};
+
+use crate::backend::SmokeBackend;
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::parser::{CaseKey, Expr, Message, OptionValue, Segment, SelectExpr, SelectKind};
+
+pub fn lint_message(key: &str, raw: &str, message: &Message, locale: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    if raw.trim() != raw {
+        diagnostics.push(warning("MF2W101", "leading or trailing whitespace").with_span(key, 1, 1));
+    }
+
+    let mut used = BTreeSet::new();
+    collect_used(&message.segments, &mut used);
+    for declaration in message.declarations.iter().rev() {
+        if !used.contains(declaration.name.as_str()) {
+            diagnostics.push(
+                warning(
+                    "MF2W102",
+                    format!("unused declaration ${}", declaration.name),
+                )
+                .with_span(key, declaration.span.line, declaration.span.column),
+            );
+        }
+        used.extend(expr_names(&declaration.value));
+    }
+    diagnostics.sort_by_key(|diag| (diag.line, diag.column));
+
+    let needs_one = LanguageTag::parse(locale).is_ok_and(|tag| {
+        CldrPluralBackend::<SmokeBackend>::supports(&tag)
+            && CldrPluralBackend::new(&tag, SmokeBackend).plural_category(1.0)
+                == Ok(PluralCategory::One)
+    });
+    lint_segments(key, &message.segments, needs_one, false, &mut diagnostics);
+    diagnostics
+}
+
+pub fn has_plural_rules(locale: &str) -> bool {
+    LanguageTag::parse(locale).is_ok_and(|tag| CldrPluralBackend::<SmokeBackend>::supports(&tag))
+}
+
+fn warning(code: &str, message: impl Into<String>) -> Diagnostic {
+    Diagnostic::new(code, message).with_severity(Severity::Warning)
+}
+
+fn lint_segments(
+    key: &str,
+    segments: &[Segment],
+    needs_one: bool,
+    in_plural_case: bool,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for segment in segments {
+        match segment {
+            Segment::Text { value, span } => {
+                if in_plural_case && value.chars().any(|ch| ch.is_ascii_digit()) {
+                    diagnostics.push(
+                        warning(
+                            "MF2W104",
+                            "hard-coded number in a plural case; use # or a :number placeholder",
+                        )
+                        .with_span(key, span.line, span.column),
+                    );
+                }
+            }
+            Segment::Expr(Expr::Select(select)) => lint_select(key, select, needs_one, diagnostics),
+            Segment::Pound { .. } | Segment::Expr(_) => {}
+        }
+    }
+}
+
+fn lint_select(key: &str, select: &SelectExpr, needs_one: bool, diagnostics: &mut Vec<Diagnostic>) {
+    let plural = select.selectors.len() == 1 && is_plural_selector(select);
+    let has_one = select.cases.iter().any(|case| {
+        case.keys
+            .iter()
+            .any(|key| matches!(key, CaseKey::Ident(name) if name == "one"))
+    });
+    if plural && needs_one && !has_one {
+        diagnostics.push(
+            warning("MF2W103", "plural is missing a one case").with_span(
+                key,
+                select.span.line,
+                select.span.column,
+            ),
+        );
+    }
+    for case in &select.cases {
+        let categorized = plural && !matches!(case.keys.first(), Some(CaseKey::Exact(_)));
+        lint_segments(
+            key,
+            &case.value.segments,
+            needs_one,
+            categorized,
+            diagnostics,
+        );
+    }
+}
+
+fn is_plural_selector(select: &SelectExpr) -> bool {
+    select.selectors.iter().all(|selector| {
+        selector.kind == SelectKind::Plural
+            || matches!(selector.formatter.as_deref(), Some("number" | "integer"))
+    })
+}
+
+fn collect_used<'a>(segments: &'a [Segment], used: &mut BTreeSet<&'a str>) {
+    for segment in segments {
+        match segment {
+            Segment::Expr(Expr::Select(select)) => {
+                used.extend(
+                    select
+                        .selectors
+                        .iter()
+                        .map(|selector| selector.name.as_str()),
+                );
+                for case in &select.cases {
+                    collect_used(&case.value.segments, used);
+                }
+            }
+            Segment::Expr(expr) => used.extend(expr_names(expr)),
+            Segment::Text { .. } | Segment::Pound { .. } => {}
+        }
+    }
+}
+
+fn expr_names(expr: &Expr) -> Vec<&str> {
+    let (name, options) = match expr {
+        Expr::Variable(var) => (Some(var.name.as_str()), &var.options),
+        Expr::Literal(literal) => (None, &literal.options),
+        Expr::Markup(markup) => (None, &markup.options),
+        Expr::Select(_) => return Vec::new(),
+    };
+    name.into_iter()
+        .chain(options.iter().filter_map(|option| match &option.value {
+            OptionValue::Variable(name) => Some(name.as_str()),
+            OptionValue::Literal(_) | OptionValue::Number(_) => None,
+        }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lint_message;
+    use crate::diagnostic::Severity;
+    use crate::parser::parse_message;
+
+    fn codes(raw: &str, locale: &str) -> Vec<String> {
+        let message = parse_message(raw).expect("parse");
+        lint_message("home.title", raw, &message, locale)
+            .into_iter()
+            .map(|diag| diag.code)
+            .collect()
+    }
+
+    #[test]
+    fn flags_surrounding_whitespace() {
+        assert_eq!(codes(" Hi ", "en"), vec!["MF2W101"]);
+        assert!(codes("Hi", "en").is_empty());
+    }
+
+    #[test]
+    fn flags_unused_declarations() {
+        let raw = ".input { $count :number }\n.local $label = { |items| }\n.local $unused = { $label }\n{ $count }";
+        let message = parse_message(raw).expect("parse");
+        let diagnostics = lint_message("home.title", raw, &message, "en");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "MF2W102");
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(diagnostics[0].message, "unused declaration $unused");
+        assert_eq!(diagnostics[0].line, Some(3));
+        assert!(codes(".local $label = { |items| }\n{ $label }", "en").is_empty());
+    }
+
+    #[test]
+    fn flags_plurals_missing_one_where_the_locale_needs_it() {
+        let raw = "{ $count :plural -> [=0] {none} *[other] {# items} }";
+        assert_eq!(codes(raw, "en"), vec!["MF2W103"]);
+        assert_eq!(codes(raw, "fr-CA"), vec!["MF2W103"]);
+        assert!(codes(raw, "ja").is_empty());
+        assert!(
+            codes(
+                "{ $count :plural -> [one] {# item} *[other] {# items} }",
+                "en"
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn flags_hard_coded_numbers_in_plural_cases() {
+        let raw = "{ $count :plural -> [=0] {0 items} [one] {1 item} *[other] {# items} }";
+        assert_eq!(codes(raw, "en"), vec!["MF2W104"]);
+        assert!(codes("{ $v :select -> [a] {Version 2} *[other] {x} }", "en").is_empty());
+    }
+}
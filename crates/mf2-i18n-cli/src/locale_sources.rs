@@ -1,10 +1,13 @@
 use std::collections::BTreeMap;
+use std::collections::btree_map::Entry;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use thiserror::Error;
 
-use crate::mf2_source::parse_mf2_source;
+use crate::config::DuplicateKeyPolicy;
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::mf2_source::{SourceEntry, is_valid_key, parse_mf2_source};
 
 #[derive(Debug, Clone)]
 pub struct LocaleMessage {
@@ -17,6 +20,7 @@ pub struct LocaleMessage {
 pub struct LocaleBundle {
     pub locale: String,
     pub messages: BTreeMap<String, LocaleMessage>,
+    pub diagnostics: Vec<Diagnostic>,
 }
 
 #[derive(Debug, Error)]
@@ -25,13 +29,21 @@ pub enum LocaleSourceError {
     Io(#[from] std::io::Error),
     #[error("source parse error: {0}")]
     Parse(String),
-    #[error("duplicate key {0} in locale {1}")]
-    DuplicateKey(String, String),
+    #[error("duplicate key {key} in locale {locale} at {first} and {second}")]
+    DuplicateKey {
+        key: String,
+        locale: String,
+        first: String,
+        second: String,
+    },
     #[error("no locales found")]
     NoLocales,
 }
 
-pub fn load_locales(roots: &[PathBuf]) -> Result<Vec<LocaleBundle>, LocaleSourceError> {
+pub fn load_locales(
+    roots: &[PathBuf],
+    duplicates: DuplicateKeyPolicy,
+) -> Result<Vec<LocaleBundle>, LocaleSourceError> {
     let mut bundles = Vec::new();
     for root in roots {
         let entries = fs::read_dir(root)?;
@@ -46,8 +58,12 @@ pub fn load_locales(roots: &[PathBuf]) -> Result<Vec<LocaleBundle>, LocaleSource
                 .and_then(|name| name.to_str())
                 .unwrap_or("unknown")
                 .to_string();
-            let messages = load_locale_dir(&path, &locale)?;
-            bundles.push(LocaleBundle { locale, messages });
+            let (messages, diagnostics) = load_locale_dir(&path, &locale, duplicates)?;
+            bundles.push(LocaleBundle {
+                locale,
+                messages,
+                diagnostics,
+            });
         }
     }
     if bundles.is_empty() {
@@ -56,49 +72,203 @@ pub fn load_locales(roots: &[PathBuf]) -> Result<Vec<LocaleBundle>, LocaleSource
     Ok(bundles)
 }
 
+type SourceParser = fn(&str) -> Result<Vec<SourceEntry>, (u32, String)>;
+
+type LocaleMessages = (BTreeMap<String, LocaleMessage>, Vec<Diagnostic>);
+
 fn load_locale_dir(
     path: &Path,
     locale: &str,
-) -> Result<BTreeMap<String, LocaleMessage>, LocaleSourceError> {
-    let mut messages = BTreeMap::new();
-    for entry in fs::read_dir(path)? {
-        let entry = entry?;
-        let file_path = entry.path();
-        if file_path.extension().and_then(|ext| ext.to_str()) != Some("mf2") {
-            continue;
-        }
+    duplicates: DuplicateKeyPolicy,
+) -> Result<LocaleMessages, LocaleSourceError> {
+    let mut file_paths = fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    file_paths.sort();
+
+    let mut messages: BTreeMap<String, LocaleMessage> = BTreeMap::new();
+    let mut diagnostics = Vec::new();
+    for file_path in file_paths {
+        let parse: SourceParser = match file_path.extension().and_then(|ext| ext.to_str()) {
+            Some("mf2") => parse_mf2_entries,
+            Some("arb" | "json") => parse_json_source,
+            #[cfg(feature = "yaml-sources")]
+            Some("yaml" | "yml") => parse_yaml_source,
+            #[cfg(feature = "toml-sources")]
+            Some("toml") => parse_toml_source,
+            _ => continue,
+        };
         let contents = fs::read_to_string(&file_path)?;
-        let entries = parse_mf2_source(&contents).map_err(|err| {
-            LocaleSourceError::Parse(format!(
-                "{}:{} {}",
-                file_path.display(),
-                err.line,
-                err.message
-            ))
+        let parsed = parse(&contents);
+        let entries = parsed.map_err(|(line, message)| {
+            LocaleSourceError::Parse(format!("{}:{} {}", file_path.display(), line, message))
         })?;
         for entry in entries {
-            if messages.contains_key(&entry.key) {
-                return Err(LocaleSourceError::DuplicateKey(
-                    entry.key,
-                    locale.to_string(),
-                ));
-            }
-            messages.insert(
-                entry.key.clone(),
-                LocaleMessage {
-                    value: entry.value,
-                    file: file_path.display().to_string(),
-                    line: entry.line,
-                },
+            let message = LocaleMessage {
+                value: entry.value,
+                file: file_path.display().to_string(),
+                line: entry.line,
+            };
+            let mut slot = match messages.entry(entry.key) {
+                Entry::Vacant(slot) => {
+                    slot.insert(message);
+                    continue;
+                }
+                Entry::Occupied(slot) => slot,
+            };
+            let existing = slot.get();
+            let first = format!("{}:{}", existing.file, existing.line);
+            let second = format!("{}:{}", message.file, message.line);
+            let (kept, dropped) = match duplicates {
+                DuplicateKeyPolicy::Error => {
+                    return Err(LocaleSourceError::DuplicateKey {
+                        key: slot.key().clone(),
+                        locale: locale.to_string(),
+                        first,
+                        second,
+                    });
+                }
+                DuplicateKeyPolicy::FirstWins => (first, message),
+                DuplicateKeyPolicy::LastWins => (second, slot.insert(message)),
+            };
+            diagnostics.push(
+                Diagnostic::new(
+                    "MF2W002",
+                    format!("duplicate key {}; keeping {kept}", slot.key()),
+                )
+                .with_severity(Severity::Warning)
+                .with_span(dropped.file, dropped.line, 1),
             );
         }
     }
-    Ok(messages)
+    Ok((messages, diagnostics))
+}
+
+fn parse_mf2_entries(input: &str) -> Result<Vec<SourceEntry>, (u32, String)> {
+    parse_mf2_source(input).map_err(|err| (err.line, err.message))
+}
+
+fn parse_json_source(input: &str) -> Result<Vec<SourceEntry>, (u32, String)> {
+    let value: serde_json::Value =
+        serde_json::from_str(input).map_err(|err| (err.line() as u32, err.to_string()))?;
+    let serde_json::Value::Object(map) = value else {
+        return Err((1, "expected a json object".to_string()));
+    };
+    let mut entries = Vec::new();
+    for (key, value) in map {
+        if key.starts_with('@') {
+            continue;
+        }
+        let line = json_key_line(input, &key);
+        if !is_valid_key(&key) {
+            return Err((line, "invalid key".to_string()));
+        }
+        let serde_json::Value::String(value) = value else {
+            return Err((line, "expected a string message".to_string()));
+        };
+        entries.push(SourceEntry {
+            key,
+            value,
+            line,
+            comments: Vec::new(),
+        });
+    }
+    Ok(entries)
+}
+
+fn json_key_line(input: &str, key: &str) -> u32 {
+    let quoted = format!("\"{key}\"");
+    for (idx, line) in input.lines().enumerate() {
+        if let Some(pos) = line.find(&quoted)
+            && line[pos + quoted.len()..].trim_start().starts_with(':')
+        {
+            return (idx + 1) as u32;
+        }
+    }
+    1
+}
+
+#[cfg(feature = "yaml-sources")]
+fn parse_yaml_source(input: &str) -> Result<Vec<SourceEntry>, (u32, String)> {
+    let value: serde_json::Value = serde_yaml::from_str(input).map_err(|err| {
+        let line = err.location().map(|location| location.line()).unwrap_or(1);
+        (line as u32, err.to_string())
+    })?;
+    flatten_source(input, value, ':')
+}
+
+#[cfg(feature = "toml-sources")]
+fn parse_toml_source(input: &str) -> Result<Vec<SourceEntry>, (u32, String)> {
+    let value: serde_json::Value = toml::from_str(input).map_err(|err| {
+        let offset = err.span().map(|span| span.start).unwrap_or(0);
+        let line = input[..offset.min(input.len())].matches('\n').count() + 1;
+        (line as u32, err.message().to_string())
+    })?;
+    flatten_source(input, value, '=')
+}
+
+#[cfg(any(feature = "yaml-sources", feature = "toml-sources"))]
+fn flatten_source(
+    input: &str,
+    value: serde_json::Value,
+    separator: char,
+) -> Result<Vec<SourceEntry>, (u32, String)> {
+    let serde_json::Value::Object(map) = value else {
+        return Err((1, "expected a map of messages".to_string()));
+    };
+    let mut entries = Vec::new();
+    let mut pending: Vec<(String, serde_json::Value)> = map.into_iter().collect();
+    pending.reverse();
+    while let Some((key, value)) = pending.pop() {
+        let leaf = key.rsplit('.').next().unwrap_or(&key);
+        let line = leaf_line(input, leaf, separator);
+        match value {
+            serde_json::Value::String(value) => {
+                if !is_valid_key(&key) {
+                    return Err((line, format!("invalid key {key}")));
+                }
+                entries.push(SourceEntry {
+                    key,
+                    value,
+                    line,
+                    comments: Vec::new(),
+                });
+            }
+            serde_json::Value::Object(children) => {
+                let mut children: Vec<(String, serde_json::Value)> = children
+                    .into_iter()
+                    .map(|(child, value)| (format!("{key}.{child}"), value))
+                    .collect();
+                children.reverse();
+                pending.extend(children);
+            }
+            _ => return Err((line, format!("expected a string message at {key}"))),
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(any(feature = "yaml-sources", feature = "toml-sources"))]
+fn leaf_line(input: &str, leaf: &str, separator: char) -> u32 {
+    for (idx, line) in input.lines().enumerate() {
+        let trimmed = line.trim_start().trim_start_matches('"');
+        if let Some(rest) = trimmed.strip_prefix(leaf)
+            && rest
+                .trim_start_matches('"')
+                .trim_start()
+                .starts_with(separator)
+        {
+            return (idx + 1) as u32;
+        }
+    }
+    1
 }
 
 #[cfg(test)]
 mod tests {
-    use super::load_locales;
+    use super::{LocaleSourceError, load_locales};
+    use crate::config::DuplicateKeyPolicy;
+    use crate::diagnostic::Severity;
     use std::fs;
     use std::path::PathBuf;
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -121,10 +291,132 @@ mod tests {
         fs::create_dir_all(&locale_dir).expect("locale");
         fs::write(locale_dir.join("messages.mf2"), "home.title = Hi").expect("write");
 
-        let locales = load_locales(&[dir.clone()]).expect("load");
+        let locales =
+            load_locales(std::slice::from_ref(&dir), DuplicateKeyPolicy::Error).expect("load");
         assert_eq!(locales.len(), 1);
         assert!(locales[0].messages.contains_key("home.title"));
 
         fs::remove_dir_all(&dir).ok();
     }
+
+    #[test]
+    fn loads_arb_alongside_mf2() {
+        let dir = temp_dir();
+        let locale_dir = dir.join("fr");
+        fs::create_dir_all(&locale_dir).expect("locale");
+        fs::write(locale_dir.join("messages.mf2"), "home.title = Salut").expect("write");
+        fs::write(
+            locale_dir.join("app_fr.arb"),
+            "{\n  \"@@locale\": \"fr\",\n  \"home.body\": \"Bonjour { $name }\",\n  \"@home.body\": {\n    \"description\": \"Greeting\"\n  }\n}\n",
+        )
+        .expect("write");
+
+        let locales =
+            load_locales(std::slice::from_ref(&dir), DuplicateKeyPolicy::Error).expect("load");
+        let messages = &locales[0].messages;
+        assert_eq!(messages.len(), 2);
+        let body = &messages["home.body"];
+        assert_eq!(body.value, "Bonjour { $name }");
+        assert_eq!(body.line, 3);
+        assert!(body.file.ends_with("app_fr.arb"));
+
+        fs::write(locale_dir.join("extra.json"), "{ \"home.title\": \"Dup\" }").expect("write");
+        let err = load_locales(std::slice::from_ref(&dir), DuplicateKeyPolicy::Error)
+            .expect_err("duplicate");
+        assert!(
+            matches!(err, LocaleSourceError::DuplicateKey { ref key, .. } if key == "home.title")
+        );
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn reports_both_locations_of_duplicate_keys() {
+        let dir = temp_dir();
+        let locale_dir = dir.join("en");
+        fs::create_dir_all(&locale_dir).expect("locale");
+        let first = locale_dir.join("a.mf2");
+        let second = locale_dir.join("b.mf2");
+        fs::write(&first, "home.title = First\n").expect("write");
+        fs::write(&second, "home.body = Body\n\nhome.title = Second\n").expect("write");
+
+        let err = load_locales(std::slice::from_ref(&dir), DuplicateKeyPolicy::Error)
+            .expect_err("duplicate");
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "duplicate key home.title in locale en at {}:1 and {}:3",
+                first.display(),
+                second.display()
+            )
+        );
+
+        let locales =
+            load_locales(std::slice::from_ref(&dir), DuplicateKeyPolicy::FirstWins).expect("load");
+        assert_eq!(locales[0].messages["home.title"].value, "First");
+        let diagnostic = &locales[0].diagnostics[0];
+        assert_eq!(diagnostic.code, "MF2W002");
+        assert_eq!(diagnostic.severity, Severity::Warning);
+        assert_eq!(diagnostic.file, Some(second.display().to_string()));
+        assert_eq!(diagnostic.line, Some(3));
+
+        let locales =
+            load_locales(std::slice::from_ref(&dir), DuplicateKeyPolicy::LastWins).expect("load");
+        assert_eq!(locales[0].messages["home.title"].value, "Second");
+        let diagnostic = &locales[0].diagnostics[0];
+        assert_eq!(diagnostic.file, Some(first.display().to_string()));
+        assert_eq!(diagnostic.line, Some(1));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[cfg(feature = "yaml-sources")]
+    #[test]
+    fn flattens_nested_yaml_keys() {
+        let dir = temp_dir();
+        let locale_dir = dir.join("en");
+        fs::create_dir_all(&locale_dir).expect("locale");
+        fs::write(
+            locale_dir.join("messages.yaml"),
+            "home:\n  title: Hi\n  body: \"Hello { $name }\"\n",
+        )
+        .expect("write");
+
+        let locales =
+            load_locales(std::slice::from_ref(&dir), DuplicateKeyPolicy::Error).expect("load");
+        let messages = &locales[0].messages;
+        assert_eq!(messages["home.title"].value, "Hi");
+        assert_eq!(messages["home.title"].line, 2);
+        assert_eq!(messages["home.body"].value, "Hello { $name }");
+
+        fs::write(locale_dir.join("messages.yaml"), "home:\n  count: 3\n").expect("write");
+        let err = load_locales(std::slice::from_ref(&dir), DuplicateKeyPolicy::Error)
+            .expect_err("non-string leaf");
+        assert!(
+            matches!(err, LocaleSourceError::Parse(message) if message.ends_with(":2 expected a string message at home.count"))
+        );
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[cfg(feature = "toml-sources")]
+    #[test]
+    fn flattens_nested_toml_keys() {
+        let dir = temp_dir();
+        let locale_dir = dir.join("en");
+        fs::create_dir_all(&locale_dir).expect("locale");
+        fs::write(locale_dir.join("messages.toml"), "[home]\ntitle = \"Hi\"\n").expect("write");
+
+        let locales =
+            load_locales(std::slice::from_ref(&dir), DuplicateKeyPolicy::Error).expect("load");
+        assert_eq!(locales[0].messages["home.title"].value, "Hi");
+        assert_eq!(locales[0].messages["home.title"].line, 2);
+
+        fs::write(locale_dir.join("messages.toml"), "[home\n").expect("write");
+        let err = load_locales(std::slice::from_ref(&dir), DuplicateKeyPolicy::Error)
+            .expect_err("malformed");
+        assert!(matches!(err, LocaleSourceError::Parse(_)));
+
+        fs::remove_dir_all(&dir).ok();
+    }
 }
@@ -1,30 +1,43 @@
 #![forbid(unsafe_code)]
 
 mod artifacts;
+mod backend;
 mod catalog;
 mod catalog_builder;
 mod catalog_reader;
 mod cli;
 mod command_build;
+mod command_check;
 mod command_coverage;
+mod command_diff;
+mod command_emit_id_map;
+mod command_export;
 mod command_extract;
+mod command_fmt;
+mod command_import_ftl;
+mod command_import_po;
+mod command_lint;
 mod command_pseudo;
 mod command_sign;
+mod command_stats;
 mod command_validate;
+mod command_verify;
+mod command_watch;
 mod compiler;
 mod config;
 mod diagnostic;
 mod error;
 mod extract;
+mod extract_cache;
 mod extract_pipeline;
 mod id_map;
 mod lexer;
+mod linter;
 mod locale_sources;
 mod manifest;
 mod mf2_source;
 mod micro_locales;
 mod model;
-mod pack_encode;
 mod parser;
 mod validator;
 
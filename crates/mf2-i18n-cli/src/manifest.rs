@@ -18,7 +18,7 @@ pub struct Manifest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub budgets: Option<BTreeMap<String, u64>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub signing: Option<ManifestSigning>,
+    pub signing: Option<ManifestSignatures>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,6 +40,28 @@ pub struct ManifestSigning {
     pub manifest_sig: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ManifestSignatures {
+    Single(ManifestSigning),
+    Multiple(Vec<ManifestSigning>),
+}
+
+impl ManifestSignatures {
+    pub fn as_slice(&self) -> &[ManifestSigning] {
+        match self {
+            Self::Single(signing) => std::slice::from_ref(signing),
+            Self::Multiple(signatures) => signatures,
+        }
+    }
+
+    pub fn push(&mut self, signing: ManifestSigning) {
+        let mut signatures = self.as_slice().to_vec();
+        signatures.push(signing);
+        *self = Self::Multiple(signatures);
+    }
+}
+
 impl Manifest {
     pub fn to_canonical_bytes(&self) -> Vec<u8> {
         serde_json::to_vec(self).unwrap_or_default()
@@ -48,7 +70,10 @@ impl Manifest {
     pub fn to_signing_bytes(&self) -> Vec<u8> {
         let mut clone = self.clone();
         clone.signing = None;
-        serde_json::to_vec(&clone).unwrap_or_default()
+        clone.supported_locales.sort();
+        serde_json::to_value(&clone)
+            .map(|value| mf2_i18n_core::canonical_json(&value).into_bytes())
+            .unwrap_or_default()
     }
 }
 
@@ -65,40 +90,27 @@ pub fn sha256_raw(bytes: &[u8]) -> [u8; 32] {
 
 #[cfg(test)]
 mod tests {
-    use super::{Manifest, PackEntry, sha256_hex};
-    use std::collections::BTreeMap;
+    use super::{Manifest, sha256_hex};
+    use mf2_i18n_test_support::ManifestBuilder;
 
     #[test]
     fn canonical_json_is_stable() {
-        let mut mf2_packs = BTreeMap::new();
-        mf2_packs.insert(
-            "en".to_string(),
-            PackEntry {
-                kind: "base".to_string(),
-                url: "packs/en.mf2pack".to_string(),
-                hash: "sha256:abc".to_string(),
-                size: 12,
-                content_encoding: "br".to_string(),
-                pack_schema: 0,
-                parent: None,
-            },
-        );
-        let manifest = Manifest {
-            schema: 1,
-            release_id: "r1".to_string(),
-            generated_at: "2026-02-01T00:00:00Z".to_string(),
-            default_locale: "en".to_string(),
-            supported_locales: vec!["en".to_string()],
-            id_map_hash: "sha256:dead".to_string(),
-            mf2_packs,
-            icu_packs: None,
-            micro_locales: None,
-            budgets: None,
-            signing: None,
-        };
+        let manifest: Manifest = serde_json::from_value(
+            ManifestBuilder::new([0; 32])
+                .encoded_pack("en", None, "br", b"pack")
+                .build(),
+        )
+        .expect("manifest");
         let bytes_a = manifest.to_canonical_bytes();
         let bytes_b = manifest.to_canonical_bytes();
         assert_eq!(bytes_a, bytes_b);
+
+        let runtime: mf2_i18n_runtime::Manifest =
+            serde_json::from_slice(&bytes_a).expect("runtime manifest");
+        assert_eq!(
+            manifest.to_signing_bytes(),
+            runtime.to_signing_bytes().expect("bytes")
+        );
     }
 
     #[test]
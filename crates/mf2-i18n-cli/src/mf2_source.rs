@@ -1,8 +1,16 @@
+use std::collections::BTreeMap;
+
+use crate::parser::{
+    CaseKey, DeclarationKind, Expr, FormatOption, Literal, MarkupKind, Message, OptionValue,
+    Segment, SelectKind,
+};
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SourceEntry {
     pub key: String,
     pub value: String,
     pub line: u32,
+    pub comments: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -13,7 +21,12 @@ pub struct SourceError {
 }
 
 pub fn parse_mf2_source(input: &str) -> Result<Vec<SourceEntry>, SourceError> {
+    parse_mf2_document(input).map(|(entries, _)| entries)
+}
+
+pub fn parse_mf2_document(input: &str) -> Result<(Vec<SourceEntry>, Vec<String>), SourceError> {
     let mut entries = Vec::new();
+    let mut comments = Vec::new();
     let mut current_key: Option<String> = None;
     let mut current_value = String::new();
     let mut current_line = 0u32;
@@ -24,7 +37,14 @@ pub fn parse_mf2_source(input: &str) -> Result<Vec<SourceEntry>, SourceError> {
         let trimmed = line.trim();
 
         if current_key.is_none() {
-            if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with("//") {
+            if trimmed.is_empty() {
+                continue;
+            }
+            if let Some(comment) = trimmed
+                .strip_prefix('#')
+                .or_else(|| trimmed.strip_prefix("//"))
+            {
+                comments.push(comment.strip_prefix(' ').unwrap_or(comment).to_string());
                 continue;
             }
             let mut parts = line.splitn(2, '=');
@@ -58,6 +78,7 @@ pub fn parse_mf2_source(input: &str) -> Result<Vec<SourceEntry>, SourceError> {
                 &mut current_key,
                 &mut current_value,
                 current_line,
+                &mut comments,
             );
         } else {
             if !current_value.is_empty() {
@@ -73,10 +94,11 @@ pub fn parse_mf2_source(input: &str) -> Result<Vec<SourceEntry>, SourceError> {
             &mut current_key,
             &mut current_value,
             current_line,
+            &mut comments,
         );
     }
 
-    Ok(entries)
+    Ok((entries, comments))
 }
 
 fn flush_entry(
@@ -84,18 +106,209 @@ fn flush_entry(
     key: &mut Option<String>,
     value: &mut String,
     line: u32,
+    comments: &mut Vec<String>,
 ) {
     if let Some(key_value) = key.take() {
         entries.push(SourceEntry {
             key: key_value,
             value: value.trim_end().to_string(),
             line,
+            comments: std::mem::take(comments),
         });
     }
     value.clear();
 }
 
-fn is_valid_key(key: &str) -> bool {
+pub fn serialize_mf2_source(entries: &BTreeMap<String, String>) -> String {
+    let mut out = String::new();
+    for (key, value) in entries {
+        push_source_entry(&mut out, &[], key, value);
+    }
+    out
+}
+
+pub fn push_source_entry(out: &mut String, comments: &[String], key: &str, value: &str) {
+    if !out.is_empty() {
+        out.push_str("\n\n");
+    }
+    push_comments(out, comments);
+    out.push_str(key);
+    out.push_str(" = ");
+    let mut lines = value.lines();
+    if let Some(first) = lines.next() {
+        out.push_str(first);
+    }
+    for line in lines {
+        out.push('\n');
+        out.push_str(line);
+    }
+}
+
+pub fn push_source_comments(out: &mut String, comments: &[String]) {
+    if comments.is_empty() {
+        return;
+    }
+    if !out.is_empty() {
+        out.push_str("\n\n");
+    }
+    push_comments(out, comments);
+    out.pop();
+}
+
+fn push_comments(out: &mut String, comments: &[String]) {
+    for comment in comments {
+        out.push('#');
+        if !comment.is_empty() {
+            out.push(' ');
+            out.push_str(comment);
+        }
+        out.push('\n');
+    }
+}
+
+pub fn serialize_message(message: &Message) -> String {
+    let mut out = String::new();
+    push_message(&mut out, message);
+    out
+}
+
+fn push_message(out: &mut String, message: &Message) {
+    for declaration in &message.declarations {
+        match declaration.kind {
+            DeclarationKind::Local => {
+                out.push_str(".local $");
+                out.push_str(&declaration.name);
+                out.push_str(" = ");
+            }
+            DeclarationKind::Input => out.push_str(".input "),
+        }
+        push_expr(out, &declaration.value);
+        out.push('\n');
+    }
+    for segment in &message.segments {
+        match segment {
+            Segment::Text { value, .. } => push_text(out, value),
+            Segment::Pound { .. } => out.push('#'),
+            Segment::Expr(expr) => push_expr(out, expr),
+        }
+    }
+}
+
+fn push_text(out: &mut String, value: &str) {
+    for ch in value.chars() {
+        if matches!(ch, '{' | '}' | '\\') {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+}
+
+fn push_expr(out: &mut String, expr: &Expr) {
+    match expr {
+        Expr::Variable(var) => {
+            out.push_str("{ $");
+            out.push_str(&var.name);
+            if let Some(formatter) = &var.formatter {
+                out.push_str(" :");
+                out.push_str(formatter);
+                push_options(out, &var.options);
+            }
+            out.push_str(" }");
+        }
+        Expr::Literal(literal) => {
+            out.push_str("{ ");
+            match &literal.value {
+                Literal::Number(raw) => out.push_str(raw),
+                Literal::Quoted(raw) => {
+                    out.push('|');
+                    for ch in raw.chars() {
+                        if matches!(ch, '|' | '\\') {
+                            out.push('\\');
+                        }
+                        out.push(ch);
+                    }
+                    out.push('|');
+                }
+            }
+            if let Some(formatter) = &literal.formatter {
+                out.push_str(" :");
+                out.push_str(formatter);
+                push_options(out, &literal.options);
+            }
+            out.push_str(" }");
+        }
+        Expr::Markup(markup) => {
+            out.push_str(match markup.kind {
+                MarkupKind::Close => "{/",
+                MarkupKind::Open | MarkupKind::Standalone => "{#",
+            });
+            out.push_str(&markup.name);
+            push_options(out, &markup.options);
+            if markup.kind == MarkupKind::Standalone {
+                out.push_str(" /");
+            }
+            out.push('}');
+        }
+        Expr::Select(select) => {
+            out.push('{');
+            for selector in &select.selectors {
+                out.push_str(" $");
+                out.push_str(&selector.name);
+                match (&selector.formatter, &selector.kind) {
+                    (Some(formatter), _) => {
+                        out.push_str(" :");
+                        out.push_str(formatter);
+                    }
+                    (None, SelectKind::Select) => {}
+                    (None, SelectKind::Plural) => out.push_str(" :plural"),
+                    (None, SelectKind::Ordinal) => out.push_str(" :ordinal"),
+                }
+            }
+            out.push_str(" ->");
+            if let Some(offset) = select.offset {
+                out.push_str(&format!(" offset:{offset}"));
+            }
+            for case in &select.cases {
+                out.push(' ');
+                if case.is_default {
+                    out.push('*');
+                }
+                out.push('[');
+                for (idx, key) in case.keys.iter().enumerate() {
+                    if idx > 0 {
+                        out.push(' ');
+                    }
+                    match key {
+                        CaseKey::Ident(value) => out.push_str(value),
+                        CaseKey::Exact(value) => out.push_str(&format!("={value}")),
+                        CaseKey::Other => out.push_str("other"),
+                    }
+                }
+                out.push_str("] {");
+                push_message(out, &case.value);
+                out.push('}');
+            }
+            out.push_str(" }");
+        }
+    }
+}
+
+fn push_options(out: &mut String, options: &[FormatOption]) {
+    for option in options {
+        out.push(' ');
+        out.push_str(&option.name);
+        out.push('=');
+        match &option.value {
+            OptionValue::Literal(value) | OptionValue::Number(value) => out.push_str(value),
+            OptionValue::Variable(name) => {
+                out.push('$');
+                out.push_str(name);
+            }
+        }
+    }
+}
+
+pub(crate) fn is_valid_key(key: &str) -> bool {
     key.bytes().all(|byte| {
         byte.is_ascii_lowercase()
             || byte.is_ascii_digit()
@@ -107,7 +320,10 @@ fn is_valid_key(key: &str) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::parse_mf2_source;
+    use std::collections::BTreeMap;
+
+    use super::{parse_mf2_source, push_source_entry, serialize_message, serialize_mf2_source};
+    use crate::parser::parse_message;
 
     #[test]
     fn parses_single_line_entry() {
@@ -139,4 +355,40 @@ mod tests {
         let err = parse_mf2_source(input).expect_err("error");
         assert_eq!(err.message, "invalid key");
     }
+
+    #[test]
+    fn serialized_entries_parse_back() {
+        let mut entries = BTreeMap::new();
+        entries.insert("home.body".to_string(), "line1\nline2".to_string());
+        entries.insert("home.title".to_string(), "Hi".to_string());
+        let mut out = serialize_mf2_source(&entries);
+        push_source_entry(&mut out, &["note".to_string()], "zed", "Z");
+        let parsed = parse_mf2_source(&out).expect("parse");
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[0].value, "line1\nline2");
+        assert_eq!(parsed[2].key, "zed");
+    }
+
+    #[test]
+    fn preserves_and_round_trips_escapes() {
+        let input = r"home.hint = Use \{braces\} or \\";
+        let entries = parse_mf2_source(input).expect("parse");
+        assert_eq!(entries[0].value, r"Use \{braces\} or \\");
+        let message = parse_message(&entries[0].value).expect("message");
+        assert_eq!(serialize_message(&message), entries[0].value);
+    }
+
+    #[test]
+    fn serialized_messages_parse_back() {
+        let input = "{#b}Hi{/b} { $name :number style=percent } { 42 :number } { |a \\| b| } {#br /}{ $n :plural -> offset:1 [=0] {none} [one] {# item} *[other] {# items} }";
+        let first = serialize_message(&parse_message(input).expect("parse"));
+        assert_eq!(first, input);
+        let second = serialize_message(&parse_message(&first).expect("reparse"));
+        assert_eq!(first, second);
+
+        let declared =
+            ".input { $count :number }\n.local $label = { |items| }\n{ $count } { $label }";
+        let message = parse_message(declared).expect("parse");
+        assert_eq!(serialize_message(&message), declared);
+    }
 }
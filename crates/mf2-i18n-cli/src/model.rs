@@ -12,6 +12,20 @@ pub enum ArgType {
     Any,
 }
 
+impl ArgType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Number => "number",
+            Self::Bool => "bool",
+            Self::DateTime => "datetime",
+            Self::Unit => "unit",
+            Self::Currency => "currency",
+            Self::Any => "any",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ArgSpec {
     pub name: String,
@@ -1,26 +1,89 @@
 use crate::lexer::{LexError, Lexer, Span, Token, TokenKind};
+use crate::model::{ArgSpec, ArgType};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Message {
+    pub declarations: Vec<Declaration>,
     pub segments: Vec<Segment>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeclarationKind {
+    Local,
+    Input,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Declaration {
+    pub kind: DeclarationKind,
+    pub name: String,
+    pub value: Expr,
+    pub span: Span,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Segment {
     Text { value: String, span: Span },
+    Pound { span: Span },
     Expr(Expr),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Expr {
     Variable(VarExpr),
+    Literal(LiteralExpr),
     Select(SelectExpr),
+    Markup(MarkupExpr),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct VarExpr {
     pub name: String,
     pub formatter: Option<String>,
+    pub options: Vec<FormatOption>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiteralExpr {
+    pub value: Literal,
+    pub formatter: Option<String>,
+    pub options: Vec<FormatOption>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Literal {
+    Number(String),
+    Quoted(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatOption {
+    pub name: String,
+    pub value: OptionValue,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OptionValue {
+    Literal(String),
+    Number(String),
+    Variable(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkupKind {
+    Open,
+    Close,
+    Standalone,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkupExpr {
+    pub kind: MarkupKind,
+    pub name: String,
+    pub options: Vec<FormatOption>,
     pub span: Span,
 }
 
@@ -28,19 +91,36 @@ pub struct VarExpr {
 pub enum SelectKind {
     Select,
     Plural,
+    Ordinal,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SelectExpr {
-    pub selector: String,
+    pub selectors: Vec<Selector>,
+    pub offset: Option<u32>,
     pub cases: Vec<SelectCase>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    pub name: String,
+    pub formatter: Option<String>,
     pub kind: SelectKind,
     pub span: Span,
 }
 
+impl SelectExpr {
+    pub fn is_plural(&self) -> bool {
+        self.selectors
+            .iter()
+            .any(|selector| selector.kind != SelectKind::Select)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SelectCase {
-    pub key: CaseKey,
+    pub keys: Vec<CaseKey>,
     pub value: Message,
     pub is_default: bool,
     pub span: Span,
@@ -49,7 +129,7 @@ pub struct SelectCase {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CaseKey {
     Ident(String),
-    Exact(u32),
+    Exact(i64),
     Other,
 }
 
@@ -71,9 +151,14 @@ impl From<LexError> for ParseError {
 pub fn parse_message(input: &str) -> Result<Message, ParseError> {
     let tokens = Lexer::new(input).lex_all()?;
     let mut parser = Parser::new(tokens);
-    parser.parse_message(false)
+    let declarations = parser.parse_declarations()?;
+    let mut message = parser.parse_message(false)?;
+    message.declarations = declarations;
+    Ok(message)
 }
 
+type Operand = (String, Option<String>, Vec<FormatOption>, Span);
+
 struct Parser {
     tokens: Vec<Token>,
     index: usize,
@@ -109,55 +194,255 @@ impl Parser {
                 }
             }
         }
-        Ok(Message { segments })
+        Ok(Message {
+            declarations: Vec::new(),
+            segments,
+        })
     }
 
-    fn parse_expr(&mut self) -> Result<Expr, ParseError> {
-        let start = self.peek_span().unwrap_or_else(|| Span {
-            start: 0,
-            end: 0,
-            line: 1,
-            column: 1,
-        });
-        self.expect(TokenKind::Dollar)?;
-        let name = self.expect_ident()?;
-        let formatter = if self.peek_is(&TokenKind::Colon) {
+    fn parse_declarations(&mut self) -> Result<Vec<Declaration>, ParseError> {
+        let mut declarations = Vec::new();
+        while let Some(Token {
+            kind: TokenKind::Keyword(keyword),
+            span: start,
+        }) = self.peek().cloned()
+        {
             self.next();
-            Some(self.expect_ident()?)
-        } else {
-            None
-        };
+            let kind = match keyword.as_str() {
+                "local" => DeclarationKind::Local,
+                _ => DeclarationKind::Input,
+            };
+            let local_name = if kind == DeclarationKind::Local {
+                self.expect(TokenKind::Dollar)?;
+                let name = self.expect_ident()?;
+                self.expect(TokenKind::Equals)?;
+                Some(name)
+            } else {
+                None
+            };
+            let brace = self.expect(TokenKind::LBrace)?.span;
+            let value = self.parse_expr()?;
+            let (name, end) = match (&value, local_name) {
+                (Expr::Variable(var), None) => (var.name.clone(), var.span.clone()),
+                (Expr::Variable(VarExpr { span, .. }), Some(name))
+                | (Expr::Literal(LiteralExpr { span, .. }), Some(name)) => (name, span.clone()),
+                (_, None) => {
+                    return Err(self.error("input declaration requires a variable", brace));
+                }
+                (_, Some(_)) => {
+                    return Err(
+                        self.error("declaration value must be a variable or literal", brace)
+                    );
+                }
+            };
+            declarations.push(Declaration {
+                kind,
+                name,
+                value,
+                span: span_merge(start, end),
+            });
+        }
+        Ok(declarations)
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr, ParseError> {
+        let start = self.current_span();
+        if self.peek_is(&TokenKind::Hash) || self.peek_is(&TokenKind::Slash) {
+            return self.parse_markup(start);
+        }
+        if matches!(
+            self.peek().map(|token| &token.kind),
+            Some(TokenKind::Number(_) | TokenKind::Quoted(_))
+        ) {
+            return self.parse_literal(start);
+        }
+        let mut operands = vec![self.parse_operand()?];
+        while self.peek_is(&TokenKind::Dollar) {
+            operands.push(self.parse_operand()?);
+        }
         if self.peek_is(&TokenKind::Arrow) {
             self.next();
-            let cases = self.parse_cases()?;
-            let end = self.expect(TokenKind::RBrace)?;
-            let mut kind = SelectKind::Select;
-            if formatter.as_deref() == Some("plural") {
-                kind = SelectKind::Plural;
+            let offset = self.parse_offset()?;
+            if offset.is_some() && operands.len() > 1 {
+                return Err(self.error("plural offset requires a single selector", start));
             }
-            if cases
+            let mut cases = self.parse_cases(operands.len())?;
+            let end = self.expect(TokenKind::RBrace)?;
+            let pound_plural = operands.len() == 1 && uses_pound_with_plural_keys(&cases);
+            let selectors: Vec<Selector> = operands
+                .into_iter()
+                .map(|(name, formatter, _, span)| {
+                    let kind = match formatter.as_deref() {
+                        Some("plural") => SelectKind::Plural,
+                        Some("ordinal") => SelectKind::Ordinal,
+                        _ if offset.is_some() => SelectKind::Plural,
+                        None if pound_plural => SelectKind::Plural,
+                        _ => SelectKind::Select,
+                    };
+                    Selector {
+                        name,
+                        formatter,
+                        kind,
+                        span,
+                    }
+                })
+                .collect();
+            if selectors
                 .iter()
-                .any(|case| matches!(case.key, CaseKey::Exact(_)))
+                .any(|selector| selector.kind != SelectKind::Select)
             {
-                kind = SelectKind::Plural;
+                for case in &mut cases {
+                    split_pound(&mut case.value);
+                }
             }
             Ok(Expr::Select(SelectExpr {
-                selector: name,
+                selectors,
+                offset,
                 cases,
-                kind,
                 span: span_merge(start, end.span),
             }))
         } else {
+            if operands.len() > 1 {
+                return Err(self.error("multiple selectors require cases", start));
+            }
+            let (name, formatter, options, _) = operands.remove(0);
             let end = self.expect(TokenKind::RBrace)?;
             Ok(Expr::Variable(VarExpr {
                 name,
                 formatter,
+                options,
                 span: span_merge(start, end.span),
             }))
         }
     }
 
-    fn parse_cases(&mut self) -> Result<Vec<SelectCase>, ParseError> {
+    fn parse_markup(&mut self, start: Span) -> Result<Expr, ParseError> {
+        let mut kind = if self.peek_is(&TokenKind::Hash) {
+            MarkupKind::Open
+        } else {
+            MarkupKind::Close
+        };
+        self.next();
+        let name = self.expect_ident()?;
+        let mut options = Vec::new();
+        while self.peek_option() {
+            options.push(self.parse_option()?);
+        }
+        if kind == MarkupKind::Open && self.peek_is(&TokenKind::Slash) {
+            self.next();
+            kind = MarkupKind::Standalone;
+        }
+        let end = self.expect(TokenKind::RBrace)?;
+        Ok(Expr::Markup(MarkupExpr {
+            kind,
+            name,
+            options,
+            span: span_merge(start, end.span),
+        }))
+    }
+
+    fn parse_literal(&mut self, start: Span) -> Result<Expr, ParseError> {
+        let value = match self.next().map(|token| token.kind) {
+            Some(TokenKind::Number(value)) => Literal::Number(value),
+            Some(TokenKind::Quoted(value)) => Literal::Quoted(value),
+            _ => return Err(self.error("expected literal", start)),
+        };
+        let (formatter, options) = self.parse_annotation()?;
+        if self.peek_is(&TokenKind::Arrow) {
+            return Err(self.error("literal selectors are not supported", start));
+        }
+        let end = self.expect(TokenKind::RBrace)?;
+        Ok(Expr::Literal(LiteralExpr {
+            value,
+            formatter,
+            options,
+            span: span_merge(start, end.span),
+        }))
+    }
+
+    fn parse_operand(&mut self) -> Result<Operand, ParseError> {
+        let start = self.expect(TokenKind::Dollar)?.span;
+        let name = self.expect_ident()?;
+        let (formatter, options) = self.parse_annotation()?;
+        Ok((
+            name,
+            formatter,
+            options,
+            span_merge(start, self.previous_span()),
+        ))
+    }
+
+    fn parse_annotation(&mut self) -> Result<(Option<String>, Vec<FormatOption>), ParseError> {
+        let mut options = Vec::new();
+        if !self.peek_is(&TokenKind::Colon) {
+            return Ok((None, options));
+        }
+        self.next();
+        let formatter = self.expect_ident()?;
+        while self.peek_option() {
+            options.push(self.parse_option()?);
+        }
+        Ok((Some(formatter), options))
+    }
+
+    fn peek_option(&self) -> bool {
+        matches!(
+            self.peek().map(|token| &token.kind),
+            Some(TokenKind::Ident(_))
+        ) && matches!(
+            self.tokens.get(self.index + 1).map(|token| &token.kind),
+            Some(TokenKind::Equals)
+        )
+    }
+
+    fn parse_option(&mut self) -> Result<FormatOption, ParseError> {
+        let start = self.peek_span().expect("option token");
+        let option_name = self.expect_ident()?;
+        self.expect(TokenKind::Equals)?;
+        let span = self.current_span();
+        let token = self
+            .next()
+            .ok_or_else(|| self.error("unexpected eof", span))?;
+        let value = match token.kind {
+            TokenKind::Ident(value) => OptionValue::Literal(value),
+            TokenKind::Number(value) => OptionValue::Number(value),
+            TokenKind::Dollar => {
+                let end = self.peek_span().unwrap_or(token.span);
+                let name = self.expect_ident()?;
+                return Ok(FormatOption {
+                    name: option_name,
+                    value: OptionValue::Variable(name),
+                    span: span_merge(start, end),
+                });
+            }
+            _ => return Err(self.error("expected option value", token.span)),
+        };
+        Ok(FormatOption {
+            name: option_name,
+            value,
+            span: span_merge(start, token.span),
+        })
+    }
+
+    fn parse_offset(&mut self) -> Result<Option<u32>, ParseError> {
+        let is_offset = matches!(
+            self.peek().map(|token| &token.kind),
+            Some(TokenKind::Ident(value)) if value == "offset"
+        );
+        if !is_offset {
+            return Ok(None);
+        }
+        self.next();
+        self.expect(TokenKind::Colon)?;
+        let span = self.current_span();
+        let number = self.expect_number()?;
+        let value = number
+            .parse::<u32>()
+            .map_err(|_| self.error("invalid plural offset", span))?;
+        Ok(Some(value))
+    }
+
+    fn parse_cases(&mut self, selector_count: usize) -> Result<Vec<SelectCase>, ParseError> {
         let mut cases = Vec::new();
         while let Some(token) = self.peek() {
             if matches!(token.kind, TokenKind::RBrace) {
@@ -169,14 +454,20 @@ impl Parser {
             } else {
                 false
             };
-            self.expect(TokenKind::LBracket)?;
-            let key = self.parse_case_key()?;
+            let open_span = self.expect(TokenKind::LBracket)?.span;
+            let mut keys = Vec::with_capacity(selector_count);
+            while !self.peek_is(&TokenKind::RBracket) {
+                keys.push(self.parse_case_key()?);
+            }
+            if keys.len() != selector_count {
+                return Err(self.error("case key count does not match selectors", open_span));
+            }
             let key_span = self.expect(TokenKind::RBracket)?.span;
             self.expect(TokenKind::LBrace)?;
             let value = self.parse_message(true)?;
             let end_span = self.expect(TokenKind::RBrace)?.span;
             cases.push(SelectCase {
-                key,
+                keys,
                 value,
                 is_default,
                 span: span_merge(key_span, end_span),
@@ -186,12 +477,16 @@ impl Parser {
     }
 
     fn parse_case_key(&mut self) -> Result<CaseKey, ParseError> {
+        if self.peek_is(&TokenKind::Star) {
+            self.next();
+            return Ok(CaseKey::Other);
+        }
         if self.peek_is(&TokenKind::Equals) {
             self.next();
             let number = self.expect_number()?;
             let value = number
-                .parse::<u32>()
-                .map_err(|_| self.error("invalid exact number", self.peek_span().unwrap()))?;
+                .parse::<i64>()
+                .map_err(|_| self.error("invalid exact number", self.previous_span()))?;
             return Ok(CaseKey::Exact(value));
         }
         if let Some(token) = self.peek().cloned() {
@@ -203,28 +498,21 @@ impl Parser {
                     }
                     return Ok(CaseKey::Ident(value));
                 }
-                TokenKind::Number(value) => {
+                TokenKind::Number(value) | TokenKind::Quoted(value) => {
                     self.next();
                     return Ok(CaseKey::Ident(value));
                 }
                 _ => {}
             }
         }
-        Err(self.error("expected case key", self.peek_span().unwrap()))
+        Err(self.error("expected case key", self.current_span()))
     }
 
     fn expect(&mut self, kind: TokenKind) -> Result<Token, ParseError> {
-        let token = self.next().ok_or_else(|| {
-            self.error(
-                "unexpected eof",
-                Span {
-                    start: 0,
-                    end: 0,
-                    line: 1,
-                    column: 1,
-                },
-            )
-        })?;
+        let span = self.current_span();
+        let token = self
+            .next()
+            .ok_or_else(|| self.error("unexpected eof", span))?;
         if token.kind == kind {
             Ok(token)
         } else {
@@ -233,17 +521,10 @@ impl Parser {
     }
 
     fn expect_ident(&mut self) -> Result<String, ParseError> {
-        let token = self.next().ok_or_else(|| {
-            self.error(
-                "unexpected eof",
-                Span {
-                    start: 0,
-                    end: 0,
-                    line: 1,
-                    column: 1,
-                },
-            )
-        })?;
+        let span = self.current_span();
+        let token = self
+            .next()
+            .ok_or_else(|| self.error("unexpected eof", span))?;
         match token.kind {
             TokenKind::Ident(value) => Ok(value),
             _ => Err(self.error("expected identifier", token.span)),
@@ -251,17 +532,10 @@ impl Parser {
     }
 
     fn expect_number(&mut self) -> Result<String, ParseError> {
-        let token = self.next().ok_or_else(|| {
-            self.error(
-                "unexpected eof",
-                Span {
-                    start: 0,
-                    end: 0,
-                    line: 1,
-                    column: 1,
-                },
-            )
-        })?;
+        let span = self.current_span();
+        let token = self
+            .next()
+            .ok_or_else(|| self.error("unexpected eof", span))?;
         match token.kind {
             TokenKind::Number(value) => Ok(value),
             _ => Err(self.error("expected number", token.span)),
@@ -290,6 +564,29 @@ impl Parser {
         self.peek().map(|token| token.span.clone())
     }
 
+    fn current_span(&self) -> Span {
+        self.peek()
+            .or_else(|| self.tokens.last())
+            .map(|token| token.span.clone())
+            .unwrap_or(Span {
+                start: 0,
+                end: 0,
+                line: 1,
+                column: 1,
+            })
+    }
+
+    fn previous_span(&self) -> Span {
+        match self
+            .index
+            .checked_sub(1)
+            .and_then(|idx| self.tokens.get(idx))
+        {
+            Some(token) => token.span.clone(),
+            None => self.current_span(),
+        }
+    }
+
     fn error(&self, message: &str, span: Span) -> ParseError {
         ParseError {
             message: message.to_string(),
@@ -298,6 +595,175 @@ impl Parser {
     }
 }
 
+pub fn resolve_selector_kinds(message: &mut Message, args: &[ArgSpec]) {
+    for segment in &mut message.segments {
+        let Segment::Expr(Expr::Select(select)) = segment else {
+            continue;
+        };
+        for case in &mut select.cases {
+            resolve_selector_kinds(&mut case.value, args);
+        }
+        let mut changed = false;
+        let mut reverted = false;
+        for selector in &mut select.selectors {
+            let arg_type = args
+                .iter()
+                .find(|arg| arg.name == selector.name)
+                .map(|arg| &arg.arg_type);
+            if selector.formatter.is_some() {
+                continue;
+            }
+            match (&selector.kind, arg_type) {
+                (SelectKind::Select, Some(ArgType::Number)) => {
+                    selector.kind = SelectKind::Plural;
+                    changed = true;
+                }
+                (SelectKind::Plural, Some(ArgType::String)) if select.offset.is_none() => {
+                    selector.kind = SelectKind::Select;
+                    reverted = true;
+                }
+                _ => {}
+            }
+        }
+        if changed {
+            for case in &mut select.cases {
+                split_pound(&mut case.value);
+            }
+        } else if reverted && !select.is_plural() {
+            for case in &mut select.cases {
+                join_pound(&mut case.value);
+            }
+        }
+    }
+}
+
+fn uses_pound_with_plural_keys(cases: &[SelectCase]) -> bool {
+    let mut saw_category = false;
+    for key in cases.iter().flat_map(|case| &case.keys) {
+        match key {
+            CaseKey::Ident(value)
+                if matches!(value.as_str(), "zero" | "one" | "two" | "few" | "many") =>
+            {
+                saw_category = true;
+            }
+            CaseKey::Ident(_) => return false,
+            CaseKey::Exact(_) | CaseKey::Other => {}
+        }
+    }
+    saw_category
+        && cases.iter().any(|case| {
+            case.value.segments.iter().any(
+                |segment| matches!(segment, Segment::Text { value, .. } if value.contains('#')),
+            )
+        })
+}
+
+fn split_pound(message: &mut Message) {
+    let mut segments = Vec::with_capacity(message.segments.len());
+    for segment in message.segments.drain(..) {
+        match segment {
+            Segment::Text { value, span } if value.contains('#') => {
+                split_text_on_pound(&value, &span, &mut segments);
+            }
+            Segment::Expr(Expr::Select(mut select)) if !select.is_plural() => {
+                for case in &mut select.cases {
+                    split_pound(&mut case.value);
+                }
+                segments.push(Segment::Expr(Expr::Select(select)));
+            }
+            other => segments.push(other),
+        }
+    }
+    message.segments = segments;
+}
+
+fn join_pound(message: &mut Message) {
+    let mut segments: Vec<Segment> = Vec::with_capacity(message.segments.len());
+    for segment in message.segments.drain(..) {
+        let segment = match segment {
+            Segment::Pound { span } => Segment::Text {
+                value: "#".to_string(),
+                span,
+            },
+            Segment::Expr(Expr::Select(mut select)) if !select.is_plural() => {
+                for case in &mut select.cases {
+                    join_pound(&mut case.value);
+                }
+                Segment::Expr(Expr::Select(select))
+            }
+            other => other,
+        };
+        if let (
+            Some(Segment::Text {
+                value: previous,
+                span: previous_span,
+            }),
+            Segment::Text { value, span },
+        ) = (segments.last_mut(), &segment)
+            && previous_span.end == span.start
+        {
+            previous.push_str(value);
+            previous_span.end = span.end;
+            continue;
+        }
+        segments.push(segment);
+    }
+    message.segments = segments;
+}
+
+fn split_text_on_pound(value: &str, span: &Span, segments: &mut Vec<Segment>) {
+    let mut line = span.line;
+    let mut column = span.column;
+    let mut piece_start = 0usize;
+    let mut piece_line = line;
+    let mut piece_column = column;
+    for (idx, ch) in value.char_indices() {
+        if ch == '#' {
+            if idx > piece_start {
+                segments.push(Segment::Text {
+                    value: value[piece_start..idx].to_string(),
+                    span: Span {
+                        start: span.start + piece_start,
+                        end: span.start + idx,
+                        line: piece_line,
+                        column: piece_column,
+                    },
+                });
+            }
+            segments.push(Segment::Pound {
+                span: Span {
+                    start: span.start + idx,
+                    end: span.start + idx + 1,
+                    line,
+                    column,
+                },
+            });
+        }
+        if ch == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+        if ch == '#' {
+            piece_start = idx + 1;
+            piece_line = line;
+            piece_column = column;
+        }
+    }
+    if piece_start < value.len() {
+        segments.push(Segment::Text {
+            value: value[piece_start..].to_string(),
+            span: Span {
+                start: span.start + piece_start,
+                end: span.end,
+                line: piece_line,
+                column: piece_column,
+            },
+        });
+    }
+}
+
 fn span_merge(start: Span, end: Span) -> Span {
     Span {
         start: start.start,
@@ -309,7 +775,11 @@ fn span_merge(start: Span, end: Span) -> Span {
 
 #[cfg(test)]
 mod tests {
-    use super::{CaseKey, Expr, Segment, SelectKind, parse_message};
+    use super::{
+        CaseKey, DeclarationKind, Expr, Literal, LiteralExpr, MarkupKind, OptionValue, Segment,
+        SelectKind, parse_message, resolve_selector_kinds,
+    };
+    use crate::model::{ArgSpec, ArgType};
 
     #[test]
     fn parses_variable_expression() {
@@ -324,6 +794,73 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_leading_declarations() {
+        let input = ".input { $count :number }\n.local $label = { |items| }\nYou have { $count } { $label }";
+        let message = parse_message(input).expect("parse");
+        let kinds: Vec<(DeclarationKind, &str)> = message
+            .declarations
+            .iter()
+            .map(|declaration| (declaration.kind, declaration.name.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (DeclarationKind::Input, "count"),
+                (DeclarationKind::Local, "label")
+            ]
+        );
+        assert_eq!(message.declarations[1].span.line, 2);
+        assert!(matches!(
+            &message.segments[0],
+            Segment::Text { value, .. } if value == "You have "
+        ));
+        assert!(parse_message(".local $x = { #b }").is_err());
+        assert!(parse_message(".input { 3 }").is_err());
+
+        let plain = parse_message("  .localized text").expect("parse");
+        assert!(plain.declarations.is_empty());
+        assert!(matches!(
+            &plain.segments[0],
+            Segment::Text { value, .. } if value == "  .localized text"
+        ));
+    }
+
+    #[test]
+    fn parses_escaped_text() {
+        let message = parse_message(r"Hello \{world\} \\ { $name }").expect("parse");
+        assert!(matches!(
+            &message.segments[0],
+            Segment::Text { value, .. } if value == r"Hello {world} \ "
+        ));
+        assert!(matches!(
+            &message.segments[1],
+            Segment::Expr(Expr::Variable(expr)) if expr.name == "name"
+        ));
+    }
+
+    #[test]
+    fn parses_literal_operands() {
+        let message = parse_message("{ 42 :number } { |2024-01-01| :date }").expect("parse");
+        assert!(matches!(
+            &message.segments[0],
+            Segment::Expr(Expr::Literal(LiteralExpr {
+                value: Literal::Number(value),
+                formatter: Some(formatter),
+                ..
+            })) if value == "42" && formatter == "number"
+        ));
+        assert!(matches!(
+            &message.segments[2],
+            Segment::Expr(Expr::Literal(LiteralExpr {
+                value: Literal::Quoted(value),
+                ..
+            })) if value == "2024-01-01"
+        ));
+        let err = parse_message("{ |a| -> *[other] {x} }").expect_err("selector");
+        assert_eq!(err.message, "literal selectors are not supported");
+    }
+
     #[test]
     fn parses_formatter_call() {
         let message = parse_message("{ $value :number }").expect("parse");
@@ -335,17 +872,211 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_formatter_options() {
+        let message = parse_message("{ $value :number maximumFractionDigits=2 useGrouping=false }")
+            .expect("parse");
+        match &message.segments[0] {
+            Segment::Expr(Expr::Variable(expr)) => {
+                let options: Vec<(&str, &OptionValue)> = expr
+                    .options
+                    .iter()
+                    .map(|option| (option.name.as_str(), &option.value))
+                    .collect();
+                assert_eq!(
+                    options,
+                    vec![
+                        (
+                            "maximumFractionDigits",
+                            &OptionValue::Number("2".to_string())
+                        ),
+                        ("useGrouping", &OptionValue::Literal("false".to_string())),
+                    ]
+                );
+            }
+            _ => panic!("expected variable expr"),
+        }
+        let err = parse_message("{ $value :number style= }").expect_err("missing value");
+        assert_eq!(err.message, "expected option value");
+    }
+
+    #[test]
+    fn parses_markup_placeholders() {
+        let message = parse_message("{#link href=$url}click{/link}{#br/}").expect("parse");
+        assert_eq!(message.segments.len(), 4);
+        match &message.segments[0] {
+            Segment::Expr(Expr::Markup(markup)) => {
+                assert_eq!(markup.kind, MarkupKind::Open);
+                assert_eq!(markup.name, "link");
+                assert_eq!(markup.options[0].name, "href");
+                assert_eq!(
+                    markup.options[0].value,
+                    OptionValue::Variable("url".to_string())
+                );
+            }
+            _ => panic!("expected markup"),
+        }
+        match &message.segments[2] {
+            Segment::Expr(Expr::Markup(markup)) => {
+                assert_eq!(markup.kind, MarkupKind::Close);
+                assert_eq!(markup.name, "link");
+            }
+            _ => panic!("expected markup"),
+        }
+        match &message.segments[3] {
+            Segment::Expr(Expr::Markup(markup)) => {
+                assert_eq!(markup.kind, MarkupKind::Standalone);
+                assert_eq!(markup.name, "br");
+            }
+            _ => panic!("expected markup"),
+        }
+    }
+
     #[test]
     fn parses_select_cases() {
         let message = parse_message("{ $count -> [one] {1} *[other] {n} }").expect("parse");
         match &message.segments[0] {
             Segment::Expr(Expr::Select(expr)) => {
-                assert_eq!(expr.kind, SelectKind::Select);
+                assert_eq!(expr.selectors[0].kind, SelectKind::Select);
                 assert_eq!(expr.cases.len(), 2);
-                assert!(matches!(expr.cases[0].key, CaseKey::Ident(_)));
+                assert!(matches!(expr.cases[0].keys[0], CaseKey::Ident(_)));
                 assert!(expr.cases[1].is_default);
             }
             _ => panic!("expected select expr"),
         }
     }
+
+    #[test]
+    fn parses_ordinal_select() {
+        let message = parse_message("{ $place :ordinal -> [=11] {th} [one] {st} *[other] {th} }")
+            .expect("parse");
+        match &message.segments[0] {
+            Segment::Expr(Expr::Select(expr)) => {
+                assert_eq!(expr.selectors[0].kind, SelectKind::Ordinal);
+            }
+            _ => panic!("expected select expr"),
+        }
+    }
+
+    #[test]
+    fn numeric_looking_keys_keep_string_selects() {
+        let input = "{ $code :string -> [|0|] {zero} [=1] {one} *[other] {# many} }";
+        let mut message = parse_message(input).expect("parse");
+        let args = vec![ArgSpec {
+            name: "code".to_string(),
+            arg_type: ArgType::Number,
+            required: true,
+        }];
+        resolve_selector_kinds(&mut message, &args);
+        let Segment::Expr(Expr::Select(expr)) = &message.segments[0] else {
+            panic!("expected select expr");
+        };
+        assert_eq!(expr.selectors[0].kind, SelectKind::Select);
+        assert_eq!(expr.cases[0].keys[0], CaseKey::Ident("0".to_string()));
+        assert_eq!(expr.cases[2].value.segments.len(), 1);
+
+        let mut message =
+            parse_message("{ $code -> [=1] {one} *[other] {# many} }").expect("parse");
+        let Segment::Expr(Expr::Select(expr)) = &message.segments[0] else {
+            panic!("expected select expr");
+        };
+        assert_eq!(expr.selectors[0].kind, SelectKind::Select);
+        resolve_selector_kinds(&mut message, &args);
+        let Segment::Expr(Expr::Select(expr)) = &message.segments[0] else {
+            panic!("expected select expr");
+        };
+        assert_eq!(expr.selectors[0].kind, SelectKind::Plural);
+        assert!(matches!(
+            expr.cases[1].value.segments[0],
+            Segment::Pound { .. }
+        ));
+    }
+
+    #[test]
+    fn declared_string_args_keep_pound_literal() {
+        let mut message =
+            parse_message("{ $tag -> [one] {#1 pick} *[other] {x#y} }").expect("parse");
+        let Segment::Expr(Expr::Select(expr)) = &message.segments[0] else {
+            panic!("expected select expr");
+        };
+        assert_eq!(expr.selectors[0].kind, SelectKind::Plural);
+        let args = vec![ArgSpec {
+            name: "tag".to_string(),
+            arg_type: ArgType::String,
+            required: true,
+        }];
+        resolve_selector_kinds(&mut message, &args);
+        let Segment::Expr(Expr::Select(expr)) = &message.segments[0] else {
+            panic!("expected select expr");
+        };
+        assert_eq!(expr.selectors[0].kind, SelectKind::Select);
+        for (case, expected) in expr.cases.iter().zip(["#1 pick", "x#y"]) {
+            match &case.value.segments[..] {
+                [Segment::Text { value, .. }] => assert_eq!(value, expected),
+                other => panic!("expected text, got {other:?}"),
+            }
+        }
+
+        let mut message =
+            parse_message("{ $tag :plural -> [one] {# pick} *[other] {# picks} }").expect("parse");
+        resolve_selector_kinds(&mut message, &args);
+        let Segment::Expr(Expr::Select(expr)) = &message.segments[0] else {
+            panic!("expected select expr");
+        };
+        assert_eq!(expr.selectors[0].kind, SelectKind::Plural);
+    }
+
+    #[test]
+    fn parses_plural_offset_and_pound() {
+        let message =
+            parse_message("{ $count -> offset:1 [=0] {you} *[other] {you and # others} }")
+                .expect("parse");
+        match &message.segments[0] {
+            Segment::Expr(Expr::Select(expr)) => {
+                assert_eq!(expr.selectors[0].kind, SelectKind::Plural);
+                assert_eq!(expr.offset, Some(1));
+                let other = &expr.cases[1].value.segments;
+                assert_eq!(other.len(), 3);
+                assert!(matches!(other[1], Segment::Pound { .. }));
+                match &other[2] {
+                    Segment::Text { value, .. } => assert_eq!(value, " others"),
+                    _ => panic!("expected text"),
+                }
+            }
+            _ => panic!("expected select expr"),
+        }
+    }
+
+    #[test]
+    fn parses_multi_selector_cases() {
+        let message = parse_message(
+            "{ $count :plural $gender -> [one female] {a} [* male] {b} *[other other] {c} }",
+        )
+        .expect("parse");
+        match &message.segments[0] {
+            Segment::Expr(Expr::Select(expr)) => {
+                assert_eq!(expr.selectors.len(), 2);
+                assert_eq!(expr.selectors[0].name, "count");
+                assert_eq!(expr.selectors[0].kind, SelectKind::Plural);
+                assert_eq!(expr.selectors[1].kind, SelectKind::Select);
+                assert_eq!(expr.cases[1].keys[0], CaseKey::Other);
+                assert_eq!(expr.cases[1].keys[1], CaseKey::Ident("male".to_string()));
+                assert!(expr.cases[2].is_default);
+            }
+            _ => panic!("expected select expr"),
+        }
+    }
+
+    #[test]
+    fn rejects_mismatched_case_key_count() {
+        let err = parse_message("{ $a $b -> [x] {a} *[other other] {b} }").expect_err("error");
+        assert_eq!(err.message, "case key count does not match selectors");
+    }
+
+    #[test]
+    fn keeps_pound_literal_outside_plurals() {
+        let message = parse_message("Item #1").expect("parse");
+        assert_eq!(message.segments.len(), 1);
+        assert!(matches!(message.segments[0], Segment::Text { .. }));
+    }
 }
@@ -1,26 +1,189 @@
+use std::collections::BTreeMap;
+
 use crate::diagnostic::Diagnostic;
-use crate::model::{ArgType, MessageSpec};
-use crate::parser::{CaseKey, Expr, Message, Segment, SelectExpr, SelectKind, VarExpr};
+use crate::model::{ArgSpec, ArgType, MessageSpec};
+use crate::parser::{
+    CaseKey, Declaration, DeclarationKind, Expr, FormatOption, Literal, LiteralExpr, MarkupExpr,
+    MarkupKind, Message, OptionValue, Segment, SelectExpr, SelectKind, Selector, VarExpr,
+};
+
+type AllowedOptions = BTreeMap<String, Vec<String>>;
 
-pub fn validate_message(message: &Message, spec: &MessageSpec) -> Vec<Diagnostic> {
+pub fn validate_message(
+    message: &Message,
+    spec: &MessageSpec,
+    allowed: &AllowedOptions,
+) -> Vec<Diagnostic> {
     let mut diagnostics = Vec::new();
-    validate_segments(&message.segments, spec, &mut diagnostics);
+    let scope = validate_declarations(&message.declarations, spec, allowed, &mut diagnostics);
+    validate_segments(&message.segments, &scope, allowed, None, &mut diagnostics);
     diagnostics
 }
 
-fn validate_segments(segments: &[Segment], spec: &MessageSpec, diagnostics: &mut Vec<Diagnostic>) {
+fn validate_declarations(
+    declarations: &[Declaration],
+    spec: &MessageSpec,
+    allowed: &AllowedOptions,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> MessageSpec {
+    let mut scope = spec.clone();
+    for (idx, declaration) in declarations.iter().enumerate() {
+        let span = &declaration.span;
+        let forward = referenced_names(&declaration.value)
+            .into_iter()
+            .any(|name| {
+                declarations[idx + 1..]
+                    .iter()
+                    .any(|later| &later.name == name)
+            });
+        if forward {
+            diagnostics.push(
+                Diagnostic::new("MF2E050", "reference to a later declaration").with_span(
+                    spec.key.clone(),
+                    span.line,
+                    span.column,
+                ),
+            );
+        } else {
+            match &declaration.value {
+                Expr::Variable(var) => validate_var(var, &scope, allowed, diagnostics),
+                Expr::Literal(literal) => validate_literal(literal, &scope, allowed, diagnostics),
+                Expr::Select(_) | Expr::Markup(_) => {}
+            }
+        }
+
+        let is_local = declaration.kind == DeclarationKind::Local;
+        let shadows = declarations[..idx]
+            .iter()
+            .any(|earlier| earlier.name == declaration.name)
+            || (is_local && spec.args.iter().any(|arg| arg.name == declaration.name));
+        if shadows {
+            diagnostics.push(
+                Diagnostic::new("MF2E051", "declaration shadows an existing variable").with_span(
+                    spec.key.clone(),
+                    span.line,
+                    span.column,
+                ),
+            );
+        } else if is_local {
+            let arg_type = match &declaration.value {
+                Expr::Literal(literal) => literal_type(literal),
+                _ => referenced_names(&declaration.value)
+                    .first()
+                    .and_then(|name| scope.args.iter().find(|arg| arg.name == **name))
+                    .map_or(ArgType::Any, |arg| arg.arg_type.clone()),
+            };
+            scope.args.push(ArgSpec {
+                name: declaration.name.clone(),
+                arg_type,
+                required: true,
+            });
+        }
+    }
+    scope
+}
+
+fn referenced_names(expr: &Expr) -> Vec<&String> {
+    let (name, options) = match expr {
+        Expr::Variable(var) => (Some(&var.name), &var.options),
+        Expr::Literal(literal) => (None, &literal.options),
+        Expr::Select(_) | Expr::Markup(_) => return Vec::new(),
+    };
+    name.into_iter()
+        .chain(options.iter().filter_map(|option| match &option.value {
+            OptionValue::Variable(name) => Some(name),
+            OptionValue::Literal(_) | OptionValue::Number(_) => None,
+        }))
+        .collect()
+}
+
+fn validate_segments(
+    segments: &[Segment],
+    spec: &MessageSpec,
+    allowed: &AllowedOptions,
+    plural_numeric: Option<bool>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let mut open_markup: Vec<&MarkupExpr> = Vec::new();
     for segment in segments {
         match segment {
             Segment::Text { .. } => {}
+            Segment::Pound { span } => {
+                if plural_numeric == Some(false) {
+                    diagnostics.push(
+                        Diagnostic::new("MF2E011", "# used with a non-numeric plural selector")
+                            .with_span(spec.key.clone(), span.line, span.column),
+                    );
+                }
+            }
             Segment::Expr(expr) => match expr {
-                Expr::Variable(var) => validate_var(var, spec, diagnostics),
-                Expr::Select(select) => validate_select(select, spec, diagnostics),
+                Expr::Variable(var) => validate_var(var, spec, allowed, diagnostics),
+                Expr::Literal(literal) => validate_literal(literal, spec, allowed, diagnostics),
+                Expr::Select(select) => {
+                    validate_select(select, spec, allowed, plural_numeric, diagnostics)
+                }
+                Expr::Markup(markup) => {
+                    validate_markup(markup, spec, diagnostics);
+                    track_markup(markup, &mut open_markup, spec, diagnostics);
+                }
             },
         }
     }
+    for markup in open_markup {
+        diagnostics.push(Diagnostic::new("MF2E041", "unclosed markup").with_span(
+            spec.key.clone(),
+            markup.span.line,
+            markup.span.column,
+        ));
+    }
 }
 
-fn validate_var(var: &VarExpr, spec: &MessageSpec, diagnostics: &mut Vec<Diagnostic>) {
+fn track_markup<'a>(
+    markup: &'a MarkupExpr,
+    open_markup: &mut Vec<&'a MarkupExpr>,
+    spec: &MessageSpec,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    match markup.kind {
+        MarkupKind::Open => open_markup.push(markup),
+        MarkupKind::Standalone => {}
+        MarkupKind::Close => {
+            if open_markup.last().map(|open| &open.name) == Some(&markup.name) {
+                open_markup.pop();
+                return;
+            }
+            diagnostics.push(
+                Diagnostic::new("MF2E040", "unbalanced closing markup").with_span(
+                    spec.key.clone(),
+                    markup.span.line,
+                    markup.span.column,
+                ),
+            );
+            if let Some(idx) = open_markup
+                .iter()
+                .rposition(|open| open.name == markup.name)
+            {
+                open_markup.remove(idx);
+            }
+        }
+    }
+}
+
+fn validate_var(
+    var: &VarExpr,
+    spec: &MessageSpec,
+    allowed: &AllowedOptions,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    if var.options.len() > u8::MAX as usize {
+        diagnostics.push(
+            Diagnostic::new("MF2E031", "too many formatter options").with_span(
+                spec.key.clone(),
+                var.span.line,
+                var.span.column,
+            ),
+        );
+    }
     if let Some(arg) = spec.args.iter().find(|arg| arg.name == var.name) {
         if let Some(formatter) = &var.formatter {
             if !is_known_formatter(formatter) {
@@ -46,13 +209,136 @@ fn validate_var(var: &VarExpr, spec: &MessageSpec, diagnostics: &mut Vec<Diagnos
             var.span.column,
         ));
     }
+    validate_options(&var.options, spec, diagnostics);
+    validate_option_keys(
+        &var.options,
+        var.formatter.as_deref(),
+        spec,
+        allowed,
+        diagnostics,
+    );
+}
+
+fn validate_literal(
+    literal: &LiteralExpr,
+    spec: &MessageSpec,
+    allowed: &AllowedOptions,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    if literal.options.len() > u8::MAX as usize {
+        diagnostics.push(
+            Diagnostic::new("MF2E031", "too many formatter options").with_span(
+                spec.key.clone(),
+                literal.span.line,
+                literal.span.column,
+            ),
+        );
+    }
+    if let Some(formatter) = &literal.formatter {
+        let literal_type = literal_type(literal);
+        if !is_known_formatter(formatter) {
+            diagnostics.push(Diagnostic::new("MF2E030", "unknown formatter").with_span(
+                spec.key.clone(),
+                literal.span.line,
+                literal.span.column,
+            ));
+        } else if !formatter_accepts_arg(formatter, &literal_type) {
+            diagnostics.push(
+                Diagnostic::new("MF2E022", "literal type mismatch").with_span(
+                    spec.key.clone(),
+                    literal.span.line,
+                    literal.span.column,
+                ),
+            );
+        }
+    }
+    validate_options(&literal.options, spec, diagnostics);
+    validate_option_keys(
+        &literal.options,
+        literal.formatter.as_deref(),
+        spec,
+        allowed,
+        diagnostics,
+    );
+}
+
+fn literal_type(literal: &LiteralExpr) -> ArgType {
+    match &literal.value {
+        Literal::Number(_) => ArgType::Number,
+        Literal::Quoted(raw)
+            if literal.formatter.as_deref() == Some("number") && raw.parse::<f64>().is_ok() =>
+        {
+            ArgType::Number
+        }
+        Literal::Quoted(_) => ArgType::String,
+    }
+}
+
+fn validate_markup(markup: &MarkupExpr, spec: &MessageSpec, diagnostics: &mut Vec<Diagnostic>) {
+    if markup.options.len() > u8::MAX as usize {
+        diagnostics.push(
+            Diagnostic::new("MF2E031", "too many formatter options").with_span(
+                spec.key.clone(),
+                markup.span.line,
+                markup.span.column,
+            ),
+        );
+    }
+    validate_options(&markup.options, spec, diagnostics);
 }
 
-fn validate_select(select: &SelectExpr, spec: &MessageSpec, diagnostics: &mut Vec<Diagnostic>) {
+fn validate_options(
+    options: &[FormatOption],
+    spec: &MessageSpec,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for option in options {
+        if let OptionValue::Variable(name) = &option.value
+            && !spec.args.iter().any(|arg| &arg.name == name)
+        {
+            diagnostics.push(Diagnostic::new("MF2E020", "unknown variable").with_span(
+                spec.key.clone(),
+                option.span.line,
+                option.span.column,
+            ));
+        }
+    }
+}
+
+fn validate_option_keys(
+    options: &[FormatOption],
+    formatter: Option<&str>,
+    spec: &MessageSpec,
+    allowed: &AllowedOptions,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let Some((formatter, keys)) = formatter.and_then(|name| allowed.get_key_value(name)) else {
+        return;
+    };
+    for option in options {
+        if !keys.contains(&option.name) {
+            diagnostics.push(
+                Diagnostic::new(
+                    "MF2E032",
+                    format!("unknown option {} for :{formatter}", option.name),
+                )
+                .with_span(spec.key.clone(), option.span.line, option.span.column),
+            );
+        }
+    }
+}
+
+fn validate_select(
+    select: &SelectExpr,
+    spec: &MessageSpec,
+    allowed: &AllowedOptions,
+    plural_numeric: Option<bool>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
     let has_other = select
         .cases
         .iter()
-        .any(|case| matches!(case.key, CaseKey::Other) || case.is_default);
+        .any(|case| case.is_default || case.keys.iter().all(|key| *key == CaseKey::Other));
     if !has_other {
         diagnostics.push(
             Diagnostic::new("MF2E010", "missing required other case").with_span(
@@ -62,33 +348,75 @@ fn validate_select(select: &SelectExpr, spec: &MessageSpec, diagnostics: &mut Ve
             ),
         );
     }
-    if let Some(arg) = spec.args.iter().find(|arg| arg.name == select.selector) {
-        let required = match select.kind {
-            SelectKind::Select => ArgType::String,
-            SelectKind::Plural => ArgType::Number,
-        };
-        if arg.arg_type != ArgType::Any && arg.arg_type != required {
+    for selector in &select.selectors {
+        if let Some(arg) = spec.args.iter().find(|arg| arg.name == selector.name) {
+            let required = match selector.kind {
+                SelectKind::Select => ArgType::String,
+                SelectKind::Plural | SelectKind::Ordinal => ArgType::Number,
+            };
+            if arg.arg_type != ArgType::Any && arg.arg_type != required {
+                diagnostics.push(
+                    Diagnostic::new("MF2E021", "variable type mismatch").with_span(
+                        spec.key.clone(),
+                        selector.span.line,
+                        selector.span.column,
+                    ),
+                );
+            }
+        } else {
+            diagnostics.push(Diagnostic::new("MF2E020", "unknown variable").with_span(
+                spec.key.clone(),
+                selector.span.line,
+                selector.span.column,
+            ));
+        }
+    }
+
+    for case in &select.cases {
+        let exact_under_string = case
+            .keys
+            .iter()
+            .zip(&select.selectors)
+            .any(|(key, selector)| {
+                matches!(key, CaseKey::Exact(_)) && selector.kind == SelectKind::Select
+            });
+        if exact_under_string {
             diagnostics.push(
-                Diagnostic::new("MF2E021", "variable type mismatch").with_span(
+                Diagnostic::new("MF2E012", "exact numeric key under a string selector").with_span(
                     spec.key.clone(),
-                    select.span.line,
-                    select.span.column,
+                    case.span.line,
+                    case.span.column,
                 ),
             );
         }
-    } else {
-        diagnostics.push(Diagnostic::new("MF2E020", "unknown variable").with_span(
-            spec.key.clone(),
-            select.span.line,
-            select.span.column,
-        ));
     }
 
+    let case_numeric = match select
+        .selectors
+        .iter()
+        .find(|selector| selector.kind != SelectKind::Select)
+    {
+        Some(selector) => Some(is_numeric_selector(selector, spec)),
+        None => plural_numeric,
+    };
     for case in &select.cases {
-        validate_segments(&case.value.segments, spec, diagnostics);
+        validate_segments(
+            &case.value.segments,
+            spec,
+            allowed,
+            case_numeric,
+            diagnostics,
+        );
     }
 }
 
+fn is_numeric_selector(selector: &Selector, spec: &MessageSpec) -> bool {
+    spec.args
+        .iter()
+        .find(|arg| arg.name == selector.name)
+        .is_none_or(|arg| matches!(arg.arg_type, ArgType::Number | ArgType::Any))
+}
+
 fn is_known_formatter(name: &str) -> bool {
     matches!(
         name,
@@ -109,10 +437,14 @@ fn formatter_accepts_arg(formatter: &str, arg_type: &ArgType) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::{ArgType, MessageSpec, validate_message};
-    use crate::model::ArgSpec;
+    use super::{AllowedOptions, ArgSpec, ArgType, Message, MessageSpec};
+    use crate::diagnostic::Diagnostic;
     use crate::parser::parse_message;
 
+    fn validate_message(message: &Message, spec: &MessageSpec) -> Vec<Diagnostic> {
+        super::validate_message(message, spec, &AllowedOptions::new())
+    }
+
     fn spec(args: Vec<ArgSpec>) -> MessageSpec {
         MessageSpec {
             key: "test".to_string(),
@@ -127,6 +459,118 @@ mod tests {
         assert!(diagnostics.iter().any(|d| d.code == "MF2E020"));
     }
 
+    #[test]
+    fn checks_literal_operands_against_formatters() {
+        let message = parse_message("{ 42 :number } { |12| :number }").expect("parse");
+        assert!(validate_message(&message, &spec(vec![])).is_empty());
+
+        let message = parse_message("{ |soon| :date } { 3 :fancy }").expect("parse");
+        let codes: Vec<String> = validate_message(&message, &spec(vec![]))
+            .into_iter()
+            .map(|d| d.code)
+            .collect();
+        assert_eq!(codes, vec!["MF2E022", "MF2E030"]);
+    }
+
+    #[test]
+    fn checks_declaration_order_and_shadowing() {
+        let count = ArgSpec {
+            name: "count".to_string(),
+            arg_type: ArgType::Number,
+            required: true,
+        };
+        let message = parse_message(".local $n = { $count :number }\n{ $n :number } and { $n }")
+            .expect("parse");
+        assert!(validate_message(&message, &spec(vec![count.clone()])).is_empty());
+
+        let message = parse_message(
+            ".local $a = { $b }\n.local $b = { $count }\n.local $count = { 1 }\n.input { $count }\n{ $a }",
+        )
+        .expect("parse");
+        let diagnostics = validate_message(&message, &spec(vec![count]));
+        let found: Vec<(String, u32)> = diagnostics
+            .into_iter()
+            .map(|d| (d.code, d.line.unwrap_or(0)))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("MF2E050".to_string(), 1),
+                ("MF2E050".to_string(), 2),
+                ("MF2E051".to_string(), 3),
+                ("MF2E051".to_string(), 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_nested_mismatches_on_their_own_line() {
+        let args = vec![
+            ArgSpec {
+                name: "kind".to_string(),
+                arg_type: ArgType::String,
+                required: true,
+            },
+            ArgSpec {
+                name: "count".to_string(),
+                arg_type: ArgType::String,
+                required: true,
+            },
+        ];
+        let message = parse_message(
+            "Cart: { $kind\n  $count :plural ->\n  [a one] {fine}\n  *[other other] {{ $count :number } items}\n}",
+        )
+        .expect("parse");
+        let found: Vec<(String, Option<u32>, Option<u32>)> =
+            validate_message(&message, &spec(args))
+                .into_iter()
+                .map(|d| (d.code, d.line, d.column))
+                .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("MF2E021".to_string(), Some(2), Some(3)),
+                ("MF2E021".to_string(), Some(4), Some(21)),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_exact_keys_under_string_selectors() {
+        let message = parse_message("{ $code :string -> [=0] {zero} [|1|] {one} *[other] {x} }")
+            .expect("parse");
+        let diagnostics = validate_message(
+            &message,
+            &spec(vec![ArgSpec {
+                name: "code".to_string(),
+                arg_type: ArgType::String,
+                required: true,
+            }]),
+        );
+        let codes: Vec<&str> = diagnostics.iter().map(|d| d.code.as_str()).collect();
+        assert_eq!(codes, vec!["MF2E012"]);
+    }
+
+    #[test]
+    fn accepts_messages_omitting_optional_args() {
+        let args = vec![
+            ArgSpec {
+                name: "name".to_string(),
+                arg_type: ArgType::String,
+                required: false,
+            },
+            ArgSpec {
+                name: "count".to_string(),
+                arg_type: ArgType::Number,
+                required: true,
+            },
+        ];
+        let message = parse_message("{ $count :number } items").expect("parse");
+        assert!(validate_message(&message, &spec(args.clone())).is_empty());
+        let message = parse_message("Hi { $name }, { $count :number } items").expect("parse");
+        assert!(validate_message(&message, &spec(args)).is_empty());
+    }
+
     #[test]
     fn reports_missing_other_case() {
         let message = parse_message("{ $count -> [one] {1} }").expect("parse");
@@ -155,6 +599,37 @@ mod tests {
         assert!(diagnostics.iter().any(|d| d.code == "MF2E030"));
     }
 
+    #[test]
+    fn checks_option_keys_against_allow_list() {
+        let count = spec(vec![ArgSpec {
+            name: "count".to_string(),
+            arg_type: ArgType::Number,
+            required: true,
+        }]);
+        let mut allowed = AllowedOptions::new();
+        allowed.insert(
+            "number".to_string(),
+            vec!["minimumFractionDigits".to_string()],
+        );
+
+        let typo = parse_message("{ $count :number minimumFractionDgits=2 }").expect("parse");
+        let diagnostics = super::validate_message(&typo, &count, &allowed);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "MF2E032");
+        assert_eq!(
+            diagnostics[0].message,
+            "unknown option minimumFractionDgits for :number"
+        );
+        assert_eq!(diagnostics[0].column, Some(18));
+        assert!(validate_message(&typo, &count).is_empty());
+
+        let configured =
+            parse_message("{ $count :number minimumFractionDigits=2 }").expect("parse");
+        assert!(super::validate_message(&configured, &count, &allowed).is_empty());
+        let unlisted = parse_message("{ |5| :identity anything=1 }").expect("parse");
+        assert!(super::validate_message(&unlisted, &count, &allowed).is_empty());
+    }
+
     #[test]
     fn reports_type_mismatch() {
         let message = parse_message("{ $value :number }").expect("parse");
@@ -168,4 +643,113 @@ mod tests {
         );
         assert!(diagnostics.iter().any(|d| d.code == "MF2E021"));
     }
+
+    #[test]
+    fn reports_unknown_markup_option_variable() {
+        let message = parse_message("{#link href=$url}go{/link}").expect("parse");
+        let diagnostics = validate_message(&message, &spec(vec![]));
+        assert!(diagnostics.iter().any(|d| d.code == "MF2E020"));
+        let diagnostics = validate_message(
+            &message,
+            &spec(vec![ArgSpec {
+                name: "url".to_string(),
+                arg_type: ArgType::String,
+                required: true,
+            }]),
+        );
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn reports_pound_with_non_numeric_plural_selector() {
+        let message =
+            parse_message("{ $name :plural -> [one] {a} *[other] {x #} }").expect("parse");
+        let diagnostics = validate_message(
+            &message,
+            &spec(vec![ArgSpec {
+                name: "name".to_string(),
+                arg_type: ArgType::String,
+                required: true,
+            }]),
+        );
+        let pound = diagnostics
+            .iter()
+            .find(|d| d.code == "MF2E011")
+            .expect("MF2E011");
+        assert_eq!((pound.line, pound.column), (Some(1), Some(42)));
+
+        let diagnostics = validate_message(
+            &message,
+            &spec(vec![ArgSpec {
+                name: "name".to_string(),
+                arg_type: ArgType::Number,
+                required: true,
+            }]),
+        );
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn reports_unclosed_markup() {
+        let message = parse_message("a {#bold}b").expect("parse");
+        let diagnostics = validate_message(&message, &spec(vec![]));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "MF2E041");
+        assert_eq!(
+            (diagnostics[0].line, diagnostics[0].column),
+            (Some(1), Some(4))
+        );
+    }
+
+    #[test]
+    fn reports_unbalanced_closing_markup() {
+        let message = parse_message("{#b}{#i}x{/b}{/i}").expect("parse");
+        let diagnostics = validate_message(&message, &spec(vec![]));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "MF2E040");
+        assert_eq!(
+            (diagnostics[0].line, diagnostics[0].column),
+            (Some(1), Some(11))
+        );
+
+        let message = parse_message("{#b}x{/b}{#br/}").expect("parse");
+        assert!(validate_message(&message, &spec(vec![])).is_empty());
+    }
+
+    #[test]
+    fn markup_must_balance_within_each_branch() {
+        let message = parse_message("{#b}{ $kind -> [a] {x{/b}} *[other] {y} }").expect("parse");
+        let diagnostics = validate_message(
+            &message,
+            &spec(vec![ArgSpec {
+                name: "kind".to_string(),
+                arg_type: ArgType::String,
+                required: true,
+            }]),
+        );
+        let codes: Vec<&str> = diagnostics.iter().map(|d| d.code.as_str()).collect();
+        assert_eq!(codes, vec!["MF2E040", "MF2E041"]);
+    }
+
+    #[test]
+    fn reports_missing_other_case_for_multi_selectors() {
+        let message = parse_message("{ $a $b -> [x *] {1} [* y] {2} }").expect("parse");
+        let diagnostics = validate_message(
+            &message,
+            &spec(vec![
+                ArgSpec {
+                    name: "a".to_string(),
+                    arg_type: ArgType::String,
+                    required: true,
+                },
+                ArgSpec {
+                    name: "b".to_string(),
+                    arg_type: ArgType::String,
+                    required: true,
+                },
+            ]),
+        );
+        assert!(diagnostics.iter().any(|d| d.code == "MF2E010"));
+        assert!(!diagnostics.iter().any(|d| d.code == "MF2E020"));
+    }
 }
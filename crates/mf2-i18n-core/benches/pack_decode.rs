@@ -0,0 +1,46 @@
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use mf2_i18n_core::{Catalog, MessageId, PackCatalog};
+use mf2_i18n_test_support::{pack_bytes, text_program};
+
+const MESSAGE_COUNT: u64 = 5_000;
+const ID_MAP_HASH: [u8; 32] = [7u8; 32];
+
+fn large_pack() -> Vec<u8> {
+    pack_bytes(
+        ID_MAP_HASH,
+        "en",
+        None,
+        (0..MESSAGE_COUNT).map(|id| (id, text_program(&format!("message {id}")))),
+    )
+}
+
+fn pack_decode(c: &mut Criterion) {
+    let bytes = large_pack();
+    let hot = MessageId::new(MESSAGE_COUNT / 2);
+
+    c.bench_function("cold_start_eager_5k", |b| {
+        b.iter(|| {
+            let catalog = PackCatalog::decode(black_box(&bytes), &ID_MAP_HASH).expect("decode");
+            black_box(catalog.lookup(hot).is_some())
+        })
+    });
+    c.bench_function("cold_start_lazy_5k", |b| {
+        b.iter(|| {
+            let catalog =
+                PackCatalog::decode_lazy(black_box(&bytes), &ID_MAP_HASH).expect("decode");
+            black_box(catalog.lookup(hot).is_some())
+        })
+    });
+
+    let catalog = PackCatalog::decode_lazy(&bytes, &ID_MAP_HASH).expect("decode");
+    let first = catalog.lookup(hot).expect("program");
+    assert!(std::ptr::eq(first, catalog.lookup(hot).expect("program")));
+    c.bench_function("lazy_cached_lookup", |b| {
+        b.iter(|| black_box(catalog.lookup(black_box(hot))))
+    });
+}
+
+criterion_group!(benches, pack_decode);
+criterion_main!(benches);
@@ -0,0 +1,84 @@
+use std::fs;
+use std::mem::size_of;
+
+use mf2_i18n_core::{
+    BytecodeProgram, CaseEntry, CaseKey, CaseTable, Catalog, MessageId, Opcode, PackCatalog,
+};
+use mf2_i18n_test_support::pack_bytes;
+
+const MESSAGE_COUNT: u64 = 5_000;
+const ID_MAP_HASH: [u8; 32] = [7u8; 32];
+
+fn select_program(id: u64) -> BytecodeProgram {
+    let mut program = BytecodeProgram::new();
+    let aidx = program.push_arg_name("kind");
+    let key = program.string_pool.push(format!("kind{id}"));
+    let matched = program.string_pool.push(format!("matched {id}"));
+    let other = program.string_pool.push(format!("other {id}"));
+    program.opcodes = vec![
+        Opcode::Select { aidx, table: 0 },
+        Opcode::EmitText { sidx: matched },
+        Opcode::End,
+        Opcode::EmitText { sidx: other },
+        Opcode::End,
+    ];
+    program.case_tables.push(CaseTable {
+        entries: vec![
+            CaseEntry {
+                key: CaseKey::String(key),
+                target: 1,
+            },
+            CaseEntry {
+                key: CaseKey::Other,
+                target: 3,
+            },
+        ],
+    });
+    program
+}
+
+fn resident_bytes() -> Option<usize> {
+    let statm = fs::read_to_string("/proc/self/statm").ok()?;
+    let pages: usize = statm.split_whitespace().nth(1)?.parse().ok()?;
+    Some(pages * 4096)
+}
+
+fn main() {
+    let bytes = pack_bytes(
+        ID_MAP_HASH,
+        "en",
+        None,
+        (0..MESSAGE_COUNT).map(|id| (id, select_program(id))),
+    );
+
+    let before = resident_bytes();
+    let catalog = PackCatalog::decode(&bytes, &ID_MAP_HASH).expect("decode");
+    let after = resident_bytes();
+
+    let first = catalog.lookup(MessageId::new(0)).expect("program");
+    let mut entry_count = 0;
+    for table in &first.case_tables {
+        entry_count += table.entries.len();
+    }
+    for id in 1..MESSAGE_COUNT {
+        let program = catalog.lookup(MessageId::new(id)).expect("program");
+        assert!(program.case_tables.shares_storage(&first.case_tables));
+        assert!(program.string_pool.shares_storage(&first.string_pool));
+    }
+
+    let copied = MESSAGE_COUNT as usize
+        * (first.case_tables.len() * size_of::<CaseTable>() + entry_count * size_of::<CaseEntry>());
+    println!("pack: {MESSAGE_COUNT} messages, {} bytes", bytes.len());
+    println!(
+        "case tables: {} tables shared by every program",
+        first.case_tables.len()
+    );
+    println!("per-program copies would hold at least {copied} bytes of case tables");
+    match (before, after) {
+        (Some(before), Some(after)) => println!(
+            "resident growth for eager decode: {} bytes",
+            after.saturating_sub(before)
+        ),
+        _ => println!("resident size unavailable on this platform"),
+    }
+}
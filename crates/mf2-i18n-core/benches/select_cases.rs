@@ -0,0 +1,103 @@
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use mf2_i18n_core::{
+    Args, BytecodeProgram, CaseEntry, CaseKey, CaseTable, CoreError, CoreResult, FormatBackend,
+    FormatterOption, Opcode, PluralCategory, execute,
+};
+
+const CASE_COUNT: u32 = 500;
+
+struct TextOnlyBackend;
+
+impl FormatBackend for TextOnlyBackend {
+    fn plural_category(&self, _value: f64) -> CoreResult<PluralCategory> {
+        Ok(PluralCategory::Other)
+    }
+
+    fn format_number(&self, _value: f64, _options: &[FormatterOption]) -> CoreResult<String> {
+        Err(CoreError::Unsupported("number"))
+    }
+
+    fn format_date(&self, _value: i64, _options: &[FormatterOption]) -> CoreResult<String> {
+        Err(CoreError::Unsupported("date"))
+    }
+
+    fn format_time(&self, _value: i64, _options: &[FormatterOption]) -> CoreResult<String> {
+        Err(CoreError::Unsupported("time"))
+    }
+
+    fn format_datetime(&self, _value: i64, _options: &[FormatterOption]) -> CoreResult<String> {
+        Err(CoreError::Unsupported("datetime"))
+    }
+
+    fn format_unit(
+        &self,
+        _value: f64,
+        _unit_id: u32,
+        _options: &[FormatterOption],
+    ) -> CoreResult<String> {
+        Err(CoreError::Unsupported("unit"))
+    }
+
+    fn format_currency(
+        &self,
+        _value: f64,
+        _code: [u8; 3],
+        _options: &[FormatterOption],
+    ) -> CoreResult<String> {
+        Err(CoreError::Unsupported("currency"))
+    }
+}
+
+fn program() -> BytecodeProgram {
+    let mut program = BytecodeProgram::new();
+    let aidx = program.push_arg_name("country");
+    program.opcodes.push(Opcode::Select { aidx, table: 0 });
+    let mut entries = Vec::new();
+    for index in 0..CASE_COUNT {
+        let key = program.string_pool.push(format!("c{index:03}"));
+        let text = program.string_pool.push(format!("country {index}"));
+        entries.push(CaseEntry {
+            key: CaseKey::String(key),
+            target: program.opcodes.len() as u32,
+        });
+        program.opcodes.push(Opcode::EmitText { sidx: text });
+        program.opcodes.push(Opcode::End);
+    }
+    let other = program.string_pool.push("elsewhere");
+    entries.push(CaseEntry {
+        key: CaseKey::Other,
+        target: program.opcodes.len() as u32,
+    });
+    program.opcodes.push(Opcode::EmitText { sidx: other });
+    program.opcodes.push(Opcode::End);
+    let mut table = CaseTable { entries };
+    table.sort_entries(&program.string_pool);
+    program.case_tables.push(table);
+    program
+}
+
+fn select_cases(c: &mut Criterion) {
+    let program = program();
+    let backend = TextOnlyBackend;
+    let args: Vec<Args> = ["c007", "c250", "c499", "nowhere"]
+        .into_iter()
+        .map(|country| {
+            let mut args = Args::new();
+            args.with_str("country", country);
+            args
+        })
+        .collect();
+
+    c.bench_function("select_500_cases", |b| {
+        b.iter(|| {
+            for args in &args {
+                black_box(execute(&program, black_box(args), &backend).expect("execute"));
+            }
+        })
+    });
+}
+
+criterion_group!(benches, select_cases);
+criterion_main!(benches);
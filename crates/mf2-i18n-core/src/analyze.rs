@@ -0,0 +1,251 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{ArgType, Args, BytecodeProgram, FormatterId, Opcode};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArgRequirement {
+    pub name: String,
+    pub arg_type: ArgType,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MessageRequirements {
+    pub args: Vec<ArgRequirement>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ArgProblem {
+    Missing(String),
+    TypeMismatch { name: String, expected: ArgType },
+}
+
+impl MessageRequirements {
+    pub fn get(&self, name: &str) -> Option<ArgType> {
+        self.args
+            .iter()
+            .find(|arg| arg.name == name)
+            .map(|arg| arg.arg_type)
+    }
+
+    pub fn check(&self, args: &Args) -> Vec<ArgProblem> {
+        let mut problems = Vec::new();
+        for arg in &self.args {
+            match args.get(&arg.name) {
+                None => problems.push(ArgProblem::Missing(arg.name.clone())),
+                Some(value) if !arg.arg_type.matches(value) => {
+                    problems.push(ArgProblem::TypeMismatch {
+                        name: arg.name.clone(),
+                        expected: arg.arg_type,
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+        problems
+    }
+}
+
+pub fn analyze(program: &BytecodeProgram) -> MessageRequirements {
+    let mut types: Vec<Option<ArgType>> = alloc::vec![None; program.arg_names.len()];
+    let mut stack: Vec<Option<u32>> = Vec::new();
+
+    for opcode in &program.opcodes {
+        match *opcode {
+            Opcode::PushStr { .. } | Opcode::PushNum { .. } => stack.push(None),
+            Opcode::PushArg { aidx } => stack.push(Some(aidx)),
+            Opcode::PushPluralArg { aidx, .. } => {
+                record(&mut types, aidx, ArgType::Num);
+                stack.push(None);
+            }
+            Opcode::Dup => {
+                let top = stack.last().copied().flatten();
+                stack.push(top);
+            }
+            Opcode::Pop => {
+                stack.pop();
+            }
+            Opcode::EmitStack => {
+                if let Some(Some(aidx)) = stack.pop() {
+                    record(&mut types, aidx, ArgType::Any);
+                }
+            }
+            Opcode::CallFmt { fid, opt_count } => {
+                pop_options(&mut stack, &mut types, opt_count);
+                if let Some(Some(aidx)) = stack.pop() {
+                    record(&mut types, aidx, formatter_arg_type(fid));
+                }
+                stack.push(None);
+            }
+            Opcode::Select { aidx, .. } => record(&mut types, aidx, ArgType::Str),
+            Opcode::SelectPlural { aidx, .. } => record(&mut types, aidx, ArgType::Num),
+            Opcode::SelectMulti { table } => {
+                if let Some(table) = program.multi_case_tables.get(table as usize) {
+                    for selector in &table.selectors {
+                        let arg_type = match selector.ruleset {
+                            Some(_) => ArgType::Num,
+                            None => ArgType::Str,
+                        };
+                        record(&mut types, selector.aidx, arg_type);
+                    }
+                }
+            }
+            Opcode::OpenMarkup { opt_count, .. }
+            | Opcode::CloseMarkup { opt_count, .. }
+            | Opcode::StandaloneMarkup { opt_count, .. } => {
+                pop_options(&mut stack, &mut types, opt_count);
+            }
+            Opcode::EmitText { .. } | Opcode::Jump { .. } | Opcode::End => {}
+        }
+    }
+
+    let args = program
+        .arg_names
+        .iter()
+        .zip(types)
+        .map(|(name, arg_type)| ArgRequirement {
+            name: name.clone(),
+            arg_type: arg_type.unwrap_or(ArgType::Any),
+        })
+        .collect();
+    MessageRequirements { args }
+}
+
+fn record(types: &mut [Option<ArgType>], aidx: u32, arg_type: ArgType) {
+    if let Some(slot) = types.get_mut(aidx as usize)
+        && matches!(slot, None | Some(ArgType::Any))
+    {
+        *slot = Some(arg_type);
+    }
+}
+
+fn pop_options(stack: &mut Vec<Option<u32>>, types: &mut [Option<ArgType>], count: u8) {
+    for _ in 0..count {
+        if let Some(Some(aidx)) = stack.pop() {
+            record(types, aidx, ArgType::Any);
+        }
+        stack.pop();
+    }
+}
+
+fn formatter_arg_type(fid: FormatterId) -> ArgType {
+    match fid {
+        FormatterId::Number => ArgType::Num,
+        FormatterId::Date | FormatterId::Time | FormatterId::DateTime => ArgType::DateTime,
+        FormatterId::Unit => ArgType::Unit,
+        FormatterId::Currency => ArgType::Currency,
+        FormatterId::Identity => ArgType::Any,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use alloc::vec;
+
+    use super::{ArgProblem, analyze};
+    use crate::{
+        ArgType, Args, BytecodeProgram, CaseEntry, CaseKey, CaseTable, FormatterId, Opcode,
+        PluralCategory, PluralRuleset, Value,
+    };
+
+    fn plural_program() -> BytecodeProgram {
+        let mut program = BytecodeProgram::new();
+        let count = program.push_arg_name("count");
+        let name = program.push_arg_name("name");
+        let items = program.string_pool.push(" items for ");
+        program.case_tables.push(CaseTable {
+            entries: vec![
+                CaseEntry {
+                    key: CaseKey::Category(PluralCategory::One),
+                    target: 1,
+                },
+                CaseEntry {
+                    key: CaseKey::Other,
+                    target: 1,
+                },
+            ],
+        });
+        program.opcodes = vec![
+            Opcode::SelectPlural {
+                aidx: count,
+                ruleset: PluralRuleset::Cardinal,
+                table: 0,
+                offset: 0.0,
+            },
+            Opcode::PushPluralArg {
+                aidx: count,
+                offset: 0.0,
+            },
+            Opcode::CallFmt {
+                fid: FormatterId::Number,
+                opt_count: 0,
+            },
+            Opcode::EmitStack,
+            Opcode::EmitText { sidx: items },
+            Opcode::PushArg { aidx: name },
+            Opcode::EmitStack,
+            Opcode::End,
+        ];
+        program
+    }
+
+    #[test]
+    fn recovers_args_and_types_without_executing() {
+        let requirements = analyze(&plural_program());
+        assert_eq!(requirements.args.len(), 2);
+        assert_eq!(requirements.get("count"), Some(ArgType::Num));
+        assert_eq!(requirements.get("name"), Some(ArgType::Any));
+    }
+
+    #[test]
+    fn check_reports_every_problem() {
+        let requirements = analyze(&plural_program());
+        assert_eq!(
+            requirements.check(&Args::new()),
+            vec![
+                ArgProblem::Missing(String::from("count")),
+                ArgProblem::Missing(String::from("name")),
+            ]
+        );
+
+        let mut args = Args::new();
+        args.insert("count", Value::Str(String::from("three")));
+        args.insert("name", Value::Str(String::from("Ada")));
+        assert_eq!(
+            requirements.check(&args),
+            vec![ArgProblem::TypeMismatch {
+                name: String::from("count"),
+                expected: ArgType::Num,
+            }]
+        );
+    }
+
+    #[test]
+    fn infers_types_from_formatters_and_string_selectors() {
+        let mut program = BytecodeProgram::new();
+        let when = program.push_arg_name("when");
+        let kind = program.push_arg_name("kind");
+        let precision = program.push_arg_name("precision");
+        let key = program.string_pool.push("dateStyle");
+        program.opcodes = vec![
+            Opcode::Select {
+                aidx: kind,
+                table: 0,
+            },
+            Opcode::PushArg { aidx: when },
+            Opcode::PushStr { sidx: key },
+            Opcode::PushArg { aidx: precision },
+            Opcode::CallFmt {
+                fid: FormatterId::Date,
+                opt_count: 1,
+            },
+            Opcode::EmitStack,
+            Opcode::End,
+        ];
+        let requirements = analyze(&program);
+        assert_eq!(requirements.get("when"), Some(ArgType::DateTime));
+        assert_eq!(requirements.get("kind"), Some(ArgType::Str));
+        assert_eq!(requirements.get("precision"), Some(ArgType::Any));
+    }
+}
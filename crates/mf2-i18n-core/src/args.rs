@@ -1,6 +1,7 @@
-use alloc::boxed::Box;
 use alloc::collections::BTreeMap;
 use alloc::string::String;
+#[cfg(feature = "dynamic-values")]
+use alloc::sync::Arc;
 
 use crate::{CoreError, CoreResult};
 
@@ -17,28 +18,137 @@ pub enum ArgType {
 
 impl ArgType {
     pub fn matches(self, value: &Value) -> bool {
-        match (self, value) {
-            (ArgType::Any, _) => true,
-            (ArgType::Str, Value::Str(_)) => true,
-            (ArgType::Num, Value::Num(_)) => true,
-            (ArgType::Bool, Value::Bool(_)) => true,
-            (ArgType::DateTime, Value::DateTime(_)) => true,
-            (ArgType::Unit, Value::Unit { .. }) => true,
-            (ArgType::Currency, Value::Currency { .. }) => true,
-            _ => false,
-        }
+        matches!(
+            (self, value),
+            (ArgType::Any, _)
+                | (ArgType::Str, Value::Str(_))
+                | (ArgType::Num, Value::Num(_) | Value::Int(_))
+                | (ArgType::Bool, Value::Bool(_))
+                | (ArgType::DateTime, Value::DateTime(_))
+                | (ArgType::Unit, Value::Unit { .. })
+                | (ArgType::Currency, Value::Currency { .. })
+        )
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum Value {
     Str(String),
     Num(f64),
+    Int(i64),
     Bool(bool),
     DateTime(i64),
-    Unit { value: f64, unit_id: u32 },
-    Currency { value: f64, code: [u8; 3] },
-    Any(Box<dyn core::any::Any>),
+    Unit {
+        value: f64,
+        unit_id: u32,
+    },
+    Currency {
+        value: f64,
+        code: [u8; 3],
+    },
+    #[cfg(feature = "dynamic-values")]
+    Any(Arc<dyn core::any::Any>),
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Str(left), Value::Str(right)) => left == right,
+            (Value::Num(left), Value::Num(right)) => left == right,
+            (Value::Int(left), Value::Int(right)) => left == right,
+            (Value::Bool(left), Value::Bool(right)) => left == right,
+            (Value::DateTime(left), Value::DateTime(right)) => left == right,
+            (
+                Value::Unit { value, unit_id },
+                Value::Unit {
+                    value: other_value,
+                    unit_id: other_unit_id,
+                },
+            ) => value == other_value && unit_id == other_unit_id,
+            (
+                Value::Currency { value, code },
+                Value::Currency {
+                    value: other_value,
+                    code: other_code,
+                },
+            ) => value == other_value && code == other_code,
+            #[cfg(feature = "dynamic-values")]
+            (Value::Any(left), Value::Any(right)) => Arc::ptr_eq(left, right),
+            _ => false,
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::Str(String::from(value))
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Str(value)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::Num(value)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::Int(value)
+    }
+}
+
+impl From<i32> for Value {
+    fn from(value: i32) -> Self {
+        Value::Int(i64::from(value))
+    }
+}
+
+impl From<u32> for Value {
+    fn from(value: u32) -> Self {
+        Value::Int(i64::from(value))
+    }
+}
+
+impl From<i16> for Value {
+    fn from(value: i16) -> Self {
+        Value::Int(i64::from(value))
+    }
+}
+
+impl From<u16> for Value {
+    fn from(value: u16) -> Self {
+        Value::Int(i64::from(value))
+    }
+}
+
+impl From<i8> for Value {
+    fn from(value: i8) -> Self {
+        Value::Int(i64::from(value))
+    }
+}
+
+impl From<u8> for Value {
+    fn from(value: u8) -> Self {
+        Value::Int(i64::from(value))
+    }
+}
+
+impl From<f32> for Value {
+    fn from(value: f32) -> Self {
+        Value::Num(f64::from(value))
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
 }
 
 pub struct Args {
@@ -52,14 +162,43 @@ impl Args {
         }
     }
 
-    pub fn insert(&mut self, name: impl Into<String>, value: Value) -> Option<Value> {
-        self.values.insert(name.into(), value)
+    pub fn insert(&mut self, name: impl Into<String>, value: impl Into<Value>) -> Option<Value> {
+        self.values.insert(name.into(), value.into())
+    }
+
+    pub fn with_str(&mut self, name: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        self.insert(name, Value::Str(value.into()));
+        self
+    }
+
+    pub fn with_num(&mut self, name: impl Into<String>, value: f64) -> &mut Self {
+        self.insert(name, Value::Num(value));
+        self
+    }
+
+    pub fn with_int(&mut self, name: impl Into<String>, value: i64) -> &mut Self {
+        self.insert(name, Value::Int(value));
+        self
+    }
+
+    pub fn with_bool(&mut self, name: impl Into<String>, value: bool) -> &mut Self {
+        self.insert(name, Value::Bool(value));
+        self
+    }
+
+    pub fn with_datetime(&mut self, name: impl Into<String>, millis: i64) -> &mut Self {
+        self.insert(name, Value::DateTime(millis));
+        self
     }
 
     pub fn get(&self, name: &str) -> Option<&Value> {
         self.values.get(name)
     }
 
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+
     pub fn require(&self, name: &str) -> CoreResult<&Value> {
         self.values
             .get(name)
@@ -84,6 +223,7 @@ impl Default for Args {
 
 #[cfg(test)]
 mod tests {
+    use alloc::format;
     use alloc::string::String;
 
     use super::{ArgType, Args, Value};
@@ -99,6 +239,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn insert_accepts_plain_integers() {
+        let mut args = Args::new();
+        args.insert("n", 3);
+        args.insert("small", 7u8);
+        args.insert("wide", u32::MAX);
+        args.insert("ratio", 0.5f32);
+        assert_eq!(args.get("n"), Some(&Value::Int(3)));
+        assert_eq!(args.get("small"), Some(&Value::Int(7)));
+        assert_eq!(args.get("wide"), Some(&Value::Int(i64::from(u32::MAX))));
+        assert_eq!(args.get("ratio"), Some(&Value::Num(0.5)));
+    }
+
     #[test]
     fn require_reports_missing_argument() {
         let args = Args::new();
@@ -126,4 +279,64 @@ mod tests {
             crate::CoreError::InvalidInput("argument type mismatch")
         );
     }
+
+    #[test]
+    fn chained_builders_match_plain_inserts() {
+        let mut chained = Args::new();
+        chained
+            .with_str("name", "Nova")
+            .with_num("ratio", 0.5)
+            .with_int("count", 3)
+            .with_bool("admin", true)
+            .with_datetime("at", 1_700_000_000_000);
+
+        let mut plain = Args::new();
+        plain.insert("name", "Nova");
+        plain.insert("ratio", 0.5);
+        plain.insert("count", 3i64);
+        plain.insert("admin", true);
+        plain.insert("at", Value::DateTime(1_700_000_000_000));
+
+        for name in ["name", "ratio", "count", "admin", "at"] {
+            assert_eq!(
+                format!("{:?}", chained.get(name)),
+                format!("{:?}", plain.get(name))
+            );
+        }
+        assert!(chained.get("missing").is_none());
+    }
+
+    #[test]
+    fn closed_values_clone_and_compare() {
+        let values = [
+            Value::Str(String::from("Nova")),
+            Value::Num(0.5),
+            Value::Int(3),
+            Value::Bool(true),
+            Value::DateTime(1_700_000_000_000),
+            Value::Unit {
+                value: 2.0,
+                unit_id: 7,
+            },
+            Value::Currency {
+                value: 9.5,
+                code: *b"EUR",
+            },
+        ];
+        for value in &values {
+            assert_eq!(value.clone(), *value);
+        }
+        assert_ne!(values[1], values[2]);
+    }
+
+    #[cfg(feature = "dynamic-values")]
+    #[test]
+    fn any_values_compare_by_pointer() {
+        let shared: alloc::sync::Arc<dyn core::any::Any> = alloc::sync::Arc::new(5u8);
+        let value = Value::Any(shared.clone());
+        assert_eq!(value.clone(), value);
+        assert_eq!(value, Value::Any(shared));
+        assert_ne!(value, Value::Any(alloc::sync::Arc::new(5u8)));
+        assert_ne!(value, Value::Int(5));
+    }
 }
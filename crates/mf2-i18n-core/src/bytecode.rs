@@ -1,5 +1,8 @@
 use alloc::string::String;
+use alloc::sync::Arc;
 use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::ops::{Deref, DerefMut};
 
 use crate::{FormatterId, PluralCategory};
 
@@ -7,13 +10,15 @@ pub type StringIndex = u32;
 pub type NumberIndex = u32;
 pub type ArgIndex = u32;
 pub type CaseTableIndex = u32;
+pub type MultiCaseTableIndex = u32;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PluralRuleset {
     Cardinal,
+    Ordinal,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Opcode {
     EmitText {
         sidx: StringIndex,
@@ -28,6 +33,10 @@ pub enum Opcode {
     PushArg {
         aidx: ArgIndex,
     },
+    PushPluralArg {
+        aidx: ArgIndex,
+        offset: f64,
+    },
     Dup,
     Pop,
     CallFmt {
@@ -42,10 +51,26 @@ pub enum Opcode {
         aidx: ArgIndex,
         ruleset: PluralRuleset,
         table: CaseTableIndex,
+        offset: f64,
+    },
+    SelectMulti {
+        table: MultiCaseTableIndex,
     },
     Jump {
         rel: i32,
     },
+    OpenMarkup {
+        sidx: StringIndex,
+        opt_count: u8,
+    },
+    CloseMarkup {
+        sidx: StringIndex,
+        opt_count: u8,
+    },
+    StandaloneMarkup {
+        sidx: StringIndex,
+        opt_count: u8,
+    },
     End,
 }
 
@@ -54,34 +79,87 @@ pub struct CaseTable {
     pub entries: Vec<CaseEntry>,
 }
 
+impl CaseTable {
+    pub fn sort_entries(&mut self, strings: &StringPool) {
+        self.entries
+            .sort_by(|left, right| compare_case_keys(&left.key, &right.key, strings));
+    }
+
+    pub fn is_sorted(&self, strings: &StringPool) -> bool {
+        self.entries
+            .windows(2)
+            .all(|pair| compare_case_keys(&pair[0].key, &pair[1].key, strings) != Ordering::Greater)
+    }
+}
+
+fn compare_case_keys(left: &CaseKey, right: &CaseKey, strings: &StringPool) -> Ordering {
+    match (left, right) {
+        (CaseKey::String(left), CaseKey::String(right)) => strings
+            .get(*left)
+            .unwrap_or_default()
+            .cmp(strings.get(*right).unwrap_or_default()),
+        (CaseKey::Exact(left), CaseKey::Exact(right)) => left.cmp(right),
+        _ => case_key_rank(left).cmp(&case_key_rank(right)),
+    }
+}
+
+fn case_key_rank(key: &CaseKey) -> u8 {
+    match key {
+        CaseKey::String(_) => 0,
+        CaseKey::Exact(_) => 1,
+        CaseKey::Category(_) => 2,
+        CaseKey::Other => 3,
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct CaseEntry {
     pub key: CaseKey,
     pub target: u32,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultiCaseTable {
+    pub selectors: Vec<MultiSelector>,
+    pub entries: Vec<MultiCaseEntry>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MultiSelector {
+    pub aidx: ArgIndex,
+    pub ruleset: Option<PluralRuleset>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultiCaseEntry {
+    pub keys: Vec<CaseKey>,
+    pub target: u32,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum CaseKey {
     String(StringIndex),
-    Exact(u32),
+    Exact(i64),
     Category(PluralCategory),
     Other,
 }
 
+#[derive(Clone, Debug, PartialEq)]
 pub struct StringPool {
-    entries: Vec<String>,
+    entries: Arc<Vec<String>>,
 }
 
 impl StringPool {
     pub fn new() -> Self {
         Self {
-            entries: Vec::new(),
+            entries: Arc::new(Vec::new()),
         }
     }
 
     pub fn push(&mut self, value: impl Into<String>) -> StringIndex {
-        let idx = self.entries.len();
-        self.entries.push(value.into());
+        let entries = Arc::make_mut(&mut self.entries);
+        let idx = entries.len();
+        entries.push(value.into());
         idx as StringIndex
     }
 
@@ -92,6 +170,22 @@ impl StringPool {
     pub fn len(&self) -> usize {
         self.entries.len()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn shares_storage(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.entries, &other.entries)
+    }
+}
+
+impl From<Vec<String>> for StringPool {
+    fn from(entries: Vec<String>) -> Self {
+        Self {
+            entries: Arc::new(entries),
+        }
+    }
 }
 
 impl Default for StringPool {
@@ -100,11 +194,67 @@ impl Default for StringPool {
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct SharedTables<T> {
+    entries: Arc<Vec<T>>,
+}
+
+impl<T> SharedTables<T> {
+    pub fn new() -> Self {
+        Self {
+            entries: Arc::new(Vec::new()),
+        }
+    }
+
+    pub fn shares_storage(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.entries, &other.entries)
+    }
+}
+
+impl<T> From<Vec<T>> for SharedTables<T> {
+    fn from(entries: Vec<T>) -> Self {
+        Self {
+            entries: Arc::new(entries),
+        }
+    }
+}
+
+impl<T> Default for SharedTables<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Deref for SharedTables<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Vec<T> {
+        &self.entries
+    }
+}
+
+impl<T: Clone> DerefMut for SharedTables<T> {
+    fn deref_mut(&mut self) -> &mut Vec<T> {
+        Arc::make_mut(&mut self.entries)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SharedTables<T> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter()
+    }
+}
+
+#[derive(Debug, PartialEq)]
 pub struct BytecodeProgram {
     pub opcodes: Vec<Opcode>,
     pub string_pool: StringPool,
     pub number_pool: Vec<f64>,
-    pub case_tables: Vec<CaseTable>,
+    pub case_tables: SharedTables<CaseTable>,
+    pub multi_case_tables: SharedTables<MultiCaseTable>,
     pub arg_names: Vec<String>,
 }
 
@@ -114,7 +264,8 @@ impl BytecodeProgram {
             opcodes: Vec::new(),
             string_pool: StringPool::new(),
             number_pool: Vec::new(),
-            case_tables: Vec::new(),
+            case_tables: SharedTables::new(),
+            multi_case_tables: SharedTables::new(),
             arg_names: Vec::new(),
         }
     }
@@ -144,8 +295,9 @@ impl Default for BytecodeProgram {
 #[cfg(test)]
 mod tests {
     use alloc::vec;
+    use alloc::vec::Vec;
 
-    use super::{BytecodeProgram, CaseEntry, CaseKey, CaseTable, Opcode, StringPool};
+    use super::{BytecodeProgram, CaseEntry, CaseKey, CaseTable, Opcode, SharedTables, StringPool};
 
     #[test]
     fn string_pool_round_trips() {
@@ -155,6 +307,37 @@ mod tests {
         assert_eq!(pool.len(), 1);
     }
 
+    #[test]
+    fn string_pool_clones_share_until_written() {
+        let mut pool = StringPool::new();
+        pool.push("hello");
+        let mut shared = pool.clone();
+        assert!(shared.shares_storage(&pool));
+        shared.push("world");
+        assert!(!shared.shares_storage(&pool));
+        assert_eq!(pool.len(), 1);
+        assert_eq!(shared.get(1), Some("world"));
+    }
+
+    #[test]
+    fn shared_tables_copy_on_write() {
+        let mut tables = SharedTables::from(vec![CaseTable {
+            entries: Vec::new(),
+        }]);
+        let mut shared = tables.clone();
+        assert!(shared.shares_storage(&tables));
+        shared.push(CaseTable {
+            entries: Vec::new(),
+        });
+        assert!(!shared.shares_storage(&tables));
+        assert_eq!(tables.len(), 1);
+        tables[0].entries.push(CaseEntry {
+            key: CaseKey::Other,
+            target: 0,
+        });
+        assert!(shared[0].entries.is_empty());
+    }
+
     #[test]
     fn program_pushes_opcodes() {
         let mut program = BytecodeProgram::new();
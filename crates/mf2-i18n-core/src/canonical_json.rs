@@ -0,0 +1,104 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use serde_json::Value;
+
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_992.0;
+
+pub fn canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value);
+    out
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(value) => out.push_str(if *value { "true" } else { "false" }),
+        Value::Number(number) => write_number(out, number),
+        Value::String(value) => write_string(out, value),
+        Value::Array(items) => {
+            out.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_value(out, item);
+            }
+            out.push(']');
+        }
+        Value::Object(entries) => {
+            let mut entries: Vec<_> = entries.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (index, (key, item)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_string(out, key);
+                out.push(':');
+                write_value(out, item);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_number(out: &mut String, number: &serde_json::Number) {
+    if let Some(value) = number.as_u64() {
+        let _ = write!(out, "{value}");
+    } else if let Some(value) = number.as_i64() {
+        let _ = write!(out, "{value}");
+    } else if let Some(value) = number.as_f64() {
+        if value > -MAX_SAFE_INTEGER && value < MAX_SAFE_INTEGER && value == (value as i64) as f64 {
+            let _ = write!(out, "{}", value as i64);
+        } else {
+            let _ = write!(out, "{value:?}");
+        }
+    }
+}
+
+fn write_string(out: &mut String, value: &str) {
+    out.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            ch if (ch as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", ch as u32);
+            }
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::canonical_json;
+    use serde_json::json;
+
+    #[test]
+    fn sorts_keys_and_drops_whitespace() {
+        let value = json!({ "b": [1, 2.0, -3], "a": { "z": null, "y": true } });
+        assert_eq!(
+            canonical_json(&value),
+            r#"{"a":{"y":true,"z":null},"b":[1,2,-3]}"#
+        );
+    }
+
+    #[test]
+    fn escapes_only_what_json_requires() {
+        let value = json!({ "text": "a\"b\\c\n\u{1}é" });
+        assert_eq!(
+            canonical_json(&value),
+            "{\"text\":\"a\\\"b\\\\c\\n\\u0001é\"}"
+        );
+    }
+}
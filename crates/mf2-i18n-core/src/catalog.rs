@@ -16,9 +16,13 @@ impl<'a> CatalogChain<'a> {
     }
 
     pub fn lookup(&self, id: MessageId) -> Option<&'a BytecodeProgram> {
-        for catalog in &self.catalogs {
+        self.lookup_with_source(id).map(|(_, message)| message)
+    }
+
+    pub fn lookup_with_source(&self, id: MessageId) -> Option<(usize, &'a BytecodeProgram)> {
+        for (layer, catalog) in self.catalogs.iter().enumerate() {
             if let Some(message) = catalog.lookup(id) {
-                return Some(message);
+                return Some((layer, message));
             }
         }
         None
@@ -81,4 +85,29 @@ mod tests {
 
         assert!(chain.lookup(id).is_some());
     }
+
+    #[test]
+    fn lookup_reports_the_serving_layer() {
+        let overlay_id = MessageId::new(1);
+        let base_id = MessageId::new(2);
+        let program = || {
+            let mut program = BytecodeProgram::new();
+            program.opcodes.push(Opcode::End);
+            program
+        };
+
+        let overlay = TestCatalog::new(vec![(overlay_id, program())]);
+        let base = TestCatalog::new(vec![(overlay_id, program()), (base_id, program())]);
+        let chain = CatalogChain::new(vec![&overlay, &base]);
+
+        assert_eq!(
+            chain.lookup_with_source(overlay_id).map(|(layer, _)| layer),
+            Some(0)
+        );
+        assert_eq!(
+            chain.lookup_with_source(base_id).map(|(layer, _)| layer),
+            Some(1)
+        );
+        assert!(chain.lookup_with_source(MessageId::new(3)).is_none());
+    }
 }
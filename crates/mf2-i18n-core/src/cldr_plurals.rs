@@ -0,0 +1,599 @@
+use alloc::string::String;
+
+use crate::icu_data::Operands;
+use crate::{CoreResult, FormatBackend, FormatterOption, LanguageTag, PluralCategory};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PluralFamily {
+    Other,
+    OneInteger,
+    OneN,
+    ZeroOne,
+    French,
+    Danish,
+    Icelandic,
+    Filipino,
+    Russian,
+    Belarusian,
+    Polish,
+    Czech,
+    Croatian,
+    Macedonian,
+    Slovenian,
+    Lithuanian,
+    Latvian,
+    Romanian,
+    Hebrew,
+    Arabic,
+    Irish,
+    ScottishGaelic,
+    Welsh,
+    Maltese,
+}
+
+const LOCALES: &[(&str, PluralFamily)] = &[
+    ("af", PluralFamily::OneN),
+    ("am", PluralFamily::ZeroOne),
+    ("an", PluralFamily::OneN),
+    ("ar", PluralFamily::Arabic),
+    ("ars", PluralFamily::Arabic),
+    ("as", PluralFamily::ZeroOne),
+    ("ast", PluralFamily::OneInteger),
+    ("az", PluralFamily::OneN),
+    ("be", PluralFamily::Belarusian),
+    ("bg", PluralFamily::OneN),
+    ("bn", PluralFamily::ZeroOne),
+    ("bo", PluralFamily::Other),
+    ("bs", PluralFamily::Croatian),
+    ("ca", PluralFamily::OneInteger),
+    ("ce", PluralFamily::OneN),
+    ("chr", PluralFamily::OneN),
+    ("ckb", PluralFamily::OneN),
+    ("cs", PluralFamily::Czech),
+    ("cy", PluralFamily::Welsh),
+    ("da", PluralFamily::Danish),
+    ("de", PluralFamily::OneInteger),
+    ("doi", PluralFamily::ZeroOne),
+    ("dz", PluralFamily::Other),
+    ("ee", PluralFamily::OneN),
+    ("el", PluralFamily::OneN),
+    ("en", PluralFamily::OneInteger),
+    ("eo", PluralFamily::OneN),
+    ("es", PluralFamily::OneN),
+    ("et", PluralFamily::OneInteger),
+    ("eu", PluralFamily::OneN),
+    ("fa", PluralFamily::ZeroOne),
+    ("ff", PluralFamily::French),
+    ("fi", PluralFamily::OneInteger),
+    ("fil", PluralFamily::Filipino),
+    ("fo", PluralFamily::OneN),
+    ("fr", PluralFamily::French),
+    ("fur", PluralFamily::OneN),
+    ("fy", PluralFamily::OneInteger),
+    ("ga", PluralFamily::Irish),
+    ("gd", PluralFamily::ScottishGaelic),
+    ("gl", PluralFamily::OneInteger),
+    ("gsw", PluralFamily::OneN),
+    ("gu", PluralFamily::ZeroOne),
+    ("ha", PluralFamily::OneN),
+    ("haw", PluralFamily::OneN),
+    ("he", PluralFamily::Hebrew),
+    ("hi", PluralFamily::ZeroOne),
+    ("hr", PluralFamily::Croatian),
+    ("hu", PluralFamily::OneN),
+    ("hy", PluralFamily::French),
+    ("ia", PluralFamily::OneInteger),
+    ("id", PluralFamily::Other),
+    ("ig", PluralFamily::Other),
+    ("ii", PluralFamily::Other),
+    ("io", PluralFamily::OneInteger),
+    ("is", PluralFamily::Icelandic),
+    ("it", PluralFamily::OneInteger),
+    ("ja", PluralFamily::Other),
+    ("jv", PluralFamily::Other),
+    ("ka", PluralFamily::OneN),
+    ("kab", PluralFamily::French),
+    ("kea", PluralFamily::Other),
+    ("kk", PluralFamily::OneN),
+    ("kl", PluralFamily::OneN),
+    ("km", PluralFamily::Other),
+    ("kn", PluralFamily::ZeroOne),
+    ("ko", PluralFamily::Other),
+    ("ks", PluralFamily::OneN),
+    ("ku", PluralFamily::OneN),
+    ("ky", PluralFamily::OneN),
+    ("lb", PluralFamily::OneN),
+    ("lg", PluralFamily::OneN),
+    ("lij", PluralFamily::OneInteger),
+    ("lo", PluralFamily::Other),
+    ("lt", PluralFamily::Lithuanian),
+    ("lv", PluralFamily::Latvian),
+    ("mk", PluralFamily::Macedonian),
+    ("mn", PluralFamily::OneN),
+    ("ms", PluralFamily::Other),
+    ("mt", PluralFamily::Maltese),
+    ("my", PluralFamily::Other),
+    ("nb", PluralFamily::OneN),
+    ("ne", PluralFamily::OneN),
+    ("nl", PluralFamily::OneInteger),
+    ("nn", PluralFamily::OneN),
+    ("no", PluralFamily::OneN),
+    ("ny", PluralFamily::OneN),
+    ("om", PluralFamily::OneN),
+    ("or", PluralFamily::OneN),
+    ("os", PluralFamily::OneN),
+    ("pcm", PluralFamily::ZeroOne),
+    ("pl", PluralFamily::Polish),
+    ("ps", PluralFamily::OneN),
+    ("pt", PluralFamily::French),
+    ("pt-PT", PluralFamily::OneInteger),
+    ("rm", PluralFamily::OneN),
+    ("ro", PluralFamily::Romanian),
+    ("ru", PluralFamily::Russian),
+    ("sc", PluralFamily::OneInteger),
+    ("sd", PluralFamily::OneN),
+    ("ses", PluralFamily::Other),
+    ("sg", PluralFamily::Other),
+    ("sk", PluralFamily::Czech),
+    ("sl", PluralFamily::Slovenian),
+    ("so", PluralFamily::OneN),
+    ("sq", PluralFamily::OneN),
+    ("sr", PluralFamily::Croatian),
+    ("ss", PluralFamily::OneN),
+    ("st", PluralFamily::OneN),
+    ("sv", PluralFamily::OneInteger),
+    ("sw", PluralFamily::OneInteger),
+    ("syr", PluralFamily::OneN),
+    ("ta", PluralFamily::OneN),
+    ("te", PluralFamily::OneN),
+    ("th", PluralFamily::Other),
+    ("tk", PluralFamily::OneN),
+    ("tl", PluralFamily::Filipino),
+    ("tn", PluralFamily::OneN),
+    ("to", PluralFamily::Other),
+    ("tr", PluralFamily::OneN),
+    ("ts", PluralFamily::OneN),
+    ("ug", PluralFamily::OneN),
+    ("uk", PluralFamily::Russian),
+    ("ur", PluralFamily::OneInteger),
+    ("uz", PluralFamily::OneN),
+    ("ve", PluralFamily::OneN),
+    ("vi", PluralFamily::Other),
+    ("xh", PluralFamily::OneN),
+    ("yi", PluralFamily::OneInteger),
+    ("yo", PluralFamily::Other),
+    ("yue", PluralFamily::Other),
+    ("zh", PluralFamily::Other),
+    ("zu", PluralFamily::ZeroOne),
+];
+
+pub struct CldrPluralBackend<B> {
+    family: PluralFamily,
+    inner: B,
+}
+
+impl<B: FormatBackend> CldrPluralBackend<B> {
+    pub fn new(locale: &LanguageTag, inner: B) -> Self {
+        Self {
+            family: family_for(locale).unwrap_or(PluralFamily::Other),
+            inner,
+        }
+    }
+
+    pub fn supports(locale: &LanguageTag) -> bool {
+        family_for(locale).is_some()
+    }
+
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+}
+
+impl<B: FormatBackend> FormatBackend for CldrPluralBackend<B> {
+    fn plural_category(&self, value: f64) -> CoreResult<PluralCategory> {
+        Ok(select(self.family, &Operands::from_f64(value)))
+    }
+
+    fn ordinal_category(&self, value: f64) -> CoreResult<PluralCategory> {
+        self.inner.ordinal_category(value)
+    }
+
+    fn format_number(&self, value: f64, options: &[FormatterOption]) -> CoreResult<String> {
+        self.inner.format_number(value, options)
+    }
+
+    fn format_date(&self, value: i64, options: &[FormatterOption]) -> CoreResult<String> {
+        self.inner.format_date(value, options)
+    }
+
+    fn format_time(&self, value: i64, options: &[FormatterOption]) -> CoreResult<String> {
+        self.inner.format_time(value, options)
+    }
+
+    fn format_datetime(&self, value: i64, options: &[FormatterOption]) -> CoreResult<String> {
+        self.inner.format_datetime(value, options)
+    }
+
+    fn format_unit(
+        &self,
+        value: f64,
+        unit_id: u32,
+        options: &[FormatterOption],
+    ) -> CoreResult<String> {
+        self.inner.format_unit(value, unit_id, options)
+    }
+
+    fn format_currency(
+        &self,
+        value: f64,
+        code: [u8; 3],
+        options: &[FormatterOption],
+    ) -> CoreResult<String> {
+        self.inner.format_currency(value, code, options)
+    }
+}
+
+fn family_for(locale: &LanguageTag) -> Option<PluralFamily> {
+    let mut parts = locale.match_subtags().to_vec();
+    while !parts.is_empty() {
+        let candidate = parts.join("-");
+        if let Ok(idx) = LOCALES.binary_search_by_key(&candidate.as_str(), |(locale, _)| locale) {
+            return Some(LOCALES[idx].1);
+        }
+        parts.pop();
+    }
+    None
+}
+
+fn select(family: PluralFamily, operands: &Operands) -> PluralCategory {
+    let &Operands { n, i, v, f, t } = operands;
+    let n_mod10 = integral(n).map(|n| n % 10);
+    let n_mod100 = integral(n).map(|n| n % 100);
+    let n_is = |value: u64| integral(n) == Some(value);
+    let n_in = |start: u64, end: u64| integral(n).is_some_and(|n| (start..=end).contains(&n));
+    let i10 = i % 10;
+    let i100 = i % 100;
+    let f10 = f % 10;
+    let f100 = f % 100;
+
+    match family {
+        PluralFamily::Other => PluralCategory::Other,
+        PluralFamily::OneInteger => {
+            if i == 1 && v == 0 {
+                PluralCategory::One
+            } else {
+                PluralCategory::Other
+            }
+        }
+        PluralFamily::OneN => {
+            if n_is(1) {
+                PluralCategory::One
+            } else {
+                PluralCategory::Other
+            }
+        }
+        PluralFamily::ZeroOne => {
+            if i == 0 || n_is(1) {
+                PluralCategory::One
+            } else {
+                PluralCategory::Other
+            }
+        }
+        PluralFamily::French => {
+            if i <= 1 {
+                PluralCategory::One
+            } else {
+                PluralCategory::Other
+            }
+        }
+        PluralFamily::Danish => {
+            if n_is(1) || (t != 0 && i <= 1) {
+                PluralCategory::One
+            } else {
+                PluralCategory::Other
+            }
+        }
+        PluralFamily::Icelandic => {
+            let t10 = t % 10;
+            let t100 = t % 100;
+            if (t == 0 && i10 == 1 && i100 != 11) || (t10 == 1 && t100 != 11) {
+                PluralCategory::One
+            } else {
+                PluralCategory::Other
+            }
+        }
+        PluralFamily::Filipino => {
+            let last_digit = if v == 0 { i10 } else { f10 };
+            if !matches!(last_digit, 4 | 6 | 9) {
+                PluralCategory::One
+            } else {
+                PluralCategory::Other
+            }
+        }
+        PluralFamily::Russian => {
+            if v != 0 {
+                PluralCategory::Other
+            } else if i10 == 1 && i100 != 11 {
+                PluralCategory::One
+            } else if (2..=4).contains(&i10) && !(12..=14).contains(&i100) {
+                PluralCategory::Few
+            } else {
+                PluralCategory::Many
+            }
+        }
+        PluralFamily::Belarusian => match (n_mod10, n_mod100) {
+            (Some(1), Some(m100)) if m100 != 11 => PluralCategory::One,
+            (Some(2..=4), Some(m100)) if !(12..=14).contains(&m100) => PluralCategory::Few,
+            (Some(0 | 5..=9), _) | (_, Some(11..=14)) => PluralCategory::Many,
+            _ => PluralCategory::Other,
+        },
+        PluralFamily::Polish => {
+            if v != 0 {
+                PluralCategory::Other
+            } else if i == 1 {
+                PluralCategory::One
+            } else if (2..=4).contains(&i10) && !(12..=14).contains(&i100) {
+                PluralCategory::Few
+            } else {
+                PluralCategory::Many
+            }
+        }
+        PluralFamily::Czech => {
+            if v != 0 {
+                PluralCategory::Many
+            } else if i == 1 {
+                PluralCategory::One
+            } else if (2..=4).contains(&i) {
+                PluralCategory::Few
+            } else {
+                PluralCategory::Other
+            }
+        }
+        PluralFamily::Croatian => {
+            if (v == 0 && i10 == 1 && i100 != 11) || (f10 == 1 && f100 != 11) {
+                PluralCategory::One
+            } else if (v == 0 && (2..=4).contains(&i10) && !(12..=14).contains(&i100))
+                || ((2..=4).contains(&f10) && !(12..=14).contains(&f100))
+            {
+                PluralCategory::Few
+            } else {
+                PluralCategory::Other
+            }
+        }
+        PluralFamily::Macedonian => {
+            if (v == 0 && i10 == 1 && i100 != 11) || (f10 == 1 && f100 != 11) {
+                PluralCategory::One
+            } else {
+                PluralCategory::Other
+            }
+        }
+        PluralFamily::Slovenian => {
+            if v == 0 && i100 == 1 {
+                PluralCategory::One
+            } else if v == 0 && i100 == 2 {
+                PluralCategory::Two
+            } else if v != 0 || (3..=4).contains(&i100) {
+                PluralCategory::Few
+            } else {
+                PluralCategory::Other
+            }
+        }
+        PluralFamily::Lithuanian => match (n_mod10, n_mod100) {
+            _ if f != 0 => PluralCategory::Many,
+            (Some(1), Some(m100)) if !(11..=19).contains(&m100) => PluralCategory::One,
+            (Some(2..=9), Some(m100)) if !(11..=19).contains(&m100) => PluralCategory::Few,
+            _ => PluralCategory::Other,
+        },
+        PluralFamily::Latvian => {
+            if n_mod10 == Some(0)
+                || n_mod100.is_some_and(|m| (11..=19).contains(&m))
+                || (v == 2 && (11..=19).contains(&f100))
+            {
+                PluralCategory::Zero
+            } else if (n_mod10 == Some(1) && n_mod100 != Some(11))
+                || (f10 == 1 && (v != 2 || f100 != 11))
+            {
+                PluralCategory::One
+            } else {
+                PluralCategory::Other
+            }
+        }
+        PluralFamily::Romanian => {
+            if i == 1 && v == 0 {
+                PluralCategory::One
+            } else if v != 0 || n_is(0) || n_mod100.is_some_and(|m| (1..=19).contains(&m)) {
+                PluralCategory::Few
+            } else {
+                PluralCategory::Other
+            }
+        }
+        PluralFamily::Hebrew => {
+            if (i == 1 && v == 0) || (i == 0 && v != 0) {
+                PluralCategory::One
+            } else if i == 2 && v == 0 {
+                PluralCategory::Two
+            } else {
+                PluralCategory::Other
+            }
+        }
+        PluralFamily::Arabic => match n_mod100 {
+            _ if n_is(0) => PluralCategory::Zero,
+            _ if n_is(1) => PluralCategory::One,
+            _ if n_is(2) => PluralCategory::Two,
+            Some(3..=10) => PluralCategory::Few,
+            Some(11..=99) => PluralCategory::Many,
+            _ => PluralCategory::Other,
+        },
+        PluralFamily::Irish => {
+            if n_is(1) {
+                PluralCategory::One
+            } else if n_is(2) {
+                PluralCategory::Two
+            } else if n_in(3, 6) {
+                PluralCategory::Few
+            } else if n_in(7, 10) {
+                PluralCategory::Many
+            } else {
+                PluralCategory::Other
+            }
+        }
+        PluralFamily::ScottishGaelic => {
+            if n_is(1) || n_is(11) {
+                PluralCategory::One
+            } else if n_is(2) || n_is(12) {
+                PluralCategory::Two
+            } else if n_in(3, 10) || n_in(13, 19) {
+                PluralCategory::Few
+            } else {
+                PluralCategory::Other
+            }
+        }
+        PluralFamily::Welsh => match integral(n) {
+            Some(0) => PluralCategory::Zero,
+            Some(1) => PluralCategory::One,
+            Some(2) => PluralCategory::Two,
+            Some(3) => PluralCategory::Few,
+            Some(6) => PluralCategory::Many,
+            _ => PluralCategory::Other,
+        },
+        PluralFamily::Maltese => {
+            if n_is(1) {
+                PluralCategory::One
+            } else if n_is(2) {
+                PluralCategory::Two
+            } else if n_is(0) || n_mod100.is_some_and(|m| (3..=10).contains(&m)) {
+                PluralCategory::Few
+            } else if n_mod100.is_some_and(|m| (11..=19).contains(&m)) {
+                PluralCategory::Many
+            } else {
+                PluralCategory::Other
+            }
+        }
+    }
+}
+
+fn integral(n: f64) -> Option<u64> {
+    (n == (n as u64) as f64).then_some(n as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::{String, ToString};
+
+    use super::{CldrPluralBackend, LOCALES};
+    use crate::{CoreResult, FormatBackend, FormatterOption, LanguageTag, PluralCategory};
+
+    struct EchoBackend;
+
+    impl FormatBackend for EchoBackend {
+        fn plural_category(&self, _value: f64) -> CoreResult<PluralCategory> {
+            Ok(PluralCategory::Other)
+        }
+
+        fn format_number(&self, value: f64, _options: &[FormatterOption]) -> CoreResult<String> {
+            Ok(value.to_string())
+        }
+
+        fn format_date(&self, value: i64, _options: &[FormatterOption]) -> CoreResult<String> {
+            Ok(value.to_string())
+        }
+
+        fn format_time(&self, value: i64, _options: &[FormatterOption]) -> CoreResult<String> {
+            Ok(value.to_string())
+        }
+
+        fn format_datetime(&self, value: i64, _options: &[FormatterOption]) -> CoreResult<String> {
+            Ok(value.to_string())
+        }
+
+        fn format_unit(
+            &self,
+            value: f64,
+            _unit_id: u32,
+            _options: &[FormatterOption],
+        ) -> CoreResult<String> {
+            Ok(value.to_string())
+        }
+
+        fn format_currency(
+            &self,
+            value: f64,
+            _code: [u8; 3],
+            _options: &[FormatterOption],
+        ) -> CoreResult<String> {
+            Ok(value.to_string())
+        }
+    }
+
+    fn backend(locale: &str) -> CldrPluralBackend<EchoBackend> {
+        CldrPluralBackend::new(&LanguageTag::parse(locale).expect("tag"), EchoBackend)
+    }
+
+    fn categories(locale: &str, values: &[f64]) -> alloc::vec::Vec<PluralCategory> {
+        let backend = backend(locale);
+        values
+            .iter()
+            .map(|value| backend.plural_category(*value).expect("category"))
+            .collect()
+    }
+
+    #[test]
+    fn locale_table_is_sorted() {
+        assert!(LOCALES.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn english_distinguishes_one_and_other() {
+        use PluralCategory::{One, Other};
+        assert_eq!(
+            categories("en-US", &[0.0, 1.0, 1.5, 2.0, 11.0, 21.0, 101.0]),
+            [Other, One, Other, Other, Other, Other, Other]
+        );
+    }
+
+    #[test]
+    fn russian_uses_mod_10_and_mod_100() {
+        use PluralCategory::{Few, Many, One, Other};
+        assert_eq!(
+            categories(
+                "ru",
+                &[
+                    1.0, 2.0, 4.0, 5.0, 11.0, 12.0, 14.0, 21.0, 22.0, 25.0, 111.0, 112.0, 1.5
+                ]
+            ),
+            [
+                One, Few, Few, Many, Many, Many, Many, One, Few, Many, Many, Many, Other
+            ]
+        );
+    }
+
+    #[test]
+    fn polish_and_arabic_cover_remaining_categories() {
+        use PluralCategory::{Few, Many, One, Other, Two, Zero};
+        assert_eq!(
+            categories("pl", &[1.0, 2.0, 5.0, 22.0, 0.5]),
+            [One, Few, Many, Few, Other]
+        );
+        assert_eq!(
+            categories("ar-EG", &[0.0, 1.0, 2.0, 3.0, 11.0, 100.0, 103.0]),
+            [Zero, One, Two, Few, Many, Other, Few]
+        );
+        assert_eq!(categories("cs", &[1.0, 3.0, 1.5]), [One, Few, Many]);
+        assert_eq!(categories("fr", &[0.0, 1.5, 2.0]), [One, One, Other]);
+        assert_eq!(categories("pt-PT", &[0.0, 1.0]), [Other, One]);
+    }
+
+    #[test]
+    fn unknown_locale_falls_back_to_other() {
+        let tag = LanguageTag::parse("qaa").expect("tag");
+        assert!(!CldrPluralBackend::<EchoBackend>::supports(&tag));
+        assert_eq!(
+            backend("qaa").plural_category(1.0).expect("category"),
+            PluralCategory::Other
+        );
+        assert_eq!(
+            backend("en").format_number(1.5, &[]).expect("format"),
+            "1.5"
+        );
+    }
+}
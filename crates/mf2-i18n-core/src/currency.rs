@@ -0,0 +1,78 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct CurrencyInfo {
+    pub(crate) symbol: &'static str,
+    pub(crate) digits: u32,
+    pub(crate) name: &'static str,
+}
+
+const CURRENCIES: &[(&str, CurrencyInfo)] = &[
+    ("AUD", info("A$", 2, "Australian dollars")),
+    ("BHD", info("BHD", 3, "Bahraini dinars")),
+    ("BRL", info("R$", 2, "Brazilian reals")),
+    ("CAD", info("CA$", 2, "Canadian dollars")),
+    ("CHF", info("CHF", 2, "Swiss francs")),
+    ("CLP", info("CLP", 0, "Chilean pesos")),
+    ("CNY", info("CN¥", 2, "Chinese yuan")),
+    ("CZK", info("CZK", 2, "Czech korunas")),
+    ("DKK", info("DKK", 2, "Danish kroner")),
+    ("EUR", info("€", 2, "euros")),
+    ("GBP", info("£", 2, "British pounds")),
+    ("HKD", info("HK$", 2, "Hong Kong dollars")),
+    ("HUF", info("HUF", 2, "Hungarian forints")),
+    ("IDR", info("IDR", 2, "Indonesian rupiahs")),
+    ("ILS", info("₪", 2, "Israeli new shekels")),
+    ("INR", info("₹", 2, "Indian rupees")),
+    ("ISK", info("ISK", 0, "Icelandic krónur")),
+    ("JOD", info("JOD", 3, "Jordanian dinars")),
+    ("JPY", info("¥", 0, "Japanese yen")),
+    ("KRW", info("₩", 0, "South Korean won")),
+    ("KWD", info("KWD", 3, "Kuwaiti dinars")),
+    ("MXN", info("MX$", 2, "Mexican pesos")),
+    ("NOK", info("NOK", 2, "Norwegian kroner")),
+    ("NZD", info("NZ$", 2, "New Zealand dollars")),
+    ("OMR", info("OMR", 3, "Omani rials")),
+    ("PLN", info("PLN", 2, "Polish zlotys")),
+    ("RUB", info("RUB", 2, "Russian rubles")),
+    ("SEK", info("SEK", 2, "Swedish kronor")),
+    ("SGD", info("SGD", 2, "Singapore dollars")),
+    ("THB", info("THB", 2, "Thai baht")),
+    ("TND", info("TND", 3, "Tunisian dinars")),
+    ("TRY", info("TRY", 2, "Turkish lira")),
+    ("TWD", info("NT$", 2, "New Taiwan dollars")),
+    ("USD", info("$", 2, "US dollars")),
+    ("VND", info("₫", 0, "Vietnamese dong")),
+    ("ZAR", info("ZAR", 2, "South African rand")),
+];
+
+const fn info(symbol: &'static str, digits: u32, name: &'static str) -> CurrencyInfo {
+    CurrencyInfo {
+        symbol,
+        digits,
+        name,
+    }
+}
+
+pub(crate) fn currency_info(code: &str) -> Option<CurrencyInfo> {
+    CURRENCIES
+        .binary_search_by_key(&code, |(code, _)| code)
+        .ok()
+        .map(|idx| CURRENCIES[idx].1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CURRENCIES, currency_info};
+
+    #[test]
+    fn currency_table_is_sorted() {
+        assert!(CURRENCIES.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn looks_up_minor_units() {
+        assert_eq!(currency_info("JPY").map(|info| info.digits), Some(0));
+        assert_eq!(currency_info("USD").map(|info| info.digits), Some(2));
+        assert_eq!(currency_info("BHD").map(|info| info.digits), Some(3));
+        assert_eq!(currency_info("XXX"), None);
+    }
+}
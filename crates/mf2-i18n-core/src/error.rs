@@ -1,3 +1,5 @@
+use alloc::string::String;
+use alloc::vec::Vec;
 use core::fmt;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -5,16 +7,84 @@ pub enum CoreError {
     Unsupported(&'static str),
     InvalidInput(&'static str),
     Internal(&'static str),
+    InvalidPack {
+        section: Option<u8>,
+        offset: usize,
+        reason: &'static str,
+    },
+    UnusedArgs(Vec<String>),
+    MissingOtherCase {
+        table: u32,
+        multi: bool,
+    },
 }
 
 pub type CoreResult<T> = Result<T, CoreError>;
 
+impl CoreError {
+    pub(crate) fn invalid_pack(offset: usize, reason: &'static str) -> Self {
+        CoreError::InvalidPack {
+            section: None,
+            offset,
+            reason,
+        }
+    }
+
+    pub(crate) fn at_offset(self, base: usize) -> Self {
+        match self {
+            CoreError::InvalidPack {
+                section: None,
+                offset,
+                reason,
+            } => CoreError::invalid_pack(base + offset, reason),
+            other => other,
+        }
+    }
+
+    pub(crate) fn in_section(self, section_type: u8, base: usize) -> Self {
+        match self.at_offset(base) {
+            CoreError::InvalidPack {
+                section: None,
+                offset,
+                reason,
+            } => CoreError::InvalidPack {
+                section: Some(section_type),
+                offset,
+                reason,
+            },
+            other => other,
+        }
+    }
+}
+
 impl fmt::Display for CoreError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             CoreError::Unsupported(message) => write!(f, "unsupported: {message}"),
             CoreError::InvalidInput(message) => write!(f, "invalid input: {message}"),
             CoreError::Internal(message) => write!(f, "internal error: {message}"),
+            CoreError::InvalidPack {
+                section: Some(section),
+                offset,
+                reason,
+            } => write!(
+                f,
+                "invalid pack: {reason} at offset {offset} in section {section}"
+            ),
+            CoreError::InvalidPack {
+                section: None,
+                offset,
+                reason,
+            } => write!(f, "invalid pack: {reason} at offset {offset}"),
+            CoreError::UnusedArgs(names) => write!(f, "unused arguments: {}", names.join(", ")),
+            CoreError::MissingOtherCase { table, multi } => {
+                let kind = if *multi {
+                    "multi case table"
+                } else {
+                    "case table"
+                };
+                write!(f, "missing other case in {kind} {table}")
+            }
         }
     }
 }
@@ -44,4 +114,14 @@ mod tests {
         let err = CoreError::Internal("state");
         assert_eq!(err.to_string(), "internal error: state");
     }
+
+    #[test]
+    fn display_formats_invalid_pack() {
+        let err = CoreError::invalid_pack(4, "unexpected eof");
+        assert_eq!(err.to_string(), "invalid pack: unexpected eof at offset 4");
+        assert_eq!(
+            err.in_section(1, 100).to_string(),
+            "invalid pack: unexpected eof at offset 104 in section 1"
+        );
+    }
 }
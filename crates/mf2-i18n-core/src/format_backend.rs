@@ -27,6 +27,46 @@ pub struct FormatterOption {
     pub value: FormatterOptionValue,
 }
 
+impl FormatterOptionValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Str(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_num(&self) -> Option<f64> {
+        match self {
+            Self::Num(value) => Some(*value),
+            Self::Str(value) => value.parse().ok(),
+            Self::Bool(_) => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            Self::Str(value) => match value.as_str() {
+                "true" => Some(true),
+                "false" => Some(false),
+                _ => None,
+            },
+            Self::Num(_) => None,
+        }
+    }
+}
+
+pub fn find_option<'a>(
+    options: &'a [FormatterOption],
+    key: &str,
+) -> Option<&'a FormatterOptionValue> {
+    options
+        .iter()
+        .rev()
+        .find(|option| option.key == key)
+        .map(|option| &option.value)
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PluralCategory {
     Zero,
@@ -39,7 +79,13 @@ pub enum PluralCategory {
 
 pub trait FormatBackend {
     fn plural_category(&self, value: f64) -> CoreResult<PluralCategory>;
+    fn ordinal_category(&self, _value: f64) -> CoreResult<PluralCategory> {
+        Ok(PluralCategory::Other)
+    }
     fn format_number(&self, value: f64, options: &[FormatterOption]) -> CoreResult<String>;
+    fn format_integer(&self, value: i64, options: &[FormatterOption]) -> CoreResult<String> {
+        self.format_number(value as f64, options)
+    }
     fn format_date(&self, value: i64, options: &[FormatterOption]) -> CoreResult<String>;
     fn format_time(&self, value: i64, options: &[FormatterOption]) -> CoreResult<String>;
     fn format_datetime(&self, value: i64, options: &[FormatterOption]) -> CoreResult<String>;
@@ -66,6 +112,7 @@ pub fn format_value(
     match formatter {
         FormatterId::Number => match value {
             Value::Num(number) => backend.format_number(*number, options),
+            Value::Int(number) => backend.format_integer(*number, options),
             _ => Err(CoreError::InvalidInput("formatter expects number")),
         },
         FormatterId::Date => match value {
@@ -96,6 +143,7 @@ fn format_value_default(value: &Value) -> CoreResult<String> {
     match value {
         Value::Str(text) => Ok(text.clone()),
         Value::Num(number) => Ok(number.to_string()),
+        Value::Int(number) => Ok(number.to_string()),
         Value::Bool(value) => Ok(value.to_string()),
         Value::DateTime(timestamp) => Ok(timestamp.to_string()),
         Value::Unit { value, unit_id } => Ok(format!("{value}:{unit_id}")),
@@ -104,6 +152,7 @@ fn format_value_default(value: &Value) -> CoreResult<String> {
                 core::str::from_utf8(code).map_err(|_| CoreError::InvalidInput("currency code"))?;
             Ok(format!("{value}:{code}"))
         }
+        #[cfg(feature = "dynamic-values")]
         Value::Any(_) => Err(CoreError::Unsupported("identity formatting for any value")),
     }
 }
@@ -0,0 +1,454 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::pack_catalog::{map_sections, read_u8, read_u16, read_u32};
+use crate::{
+    CoreError, CoreResult, PACK_SCHEMA_VERSION, PackHeader, PackKind, PluralCategory,
+    PluralRuleset, decode_string_pool, parse_pack_header, parse_section_directory,
+};
+
+const SECTION_STRING_POOL: u8 = 1;
+const SECTION_PLURAL_RULES: u8 = 7;
+const SECTION_NUMBER_SYMBOLS: u8 = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PluralOperand {
+    N,
+    I,
+    V,
+    F,
+    T,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PluralRelation {
+    pub operand: PluralOperand,
+    pub modulus: Option<u32>,
+    pub negate: bool,
+    pub ranges: Vec<(u32, u32)>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PluralRule {
+    pub category: PluralCategory,
+    pub conditions: Vec<Vec<PluralRelation>>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PluralRules {
+    pub rules: Vec<PluralRule>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NumberSymbols {
+    pub decimal: String,
+    pub group: String,
+    pub minus: String,
+}
+
+impl Default for NumberSymbols {
+    fn default() -> Self {
+        Self {
+            decimal: String::from("."),
+            group: String::from(","),
+            minus: String::from("-"),
+        }
+    }
+}
+
+pub struct IcuDataPack {
+    header: PackHeader,
+    locale: String,
+    cardinal: PluralRules,
+    ordinal: PluralRules,
+    symbols: NumberSymbols,
+}
+
+impl IcuDataPack {
+    pub fn decode(bytes: &[u8]) -> CoreResult<Self> {
+        let (header, mut cursor) = parse_pack_header(bytes)?;
+        if header.schema_version > PACK_SCHEMA_VERSION {
+            return Err(CoreError::Unsupported("pack schema too new"));
+        }
+        if header.pack_kind != PackKind::IcuData {
+            return Err(CoreError::InvalidInput("expected icu data pack"));
+        }
+        let section_count = read_u16(bytes, &mut cursor)? as usize;
+        let sections =
+            parse_section_directory(bytes, cursor, section_count, header.has_section_checksums())?;
+        let section_map = map_sections(bytes, &sections)?;
+
+        let string_pool_bytes = section_map
+            .get(&SECTION_STRING_POOL)
+            .ok_or(CoreError::InvalidInput("missing string pool section"))?;
+        let string_pool = decode_string_pool(string_pool_bytes)?;
+        let locale = string_pool
+            .get(header.locale_tag_sidx as usize)
+            .cloned()
+            .ok_or(CoreError::InvalidInput("locale tag string index"))?;
+
+        let (cardinal, ordinal) = match section_map.get(&SECTION_PLURAL_RULES) {
+            Some(bytes) => decode_plural_rules(bytes)?,
+            None => (PluralRules::default(), PluralRules::default()),
+        };
+        let symbols = match section_map.get(&SECTION_NUMBER_SYMBOLS) {
+            Some(bytes) => decode_number_symbols(bytes, &string_pool)?,
+            None => NumberSymbols::default(),
+        };
+
+        Ok(Self {
+            header,
+            locale,
+            cardinal,
+            ordinal,
+            symbols,
+        })
+    }
+
+    pub fn header(&self) -> &PackHeader {
+        &self.header
+    }
+
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    pub fn symbols(&self) -> &NumberSymbols {
+        &self.symbols
+    }
+
+    pub fn plural_rules(&self, ruleset: PluralRuleset) -> &PluralRules {
+        match ruleset {
+            PluralRuleset::Cardinal => &self.cardinal,
+            PluralRuleset::Ordinal => &self.ordinal,
+        }
+    }
+
+    pub fn plural_category(&self, ruleset: PluralRuleset, value: f64) -> PluralCategory {
+        self.plural_rules(ruleset).select(value)
+    }
+}
+
+impl PluralRules {
+    pub fn select(&self, value: f64) -> PluralCategory {
+        let operands = Operands::from_f64(value);
+        for rule in &self.rules {
+            if rule
+                .conditions
+                .iter()
+                .any(|relations| relations.iter().all(|relation| relation.matches(&operands)))
+            {
+                return rule.category;
+            }
+        }
+        PluralCategory::Other
+    }
+}
+
+impl PluralRelation {
+    fn matches(&self, operands: &Operands) -> bool {
+        let mut value = match self.operand {
+            PluralOperand::N => operands.n,
+            PluralOperand::I => operands.i as f64,
+            PluralOperand::V => operands.v as f64,
+            PluralOperand::F => operands.f as f64,
+            PluralOperand::T => operands.t as f64,
+        };
+        if let Some(modulus) = self.modulus
+            && modulus != 0
+        {
+            value %= modulus as f64;
+        }
+        let in_range = value == (value as u64) as f64
+            && self
+                .ranges
+                .iter()
+                .any(|(start, end)| value >= *start as f64 && value <= *end as f64);
+        in_range != self.negate
+    }
+}
+
+pub(crate) struct Operands {
+    pub(crate) n: f64,
+    pub(crate) i: u64,
+    pub(crate) v: u32,
+    pub(crate) f: u64,
+    pub(crate) t: u64,
+}
+
+impl Operands {
+    pub(crate) fn from_f64(value: f64) -> Self {
+        let n = if value < 0.0 { -value } else { value };
+        let i = n as u64;
+        let mut fraction = n - i as f64;
+        let mut v = 0u32;
+        let mut f = 0u64;
+        while fraction > 1e-9 && v < 6 {
+            fraction *= 10.0;
+            let digit = (fraction + 1e-9) as u64;
+            f = f * 10 + digit;
+            fraction -= digit as f64;
+            v += 1;
+        }
+        let mut t = f;
+        while t != 0 && t.is_multiple_of(10) {
+            t /= 10;
+        }
+        Self { n, i, v, f, t }
+    }
+}
+
+fn decode_plural_rules(input: &[u8]) -> CoreResult<(PluralRules, PluralRules)> {
+    let mut cursor = 0usize;
+    let mut cardinal = PluralRules::default();
+    let mut ordinal = PluralRules::default();
+    let count = read_u32(input, &mut cursor)? as usize;
+    for _ in 0..count {
+        let ruleset = PluralRuleset::try_from(read_u8(input, &mut cursor)?)?;
+        let rule_count = read_u32(input, &mut cursor)? as usize;
+        let mut rules = Vec::with_capacity(rule_count);
+        for _ in 0..rule_count {
+            let category = decode_category(read_u8(input, &mut cursor)?)?;
+            let or_count = read_u32(input, &mut cursor)? as usize;
+            let mut conditions = Vec::with_capacity(or_count);
+            for _ in 0..or_count {
+                let and_count = read_u32(input, &mut cursor)? as usize;
+                let mut relations = Vec::with_capacity(and_count);
+                for _ in 0..and_count {
+                    relations.push(decode_relation(input, &mut cursor)?);
+                }
+                conditions.push(relations);
+            }
+            rules.push(PluralRule {
+                category,
+                conditions,
+            });
+        }
+        match ruleset {
+            PluralRuleset::Cardinal => cardinal.rules = rules,
+            PluralRuleset::Ordinal => ordinal.rules = rules,
+        }
+    }
+    Ok((cardinal, ordinal))
+}
+
+fn decode_relation(input: &[u8], cursor: &mut usize) -> CoreResult<PluralRelation> {
+    let operand = match read_u8(input, cursor)? {
+        0 => PluralOperand::N,
+        1 => PluralOperand::I,
+        2 => PluralOperand::V,
+        3 => PluralOperand::F,
+        4 => PluralOperand::T,
+        _ => return Err(CoreError::InvalidInput("unknown plural operand")),
+    };
+    let modulus = match read_u32(input, cursor)? {
+        0 => None,
+        value => Some(value),
+    };
+    let negate = read_u8(input, cursor)? != 0;
+    let range_count = read_u32(input, cursor)? as usize;
+    let mut ranges = Vec::with_capacity(range_count);
+    for _ in 0..range_count {
+        let start = read_u32(input, cursor)?;
+        let end = read_u32(input, cursor)?;
+        ranges.push((start, end));
+    }
+    Ok(PluralRelation {
+        operand,
+        modulus,
+        negate,
+        ranges,
+    })
+}
+
+fn decode_category(raw: u8) -> CoreResult<PluralCategory> {
+    match raw {
+        0 => Ok(PluralCategory::Zero),
+        1 => Ok(PluralCategory::One),
+        2 => Ok(PluralCategory::Two),
+        3 => Ok(PluralCategory::Few),
+        4 => Ok(PluralCategory::Many),
+        5 => Ok(PluralCategory::Other),
+        _ => Err(CoreError::InvalidInput("unknown plural category")),
+    }
+}
+
+fn decode_number_symbols(input: &[u8], string_pool: &[String]) -> CoreResult<NumberSymbols> {
+    let mut cursor = 0usize;
+    let mut lookup = || -> CoreResult<String> {
+        let sidx = read_u32(input, &mut cursor)? as usize;
+        string_pool
+            .get(sidx)
+            .cloned()
+            .ok_or(CoreError::InvalidInput("number symbol string index"))
+    };
+    Ok(NumberSymbols {
+        decimal: lookup()?,
+        group: lookup()?,
+        minus: lookup()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use super::{
+        IcuDataPack, PluralOperand, PluralRelation, PluralRule, PluralRules,
+        SECTION_NUMBER_SYMBOLS, SECTION_PLURAL_RULES, SECTION_STRING_POOL,
+    };
+    use crate::{CoreError, PACK_SCHEMA_VERSION, PluralCategory, PluralRuleset};
+
+    fn english_cardinal() -> PluralRules {
+        PluralRules {
+            rules: vec![PluralRule {
+                category: PluralCategory::One,
+                conditions: vec![vec![
+                    PluralRelation {
+                        operand: PluralOperand::I,
+                        modulus: None,
+                        negate: false,
+                        ranges: vec![(1, 1)],
+                    },
+                    PluralRelation {
+                        operand: PluralOperand::V,
+                        modulus: None,
+                        negate: false,
+                        ranges: vec![(0, 0)],
+                    },
+                ]],
+            }],
+        }
+    }
+
+    fn build_icu_pack() -> Vec<u8> {
+        build_icu_pack_with_schema(0)
+    }
+
+    fn build_icu_pack_with_schema(schema: u16) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"MF2PACK\0");
+        bytes.extend_from_slice(&schema.to_le_bytes());
+        bytes.push(2);
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        bytes.extend_from_slice(&[0u8; 32]);
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        bytes.extend_from_slice(&0u64.to_le_bytes());
+
+        let mut string_pool = Vec::new();
+        string_pool.extend_from_slice(&4u32.to_le_bytes());
+        for value in ["fr", ",", "\u{202f}", "-"] {
+            string_pool.extend_from_slice(&(value.len() as u32).to_le_bytes());
+            string_pool.extend_from_slice(value.as_bytes());
+        }
+
+        let mut plural_rules = Vec::new();
+        plural_rules.extend_from_slice(&1u32.to_le_bytes());
+        plural_rules.push(0);
+        plural_rules.extend_from_slice(&1u32.to_le_bytes());
+        plural_rules.push(1);
+        plural_rules.extend_from_slice(&1u32.to_le_bytes());
+        plural_rules.extend_from_slice(&1u32.to_le_bytes());
+        plural_rules.push(1);
+        plural_rules.extend_from_slice(&0u32.to_le_bytes());
+        plural_rules.push(0);
+        plural_rules.extend_from_slice(&1u32.to_le_bytes());
+        plural_rules.extend_from_slice(&0u32.to_le_bytes());
+        plural_rules.extend_from_slice(&1u32.to_le_bytes());
+
+        let mut symbols = Vec::new();
+        symbols.extend_from_slice(&1u32.to_le_bytes());
+        symbols.extend_from_slice(&2u32.to_le_bytes());
+        symbols.extend_from_slice(&3u32.to_le_bytes());
+
+        let sections = vec![
+            (SECTION_STRING_POOL, string_pool),
+            (SECTION_PLURAL_RULES, plural_rules),
+            (SECTION_NUMBER_SYMBOLS, symbols),
+        ];
+        bytes.extend_from_slice(&(sections.len() as u16).to_le_bytes());
+        let dir_start = bytes.len();
+        bytes.resize(dir_start + sections.len() * 9, 0);
+        let mut offset = bytes.len() as u32;
+        for (idx, (section_type, data)) in sections.into_iter().enumerate() {
+            let entry_offset = dir_start + idx * 9;
+            bytes[entry_offset] = section_type;
+            bytes[entry_offset + 1..entry_offset + 5].copy_from_slice(&offset.to_le_bytes());
+            bytes[entry_offset + 5..entry_offset + 9]
+                .copy_from_slice(&(data.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(&data);
+            offset += data.len() as u32;
+        }
+        bytes
+    }
+
+    #[test]
+    fn selects_english_cardinal_categories() {
+        let rules = english_cardinal();
+        assert_eq!(rules.select(1.0), PluralCategory::One);
+        assert_eq!(rules.select(1.5), PluralCategory::Other);
+        assert_eq!(rules.select(0.0), PluralCategory::Other);
+        assert_eq!(rules.select(21.0), PluralCategory::Other);
+    }
+
+    #[test]
+    fn applies_modulus_and_negation() {
+        let rules = PluralRules {
+            rules: vec![PluralRule {
+                category: PluralCategory::Few,
+                conditions: vec![vec![
+                    PluralRelation {
+                        operand: PluralOperand::N,
+                        modulus: Some(10),
+                        negate: false,
+                        ranges: vec![(2, 4)],
+                    },
+                    PluralRelation {
+                        operand: PluralOperand::N,
+                        modulus: Some(100),
+                        negate: true,
+                        ranges: vec![(12, 14)],
+                    },
+                ]],
+            }],
+        };
+        assert_eq!(rules.select(23.0), PluralCategory::Few);
+        assert_eq!(rules.select(13.0), PluralCategory::Other);
+        assert_eq!(rules.select(2.5), PluralCategory::Other);
+    }
+
+    #[test]
+    fn decodes_icu_data_pack() {
+        let pack = IcuDataPack::decode(&build_icu_pack()).expect("pack");
+        assert_eq!(pack.locale(), "fr");
+        assert_eq!(pack.symbols().decimal, ",");
+        assert_eq!(pack.symbols().group, "\u{202f}");
+        assert_eq!(pack.symbols().minus, "-");
+        assert_eq!(
+            pack.plural_category(PluralRuleset::Cardinal, 0.0),
+            PluralCategory::One
+        );
+        assert_eq!(
+            pack.plural_category(PluralRuleset::Cardinal, 1.0),
+            PluralCategory::One
+        );
+        assert_eq!(
+            pack.plural_category(PluralRuleset::Cardinal, 2.0),
+            PluralCategory::Other
+        );
+        assert_eq!(
+            pack.plural_category(PluralRuleset::Ordinal, 1.0),
+            PluralCategory::Other
+        );
+    }
+
+    #[test]
+    fn rejects_newer_pack_schema() {
+        let err = IcuDataPack::decode(&build_icu_pack_with_schema(PACK_SCHEMA_VERSION + 1))
+            .err()
+            .expect("error");
+        assert_eq!(err, CoreError::Unsupported("pack schema too new"));
+    }
+}
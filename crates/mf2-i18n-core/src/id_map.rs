@@ -0,0 +1,51 @@
+use sha2::{Digest, Sha256};
+
+use crate::{CoreError, CoreResult, MessageId};
+
+pub fn id_map_hash<'a>(
+    entries: impl IntoIterator<Item = (&'a str, MessageId)>,
+) -> CoreResult<[u8; 32]> {
+    let mut hasher = Sha256::new();
+    for (key, id) in entries {
+        let len: u32 = key
+            .len()
+            .try_into()
+            .map_err(|_| CoreError::InvalidInput("id map key too long"))?;
+        hasher.update(len.to_le_bytes());
+        hasher.update(key.as_bytes());
+        hasher.update(u64::from(id).to_le_bytes());
+    }
+    Ok(hasher.finalize().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeMap;
+    use alloc::string::{String, ToString};
+
+    use super::id_map_hash;
+    use crate::MessageId;
+
+    #[test]
+    fn hash_depends_on_keys_and_ids() {
+        let mut entries = BTreeMap::new();
+        entries.insert("home.title".to_string(), MessageId::new(1));
+        let hash = |entries: &BTreeMap<_, _>| {
+            id_map_hash(
+                entries
+                    .iter()
+                    .map(|(key, id): (&String, &MessageId)| (key.as_str(), *id)),
+            )
+            .expect("hash")
+        };
+        let original = hash(&entries);
+        assert_eq!(original, hash(&entries.clone()));
+        assert_eq!(
+            original,
+            id_map_hash([("home.title", MessageId::new(1))]).expect("hash")
+        );
+
+        entries.insert("home.title".to_string(), MessageId::new(2));
+        assert_ne!(original, hash(&entries));
+    }
+}
@@ -1,21 +1,99 @@
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::cmp::Ordering;
 
 use crate::{
     Args, BytecodeProgram, CaseKey, CaseTable, CoreError, CoreResult, FormatBackend, FormatterId,
-    Opcode, PluralRuleset, Value, format_value,
+    FormatterOption, FormatterOptionValue, IgnoreMarkup, MarkupSink, MultiCaseTable, Opcode,
+    PluralCategory, PluralRuleset, Value, analyze, format_value,
 };
 
+pub const DEFAULT_INSTRUCTION_LIMIT: usize = 100_000;
+
+const FIRST_STRONG_ISOLATE: char = '\u{2068}';
+const POP_DIRECTIONAL_ISOLATE: char = '\u{2069}';
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExecuteOptions {
+    pub instruction_limit: usize,
+    pub isolate_placeholders: bool,
+}
+
+impl Default for ExecuteOptions {
+    fn default() -> Self {
+        Self {
+            instruction_limit: DEFAULT_INSTRUCTION_LIMIT,
+            isolate_placeholders: false,
+        }
+    }
+}
+
 pub fn execute(
     program: &BytecodeProgram,
     args: &Args,
     backend: &dyn FormatBackend,
 ) -> CoreResult<String> {
+    execute_with_limit(program, args, backend, DEFAULT_INSTRUCTION_LIMIT)
+}
+
+pub fn execute_strict(
+    program: &BytecodeProgram,
+    args: &Args,
+    backend: &dyn FormatBackend,
+) -> CoreResult<String> {
+    let output = execute(program, args, backend)?;
+    let requirements = analyze(program);
+    let unused: Vec<String> = args
+        .names()
+        .filter(|name| requirements.get(name).is_none())
+        .map(String::from)
+        .collect();
+    if !unused.is_empty() {
+        return Err(CoreError::UnusedArgs(unused));
+    }
+    Ok(output)
+}
+
+pub fn execute_with_limit(
+    program: &BytecodeProgram,
+    args: &Args,
+    backend: &dyn FormatBackend,
+    limit: usize,
+) -> CoreResult<String> {
+    let options = ExecuteOptions {
+        instruction_limit: limit,
+        ..ExecuteOptions::default()
+    };
+    execute_with_options(program, args, backend, &options)
+}
+
+pub fn execute_with_options(
+    program: &BytecodeProgram,
+    args: &Args,
+    backend: &dyn FormatBackend,
+    options: &ExecuteOptions,
+) -> CoreResult<String> {
+    execute_with_markup(program, args, backend, options, &mut IgnoreMarkup)
+}
+
+pub fn execute_with_markup(
+    program: &BytecodeProgram,
+    args: &Args,
+    backend: &dyn FormatBackend,
+    options: &ExecuteOptions,
+    markup: &mut dyn MarkupSink,
+) -> CoreResult<String> {
+    let limit = options.instruction_limit;
     let mut stack: Vec<Value> = Vec::new();
     let mut output = String::new();
     let mut pc: usize = 0;
+    let mut steps: usize = 0;
 
     while pc < program.opcodes.len() {
+        if steps >= limit {
+            return Err(CoreError::Unsupported("instruction budget exceeded"));
+        }
+        steps += 1;
         let opcode = program.opcodes[pc];
         match opcode {
             Opcode::EmitText { sidx } => {
@@ -30,7 +108,13 @@ pub fn execute(
                     .pop()
                     .ok_or(CoreError::InvalidInput("stack underflow"))?;
                 let rendered = format_value(backend, FormatterId::Identity, &value, &[])?;
-                output.push_str(&rendered);
+                if options.isolate_placeholders {
+                    output.push(FIRST_STRONG_ISOLATE);
+                    output.push_str(&rendered);
+                    output.push(POP_DIRECTIONAL_ISOLATE);
+                } else {
+                    output.push_str(&rendered);
+                }
             }
             Opcode::PushStr { sidx } => {
                 let text = program
@@ -51,13 +135,17 @@ pub fn execute(
                     .arg_name(aidx)
                     .ok_or(CoreError::InvalidInput("arg index out of bounds"))?;
                 let value = args.require(name)?;
-                stack.push(clone_value(value)?);
+                stack.push(value.clone());
+            }
+            Opcode::PushPluralArg { aidx, offset } => {
+                let operand = plural_operand(program, args, aidx)?.offset(offset);
+                stack.push(operand.into_value());
             }
             Opcode::Dup => {
                 let value = stack
                     .last()
                     .ok_or(CoreError::InvalidInput("stack underflow"))?;
-                stack.push(clone_value(value)?);
+                stack.push(value.clone());
             }
             Opcode::Pop => {
                 let _ = stack
@@ -65,13 +153,11 @@ pub fn execute(
                     .ok_or(CoreError::InvalidInput("stack underflow"))?;
             }
             Opcode::CallFmt { fid, opt_count } => {
-                if opt_count != 0 {
-                    return Err(CoreError::Unsupported("formatter options not supported"));
-                }
+                let options = pop_options(&mut stack, opt_count)?;
                 let value = stack
                     .pop()
                     .ok_or(CoreError::InvalidInput("stack underflow"))?;
-                let rendered = format_value(backend, fid, &value, &[])?;
+                let rendered = format_value(backend, fid, &value, &options)?;
                 stack.push(Value::Str(rendered));
             }
             Opcode::Select { aidx, table } => {
@@ -83,8 +169,15 @@ pub fn execute(
                 aidx,
                 ruleset,
                 table,
+                offset,
             } => {
-                let target = select_plural_case(program, args, backend, aidx, ruleset, table)?;
+                let target =
+                    select_plural_case(program, args, backend, aidx, ruleset, table, offset)?;
+                pc = target;
+                continue;
+            }
+            Opcode::SelectMulti { table } => {
+                let target = select_multi_case(program, args, backend, table)?;
                 pc = target;
                 continue;
             }
@@ -96,6 +189,18 @@ pub fn execute(
                 pc = next as usize;
                 continue;
             }
+            Opcode::OpenMarkup { sidx, opt_count } => {
+                let options = pop_options(&mut stack, opt_count)?;
+                markup.open(markup_name(program, sidx)?, &options, &mut output)?;
+            }
+            Opcode::CloseMarkup { sidx, opt_count } => {
+                let options = pop_options(&mut stack, opt_count)?;
+                markup.close(markup_name(program, sidx)?, &options, &mut output)?;
+            }
+            Opcode::StandaloneMarkup { sidx, opt_count } => {
+                let options = pop_options(&mut stack, opt_count)?;
+                markup.standalone(markup_name(program, sidx)?, &options, &mut output)?;
+            }
             Opcode::End => break,
         }
         pc += 1;
@@ -104,6 +209,13 @@ pub fn execute(
     Ok(output)
 }
 
+fn markup_name(program: &BytecodeProgram, sidx: u32) -> CoreResult<&str> {
+    program
+        .string_pool
+        .get(sidx)
+        .ok_or(CoreError::InvalidInput("string index out of bounds"))
+}
+
 fn select_case(
     program: &BytecodeProgram,
     args: &Args,
@@ -119,7 +231,7 @@ fn select_case(
         _ => return Err(CoreError::InvalidInput("select expects string")),
     };
     let table = get_case_table(program, table_idx)?;
-    match_case(table, program, value)
+    match_case(table, program, value).ok_or(missing_other(table_idx, false))
 }
 
 fn select_plural_case(
@@ -129,109 +241,263 @@ fn select_plural_case(
     aidx: u32,
     ruleset: PluralRuleset,
     table_idx: u32,
+    offset: f64,
 ) -> CoreResult<usize> {
-    let name = program
-        .arg_name(aidx)
-        .ok_or(CoreError::InvalidInput("arg index out of bounds"))?;
-    let value = args.require(name)?;
-    let number = match value {
-        Value::Num(value) => *value,
-        _ => return Err(CoreError::InvalidInput("plural expects number")),
-    };
+    let operand = plural_operand(program, args, aidx)?.offset(offset);
     let table = get_case_table(program, table_idx)?;
-    if let Some(target) = match_exact_number(table, number) {
+    if let Some(target) = match_exact_number(table, operand) {
         return Ok(target);
     }
-    if matches!(ruleset, PluralRuleset::Cardinal) {
-        let category = backend.plural_category(number)?;
-        if let Some(target) = match_plural_category(table, category) {
-            return Ok(target);
+    let number = operand.as_f64();
+    let category = match ruleset {
+        PluralRuleset::Cardinal => backend.plural_category(number)?,
+        PluralRuleset::Ordinal => backend.ordinal_category(number)?,
+    };
+    if let Some(target) = match_plural_category(table, category) {
+        return Ok(target);
+    }
+    match_other(table).ok_or(missing_other(table_idx, false))
+}
+
+enum SelectorValue<'a> {
+    Str(&'a str),
+    Plural(PluralOperand, PluralCategory),
+}
+
+fn select_multi_case(
+    program: &BytecodeProgram,
+    args: &Args,
+    backend: &dyn FormatBackend,
+    table_idx: u32,
+) -> CoreResult<usize> {
+    let table = get_multi_case_table(program, table_idx)?;
+    let mut values = Vec::with_capacity(table.selectors.len());
+    for selector in &table.selectors {
+        let value = match selector.ruleset {
+            Some(ruleset) => {
+                let operand = plural_operand(program, args, selector.aidx)?;
+                let category = match ruleset {
+                    PluralRuleset::Cardinal => backend.plural_category(operand.as_f64())?,
+                    PluralRuleset::Ordinal => backend.ordinal_category(operand.as_f64())?,
+                };
+                SelectorValue::Plural(operand, category)
+            }
+            None => {
+                let name = program
+                    .arg_name(selector.aidx)
+                    .ok_or(CoreError::InvalidInput("arg index out of bounds"))?;
+                match args.require(name)? {
+                    Value::Str(text) => SelectorValue::Str(text),
+                    _ => return Err(CoreError::InvalidInput("select expects string")),
+                }
+            }
+        };
+        values.push(value);
+    }
+
+    let mut best: Option<(Vec<u8>, usize)> = None;
+    for entry in &table.entries {
+        if entry.keys.len() != values.len() {
+            return Err(CoreError::InvalidInput("case key count mismatch"));
+        }
+        let mut scores = Vec::with_capacity(values.len());
+        for (key, value) in entry.keys.iter().zip(&values) {
+            match score_key(program, key, value) {
+                Some(score) => scores.push(score),
+                None => break,
+            }
+        }
+        if scores.len() != values.len() {
+            continue;
+        }
+        let better = match &best {
+            Some((best_scores, _)) => scores < *best_scores,
+            None => true,
+        };
+        if better {
+            best = Some((scores, entry.target as usize));
         }
     }
-    match_other(table)
+    best.map(|(_, target)| target)
+        .ok_or(missing_other(table_idx, true))
 }
 
-fn get_case_table<'a>(program: &'a BytecodeProgram, table_idx: u32) -> CoreResult<&'a CaseTable> {
+fn score_key(program: &BytecodeProgram, key: &CaseKey, value: &SelectorValue<'_>) -> Option<u8> {
+    match (key, value) {
+        (CaseKey::Other, _) => Some(2),
+        (CaseKey::String(sidx), SelectorValue::Str(text)) => {
+            (program.string_pool.get(*sidx) == Some(*text)).then_some(0)
+        }
+        (CaseKey::Exact(exact), SelectorValue::Plural(operand, _)) => {
+            (operand.exact() == Some(*exact)).then_some(0)
+        }
+        (CaseKey::Category(category), SelectorValue::Plural(_, actual)) => {
+            (category == actual).then_some(1)
+        }
+        _ => None,
+    }
+}
+
+fn get_multi_case_table(program: &BytecodeProgram, table_idx: u32) -> CoreResult<&MultiCaseTable> {
     program
-        .case_tables
+        .multi_case_tables
         .get(table_idx as usize)
         .ok_or(CoreError::InvalidInput("case table index out of bounds"))
 }
 
-fn match_case(table: &CaseTable, program: &BytecodeProgram, value: &str) -> CoreResult<usize> {
-    let mut other = None;
-    for entry in &table.entries {
-        match &entry.key {
-            CaseKey::String(sidx) => {
-                if let Some(candidate) = program.string_pool.get(*sidx) {
-                    if candidate == value {
-                        return Ok(entry.target as usize);
-                    }
-                }
-            }
-            CaseKey::Other => other = Some(entry.target as usize),
-            _ => {}
+#[derive(Clone, Copy)]
+enum PluralOperand {
+    Int(i64),
+    Num(f64),
+}
+
+impl PluralOperand {
+    fn offset(self, offset: f64) -> Self {
+        match (self, exact_integer(offset)) {
+            (Self::Int(value), Some(offset)) => match value.checked_sub(offset) {
+                Some(value) => Self::Int(value),
+                None => Self::Num(value as f64 - offset as f64),
+            },
+            (Self::Int(value), None) => Self::Num(value as f64 - offset),
+            (Self::Num(value), _) => Self::Num(value - offset),
+        }
+    }
+
+    fn as_f64(self) -> f64 {
+        match self {
+            Self::Int(value) => value as f64,
+            Self::Num(value) => value,
+        }
+    }
+
+    fn exact(self) -> Option<i64> {
+        match self {
+            Self::Int(value) => Some(value),
+            Self::Num(value) => exact_integer(value),
+        }
+    }
+
+    fn into_value(self) -> Value {
+        match self {
+            Self::Int(value) => Value::Int(value),
+            Self::Num(value) => Value::Num(value),
         }
     }
-    other.ok_or(CoreError::InvalidInput("missing other case"))
 }
 
-fn match_exact_number(table: &CaseTable, value: f64) -> Option<usize> {
-    if value < 0.0 {
+fn exact_integer(value: f64) -> Option<i64> {
+    if !(-9_223_372_036_854_775_808.0..9_223_372_036_854_775_808.0).contains(&value) {
         return None;
     }
-    let candidate = value as u32;
-    if (candidate as f64) != value {
-        return None;
+    let candidate = value as i64;
+    (candidate as f64 == value).then_some(candidate)
+}
+
+fn plural_operand(program: &BytecodeProgram, args: &Args, aidx: u32) -> CoreResult<PluralOperand> {
+    let name = program
+        .arg_name(aidx)
+        .ok_or(CoreError::InvalidInput("arg index out of bounds"))?;
+    match args.require(name)? {
+        Value::Int(value) => Ok(PluralOperand::Int(*value)),
+        Value::Num(value) => Ok(PluralOperand::Num(*value)),
+        _ => Err(CoreError::InvalidInput("plural expects number")),
     }
-    for entry in &table.entries {
-        if let CaseKey::Exact(exact) = entry.key {
-            if exact == candidate {
-                return Some(entry.target as usize);
-            }
-        }
+}
+
+fn get_case_table(program: &BytecodeProgram, table_idx: u32) -> CoreResult<&CaseTable> {
+    program
+        .case_tables
+        .get(table_idx as usize)
+        .ok_or(CoreError::InvalidInput("case table index out of bounds"))
+}
+
+fn match_case(table: &CaseTable, program: &BytecodeProgram, value: &str) -> Option<usize> {
+    let strings = &table.entries[..keyed_end(table, 0)];
+    let found = strings.binary_search_by(|entry| match entry.key {
+        CaseKey::String(sidx) => program.string_pool.get(sidx).unwrap_or_default().cmp(value),
+        _ => Ordering::Greater,
+    });
+    match found {
+        Ok(idx) => Some(strings[idx].target as usize),
+        Err(_) => match_other(table),
     }
-    None
 }
 
-fn match_plural_category(table: &CaseTable, category: crate::PluralCategory) -> Option<usize> {
+fn match_exact_number(table: &CaseTable, operand: PluralOperand) -> Option<usize> {
+    let candidate = operand.exact()?;
+    let exact = &table.entries[keyed_end(table, 0)..keyed_end(table, 1)];
+    exact
+        .binary_search_by(|entry| match entry.key {
+            CaseKey::Exact(value) => value.cmp(&candidate),
+            _ => Ordering::Greater,
+        })
+        .ok()
+        .map(|idx| exact[idx].target as usize)
+}
+
+fn keyed_end(table: &CaseTable, rank: u8) -> usize {
+    table.entries.partition_point(|entry| {
+        let entry_rank = match entry.key {
+            CaseKey::String(_) => 0,
+            CaseKey::Exact(_) => 1,
+            _ => 2,
+        };
+        entry_rank <= rank
+    })
+}
+
+fn match_plural_category(table: &CaseTable, category: PluralCategory) -> Option<usize> {
     for entry in &table.entries {
-        if let CaseKey::Category(case_category) = entry.key {
-            if case_category == category {
-                return Some(entry.target as usize);
-            }
+        if let CaseKey::Category(case_category) = entry.key
+            && case_category == category
+        {
+            return Some(entry.target as usize);
         }
     }
     None
 }
 
-fn match_other(table: &CaseTable) -> CoreResult<usize> {
+fn match_other(table: &CaseTable) -> Option<usize> {
     table
         .entries
         .iter()
+        .rev()
         .find_map(|entry| match entry.key {
             CaseKey::Other => Some(entry.target as usize),
             _ => None,
         })
-        .ok_or(CoreError::InvalidInput("missing other case"))
 }
 
-fn clone_value(value: &Value) -> CoreResult<Value> {
-    match value {
-        Value::Str(text) => Ok(Value::Str(text.clone())),
-        Value::Num(number) => Ok(Value::Num(*number)),
-        Value::Bool(value) => Ok(Value::Bool(*value)),
-        Value::DateTime(value) => Ok(Value::DateTime(*value)),
-        Value::Unit { value, unit_id } => Ok(Value::Unit {
-            value: *value,
-            unit_id: *unit_id,
-        }),
-        Value::Currency { value, code } => Ok(Value::Currency {
-            value: *value,
-            code: *code,
-        }),
-        Value::Any(_) => Err(CoreError::Unsupported("cloning any value")),
+fn missing_other(table: u32, multi: bool) -> CoreError {
+    CoreError::MissingOtherCase { table, multi }
+}
+
+fn pop_options(stack: &mut Vec<Value>, count: u8) -> CoreResult<Vec<FormatterOption>> {
+    let mut options = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let value = stack
+            .pop()
+            .ok_or(CoreError::InvalidInput("stack underflow"))?;
+        let Some(Value::Str(key)) = stack.pop() else {
+            return Err(CoreError::InvalidInput(
+                "formatter option key must be a string",
+            ));
+        };
+        let value = match value {
+            Value::Str(text) => FormatterOptionValue::Str(text),
+            Value::Num(number) => FormatterOptionValue::Num(number),
+            Value::Int(number) => FormatterOptionValue::Num(number as f64),
+            Value::Bool(flag) => FormatterOptionValue::Bool(flag),
+            _ => {
+                return Err(CoreError::InvalidInput(
+                    "unsupported formatter option value",
+                ));
+            }
+        };
+        options.push(FormatterOption { key, value });
     }
+    options.reverse();
+    Ok(options)
 }
 
 #[cfg(test)]
@@ -240,10 +506,13 @@ mod tests {
     use alloc::string::String;
     use alloc::vec;
 
-    use super::execute;
+    use super::{
+        ExecuteOptions, execute, execute_strict, execute_with_limit, execute_with_markup,
+        execute_with_options,
+    };
     use crate::{
-        Args, BytecodeProgram, FormatBackend, FormatterId, FormatterOption, Opcode, PluralCategory,
-        Value,
+        Args, BytecodeProgram, CoreError, FormatBackend, FormatterId, FormatterOption, MarkupSink,
+        Opcode, PluralCategory, Value,
     };
 
     struct TestBackend;
@@ -256,9 +525,13 @@ mod tests {
         fn format_number(
             &self,
             value: f64,
-            _options: &[FormatterOption],
+            options: &[FormatterOption],
         ) -> crate::CoreResult<String> {
-            Ok(format!("num:{value}"))
+            let mut out = format!("num:{value}");
+            for option in options {
+                out.push_str(&format!(";{}={:?}", option.key, option.value));
+            }
+            Ok(out)
         }
 
         fn format_date(
@@ -325,6 +598,35 @@ mod tests {
         assert_eq!(out, "Hello Nova");
     }
 
+    #[test]
+    fn strict_execution_rejects_unused_args() {
+        let backend = TestBackend;
+        let mut program = BytecodeProgram::new();
+        let count_arg = program.push_arg_name("count");
+        program.opcodes = vec![
+            Opcode::PushArg { aidx: count_arg },
+            Opcode::EmitStack,
+            Opcode::End,
+        ];
+
+        let mut args = Args::new();
+        args.with_int("count", 2).with_int("conut", 3);
+
+        let out = execute(&program, &args, &backend).expect("exec ok");
+        assert_eq!(out, "2");
+        assert_eq!(
+            execute_strict(&program, &args, &backend),
+            Err(CoreError::UnusedArgs(vec![String::from("conut")]))
+        );
+
+        let mut args = Args::new();
+        args.with_int("count", 2);
+        assert_eq!(
+            execute_strict(&program, &args, &backend).expect("exec ok"),
+            "2"
+        );
+    }
+
     #[test]
     fn executes_call_fmt() {
         let backend = TestBackend;
@@ -345,6 +647,176 @@ mod tests {
         assert_eq!(out, "num:3.5");
     }
 
+    #[test]
+    fn isolates_interpolated_values_when_enabled() {
+        let backend = TestBackend;
+        let mut program = BytecodeProgram::new();
+        let name_arg = program.push_arg_name("name");
+        let text_idx = program.string_pool.push("Hello ");
+        program.number_pool.push(3.5);
+        program.opcodes = vec![
+            Opcode::EmitText { sidx: text_idx },
+            Opcode::PushArg { aidx: name_arg },
+            Opcode::EmitStack,
+            Opcode::PushNum { nidx: 0 },
+            Opcode::CallFmt {
+                fid: FormatterId::Number,
+                opt_count: 0,
+            },
+            Opcode::EmitStack,
+            Opcode::End,
+        ];
+        let mut args = Args::new();
+        args.insert(
+            "name",
+            Value::Str(String::from("\u{645}\u{62d}\u{645}\u{62f}")),
+        );
+
+        let plain = execute(&program, &args, &backend).expect("exec ok");
+        assert_eq!(plain, "Hello \u{645}\u{62d}\u{645}\u{62f}num:3.5");
+
+        let options = ExecuteOptions {
+            isolate_placeholders: true,
+            ..ExecuteOptions::default()
+        };
+        let isolated = execute_with_options(&program, &args, &backend, &options).expect("exec ok");
+        assert_eq!(
+            isolated,
+            "Hello \u{2068}\u{645}\u{62d}\u{645}\u{62f}\u{2069}\u{2068}num:3.5\u{2069}"
+        );
+    }
+
+    struct HtmlSink {
+        depth: usize,
+    }
+
+    impl MarkupSink for HtmlSink {
+        fn open(
+            &mut self,
+            name: &str,
+            options: &[FormatterOption],
+            output: &mut String,
+        ) -> crate::CoreResult<()> {
+            self.depth += 1;
+            output.push_str(&format!("<{name}"));
+            for option in options {
+                let value = option.value.as_str().unwrap_or_default();
+                output.push_str(&format!(" {}=\"{value}\"", option.key));
+            }
+            output.push('>');
+            Ok(())
+        }
+
+        fn close(
+            &mut self,
+            name: &str,
+            _options: &[FormatterOption],
+            output: &mut String,
+        ) -> crate::CoreResult<()> {
+            self.depth -= 1;
+            output.push_str(&format!("</{name}>"));
+            Ok(())
+        }
+
+        fn standalone(
+            &mut self,
+            name: &str,
+            _options: &[FormatterOption],
+            output: &mut String,
+        ) -> crate::CoreResult<()> {
+            output.push_str(&format!("<{name}/>"));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn executes_balanced_markup_through_sink() {
+        let backend = TestBackend;
+        let mut program = BytecodeProgram::new();
+        let url_arg = program.push_arg_name("url");
+        let link = program.string_pool.push("a");
+        let href = program.string_pool.push("href");
+        let text = program.string_pool.push("click");
+        let br = program.string_pool.push("br");
+        program.opcodes = vec![
+            Opcode::PushStr { sidx: href },
+            Opcode::PushArg { aidx: url_arg },
+            Opcode::OpenMarkup {
+                sidx: link,
+                opt_count: 1,
+            },
+            Opcode::EmitText { sidx: text },
+            Opcode::CloseMarkup {
+                sidx: link,
+                opt_count: 0,
+            },
+            Opcode::StandaloneMarkup {
+                sidx: br,
+                opt_count: 0,
+            },
+            Opcode::End,
+        ];
+        let mut args = Args::new();
+        args.insert("url", Value::Str(String::from("/docs")));
+
+        let mut sink = HtmlSink { depth: 0 };
+        let out = execute_with_markup(
+            &program,
+            &args,
+            &backend,
+            &ExecuteOptions::default(),
+            &mut sink,
+        )
+        .expect("exec ok");
+        assert_eq!(out, "<a href=\"/docs\">click</a><br/>");
+        assert_eq!(sink.depth, 0);
+
+        let plain = execute(&program, &args, &backend).expect("exec ok");
+        assert_eq!(plain, "click");
+    }
+
+    #[test]
+    fn call_fmt_passes_options_in_order() {
+        let backend = TestBackend;
+        let mut program = BytecodeProgram::new();
+        program.number_pool.push(3.5);
+        program.number_pool.push(2.0);
+        let min_key = program.string_pool.push("minimumFractionDigits");
+        let grouping_key = program.string_pool.push("useGrouping");
+        let grouping_value = program.string_pool.push("false");
+        program.opcodes = vec![
+            Opcode::PushNum { nidx: 0 },
+            Opcode::PushStr { sidx: min_key },
+            Opcode::PushNum { nidx: 1 },
+            Opcode::PushStr { sidx: grouping_key },
+            Opcode::PushStr {
+                sidx: grouping_value,
+            },
+            Opcode::CallFmt {
+                fid: FormatterId::Number,
+                opt_count: 2,
+            },
+            Opcode::EmitStack,
+            Opcode::End,
+        ];
+
+        let out = execute(&program, &Args::new(), &backend).expect("exec ok");
+        assert_eq!(
+            out,
+            "num:3.5;minimumFractionDigits=Num(2.0);useGrouping=Str(\"false\")"
+        );
+
+        program.opcodes[5] = Opcode::CallFmt {
+            fid: FormatterId::Number,
+            opt_count: 3,
+        };
+        let err = execute(&program, &Args::new(), &backend).expect_err("missing key");
+        assert_eq!(
+            err,
+            crate::CoreError::InvalidInput("formatter option key must be a string")
+        );
+    }
+
     #[test]
     fn executes_select_branch() {
         let backend = TestBackend;
@@ -382,6 +854,106 @@ mod tests {
         assert_eq!(out, "foo");
     }
 
+    #[test]
+    fn large_select_matches_sorted_cases() {
+        let backend = TestBackend;
+        let mut program = BytecodeProgram::new();
+        let country_arg = program.push_arg_name("country");
+        let mut entries = alloc::vec::Vec::new();
+        for index in (0..500u32).rev() {
+            let key = program.string_pool.push(alloc::format!("c{index:03}"));
+            let text = program.string_pool.push(alloc::format!("country {index}"));
+            entries.push(crate::CaseEntry {
+                key: crate::CaseKey::String(key),
+                target: program.opcodes.len() as u32 + 1,
+            });
+            program.opcodes.push(Opcode::EmitText { sidx: text });
+            program.opcodes.push(Opcode::End);
+        }
+        let other = program.string_pool.push("elsewhere");
+        entries.insert(
+            250,
+            crate::CaseEntry {
+                key: crate::CaseKey::Other,
+                target: program.opcodes.len() as u32 + 1,
+            },
+        );
+        program.opcodes.push(Opcode::EmitText { sidx: other });
+        program.opcodes.push(Opcode::End);
+        program.opcodes.insert(
+            0,
+            Opcode::Select {
+                aidx: country_arg,
+                table: 0,
+            },
+        );
+        let mut table = crate::CaseTable { entries };
+        table.sort_entries(&program.string_pool);
+        assert!(matches!(
+            table.entries.last().map(|entry| &entry.key),
+            Some(crate::CaseKey::Other)
+        ));
+        program.case_tables.push(table);
+
+        for (country, expected) in [
+            ("c000", "country 0"),
+            ("c137", "country 137"),
+            ("c499", "country 499"),
+            ("c500", "elsewhere"),
+            ("", "elsewhere"),
+        ] {
+            let mut args = Args::new();
+            args.insert("country", Value::Str(String::from(country)));
+            let out = execute(&program, &args, &backend).expect("exec ok");
+            assert_eq!(out, expected);
+        }
+    }
+
+    #[test]
+    fn reports_table_missing_other_case() {
+        let backend = TestBackend;
+        let mut program = BytecodeProgram::new();
+        let key_arg = program.push_arg_name("key");
+        let key_idx = program.string_pool.push("x");
+        program.case_tables.push(crate::CaseTable {
+            entries: vec![crate::CaseEntry {
+                key: crate::CaseKey::Other,
+                target: 2,
+            }],
+        });
+        program.case_tables.push(crate::CaseTable {
+            entries: vec![crate::CaseEntry {
+                key: crate::CaseKey::String(key_idx),
+                target: 2,
+            }],
+        });
+        program.opcodes = vec![
+            Opcode::Select {
+                aidx: key_arg,
+                table: 1,
+            },
+            Opcode::End,
+            Opcode::End,
+        ];
+
+        let mut args = Args::new();
+        args.insert("key", Value::Str(String::from("x")));
+        assert_eq!(execute(&program, &args, &backend).expect("exec ok"), "");
+        args.insert("key", Value::Str(String::from("y")));
+        let err = execute(&program, &args, &backend).expect_err("no other case");
+        assert_eq!(
+            err,
+            crate::CoreError::MissingOtherCase {
+                table: 1,
+                multi: false
+            }
+        );
+        assert_eq!(
+            alloc::string::ToString::to_string(&err),
+            "missing other case in case table 1"
+        );
+    }
+
     #[test]
     fn executes_plural_branch() {
         let backend = TestBackend;
@@ -406,6 +978,7 @@ mod tests {
                 aidx: count_arg,
                 ruleset: crate::PluralRuleset::Cardinal,
                 table: 0,
+                offset: 0.0,
             },
             Opcode::EmitText { sidx: one_idx },
             Opcode::Jump { rel: 2 },
@@ -418,4 +991,304 @@ mod tests {
         let out = execute(&program, &args, &backend).expect("exec ok");
         assert_eq!(out, "other");
     }
+
+    struct OrdinalBackend;
+
+    impl FormatBackend for OrdinalBackend {
+        fn plural_category(&self, _value: f64) -> crate::CoreResult<PluralCategory> {
+            Ok(PluralCategory::Other)
+        }
+
+        fn ordinal_category(&self, value: f64) -> crate::CoreResult<PluralCategory> {
+            if value == 2.0 {
+                Ok(PluralCategory::Two)
+            } else {
+                Ok(PluralCategory::Other)
+            }
+        }
+
+        fn format_number(
+            &self,
+            value: f64,
+            options: &[FormatterOption],
+        ) -> crate::CoreResult<String> {
+            TestBackend.format_number(value, options)
+        }
+
+        fn format_date(
+            &self,
+            value: i64,
+            options: &[FormatterOption],
+        ) -> crate::CoreResult<String> {
+            TestBackend.format_date(value, options)
+        }
+
+        fn format_time(
+            &self,
+            value: i64,
+            options: &[FormatterOption],
+        ) -> crate::CoreResult<String> {
+            TestBackend.format_time(value, options)
+        }
+
+        fn format_datetime(
+            &self,
+            value: i64,
+            options: &[FormatterOption],
+        ) -> crate::CoreResult<String> {
+            TestBackend.format_datetime(value, options)
+        }
+
+        fn format_unit(
+            &self,
+            value: f64,
+            unit_id: u32,
+            options: &[FormatterOption],
+        ) -> crate::CoreResult<String> {
+            TestBackend.format_unit(value, unit_id, options)
+        }
+
+        fn format_currency(
+            &self,
+            value: f64,
+            code: [u8; 3],
+            options: &[FormatterOption],
+        ) -> crate::CoreResult<String> {
+            TestBackend.format_currency(value, code, options)
+        }
+    }
+
+    #[test]
+    fn executes_ordinal_branch() {
+        let mut program = BytecodeProgram::new();
+        let place_arg = program.push_arg_name("place");
+        let nd_idx = program.string_pool.push("nd");
+        let th_idx = program.string_pool.push("th");
+        program.case_tables.push(crate::CaseTable {
+            entries: vec![
+                crate::CaseEntry {
+                    key: crate::CaseKey::Category(PluralCategory::Two),
+                    target: 1,
+                },
+                crate::CaseEntry {
+                    key: crate::CaseKey::Other,
+                    target: 3,
+                },
+            ],
+        });
+        program.opcodes = vec![
+            Opcode::SelectPlural {
+                aidx: place_arg,
+                ruleset: crate::PluralRuleset::Ordinal,
+                table: 0,
+                offset: 0.0,
+            },
+            Opcode::EmitText { sidx: nd_idx },
+            Opcode::Jump { rel: 2 },
+            Opcode::EmitText { sidx: th_idx },
+            Opcode::End,
+        ];
+
+        let mut args = Args::new();
+        args.insert("place", Value::Num(2.0));
+        let out = execute(&program, &args, &OrdinalBackend).expect("exec ok");
+        assert_eq!(out, "nd");
+
+        let out = execute(&program, &args, &TestBackend).expect("exec ok");
+        assert_eq!(out, "th");
+    }
+
+    #[test]
+    fn plural_offset_applies_before_exact_match() {
+        let backend = TestBackend;
+        let mut program = BytecodeProgram::new();
+        let count_arg = program.push_arg_name("count");
+        let solo_idx = program.string_pool.push("just you");
+        let others_idx = program.string_pool.push("you and ");
+        program.case_tables.push(crate::CaseTable {
+            entries: vec![
+                crate::CaseEntry {
+                    key: crate::CaseKey::Exact(0),
+                    target: 1,
+                },
+                crate::CaseEntry {
+                    key: crate::CaseKey::Other,
+                    target: 3,
+                },
+            ],
+        });
+        program.opcodes = vec![
+            Opcode::SelectPlural {
+                aidx: count_arg,
+                ruleset: crate::PluralRuleset::Cardinal,
+                table: 0,
+                offset: 1.0,
+            },
+            Opcode::EmitText { sidx: solo_idx },
+            Opcode::Jump { rel: 5 },
+            Opcode::EmitText { sidx: others_idx },
+            Opcode::PushPluralArg {
+                aidx: count_arg,
+                offset: 1.0,
+            },
+            Opcode::CallFmt {
+                fid: FormatterId::Number,
+                opt_count: 0,
+            },
+            Opcode::EmitStack,
+            Opcode::End,
+        ];
+
+        let mut args = Args::new();
+        args.insert("count", Value::Num(1.0));
+        let out = execute(&program, &args, &backend).expect("exec ok");
+        assert_eq!(out, "just you");
+
+        let mut args = Args::new();
+        args.insert("count", Value::Num(3.0));
+        let out = execute(&program, &args, &backend).expect("exec ok");
+        assert_eq!(out, "you and num:2");
+    }
+
+    #[test]
+    fn exact_match_handles_large_and_negative_integers() {
+        let backend = TestBackend;
+        let mut program = BytecodeProgram::new();
+        let delta_arg = program.push_arg_name("delta");
+        let large_idx = program.string_pool.push("a trillion");
+        let negative_idx = program.string_pool.push("one behind");
+        let other_idx = program.string_pool.push("other");
+        program.case_tables.push(crate::CaseTable {
+            entries: vec![
+                crate::CaseEntry {
+                    key: crate::CaseKey::Exact(1_000_000_000_000),
+                    target: 1,
+                },
+                crate::CaseEntry {
+                    key: crate::CaseKey::Exact(-1),
+                    target: 3,
+                },
+                crate::CaseEntry {
+                    key: crate::CaseKey::Other,
+                    target: 5,
+                },
+            ],
+        });
+        program.case_tables[0].sort_entries(&program.string_pool);
+        program.opcodes = vec![
+            Opcode::SelectPlural {
+                aidx: delta_arg,
+                ruleset: crate::PluralRuleset::Cardinal,
+                table: 0,
+                offset: 0.0,
+            },
+            Opcode::EmitText { sidx: large_idx },
+            Opcode::Jump { rel: 4 },
+            Opcode::EmitText { sidx: negative_idx },
+            Opcode::Jump { rel: 2 },
+            Opcode::EmitText { sidx: other_idx },
+            Opcode::End,
+        ];
+
+        let cases = [
+            (Value::Int(1_000_000_000_000), "a trillion"),
+            (Value::Int(1_000_000_000_001), "other"),
+            (Value::Int(-1), "one behind"),
+            (Value::Num(-1.0), "one behind"),
+            (Value::Num(-1.5), "other"),
+        ];
+        for (value, expected) in cases {
+            let mut args = Args::new();
+            args.insert("delta", value);
+            let out = execute(&program, &args, &backend).expect("exec ok");
+            assert_eq!(out, expected);
+        }
+    }
+
+    #[test]
+    fn executes_multi_select_best_match() {
+        let mut program = BytecodeProgram::new();
+        let count_arg = program.push_arg_name("count");
+        let gender_arg = program.push_arg_name("gender");
+        let female_idx = program.string_pool.push("female");
+        let a_idx = program.string_pool.push("a");
+        let b_idx = program.string_pool.push("b");
+        let c_idx = program.string_pool.push("c");
+        program.multi_case_tables.push(crate::MultiCaseTable {
+            selectors: vec![
+                crate::MultiSelector {
+                    aidx: count_arg,
+                    ruleset: Some(crate::PluralRuleset::Ordinal),
+                },
+                crate::MultiSelector {
+                    aidx: gender_arg,
+                    ruleset: None,
+                },
+            ],
+            entries: vec![
+                crate::MultiCaseEntry {
+                    keys: vec![crate::CaseKey::Other, crate::CaseKey::String(female_idx)],
+                    target: 1,
+                },
+                crate::MultiCaseEntry {
+                    keys: vec![
+                        crate::CaseKey::Category(PluralCategory::Two),
+                        crate::CaseKey::Other,
+                    ],
+                    target: 3,
+                },
+                crate::MultiCaseEntry {
+                    keys: vec![crate::CaseKey::Other, crate::CaseKey::Other],
+                    target: 5,
+                },
+            ],
+        });
+        program.opcodes = vec![
+            Opcode::SelectMulti { table: 0 },
+            Opcode::EmitText { sidx: a_idx },
+            Opcode::Jump { rel: 4 },
+            Opcode::EmitText { sidx: b_idx },
+            Opcode::Jump { rel: 2 },
+            Opcode::EmitText { sidx: c_idx },
+            Opcode::End,
+        ];
+
+        let mut args = Args::new();
+        args.insert("count", Value::Num(2.0));
+        args.insert("gender", Value::Str(String::from("female")));
+        let out = execute(&program, &args, &OrdinalBackend).expect("exec ok");
+        assert_eq!(out, "b");
+
+        let out = execute(&program, &args, &TestBackend).expect("exec ok");
+        assert_eq!(out, "a");
+
+        args.insert("gender", Value::Str(String::from("male")));
+        let out = execute(&program, &args, &TestBackend).expect("exec ok");
+        assert_eq!(out, "c");
+    }
+
+    #[test]
+    fn self_loop_exhausts_instruction_budget() {
+        let mut program = BytecodeProgram::new();
+        program.opcodes = vec![Opcode::Jump { rel: 0 }, Opcode::End];
+
+        let args = Args::new();
+        let err = execute(&program, &args, &TestBackend).expect_err("budget");
+        assert_eq!(
+            err,
+            crate::CoreError::Unsupported("instruction budget exceeded")
+        );
+    }
+
+    #[test]
+    fn respects_custom_instruction_limit() {
+        let mut program = BytecodeProgram::new();
+        let hello = program.string_pool.push("hi");
+        program.opcodes = vec![Opcode::EmitText { sidx: hello }, Opcode::End];
+
+        let args = Args::new();
+        assert!(execute_with_limit(&program, &args, &TestBackend, 1).is_err());
+        let out = execute_with_limit(&program, &args, &TestBackend, 2).expect("exec ok");
+        assert_eq!(out, "hi");
+    }
 }
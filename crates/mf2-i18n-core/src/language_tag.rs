@@ -1,6 +1,8 @@
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 
+#[cfg(feature = "likely-subtags")]
+use crate::likely_subtags::likely_subtags;
 use crate::{CoreError, CoreResult};
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -8,6 +10,10 @@ pub struct LanguageTag {
     original: String,
     normalized: String,
     match_subtags: Vec<String>,
+    script: Option<String>,
+    region: Option<String>,
+    variants: Vec<String>,
+    grandfathered: bool,
 }
 
 impl LanguageTag {
@@ -22,44 +28,117 @@ impl LanguageTag {
             return Err(CoreError::InvalidInput("language tag has empty subtag"));
         }
 
+        let lower = trimmed.to_ascii_lowercase();
+        if let Some((_, preferred)) = GRANDFATHERED.iter().find(|(tag, _)| *tag == lower) {
+            return match preferred {
+                Some(preferred) => {
+                    let mut tag = Self::parse(preferred)?;
+                    tag.original = trimmed.to_string();
+                    Ok(tag)
+                }
+                None => Ok(Self::verbatim(trimmed, lower, true)),
+            };
+        }
+        if subtags[0].eq_ignore_ascii_case("x") {
+            if subtags.len() == 1
+                || !subtags
+                    .iter()
+                    .all(|part| part.chars().all(|ch| ch.is_ascii_alphanumeric()))
+            {
+                return Err(CoreError::InvalidInput("invalid private use subtag"));
+            }
+            return Ok(Self::verbatim(trimmed, lower, false));
+        }
+
         let mut normalized_parts = Vec::with_capacity(subtags.len());
         let mut match_parts = Vec::with_capacity(subtags.len());
-        let mut script_seen = false;
-        let mut region_seen = false;
-        let mut stop_for_match = false;
+        let mut position = Position::Language;
+        let mut extension_open = false;
+        let mut private_use = false;
+        let mut script_subtag = None;
+        let mut region_subtag = None;
+        let mut variants: Vec<String> = Vec::new();
 
         for (idx, part) in subtags.iter().enumerate() {
             let part = part.trim();
+            if !part.chars().all(|ch| ch.is_ascii_alphanumeric()) {
+                return Err(CoreError::InvalidInput("invalid language subtag"));
+            }
             if idx == 0 {
                 if !is_alpha(part) || !(2..=8).contains(&part.len()) {
                     return Err(CoreError::InvalidInput("invalid language subtag"));
                 }
                 let lower = part.to_ascii_lowercase();
-                normalized_parts.push(lower.clone());
-                match_parts.push(lower);
+                let (language, script) = canonical_language(&lower);
+                normalized_parts.push(language.to_string());
+                match_parts.push(language.to_string());
+                if let Some(script) = script
+                    && !subtags
+                        .get(1)
+                        .is_some_and(|next| next.len() == 4 && is_alpha(next))
+                {
+                    normalized_parts.push(script.to_string());
+                    match_parts.push(script.to_string());
+                    script_subtag = Some(script.to_string());
+                    position = Position::Script;
+                }
+                continue;
+            }
+
+            if private_use {
+                extension_open = false;
+                normalized_parts.push(part.to_ascii_lowercase());
                 continue;
             }
 
             if part.len() == 1 {
-                stop_for_match = true;
+                if extension_open {
+                    return Err(CoreError::InvalidInput("extension subtag missing"));
+                }
+                position = Position::Extension;
+                extension_open = true;
+                private_use = part.eq_ignore_ascii_case("x");
                 normalized_parts.push(part.to_ascii_lowercase());
                 continue;
             }
 
-            let normalized = if !script_seen && part.len() == 4 && is_alpha(part) {
-                script_seen = true;
-                titlecase(part)
-            } else if !region_seen && is_region(part) {
-                region_seen = true;
-                part.to_ascii_uppercase()
-            } else {
+            if position == Position::Extension {
+                extension_open = false;
+                normalized_parts.push(part.to_ascii_lowercase());
+                continue;
+            }
+
+            let normalized = if position < Position::Script && part.len() == 3 && is_alpha(part) {
+                position = Position::ExtLang;
                 part.to_ascii_lowercase()
+            } else if position < Position::Script && part.len() == 4 && is_alpha(part) {
+                position = Position::Script;
+                let script = titlecase(part);
+                script_subtag = Some(script.clone());
+                script
+            } else if position < Position::Region && is_region(part) {
+                position = Position::Region;
+                let region = canonical_region(&part.to_ascii_uppercase());
+                region_subtag = Some(region.clone());
+                region
+            } else if is_variant(part) {
+                position = Position::Variant;
+                let lower = part.to_ascii_lowercase();
+                if variants.contains(&lower) {
+                    return Err(CoreError::InvalidInput("duplicate variant subtag"));
+                }
+                variants.push(lower.clone());
+                lower
+            } else {
+                return Err(CoreError::InvalidInput("language subtag out of order"));
             };
 
             normalized_parts.push(normalized.clone());
-            if !stop_for_match {
-                match_parts.push(normalized);
-            }
+            match_parts.push(normalized);
+        }
+
+        if extension_open {
+            return Err(CoreError::InvalidInput("extension subtag missing"));
         }
 
         let normalized = normalized_parts.join("-");
@@ -68,9 +147,25 @@ impl LanguageTag {
             original: trimmed.to_string(),
             normalized,
             match_subtags: match_parts,
+            script: script_subtag,
+            region: region_subtag,
+            variants,
+            grandfathered: false,
         })
     }
 
+    fn verbatim(original: &str, normalized: String, grandfathered: bool) -> Self {
+        Self {
+            original: original.to_string(),
+            match_subtags: alloc::vec![normalized.clone()],
+            normalized,
+            script: None,
+            region: None,
+            variants: Vec::new(),
+            grandfathered,
+        }
+    }
+
     pub fn original(&self) -> &str {
         &self.original
     }
@@ -82,6 +177,159 @@ impl LanguageTag {
     pub fn match_subtags(&self) -> &[String] {
         &self.match_subtags
     }
+
+    pub fn is_grandfathered(&self) -> bool {
+        self.grandfathered
+    }
+
+    pub fn language(&self) -> &str {
+        &self.match_subtags[0]
+    }
+
+    pub fn script(&self) -> Option<&str> {
+        self.script.as_deref()
+    }
+
+    pub fn region(&self) -> Option<&str> {
+        self.region.as_deref()
+    }
+
+    pub fn variants(&self) -> &[String] {
+        &self.variants
+    }
+
+    #[cfg(feature = "likely-subtags")]
+    pub fn maximize(&self) -> LanguageTag {
+        match likely_subtags(self.language(), self.script(), self.region()) {
+            Some((script, region)) => self.with_script_region(Some(script), Some(region)),
+            None => self.clone(),
+        }
+    }
+
+    #[cfg(feature = "likely-subtags")]
+    pub fn minimize(&self) -> LanguageTag {
+        let Some(maximized) = likely_subtags(self.language(), self.script(), self.region()) else {
+            return self.clone();
+        };
+        let (script, region) = maximized;
+        let candidates = [(None, None), (None, Some(region)), (Some(script), None)];
+        let (script, region) = candidates
+            .into_iter()
+            .find(|(script, region)| {
+                likely_subtags(self.language(), *script, *region) == Some(maximized)
+            })
+            .unwrap_or((Some(script), Some(region)));
+        self.with_script_region(script, region)
+    }
+
+    #[cfg(feature = "likely-subtags")]
+    fn with_script_region(&self, script: Option<&str>, region: Option<&str>) -> LanguageTag {
+        let mut parts: Vec<&str> = Vec::new();
+        let mut tail = self.normalized.split('-');
+        parts.push(tail.next().unwrap_or_default());
+        let mut inserted = false;
+        for part in tail.by_ref() {
+            if part.len() == 1 {
+                parts.extend(script.into_iter().chain(region).filter(|_| !inserted));
+                inserted = true;
+                parts.push(part);
+                break;
+            }
+            let is_extlang = part.len() == 3 && is_alpha(part);
+            let is_script = part.len() == 4 && is_alpha(part);
+            if !is_extlang && !inserted {
+                parts.extend(script.into_iter().chain(region));
+                inserted = true;
+            }
+            if is_extlang || !(is_script || is_region(part)) {
+                parts.push(part);
+            }
+        }
+        if !inserted {
+            parts.extend(script.into_iter().chain(region));
+        }
+        parts.extend(tail);
+        LanguageTag::parse(&parts.join("-")).unwrap_or_else(|_| self.clone())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Position {
+    Language,
+    ExtLang,
+    Script,
+    Region,
+    Variant,
+    Extension,
+}
+
+const GRANDFATHERED: &[(&str, Option<&str>)] = &[
+    ("art-lojban", Some("jbo")),
+    ("cel-gaulish", None),
+    ("en-gb-oed", Some("en-GB-oxendict")),
+    ("i-ami", Some("ami")),
+    ("i-bnn", Some("bnn")),
+    ("i-default", None),
+    ("i-enochian", None),
+    ("i-hak", Some("hak")),
+    ("i-klingon", Some("tlh")),
+    ("i-lux", Some("lb")),
+    ("i-mingo", None),
+    ("i-navajo", Some("nv")),
+    ("i-pwn", Some("pwn")),
+    ("i-tao", Some("tao")),
+    ("i-tay", Some("tay")),
+    ("i-tsu", Some("tsu")),
+    ("no-bok", Some("nb")),
+    ("no-nyn", Some("nn")),
+    ("sgn-be-fr", Some("sfb")),
+    ("sgn-be-nl", Some("vgt")),
+    ("sgn-ch-de", Some("sgg")),
+    ("zh-guoyu", Some("cmn")),
+    ("zh-hakka", Some("hak")),
+    ("zh-min", None),
+    ("zh-min-nan", Some("nan")),
+    ("zh-xiang", Some("hsn")),
+];
+
+const LANGUAGE_ALIASES: &[(&str, &str, Option<&str>)] = &[
+    ("in", "id", None),
+    ("iw", "he", None),
+    ("ji", "yi", None),
+    ("jw", "jv", None),
+    ("mo", "ro", None),
+    ("sh", "sr", Some("Latn")),
+];
+
+const REGION_ALIASES: &[(&str, &str)] = &[
+    ("BU", "MM"),
+    ("DD", "DE"),
+    ("FX", "FR"),
+    ("TP", "TL"),
+    ("YD", "YE"),
+    ("ZR", "CD"),
+];
+
+fn canonical_language(language: &str) -> (&str, Option<&'static str>) {
+    LANGUAGE_ALIASES
+        .iter()
+        .find(|(alias, _, _)| *alias == language)
+        .map_or((language, None), |(_, replacement, script)| {
+            (*replacement, *script)
+        })
+}
+
+fn canonical_region(region: &str) -> String {
+    REGION_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == region)
+        .map_or(region, |(_, replacement)| *replacement)
+        .to_string()
+}
+
+fn is_variant(value: &str) -> bool {
+    (5..=8).contains(&value.len())
+        || (value.len() == 4 && value.starts_with(|ch: char| ch.is_ascii_digit()))
 }
 
 fn is_alpha(value: &str) -> bool {
@@ -122,6 +370,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn exposes_parsed_subtags() {
+        let tag = LanguageTag::parse("zh-Hant-TW").expect("valid tag");
+        assert_eq!(tag.language(), "zh");
+        assert_eq!(tag.script(), Some("Hant"));
+        assert_eq!(tag.region(), Some("TW"));
+        assert!(tag.variants().is_empty());
+
+        let tag = LanguageTag::parse("sh-BU-1996-fonipa-u-co-phonebk").expect("valid tag");
+        assert_eq!(tag.language(), "sr");
+        assert_eq!(tag.script(), Some("Latn"));
+        assert_eq!(tag.region(), Some("MM"));
+        assert_eq!(
+            tag.variants(),
+            &[String::from("1996"), String::from("fonipa")]
+        );
+
+        let tag = LanguageTag::parse("i-default").expect("valid tag");
+        assert_eq!(tag.language(), "i-default");
+        assert_eq!(tag.script(), None);
+        assert_eq!(tag.region(), None);
+    }
+
     #[test]
     fn stops_matching_on_extensions() {
         let tag = LanguageTag::parse("de-DE-u-co-phonebk").expect("valid tag");
@@ -142,6 +413,125 @@ mod tests {
         );
     }
 
+    #[test]
+    fn accepts_single_character_private_use_subtags() {
+        for (input, normalized) in [
+            ("en-x-a-b", "en-x-a-b"),
+            ("de-DE-x-1", "de-DE-x-1"),
+            ("fr-FR-x-1-2", "fr-FR-x-1-2"),
+            ("en-u-ca-x-a", "en-u-ca-x-a"),
+            ("en-a-bb-x-y", "en-a-bb-x-y"),
+        ] {
+            let tag = LanguageTag::parse(input).expect("valid tag");
+            assert_eq!(tag.normalized(), normalized);
+        }
+        assert!(LanguageTag::parse("en-x").is_err());
+        assert!(LanguageTag::parse("en-u-x-a").is_err());
+    }
+
+    #[test]
+    fn canonicalizes_deprecated_language() {
+        let tag = LanguageTag::parse("iw-IL").expect("valid tag");
+        assert_eq!(tag.original(), "iw-IL");
+        assert_eq!(tag.normalized(), "he-IL");
+        assert_eq!(
+            tag.match_subtags(),
+            &[String::from("he"), String::from("IL")]
+        );
+    }
+
+    #[test]
+    fn canonicalizes_alias_with_implied_script() {
+        let tag = LanguageTag::parse("sh-RS").expect("valid tag");
+        assert_eq!(tag.normalized(), "sr-Latn-RS");
+        let tag = LanguageTag::parse("sh-Cyrl").expect("valid tag");
+        assert_eq!(tag.normalized(), "sr-Cyrl");
+    }
+
+    #[test]
+    fn keeps_variants_after_region() {
+        let tag = LanguageTag::parse("CA-es-VALENCIA").expect("valid tag");
+        assert_eq!(tag.normalized(), "ca-ES-valencia");
+        assert_eq!(
+            tag.match_subtags(),
+            &[
+                String::from("ca"),
+                String::from("ES"),
+                String::from("valencia")
+            ]
+        );
+        let tag = LanguageTag::parse("de-CH-1996-x-test").expect("valid tag");
+        assert_eq!(tag.normalized(), "de-CH-1996-x-test");
+    }
+
+    #[test]
+    fn rejects_misplaced_variant() {
+        let err = LanguageTag::parse("ca-valencia-ES").expect_err("region after variant");
+        assert_eq!(
+            err,
+            crate::CoreError::InvalidInput("language subtag out of order")
+        );
+        let err = LanguageTag::parse("de-u").expect_err("empty extension");
+        assert_eq!(
+            err,
+            crate::CoreError::InvalidInput("extension subtag missing")
+        );
+    }
+
+    #[cfg(feature = "likely-subtags")]
+    #[test]
+    fn maximize_round_trips_through_minimize() {
+        for (input, maximized) in [
+            ("en", "en-Latn-US"),
+            ("zh-TW", "zh-Hant-TW"),
+            ("sr-Latn", "sr-Latn-RS"),
+            ("ca-ES-valencia-u-nu-latn", "ca-Latn-ES-valencia-u-nu-latn"),
+        ] {
+            let tag = LanguageTag::parse(input).expect("valid tag");
+            let max = tag.maximize();
+            assert_eq!(max.normalized(), maximized);
+            assert_eq!(max.minimize().normalized(), tag.minimize().normalized());
+        }
+        let tag = LanguageTag::parse("zh-Hant-TW").expect("valid tag");
+        assert_eq!(tag.minimize().normalized(), "zh-TW");
+        let tag = LanguageTag::parse("qaa-Latn").expect("valid tag");
+        assert_eq!(tag.maximize(), tag);
+    }
+
+    #[test]
+    fn maps_grandfathered_tags_to_preferred_values() {
+        let tag = LanguageTag::parse("i-klingon").expect("valid tag");
+        assert_eq!(tag.original(), "i-klingon");
+        assert_eq!(tag.normalized(), "tlh");
+        assert_eq!(tag.match_subtags(), &[String::from("tlh")]);
+        let tag = LanguageTag::parse("zh-min-nan").expect("valid tag");
+        assert_eq!(tag.normalized(), "nan");
+        let tag = LanguageTag::parse("en-GB-oed").expect("valid tag");
+        assert_eq!(tag.normalized(), "en-GB-oxendict");
+        assert!(!tag.is_grandfathered());
+    }
+
+    #[test]
+    fn preserves_grandfathered_tags_without_replacement() {
+        let tag = LanguageTag::parse("I-Default").expect("valid tag");
+        assert_eq!(tag.normalized(), "i-default");
+        assert_eq!(tag.match_subtags(), &[String::from("i-default")]);
+        assert!(tag.is_grandfathered());
+    }
+
+    #[test]
+    fn accepts_private_use_only_tag() {
+        let tag = LanguageTag::parse("x-Pirate-Speak").expect("valid tag");
+        assert_eq!(tag.normalized(), "x-pirate-speak");
+        assert_eq!(tag.match_subtags(), &[String::from("x-pirate-speak")]);
+        assert!(!tag.is_grandfathered());
+        let err = LanguageTag::parse("x").expect_err("bare singleton");
+        assert_eq!(
+            err,
+            crate::CoreError::InvalidInput("invalid private use subtag")
+        );
+    }
+
     #[test]
     fn rejects_empty_tag() {
         let err = LanguageTag::parse(" ").expect_err("empty tag should fail");
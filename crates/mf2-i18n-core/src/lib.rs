@@ -5,36 +5,82 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+mod analyze;
 mod args;
 mod bytecode;
+#[cfg(feature = "canonical-json")]
+mod canonical_json;
 mod catalog;
+#[cfg(feature = "cldr-plurals")]
+mod cldr_plurals;
+mod currency;
 mod error;
 mod format_backend;
+mod icu_data;
+#[cfg(feature = "id-map-hash")]
+mod id_map;
 mod interpreter;
 mod language_tag;
+#[cfg(feature = "likely-subtags")]
+mod likely_subtags;
+mod markup;
 mod negotiation;
+mod number_format;
 mod pack;
 mod pack_catalog;
 mod pack_decode;
+#[cfg(feature = "pack-encode")]
+mod pack_encode;
+mod timestamp;
 mod types;
+mod verify;
 
+pub use analyze::{ArgProblem, ArgRequirement, MessageRequirements, analyze};
 pub use args::{ArgType, Args, Value};
 pub use bytecode::{
-    BytecodeProgram, CaseEntry, CaseKey, CaseTable, Opcode, PluralRuleset, StringPool,
+    BytecodeProgram, CaseEntry, CaseKey, CaseTable, MultiCaseEntry, MultiCaseTable, MultiSelector,
+    Opcode, PluralRuleset, SharedTables, StringPool,
 };
+#[cfg(feature = "canonical-json")]
+pub use canonical_json::canonical_json;
 pub use catalog::{Catalog, CatalogChain};
+#[cfg(feature = "cldr-plurals")]
+pub use cldr_plurals::CldrPluralBackend;
 pub use error::{CoreError, CoreResult};
 pub use format_backend::{
-    FormatBackend, FormatterId, FormatterOption, FormatterOptionValue, PluralCategory, format_value,
+    FormatBackend, FormatterId, FormatterOption, FormatterOptionValue, PluralCategory, find_option,
+    format_value,
+};
+pub use icu_data::{
+    IcuDataPack, NumberSymbols, PluralOperand, PluralRelation, PluralRule, PluralRules,
+};
+#[cfg(feature = "id-map-hash")]
+pub use id_map::id_map_hash;
+pub use interpreter::{
+    DEFAULT_INSTRUCTION_LIMIT, ExecuteOptions, execute, execute_strict, execute_with_limit,
+    execute_with_markup, execute_with_options,
 };
-pub use interpreter::execute;
 pub use language_tag::LanguageTag;
+pub use markup::{IgnoreMarkup, MarkupSink};
 pub use negotiation::{
-    NegotiationResult, NegotiationTrace, negotiate_lookup, negotiate_lookup_with_trace,
+    NegotiationResult, NegotiationTrace, negotiate_lookup, negotiate_lookup_with_fallbacks,
+    negotiate_lookup_with_promotion, negotiate_lookup_with_trace, parse_accept_language,
+};
+pub use number_format::{
+    CurrencyPlacement, StyledNumberBackend, format_currency_value, format_decimal,
+};
+pub use pack::{
+    PACK_FLAG_SECTION_CHECKSUMS, PACK_SCHEMA_VERSION, PackHeader, PackKind, SectionEntry, crc32,
+    parse_pack_header, parse_section_directory, section_bytes,
 };
-pub use pack::{PackHeader, PackKind, SectionEntry, parse_pack_header, parse_section_directory};
 pub use pack_catalog::PackCatalog;
 pub use pack_decode::{
     decode_dense_index, decode_sparse_index, decode_string_pool, read_bytecode_at,
 };
+#[cfg(feature = "pack-encode")]
+pub use pack_encode::{
+    IcuDataBuildInput, PackBuildInput, PackEncodeError, encode_icu_data_pack, encode_pack,
+};
+pub use timestamp::parse_rfc3339;
 pub use types::{Key, MessageId};
+pub use verify::verify;
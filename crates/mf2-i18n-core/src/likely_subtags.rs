@@ -0,0 +1,345 @@
+pub(crate) fn likely_subtags<'a>(
+    language: &str,
+    script: Option<&'a str>,
+    region: Option<&'a str>,
+) -> Option<(&'a str, &'a str)> {
+    let base = LANGUAGES
+        .binary_search_by_key(&language, |(language, _, _)| language)
+        .ok()
+        .map(|idx| (LANGUAGES[idx].1, LANGUAGES[idx].2));
+    let script = script
+        .or_else(|| region.and_then(|region| lookup_pair(LANGUAGE_REGIONS, language, region)))
+        .or(base.map(|(script, _)| script))?;
+    let region = region
+        .or_else(|| lookup_pair(LANGUAGE_SCRIPTS, language, script))
+        .or(base.map(|(_, region)| region))?;
+    Some((script, region))
+}
+
+fn lookup_pair(
+    table: &[(&str, &str, &'static str)],
+    language: &str,
+    key: &str,
+) -> Option<&'static str> {
+    table
+        .binary_search_by(|(lang, k, _)| (*lang, *k).cmp(&(language, key)))
+        .ok()
+        .map(|idx| table[idx].2)
+}
+
+const LANGUAGES: &[(&str, &str, &str)] = &[
+    ("af", "Latn", "ZA"),
+    ("agq", "Latn", "CM"),
+    ("ak", "Latn", "GH"),
+    ("am", "Ethi", "ET"),
+    ("an", "Latn", "ES"),
+    ("ar", "Arab", "EG"),
+    ("arn", "Latn", "CL"),
+    ("as", "Beng", "IN"),
+    ("ast", "Latn", "ES"),
+    ("av", "Cyrl", "RU"),
+    ("ay", "Latn", "BO"),
+    ("az", "Latn", "AZ"),
+    ("ba", "Cyrl", "RU"),
+    ("bal", "Arab", "PK"),
+    ("bas", "Latn", "CM"),
+    ("be", "Cyrl", "BY"),
+    ("bem", "Latn", "ZM"),
+    ("bg", "Cyrl", "BG"),
+    ("bgc", "Deva", "IN"),
+    ("bho", "Deva", "IN"),
+    ("bi", "Latn", "VU"),
+    ("bm", "Latn", "ML"),
+    ("bn", "Beng", "BD"),
+    ("bo", "Tibt", "CN"),
+    ("br", "Latn", "FR"),
+    ("brx", "Deva", "IN"),
+    ("bs", "Latn", "BA"),
+    ("ca", "Latn", "ES"),
+    ("ce", "Cyrl", "RU"),
+    ("ceb", "Latn", "PH"),
+    ("ch", "Latn", "GU"),
+    ("chr", "Cher", "US"),
+    ("ckb", "Arab", "IQ"),
+    ("co", "Latn", "FR"),
+    ("cs", "Latn", "CZ"),
+    ("cv", "Cyrl", "RU"),
+    ("cy", "Latn", "GB"),
+    ("da", "Latn", "DK"),
+    ("de", "Latn", "DE"),
+    ("doi", "Deva", "IN"),
+    ("dsb", "Latn", "DE"),
+    ("dua", "Latn", "CM"),
+    ("dv", "Thaa", "MV"),
+    ("dyo", "Latn", "SN"),
+    ("dz", "Tibt", "BT"),
+    ("ee", "Latn", "GH"),
+    ("el", "Grek", "GR"),
+    ("en", "Latn", "US"),
+    ("eo", "Latn", "001"),
+    ("es", "Latn", "ES"),
+    ("et", "Latn", "EE"),
+    ("eu", "Latn", "ES"),
+    ("ewo", "Latn", "CM"),
+    ("fa", "Arab", "IR"),
+    ("ff", "Latn", "SN"),
+    ("fi", "Latn", "FI"),
+    ("fil", "Latn", "PH"),
+    ("fj", "Latn", "FJ"),
+    ("fo", "Latn", "FO"),
+    ("fon", "Latn", "BJ"),
+    ("fr", "Latn", "FR"),
+    ("fur", "Latn", "IT"),
+    ("fy", "Latn", "NL"),
+    ("ga", "Latn", "IE"),
+    ("gaa", "Latn", "GH"),
+    ("gan", "Hans", "CN"),
+    ("gd", "Latn", "GB"),
+    ("gl", "Latn", "ES"),
+    ("gn", "Latn", "PY"),
+    ("gom", "Deva", "IN"),
+    ("gsw", "Latn", "CH"),
+    ("gu", "Gujr", "IN"),
+    ("gv", "Latn", "IM"),
+    ("ha", "Latn", "NG"),
+    ("hak", "Hans", "CN"),
+    ("haw", "Latn", "US"),
+    ("he", "Hebr", "IL"),
+    ("hi", "Deva", "IN"),
+    ("hil", "Latn", "PH"),
+    ("hne", "Deva", "IN"),
+    ("hr", "Latn", "HR"),
+    ("hsb", "Latn", "DE"),
+    ("hsn", "Hans", "CN"),
+    ("ht", "Latn", "HT"),
+    ("hu", "Latn", "HU"),
+    ("hy", "Armn", "AM"),
+    ("ia", "Latn", "001"),
+    ("id", "Latn", "ID"),
+    ("ig", "Latn", "NG"),
+    ("ii", "Yiii", "CN"),
+    ("ilo", "Latn", "PH"),
+    ("io", "Latn", "001"),
+    ("is", "Latn", "IS"),
+    ("it", "Latn", "IT"),
+    ("iu", "Cans", "CA"),
+    ("ja", "Jpan", "JP"),
+    ("jgo", "Latn", "CM"),
+    ("jv", "Latn", "ID"),
+    ("ka", "Geor", "GE"),
+    ("kab", "Latn", "DZ"),
+    ("kea", "Latn", "CV"),
+    ("kg", "Latn", "CD"),
+    ("khq", "Latn", "ML"),
+    ("ki", "Latn", "KE"),
+    ("kk", "Cyrl", "KZ"),
+    ("kkj", "Latn", "CM"),
+    ("kl", "Latn", "GL"),
+    ("km", "Khmr", "KH"),
+    ("kn", "Knda", "IN"),
+    ("ko", "Kore", "KR"),
+    ("kok", "Deva", "IN"),
+    ("kri", "Latn", "SL"),
+    ("ks", "Arab", "IN"),
+    ("ksf", "Latn", "CM"),
+    ("ksh", "Latn", "DE"),
+    ("ku", "Latn", "TR"),
+    ("kv", "Cyrl", "RU"),
+    ("kw", "Latn", "GB"),
+    ("ky", "Cyrl", "KG"),
+    ("la", "Latn", "VA"),
+    ("lb", "Latn", "LU"),
+    ("lg", "Latn", "UG"),
+    ("li", "Latn", "NL"),
+    ("lij", "Latn", "IT"),
+    ("lkt", "Latn", "US"),
+    ("lmo", "Latn", "IT"),
+    ("ln", "Latn", "CD"),
+    ("lo", "Laoo", "LA"),
+    ("lrc", "Arab", "IR"),
+    ("lt", "Latn", "LT"),
+    ("lu", "Latn", "CD"),
+    ("luo", "Latn", "KE"),
+    ("lus", "Latn", "IN"),
+    ("lv", "Latn", "LV"),
+    ("mag", "Deva", "IN"),
+    ("mai", "Deva", "IN"),
+    ("mfe", "Latn", "MU"),
+    ("mg", "Latn", "MG"),
+    ("mgo", "Latn", "CM"),
+    ("mh", "Latn", "MH"),
+    ("mi", "Latn", "NZ"),
+    ("min", "Latn", "ID"),
+    ("mk", "Cyrl", "MK"),
+    ("ml", "Mlym", "IN"),
+    ("mn", "Cyrl", "MN"),
+    ("mni", "Beng", "IN"),
+    ("moh", "Latn", "CA"),
+    ("mos", "Latn", "BF"),
+    ("mr", "Deva", "IN"),
+    ("ms", "Latn", "MY"),
+    ("mt", "Latn", "MT"),
+    ("mua", "Latn", "CM"),
+    ("my", "Mymr", "MM"),
+    ("mzn", "Arab", "IR"),
+    ("na", "Latn", "NR"),
+    ("nan", "Hans", "CN"),
+    ("nap", "Latn", "IT"),
+    ("nb", "Latn", "NO"),
+    ("nds", "Latn", "DE"),
+    ("ne", "Deva", "NP"),
+    ("nl", "Latn", "NL"),
+    ("nmg", "Latn", "CM"),
+    ("nn", "Latn", "NO"),
+    ("nnh", "Latn", "CM"),
+    ("no", "Latn", "NO"),
+    ("nqo", "Nkoo", "GN"),
+    ("nr", "Latn", "ZA"),
+    ("nso", "Latn", "ZA"),
+    ("nv", "Latn", "US"),
+    ("ny", "Latn", "MW"),
+    ("oc", "Latn", "FR"),
+    ("om", "Latn", "ET"),
+    ("or", "Orya", "IN"),
+    ("os", "Cyrl", "GE"),
+    ("pa", "Guru", "IN"),
+    ("pap", "Latn", "CW"),
+    ("pcm", "Latn", "NG"),
+    ("pl", "Latn", "PL"),
+    ("ps", "Arab", "AF"),
+    ("pt", "Latn", "BR"),
+    ("qu", "Latn", "PE"),
+    ("raj", "Deva", "IN"),
+    ("rm", "Latn", "CH"),
+    ("rn", "Latn", "BI"),
+    ("ro", "Latn", "RO"),
+    ("ru", "Cyrl", "RU"),
+    ("rw", "Latn", "RW"),
+    ("sa", "Deva", "IN"),
+    ("sah", "Cyrl", "RU"),
+    ("sat", "Olck", "IN"),
+    ("sc", "Latn", "IT"),
+    ("scn", "Latn", "IT"),
+    ("sd", "Arab", "PK"),
+    ("se", "Latn", "NO"),
+    ("ses", "Latn", "ML"),
+    ("sg", "Latn", "CF"),
+    ("shn", "Mymr", "MM"),
+    ("si", "Sinh", "LK"),
+    ("sk", "Latn", "SK"),
+    ("skr", "Arab", "PK"),
+    ("sl", "Latn", "SI"),
+    ("sm", "Latn", "WS"),
+    ("sn", "Latn", "ZW"),
+    ("so", "Latn", "SO"),
+    ("sq", "Latn", "AL"),
+    ("sr", "Cyrl", "RS"),
+    ("srn", "Latn", "SR"),
+    ("ss", "Latn", "ZA"),
+    ("st", "Latn", "ZA"),
+    ("su", "Latn", "ID"),
+    ("sv", "Latn", "SE"),
+    ("sw", "Latn", "TZ"),
+    ("syr", "Syrc", "IQ"),
+    ("szl", "Latn", "PL"),
+    ("ta", "Taml", "IN"),
+    ("tcy", "Knda", "IN"),
+    ("te", "Telu", "IN"),
+    ("tg", "Cyrl", "TJ"),
+    ("th", "Thai", "TH"),
+    ("ti", "Ethi", "ET"),
+    ("tk", "Latn", "TM"),
+    ("tl", "Latn", "PH"),
+    ("tn", "Latn", "ZA"),
+    ("to", "Latn", "TO"),
+    ("tpi", "Latn", "PG"),
+    ("tr", "Latn", "TR"),
+    ("ts", "Latn", "ZA"),
+    ("tt", "Cyrl", "RU"),
+    ("tum", "Latn", "MW"),
+    ("tw", "Latn", "GH"),
+    ("ty", "Latn", "PF"),
+    ("tzm", "Latn", "MA"),
+    ("udm", "Cyrl", "RU"),
+    ("ug", "Arab", "CN"),
+    ("uk", "Cyrl", "UA"),
+    ("ur", "Arab", "PK"),
+    ("uz", "Latn", "UZ"),
+    ("ve", "Latn", "ZA"),
+    ("vec", "Latn", "IT"),
+    ("vi", "Latn", "VN"),
+    ("vmw", "Latn", "MZ"),
+    ("wa", "Latn", "BE"),
+    ("wo", "Latn", "SN"),
+    ("wuu", "Hans", "CN"),
+    ("xal", "Cyrl", "RU"),
+    ("xh", "Latn", "ZA"),
+    ("yav", "Latn", "CM"),
+    ("yi", "Hebr", "UA"),
+    ("yo", "Latn", "NG"),
+    ("yrl", "Latn", "BR"),
+    ("yue", "Hant", "HK"),
+    ("zgh", "Tfng", "MA"),
+    ("zh", "Hans", "CN"),
+    ("zu", "Latn", "ZA"),
+    ("zza", "Latn", "TR"),
+];
+
+const LANGUAGE_REGIONS: &[(&str, &str, &str)] = &[
+    ("az", "IR", "Arab"),
+    ("mn", "CN", "Mong"),
+    ("pa", "PK", "Arab"),
+    ("sd", "IN", "Deva"),
+    ("sr", "ME", "Latn"),
+    ("uz", "AF", "Arab"),
+    ("yue", "CN", "Hans"),
+    ("zh", "HK", "Hant"),
+    ("zh", "MO", "Hant"),
+    ("zh", "TW", "Hant"),
+];
+
+const LANGUAGE_SCRIPTS: &[(&str, &str, &str)] = &[
+    ("az", "Arab", "IR"),
+    ("az", "Cyrl", "AZ"),
+    ("bs", "Cyrl", "BA"),
+    ("ff", "Adlm", "GN"),
+    ("ha", "Arab", "NG"),
+    ("ks", "Deva", "IN"),
+    ("mn", "Mong", "CN"),
+    ("ms", "Arab", "MY"),
+    ("pa", "Arab", "PK"),
+    ("sd", "Deva", "IN"),
+    ("sr", "Latn", "RS"),
+    ("uz", "Arab", "AF"),
+    ("uz", "Cyrl", "UZ"),
+    ("yue", "Hans", "CN"),
+    ("zh", "Hant", "TW"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{LANGUAGE_REGIONS, LANGUAGE_SCRIPTS, LANGUAGES, likely_subtags};
+
+    #[test]
+    fn tables_are_sorted() {
+        assert!(LANGUAGES.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        for table in [LANGUAGE_REGIONS, LANGUAGE_SCRIPTS] {
+            assert!(
+                table
+                    .windows(2)
+                    .all(|pair| (pair[0].0, pair[0].1) < (pair[1].0, pair[1].1))
+            );
+        }
+    }
+
+    #[test]
+    fn fills_missing_subtags() {
+        assert_eq!(likely_subtags("en", None, None), Some(("Latn", "US")));
+        assert_eq!(likely_subtags("zh", None, Some("TW")), Some(("Hant", "TW")));
+        assert_eq!(
+            likely_subtags("zh", Some("Hant"), None),
+            Some(("Hant", "TW"))
+        );
+        assert_eq!(likely_subtags("qaa", None, None), None);
+    }
+}
@@ -0,0 +1,37 @@
+use alloc::string::String;
+
+use crate::{CoreResult, FormatterOption};
+
+pub trait MarkupSink {
+    fn open(
+        &mut self,
+        _name: &str,
+        _options: &[FormatterOption],
+        _output: &mut String,
+    ) -> CoreResult<()> {
+        Ok(())
+    }
+
+    fn close(
+        &mut self,
+        _name: &str,
+        _options: &[FormatterOption],
+        _output: &mut String,
+    ) -> CoreResult<()> {
+        Ok(())
+    }
+
+    fn standalone(
+        &mut self,
+        _name: &str,
+        _options: &[FormatterOption],
+        _output: &mut String,
+    ) -> CoreResult<()> {
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IgnoreMarkup;
+
+impl MarkupSink for IgnoreMarkup {}
@@ -1,3 +1,4 @@
+use alloc::collections::BTreeMap;
 use alloc::string::String;
 use alloc::vec::Vec;
 
@@ -20,7 +21,7 @@ pub fn negotiate_lookup(
     supported: &[LanguageTag],
     default_locale: &LanguageTag,
 ) -> NegotiationResult {
-    negotiate_lookup_internal(requested, supported, default_locale, false)
+    negotiate_lookup_internal(requested, supported, default_locale, None, false, false)
 }
 
 pub fn negotiate_lookup_with_trace(
@@ -28,14 +29,74 @@ pub fn negotiate_lookup_with_trace(
     supported: &[LanguageTag],
     default_locale: &LanguageTag,
 ) -> NegotiationResult {
-    negotiate_lookup_internal(requested, supported, default_locale, true)
+    negotiate_lookup_internal(requested, supported, default_locale, None, true, false)
+}
+
+pub fn negotiate_lookup_with_promotion(
+    requested: &[LanguageTag],
+    supported: &[LanguageTag],
+    default_locale: &LanguageTag,
+) -> NegotiationResult {
+    negotiate_lookup_internal(requested, supported, default_locale, None, true, true)
+}
+
+pub fn negotiate_lookup_with_fallbacks(
+    requested: &[LanguageTag],
+    supported: &[LanguageTag],
+    default_locale: &LanguageTag,
+    fallbacks: &BTreeMap<String, Vec<String>>,
+) -> NegotiationResult {
+    negotiate_lookup_internal(
+        requested,
+        supported,
+        default_locale,
+        Some(fallbacks),
+        true,
+        false,
+    )
+}
+
+pub fn parse_accept_language(header: &str) -> Vec<LanguageTag> {
+    let mut weighted: Vec<(f32, LanguageTag)> = Vec::new();
+    for segment in header.split(',') {
+        let mut parts = segment.split(';');
+        let range = parts.next().unwrap_or("").trim();
+        let mut weight = Some(1.0f32);
+        for param in parts {
+            let param = param.trim();
+            weight = match param
+                .strip_prefix("q=")
+                .or_else(|| param.strip_prefix("Q="))
+            {
+                Some(value) => value
+                    .trim()
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|value| (0.0..=1.0).contains(value)),
+                None => weight,
+            };
+        }
+        let Some(weight) = weight else {
+            continue;
+        };
+        if weight == 0.0 || range == "*" {
+            continue;
+        }
+        if let Ok(tag) = LanguageTag::parse(range) {
+            weighted.push((weight, tag));
+        }
+    }
+    weighted.sort_by(|a, b| b.0.total_cmp(&a.0));
+    weighted.into_iter().map(|(_, tag)| tag).collect()
 }
 
 fn negotiate_lookup_internal(
     requested: &[LanguageTag],
     supported: &[LanguageTag],
     default_locale: &LanguageTag,
+    fallbacks: Option<&BTreeMap<String, Vec<String>>>,
     with_trace: bool,
+    promote: bool,
 ) -> NegotiationResult {
     let mut trace = if with_trace {
         Some(NegotiationTrace {
@@ -61,6 +122,17 @@ fn negotiate_lookup_internal(
             }
         }
 
+        if let Some(fallbacks) = fallbacks {
+            let synthesized: Vec<String> = tried
+                .iter()
+                .filter_map(|attempt| fallbacks.get(attempt))
+                .flatten()
+                .filter_map(|fallback| LanguageTag::parse(fallback).ok())
+                .map(|tag| String::from(tag.normalized()))
+                .collect();
+            tried.extend(synthesized);
+        }
+
         for attempt in tried {
             if let Some(trace) = trace.as_mut() {
                 trace.attempts.push(attempt.clone());
@@ -73,6 +145,26 @@ fn negotiate_lookup_internal(
                 };
             }
         }
+
+        #[cfg(feature = "likely-subtags")]
+        if let Some(selected) = find_likely_supported(requested_tag, supported, trace.as_mut()) {
+            return NegotiationResult {
+                selected,
+                requested: requested_tag.clone(),
+                trace,
+            };
+        }
+
+        if promote && let Some(selected) = find_same_language(requested_tag, supported) {
+            if let Some(trace) = trace.as_mut() {
+                trace.attempts.push(String::from(selected.normalized()));
+            }
+            return NegotiationResult {
+                selected,
+                requested: requested_tag.clone(),
+                trace,
+            };
+        }
     }
 
     NegotiationResult {
@@ -92,12 +184,45 @@ fn find_supported(tag: &str, supported: &[LanguageTag]) -> Option<LanguageTag> {
         .cloned()
 }
 
+fn find_same_language(requested: &LanguageTag, supported: &[LanguageTag]) -> Option<LanguageTag> {
+    supported
+        .iter()
+        .find(|candidate| candidate.language() == requested.language())
+        .cloned()
+}
+
+#[cfg(feature = "likely-subtags")]
+fn find_likely_supported(
+    requested: &LanguageTag,
+    supported: &[LanguageTag],
+    trace: Option<&mut NegotiationTrace>,
+) -> Option<LanguageTag> {
+    let maximized = requested.maximize();
+    if let Some(trace) = trace {
+        trace.attempts.push(String::from(maximized.normalized()));
+    }
+    supported
+        .iter()
+        .find(|candidate| {
+            let candidate = candidate.maximize();
+            candidate.language() == maximized.language()
+                && candidate.script() == maximized.script()
+                && candidate.region() == maximized.region()
+        })
+        .cloned()
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::string::String;
     use alloc::vec;
 
-    use super::{negotiate_lookup, negotiate_lookup_with_trace};
+    use alloc::collections::BTreeMap;
+
+    use super::{
+        negotiate_lookup, negotiate_lookup_with_fallbacks, negotiate_lookup_with_promotion,
+        negotiate_lookup_with_trace, parse_accept_language,
+    };
     use crate::LanguageTag;
 
     fn tag(value: &str) -> LanguageTag {
@@ -153,4 +278,105 @@ mod tests {
             vec![String::from("de-DE-u-co-phonebk"), String::from("de-DE")]
         );
     }
+
+    #[test]
+    fn promotion_picks_a_regional_pack_for_a_bare_language() {
+        let default_locale = tag("fr");
+        let result = negotiate_lookup_with_promotion(
+            &[tag("en")],
+            &[tag("fr"), tag("en-US")],
+            &default_locale,
+        );
+        assert_eq!(result.selected.normalized(), "en-US");
+        assert_eq!(result.requested.normalized(), "en");
+
+        let supported = vec![tag("fr"), tag("en-GB")];
+        let result = negotiate_lookup(&[tag("en")], &supported, &default_locale);
+        assert_eq!(result.selected.normalized(), "fr");
+        let result = negotiate_lookup_with_promotion(&[tag("en")], &supported, &default_locale);
+        assert_eq!(result.selected.normalized(), "en-GB");
+        assert_eq!(
+            result
+                .trace
+                .expect("trace")
+                .attempts
+                .last()
+                .map(String::as_str),
+            Some("en-GB")
+        );
+
+        let result = negotiate_lookup_with_promotion(
+            &[tag("en"), tag("fr")],
+            &[tag("fr"), tag("de-DE")],
+            &tag("de-DE"),
+        );
+        assert_eq!(result.selected.normalized(), "fr");
+    }
+
+    #[test]
+    fn parses_accept_language_by_weight() {
+        let tags = parse_accept_language("fr;q=0.8, en-GB;q=0.9, *;q=0.1, de");
+        let tags: alloc::vec::Vec<&str> = tags.iter().map(|tag| tag.normalized()).collect();
+        assert_eq!(tags, vec!["de", "en-GB", "fr"]);
+    }
+
+    #[test]
+    fn accept_language_ties_keep_header_order() {
+        let tags = parse_accept_language("es;q=0.5, pt, it;q=0.5, en");
+        let tags: alloc::vec::Vec<&str> = tags.iter().map(|tag| tag.normalized()).collect();
+        assert_eq!(tags, vec!["pt", "en", "es", "it"]);
+    }
+
+    #[test]
+    fn accept_language_skips_zero_and_malformed_segments() {
+        let tags = parse_accept_language("ja;q=0, ;q=0.4, en;q=abc, -x, fr;q=2, nl;q=0.3");
+        let tags: alloc::vec::Vec<&str> = tags.iter().map(|tag| tag.normalized()).collect();
+        assert_eq!(tags, vec!["nl"]);
+    }
+
+    #[test]
+    fn lookup_uses_region_fallbacks_after_truncation() {
+        let requested = vec![tag("pt-BR")];
+        let supported = vec![tag("pt-PT"), tag("en")];
+        let default_locale = tag("en");
+        let mut fallbacks = BTreeMap::new();
+        fallbacks.insert(String::from("pt-BR"), vec![String::from("pt-PT")]);
+
+        let result = negotiate_lookup(&requested, &supported, &default_locale);
+        assert_eq!(result.selected.normalized(), "en");
+
+        let result =
+            negotiate_lookup_with_fallbacks(&requested, &supported, &default_locale, &fallbacks);
+        assert_eq!(result.selected.normalized(), "pt-PT");
+        let trace = result.trace.expect("trace should be present");
+        assert_eq!(trace.attempts, vec!["pt-BR", "pt", "pt-PT"]);
+    }
+
+    #[test]
+    fn lookup_prefers_truncation_over_fallbacks() {
+        let requested = vec![tag("es-419")];
+        let supported = vec![tag("es"), tag("es-ES")];
+        let default_locale = tag("en");
+        let mut fallbacks = BTreeMap::new();
+        fallbacks.insert(String::from("es-419"), vec![String::from("es-ES")]);
+        let result =
+            negotiate_lookup_with_fallbacks(&requested, &supported, &default_locale, &fallbacks);
+        assert_eq!(result.selected.normalized(), "es");
+    }
+
+    #[cfg(feature = "likely-subtags")]
+    #[test]
+    fn lookup_matches_on_likely_script() {
+        let requested = vec![tag("zh-Hant")];
+        let supported = vec![tag("zh-CN"), tag("zh-TW"), tag("en")];
+        let default_locale = tag("en");
+        let result = negotiate_lookup_with_trace(&requested, &supported, &default_locale);
+        assert_eq!(result.selected.normalized(), "zh-TW");
+        let trace = result.trace.expect("trace");
+        assert_eq!(trace.attempts, vec!["zh-Hant", "zh", "zh-Hant-TW"]);
+
+        let requested = vec![tag("zh-Hant-HK")];
+        let result = negotiate_lookup(&requested, &supported, &default_locale);
+        assert_eq!(result.selected.normalized(), "en");
+    }
 }
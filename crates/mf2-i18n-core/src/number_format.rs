@@ -0,0 +1,535 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::currency::currency_info;
+use crate::{
+    CoreError, CoreResult, FormatBackend, FormatterOption, LanguageTag, NumberSymbols,
+    PluralCategory, find_option,
+};
+
+const DEFAULT_MAXIMUM_FRACTION_DIGITS: u32 = 3;
+const MAX_FRACTION_DIGITS: u32 = 20;
+
+const LOCALE_NUMBERS: &[(&str, &str, &str, CurrencyPlacement)] = &[
+    ("bg", ",", "\u{a0}", CurrencyPlacement::Suffix),
+    ("cs", ",", "\u{a0}", CurrencyPlacement::Suffix),
+    ("da", ",", ".", CurrencyPlacement::Suffix),
+    ("de", ",", ".", CurrencyPlacement::Suffix),
+    ("de-AT", ",", "\u{a0}", CurrencyPlacement::SpacedPrefix),
+    ("de-CH", ".", "\u{2019}", CurrencyPlacement::SpacedPrefix),
+    ("el", ",", ".", CurrencyPlacement::Suffix),
+    ("en", ".", ",", CurrencyPlacement::Prefix),
+    ("es", ",", ".", CurrencyPlacement::Suffix),
+    ("es-MX", ".", ",", CurrencyPlacement::Prefix),
+    ("es-US", ".", ",", CurrencyPlacement::Prefix),
+    ("fi", ",", "\u{a0}", CurrencyPlacement::Suffix),
+    ("fr", ",", "\u{202f}", CurrencyPlacement::Suffix),
+    ("fr-CA", ",", "\u{a0}", CurrencyPlacement::Suffix),
+    ("fr-CH", ",", "\u{202f}", CurrencyPlacement::Suffix),
+    ("hr", ",", ".", CurrencyPlacement::Suffix),
+    ("hu", ",", "\u{a0}", CurrencyPlacement::Suffix),
+    ("id", ",", ".", CurrencyPlacement::Prefix),
+    ("it", ",", ".", CurrencyPlacement::Suffix),
+    ("it-CH", ".", "\u{2019}", CurrencyPlacement::SpacedPrefix),
+    ("ja", ".", ",", CurrencyPlacement::Prefix),
+    ("ko", ".", ",", CurrencyPlacement::Prefix),
+    ("nb", ",", "\u{a0}", CurrencyPlacement::Suffix),
+    ("nl", ",", ".", CurrencyPlacement::SpacedPrefix),
+    ("pl", ",", "\u{a0}", CurrencyPlacement::Suffix),
+    ("pt", ",", ".", CurrencyPlacement::SpacedPrefix),
+    ("pt-PT", ",", "\u{a0}", CurrencyPlacement::Suffix),
+    ("ro", ",", ".", CurrencyPlacement::Suffix),
+    ("ru", ",", "\u{a0}", CurrencyPlacement::Suffix),
+    ("sk", ",", "\u{a0}", CurrencyPlacement::Suffix),
+    ("sr", ",", ".", CurrencyPlacement::Suffix),
+    ("sv", ",", "\u{a0}", CurrencyPlacement::Suffix),
+    ("tr", ",", ".", CurrencyPlacement::Prefix),
+    ("uk", ",", "\u{a0}", CurrencyPlacement::Suffix),
+    ("vi", ",", ".", CurrencyPlacement::Suffix),
+    ("zh", ".", ",", CurrencyPlacement::Prefix),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurrencyPlacement {
+    Prefix,
+    SpacedPrefix,
+    Suffix,
+}
+
+impl CurrencyPlacement {
+    pub fn for_locale(locale: &LanguageTag) -> Self {
+        locale_numbers(locale).map_or(Self::Prefix, |(_, _, placement)| placement)
+    }
+}
+
+pub struct StyledNumberBackend<B> {
+    symbols: NumberSymbols,
+    placement: CurrencyPlacement,
+    inner: B,
+}
+
+impl<B: FormatBackend> StyledNumberBackend<B> {
+    pub fn new(locale: &LanguageTag, inner: B) -> Self {
+        let mut symbols = NumberSymbols::default();
+        let mut placement = CurrencyPlacement::Prefix;
+        if let Some((decimal, group, currency)) = locale_numbers(locale) {
+            symbols.decimal = String::from(decimal);
+            symbols.group = String::from(group);
+            placement = currency;
+        }
+        Self::with_symbols(symbols, inner).with_currency_placement(placement)
+    }
+
+    pub fn with_symbols(symbols: NumberSymbols, inner: B) -> Self {
+        Self {
+            symbols,
+            placement: CurrencyPlacement::Prefix,
+            inner,
+        }
+    }
+
+    pub fn with_currency_placement(mut self, placement: CurrencyPlacement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    pub fn symbols(&self) -> &NumberSymbols {
+        &self.symbols
+    }
+}
+
+impl<B: FormatBackend> FormatBackend for StyledNumberBackend<B> {
+    fn plural_category(&self, value: f64) -> CoreResult<PluralCategory> {
+        self.inner.plural_category(value)
+    }
+
+    fn ordinal_category(&self, value: f64) -> CoreResult<PluralCategory> {
+        self.inner.ordinal_category(value)
+    }
+
+    fn format_number(&self, value: f64, options: &[FormatterOption]) -> CoreResult<String> {
+        format_decimal(value, &self.symbols, options)
+    }
+
+    fn format_date(&self, value: i64, options: &[FormatterOption]) -> CoreResult<String> {
+        self.inner.format_date(value, options)
+    }
+
+    fn format_time(&self, value: i64, options: &[FormatterOption]) -> CoreResult<String> {
+        self.inner.format_time(value, options)
+    }
+
+    fn format_datetime(&self, value: i64, options: &[FormatterOption]) -> CoreResult<String> {
+        self.inner.format_datetime(value, options)
+    }
+
+    fn format_unit(
+        &self,
+        value: f64,
+        unit_id: u32,
+        options: &[FormatterOption],
+    ) -> CoreResult<String> {
+        self.inner.format_unit(value, unit_id, options)
+    }
+
+    fn format_currency(
+        &self,
+        value: f64,
+        code: [u8; 3],
+        options: &[FormatterOption],
+    ) -> CoreResult<String> {
+        format_currency_value(value, code, &self.symbols, self.placement, options)
+    }
+}
+
+#[derive(Clone, Copy)]
+struct DecimalStyle {
+    minimum: u32,
+    maximum: u32,
+    grouping: bool,
+}
+
+impl DecimalStyle {
+    fn from_options(
+        options: &[FormatterOption],
+        default_minimum: u32,
+        default_maximum: u32,
+    ) -> CoreResult<Self> {
+        let maximum = fraction_digits_option(options, "maximumFractionDigits")?;
+        let minimum = fraction_digits_option(options, "minimumFractionDigits")?
+            .unwrap_or(default_minimum.min(maximum.unwrap_or(u32::MAX)));
+        let maximum = maximum.unwrap_or(default_maximum).max(minimum);
+        let grouping = match find_option(options, "useGrouping") {
+            None => true,
+            Some(value) => match (value.as_bool(), value.as_str()) {
+                (Some(flag), _) => flag,
+                (None, Some("always" | "auto" | "min2")) => true,
+                (None, Some("never")) => false,
+                _ => return Err(CoreError::InvalidInput("invalid useGrouping option")),
+            },
+        };
+        Ok(Self {
+            minimum,
+            maximum,
+            grouping,
+        })
+    }
+}
+
+pub fn format_decimal(
+    value: f64,
+    symbols: &NumberSymbols,
+    options: &[FormatterOption],
+) -> CoreResult<String> {
+    let style = DecimalStyle::from_options(options, 0, DEFAULT_MAXIMUM_FRACTION_DIGITS)?;
+    Ok(render_decimal(value, symbols, &style))
+}
+
+pub fn format_currency_value(
+    value: f64,
+    code: [u8; 3],
+    symbols: &NumberSymbols,
+    placement: CurrencyPlacement,
+    options: &[FormatterOption],
+) -> CoreResult<String> {
+    let code = core::str::from_utf8(&code).map_err(|_| CoreError::InvalidInput("currency code"))?;
+    let info = currency_info(code);
+    let digits = info.map_or(2, |info| info.digits);
+    let style = DecimalStyle::from_options(options, digits, digits)?;
+    let mode = match find_option(options, "currencyDisplay") {
+        None => "symbol",
+        Some(value) => value
+            .as_str()
+            .ok_or(CoreError::InvalidInput("invalid currencyDisplay option"))?,
+    };
+    let display = match mode {
+        "symbol" | "narrowSymbol" => info.map_or(code, |info| info.symbol),
+        "code" => code,
+        "name" => info.map_or(code, |info| info.name),
+        _ => return Err(CoreError::InvalidInput("invalid currencyDisplay option")),
+    };
+
+    let number = render_decimal(value.abs(), symbols, &style);
+    let mut out = String::new();
+    if value.is_sign_negative() && value != 0.0 {
+        out.push_str(&symbols.minus);
+    }
+    match placement {
+        _ if mode == "name" => {
+            out.push_str(&number);
+            out.push(' ');
+            out.push_str(display);
+        }
+        CurrencyPlacement::Prefix | CurrencyPlacement::SpacedPrefix => {
+            out.push_str(display);
+            let ends_with_letter = display.chars().last().is_some_and(char::is_alphabetic);
+            if placement == CurrencyPlacement::SpacedPrefix || ends_with_letter {
+                out.push('\u{a0}');
+            }
+            out.push_str(&number);
+        }
+        CurrencyPlacement::Suffix => {
+            out.push_str(&number);
+            out.push('\u{a0}');
+            out.push_str(display);
+        }
+    }
+    Ok(out)
+}
+
+fn render_decimal(value: f64, symbols: &NumberSymbols, style: &DecimalStyle) -> String {
+    let DecimalStyle {
+        minimum,
+        maximum,
+        grouping,
+    } = *style;
+    if value.is_nan() {
+        return String::from("NaN");
+    }
+    let mut out = String::new();
+    if value.is_sign_negative() && value != 0.0 {
+        out.push_str(&symbols.minus);
+    }
+    if value.is_infinite() {
+        out.push('∞');
+        return out;
+    }
+
+    let raw = format!("{}", value.abs());
+    let (integer, fraction) = raw.split_once('.').unwrap_or((&raw, ""));
+    let (integer, mut fraction) = round_half_even(integer, fraction, maximum as usize);
+    while fraction.len() > minimum as usize && fraction.ends_with('0') {
+        fraction.pop();
+    }
+    while fraction.len() < minimum as usize {
+        fraction.push('0');
+    }
+
+    if grouping {
+        let digits: Vec<char> = integer.chars().collect();
+        for (idx, digit) in digits.iter().enumerate() {
+            if idx > 0 && (digits.len() - idx).is_multiple_of(3) {
+                out.push_str(&symbols.group);
+            }
+            out.push(*digit);
+        }
+    } else {
+        out.push_str(&integer);
+    }
+    if !fraction.is_empty() {
+        out.push_str(&symbols.decimal);
+        out.push_str(&fraction);
+    }
+    out
+}
+
+fn round_half_even(integer: &str, fraction: &str, digits: usize) -> (String, String) {
+    if fraction.len() <= digits {
+        return (String::from(integer), String::from(fraction));
+    }
+    let mut kept: Vec<u8> = integer
+        .bytes()
+        .chain(fraction.bytes().take(digits))
+        .collect();
+    let dropped = &fraction.as_bytes()[digits..];
+    let first = dropped[0];
+    let rest_is_zero = dropped[1..].iter().all(|digit| *digit == b'0');
+    let last_is_odd = kept.last().is_some_and(|digit| (digit - b'0') % 2 == 1);
+    let round_up = first > b'5' || (first == b'5' && (!rest_is_zero || last_is_odd));
+    if round_up {
+        let mut idx = kept.len();
+        loop {
+            if idx == 0 {
+                kept.insert(0, b'1');
+                break;
+            }
+            idx -= 1;
+            if kept[idx] == b'9' {
+                kept[idx] = b'0';
+            } else {
+                kept[idx] += 1;
+                break;
+            }
+        }
+    }
+    let split = kept.len() - digits;
+    let (integer, fraction) = kept.split_at(split);
+    (
+        String::from_utf8_lossy(integer).into_owned(),
+        String::from_utf8_lossy(fraction).into_owned(),
+    )
+}
+
+fn fraction_digits_option(options: &[FormatterOption], key: &str) -> CoreResult<Option<u32>> {
+    let Some(value) = find_option(options, key) else {
+        return Ok(None);
+    };
+    match value.as_num() {
+        Some(digits)
+            if digits >= 0.0
+                && digits <= MAX_FRACTION_DIGITS as f64
+                && digits == (digits as u32) as f64 =>
+        {
+            Ok(Some(digits as u32))
+        }
+        _ => Err(CoreError::InvalidInput("invalid fraction digits option")),
+    }
+}
+
+fn locale_numbers(locale: &LanguageTag) -> Option<(&'static str, &'static str, CurrencyPlacement)> {
+    let mut parts = locale.match_subtags().to_vec();
+    while !parts.is_empty() {
+        let candidate = parts.join("-");
+        if let Ok(idx) =
+            LOCALE_NUMBERS.binary_search_by_key(&candidate.as_str(), |(locale, ..)| locale)
+        {
+            let (_, decimal, group, placement) = LOCALE_NUMBERS[idx];
+            return Some((decimal, group, placement));
+        }
+        parts.pop();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::{String, ToString};
+    use alloc::vec;
+
+    use super::{LOCALE_NUMBERS, StyledNumberBackend, format_decimal};
+    use crate::{
+        CoreResult, FormatBackend, FormatterOption, FormatterOptionValue, LanguageTag,
+        NumberSymbols, PluralCategory,
+    };
+
+    struct PlainBackend;
+
+    impl FormatBackend for PlainBackend {
+        fn plural_category(&self, _value: f64) -> CoreResult<PluralCategory> {
+            Ok(PluralCategory::Other)
+        }
+
+        fn format_number(&self, value: f64, _options: &[FormatterOption]) -> CoreResult<String> {
+            Ok(value.to_string())
+        }
+
+        fn format_date(&self, value: i64, _options: &[FormatterOption]) -> CoreResult<String> {
+            Ok(value.to_string())
+        }
+
+        fn format_time(&self, value: i64, _options: &[FormatterOption]) -> CoreResult<String> {
+            Ok(value.to_string())
+        }
+
+        fn format_datetime(&self, value: i64, _options: &[FormatterOption]) -> CoreResult<String> {
+            Ok(value.to_string())
+        }
+
+        fn format_unit(
+            &self,
+            value: f64,
+            _unit_id: u32,
+            _options: &[FormatterOption],
+        ) -> CoreResult<String> {
+            Ok(value.to_string())
+        }
+
+        fn format_currency(
+            &self,
+            value: f64,
+            _code: [u8; 3],
+            _options: &[FormatterOption],
+        ) -> CoreResult<String> {
+            Ok(value.to_string())
+        }
+    }
+
+    fn option(key: &str, value: FormatterOptionValue) -> FormatterOption {
+        FormatterOption {
+            key: String::from(key),
+            value,
+        }
+    }
+
+    fn format(locale: &str, value: f64, options: &[FormatterOption]) -> String {
+        let locale = LanguageTag::parse(locale).expect("tag");
+        StyledNumberBackend::new(&locale, PlainBackend)
+            .format_number(value, options)
+            .expect("format")
+    }
+
+    #[test]
+    fn locale_table_is_sorted() {
+        assert!(LOCALE_NUMBERS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn groups_and_rounds_for_locale() {
+        let options = [option(
+            "maximumFractionDigits",
+            FormatterOptionValue::Num(2.0),
+        )];
+        assert_eq!(format("en-US", 1234567.891, &options), "1,234,567.89");
+        assert_eq!(
+            format("fr", 1234567.891, &options),
+            "1\u{202f}234\u{202f}567,89"
+        );
+        assert_eq!(format("de", -1234.5, &[]), "-1.234,5");
+        assert_eq!(format("en", 999.9996, &[]), "1,000");
+    }
+
+    #[test]
+    fn honors_fraction_and_grouping_options() {
+        let options = vec![
+            option("minimumFractionDigits", FormatterOptionValue::Num(2.0)),
+            option(
+                "useGrouping",
+                FormatterOptionValue::Str(String::from("false")),
+            ),
+        ];
+        assert_eq!(format("en", 1234.5, &options), "1234.50");
+        assert_eq!(format("en", 1234.0, &options), "1234.00");
+        let options = [option(
+            "maximumFractionDigits",
+            FormatterOptionValue::Num(0.0),
+        )];
+        assert_eq!(format("en", 1234.5, &options), "1,234");
+        assert_eq!(format("en", 1235.5, &options), "1,236");
+    }
+
+    #[test]
+    fn rounds_half_to_even() {
+        let symbols = NumberSymbols::default();
+        let options = [option(
+            "maximumFractionDigits",
+            FormatterOptionValue::Num(1.0),
+        )];
+        let cases = [(0.25, "0.2"), (0.35, "0.4"), (0.251, "0.3"), (9.95, "10")];
+        for (value, expected) in cases {
+            assert_eq!(
+                format_decimal(value, &symbols, &options).expect("format"),
+                expected
+            );
+        }
+    }
+
+    fn currency(locale: &str, value: f64, code: &[u8; 3], options: &[FormatterOption]) -> String {
+        let locale = LanguageTag::parse(locale).expect("tag");
+        StyledNumberBackend::new(&locale, PlainBackend)
+            .format_currency(value, *code, options)
+            .expect("format")
+    }
+
+    #[test]
+    fn formats_currency_with_minor_units_and_placement() {
+        assert_eq!(currency("en", 1.0, b"USD", &[]), "$1.00");
+        assert_eq!(currency("en", -1234.5, b"USD", &[]), "-$1,234.50");
+        assert_eq!(currency("en", 1234.5, b"JPY", &[]), "¥1,234");
+        assert_eq!(currency("en", 1234.5, b"EUR", &[]), "€1,234.50");
+        assert_eq!(currency("en", 1.2345, b"BHD", &[]), "BHD\u{a0}1.234");
+        assert_eq!(currency("de", 1.0, b"USD", &[]), "1,00\u{a0}$");
+        assert_eq!(currency("de", 1234.5, b"JPY", &[]), "1.234\u{a0}¥");
+        assert_eq!(currency("de", -1234.5, b"EUR", &[]), "-1.234,50\u{a0}€");
+    }
+
+    #[test]
+    fn honors_currency_display_option() {
+        let code = [option(
+            "currencyDisplay",
+            FormatterOptionValue::Str(String::from("code")),
+        )];
+        let name = [option(
+            "currencyDisplay",
+            FormatterOptionValue::Str(String::from("name")),
+        )];
+        assert_eq!(currency("en", 1.0, b"USD", &code), "USD\u{a0}1.00");
+        assert_eq!(currency("de", 1.0, b"EUR", &code), "1,00\u{a0}EUR");
+        assert_eq!(currency("en", 2.0, b"JPY", &name), "2 Japanese yen");
+        let bad = [option(
+            "currencyDisplay",
+            FormatterOptionValue::Str(String::from("emoji")),
+        )];
+        let locale = LanguageTag::parse("en").expect("tag");
+        assert!(
+            StyledNumberBackend::new(&locale, PlainBackend)
+                .format_currency(1.0, *b"USD", &bad)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_options() {
+        let symbols = NumberSymbols::default();
+        let options = [option(
+            "maximumFractionDigits",
+            FormatterOptionValue::Num(1.5),
+        )];
+        assert!(format_decimal(1.0, &symbols, &options).is_err());
+        let options = [option(
+            "useGrouping",
+            FormatterOptionValue::Str("maybe".to_string()),
+        )];
+        assert!(format_decimal(1.0, &symbols, &options).is_err());
+    }
+}
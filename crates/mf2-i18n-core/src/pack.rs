@@ -2,8 +2,14 @@ use alloc::vec::Vec;
 
 use crate::{CoreError, CoreResult};
 
+pub const PACK_SCHEMA_VERSION: u16 = 2;
+pub const PACK_FLAG_SECTION_CHECKSUMS: u32 = 1;
+
+const WIDE_MESSAGE_ID_SCHEMA: u16 = 2;
+
 const PACK_MAGIC: &[u8; 8] = b"MF2PACK\0";
 const HEADER_LEN: usize = 8 + 2 + 1 + 4 + 32 + 4 + 4 + 8;
+const CRC32_TABLE: [u32; 256] = crc32_table();
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PackKind {
@@ -23,11 +29,22 @@ pub struct PackHeader {
     pub build_epoch_ms: u64,
 }
 
+impl PackHeader {
+    pub fn has_section_checksums(&self) -> bool {
+        self.flags & PACK_FLAG_SECTION_CHECKSUMS != 0
+    }
+
+    pub fn has_wide_message_ids(&self) -> bool {
+        self.schema_version >= WIDE_MESSAGE_ID_SCHEMA
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct SectionEntry {
     pub section_type: u8,
     pub offset: u32,
     pub length: u32,
+    pub checksum: Option<u32>,
 }
 
 pub fn parse_pack_header(input: &[u8]) -> CoreResult<(PackHeader, usize)> {
@@ -81,30 +98,79 @@ pub fn parse_section_directory(
     input: &[u8],
     start: usize,
     count: usize,
+    with_checksums: bool,
 ) -> CoreResult<Vec<SectionEntry>> {
     let mut cursor = start;
     let mut sections = Vec::with_capacity(count);
     for _ in 0..count {
-        let section_type = input
-            .get(cursor)
-            .copied()
-            .ok_or(CoreError::InvalidInput("section directory out of bounds"))?;
+        let section_type = input.get(cursor).copied().ok_or(CoreError::invalid_pack(
+            cursor,
+            "section directory out of bounds",
+        ))?;
         cursor += 1;
         let offset = read_u32(input, &mut cursor)?;
         let length = read_u32(input, &mut cursor)?;
+        let checksum = if with_checksums {
+            Some(read_u32(input, &mut cursor)?)
+        } else {
+            None
+        };
         sections.push(SectionEntry {
             section_type,
             offset,
             length,
+            checksum,
         });
     }
     Ok(sections)
 }
 
+pub fn section_bytes<'a>(input: &'a [u8], section: &SectionEntry) -> CoreResult<&'a [u8]> {
+    let start = section.offset as usize;
+    let end = start + section.length as usize;
+    let bytes = input
+        .get(start..end)
+        .ok_or(CoreError::invalid_pack(start, "section out of bounds"))?;
+    if let Some(expected) = section.checksum
+        && crc32(bytes) != expected
+    {
+        return Err(CoreError::InvalidInput("section checksum mismatch"));
+    }
+    Ok(bytes)
+}
+
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc = CRC32_TABLE[((crc ^ u32::from(*byte)) & 0xff) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut index = 0;
+    while index < 256 {
+        let mut crc = index as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[index] = crc;
+        index += 1;
+    }
+    table
+}
+
 fn read_u16(input: &[u8], cursor: &mut usize) -> CoreResult<u16> {
     let end = *cursor + 2;
     if end > input.len() {
-        return Err(CoreError::InvalidInput("unexpected eof"));
+        return Err(CoreError::invalid_pack(*cursor, "unexpected eof"));
     }
     let value = u16::from_le_bytes([input[*cursor], input[*cursor + 1]]);
     *cursor = end;
@@ -114,7 +180,7 @@ fn read_u16(input: &[u8], cursor: &mut usize) -> CoreResult<u16> {
 fn read_u32(input: &[u8], cursor: &mut usize) -> CoreResult<u32> {
     let end = *cursor + 4;
     if end > input.len() {
-        return Err(CoreError::InvalidInput("unexpected eof"));
+        return Err(CoreError::invalid_pack(*cursor, "unexpected eof"));
     }
     let value = u32::from_le_bytes([
         input[*cursor],
@@ -129,7 +195,7 @@ fn read_u32(input: &[u8], cursor: &mut usize) -> CoreResult<u32> {
 fn read_u64(input: &[u8], cursor: &mut usize) -> CoreResult<u64> {
     let end = *cursor + 8;
     if end > input.len() {
-        return Err(CoreError::InvalidInput("unexpected eof"));
+        return Err(CoreError::invalid_pack(*cursor, "unexpected eof"));
     }
     let value = u64::from_le_bytes([
         input[*cursor],
@@ -150,7 +216,10 @@ mod tests {
     use alloc::vec;
     use alloc::vec::Vec;
 
-    use super::{PACK_MAGIC, PackKind, SectionEntry, parse_pack_header, parse_section_directory};
+    use super::{
+        PACK_MAGIC, PackKind, SectionEntry, crc32, parse_pack_header, parse_section_directory,
+        section_bytes,
+    };
 
     fn build_header(kind: u8) -> Vec<u8> {
         let mut bytes = Vec::new();
@@ -191,14 +260,41 @@ mod tests {
         bytes.push(2);
         bytes.extend_from_slice(&10u32.to_le_bytes());
         bytes.extend_from_slice(&4u32.to_le_bytes());
-        let sections = parse_section_directory(&bytes, bytes.len() - 9, 1).expect("sections");
+        let sections =
+            parse_section_directory(&bytes, bytes.len() - 9, 1, false).expect("sections");
         assert_eq!(
             sections,
             vec![SectionEntry {
                 section_type: 2,
                 offset: 10,
-                length: 4
+                length: 4,
+                checksum: None,
             }]
         );
     }
+
+    #[test]
+    fn crc32_matches_reference_value() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn detects_section_checksum_mismatch() {
+        let data = b"section payload";
+        let mut bytes = Vec::new();
+        bytes.push(1);
+        bytes.extend_from_slice(&13u32.to_le_bytes());
+        bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&crc32(data).to_le_bytes());
+        bytes.extend_from_slice(data);
+        let sections = parse_section_directory(&bytes, 0, 1, true).expect("sections");
+        assert_eq!(section_bytes(&bytes, &sections[0]).expect("valid"), data);
+
+        bytes[20] ^= 0x01;
+        assert_eq!(
+            section_bytes(&bytes, &sections[0]).err(),
+            Some(crate::CoreError::InvalidInput("section checksum mismatch"))
+        );
+    }
 }
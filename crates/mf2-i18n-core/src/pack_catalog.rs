@@ -2,10 +2,13 @@ use alloc::collections::BTreeMap;
 use alloc::string::String;
 use alloc::vec::Vec;
 
+use crate::pack_decode::read_message_id;
 use crate::{
     BytecodeProgram, CaseEntry, CaseKey, CaseTable, Catalog, CoreError, CoreResult, FormatterId,
-    MessageId, PackHeader, PackKind, PluralRuleset, SectionEntry, StringPool, decode_sparse_index,
+    MessageId, MultiCaseEntry, MultiCaseTable, MultiSelector, PACK_SCHEMA_VERSION, PackHeader,
+    PackKind, PluralRuleset, SectionEntry, SharedTables, StringPool, decode_sparse_index,
     decode_string_pool, parse_pack_header, parse_section_directory, read_bytecode_at,
+    section_bytes, verify,
 };
 
 const SECTION_STRING_POOL: u8 = 1;
@@ -13,42 +16,137 @@ const SECTION_MESSAGE_INDEX: u8 = 2;
 const SECTION_BYTECODE_BLOB: u8 = 3;
 const SECTION_CASE_TABLES: u8 = 4;
 const SECTION_MESSAGE_META: u8 = 5;
+const SECTION_MULTI_CASE_TABLES: u8 = 6;
 
 pub struct PackCatalog {
     header: PackHeader,
-    messages: BTreeMap<MessageId, BytecodeProgram>,
+    sections: Vec<SectionEntry>,
+    messages: BTreeMap<MessageId, PackMessage>,
+    string_pool: StringPool,
+    case_tables: SharedTables<CaseTable>,
+    multi_case_tables: SharedTables<MultiCaseTable>,
+    meta: BTreeMap<MessageId, Vec<String>>,
+    blob: Vec<u8>,
+}
+
+#[cfg(feature = "std")]
+type ProgramCell = std::sync::OnceLock<BytecodeProgram>;
+#[cfg(not(feature = "std"))]
+type ProgramCell = core::cell::OnceCell<BytecodeProgram>;
+
+struct PackMessage {
+    offset: u32,
+    program: ProgramCell,
 }
 
 impl PackCatalog {
     pub fn decode(bytes: &[u8], expected_id_map_hash: &[u8; 32]) -> CoreResult<Self> {
+        let (mut catalog, blob) = Self::decode_sections(bytes, expected_id_map_hash)?;
+        for (message_id, message) in &catalog.messages {
+            let program = catalog.decode_program(blob, *message_id, message.offset)?;
+            let _ = message.program.set(program);
+        }
+        catalog.meta.clear();
+        Ok(catalog)
+    }
+
+    pub fn decode_lazy(bytes: &[u8], expected_id_map_hash: &[u8; 32]) -> CoreResult<Self> {
+        let (mut catalog, blob) = Self::decode_sections(bytes, expected_id_map_hash)?;
+        catalog.blob = blob.to_vec();
+        Ok(catalog)
+    }
+
+    pub fn header(&self) -> &PackHeader {
+        &self.header
+    }
+
+    pub fn locale_tag(&self) -> &str {
+        self.string_pool
+            .get(self.header.locale_tag_sidx)
+            .unwrap_or_default()
+    }
+
+    pub fn parent_tag(&self) -> Option<&str> {
+        self.header
+            .parent_tag_sidx
+            .and_then(|sidx| self.string_pool.get(sidx))
+    }
+
+    pub fn sections(&self) -> &[SectionEntry] {
+        &self.sections
+    }
+
+    pub fn message_ids(&self) -> impl Iterator<Item = MessageId> + '_ {
+        self.messages.keys().copied()
+    }
+
+    pub fn try_lookup(&self, id: MessageId) -> CoreResult<Option<&BytecodeProgram>> {
+        let Some(message) = self.messages.get(&id) else {
+            return Ok(None);
+        };
+        if let Some(program) = message.program.get() {
+            return Ok(Some(program));
+        }
+        let program = self.decode_program(&self.blob, id, message.offset)?;
+        Ok(Some(message.program.get_or_init(|| program)))
+    }
+
+    pub fn case_table_count(&self) -> usize {
+        self.case_tables.len() + self.multi_case_tables.len()
+    }
+
+    fn decode_sections<'a>(
+        bytes: &'a [u8],
+        expected_id_map_hash: &[u8; 32],
+    ) -> CoreResult<(Self, &'a [u8])> {
         let (header, mut cursor) = parse_pack_header(bytes)?;
+        if header.schema_version > PACK_SCHEMA_VERSION {
+            return Err(CoreError::Unsupported("pack schema too new"));
+        }
         if &header.id_map_hash != expected_id_map_hash {
             return Err(CoreError::InvalidInput("id map hash mismatch"));
         }
         let section_count = read_u16(bytes, &mut cursor)? as usize;
-        let sections = parse_section_directory(bytes, cursor, section_count)?;
+        let sections =
+            parse_section_directory(bytes, cursor, section_count, header.has_section_checksums())?;
         let section_map = map_sections(bytes, &sections)?;
 
         let string_pool_bytes = section_map
             .get(&SECTION_STRING_POOL)
             .ok_or(CoreError::InvalidInput("missing string pool section"))?;
-        let string_pool = decode_string_pool(string_pool_bytes)?;
+        let string_pool = decode_string_pool(string_pool_bytes)
+            .map_err(section_error(&sections, SECTION_STRING_POOL))?;
 
         let case_tables_bytes = section_map
             .get(&SECTION_CASE_TABLES)
             .ok_or(CoreError::InvalidInput("missing case tables section"))?;
-        let case_tables = decode_case_tables(case_tables_bytes)?;
+        let case_tables = decode_case_tables(case_tables_bytes)
+            .map_err(section_error(&sections, SECTION_CASE_TABLES))?;
+
+        let multi_case_tables = match section_map.get(&SECTION_MULTI_CASE_TABLES) {
+            Some(bytes) => decode_multi_case_tables(bytes)
+                .map_err(section_error(&sections, SECTION_MULTI_CASE_TABLES))?,
+            None => Vec::new(),
+        };
 
         let meta_bytes = section_map
             .get(&SECTION_MESSAGE_META)
             .ok_or(CoreError::InvalidInput("missing message meta section"))?;
-        let meta = decode_message_meta(meta_bytes, &string_pool)?;
+        let meta = decode_message_meta(meta_bytes, &string_pool, header.has_wide_message_ids())
+            .map_err(section_error(&sections, SECTION_MESSAGE_META))?;
+        let string_pool = StringPool::from(string_pool);
+        if string_pool.get(header.locale_tag_sidx).is_none() {
+            return Err(CoreError::InvalidInput("locale tag string index"));
+        }
 
         let index_bytes = section_map
             .get(&SECTION_MESSAGE_INDEX)
             .ok_or(CoreError::InvalidInput("missing message index section"))?;
         let index = match header.pack_kind {
-            PackKind::Base | PackKind::Overlay => decode_sparse_index(index_bytes)?,
+            PackKind::Base | PackKind::Overlay => {
+                decode_sparse_index(index_bytes, header.has_wide_message_ids())
+                    .map_err(section_error(&sections, SECTION_MESSAGE_INDEX))?
+            }
             PackKind::IcuData => {
                 return Err(CoreError::Unsupported("icu data packs not supported"));
             }
@@ -58,44 +156,82 @@ impl PackCatalog {
             .get(&SECTION_BYTECODE_BLOB)
             .ok_or(CoreError::InvalidInput("missing bytecode blob section"))?;
 
-        let mut messages = BTreeMap::new();
-        for (message_id, offset) in index {
-            let slice = read_bytecode_at(blob, offset)?;
-            let arg_names = meta.get(&message_id).cloned().unwrap_or_default();
-            let program = decode_message(slice, &string_pool, &case_tables, arg_names)?;
-            messages.insert(message_id, program);
-        }
-
-        Ok(Self { header, messages })
+        let messages = index
+            .into_iter()
+            .map(|(message_id, offset)| {
+                let message = PackMessage {
+                    offset,
+                    program: ProgramCell::new(),
+                };
+                (message_id, message)
+            })
+            .collect();
+
+        let catalog = Self {
+            header,
+            sections,
+            messages,
+            string_pool,
+            case_tables: case_tables.into(),
+            multi_case_tables: multi_case_tables.into(),
+            meta,
+            blob: Vec::new(),
+        };
+        Ok((catalog, blob))
     }
 
-    pub fn header(&self) -> &PackHeader {
-        &self.header
+    fn decode_program(
+        &self,
+        blob: &[u8],
+        message_id: MessageId,
+        offset: u32,
+    ) -> CoreResult<BytecodeProgram> {
+        let blob_error = section_error(&self.sections, SECTION_BYTECODE_BLOB);
+        let slice = read_bytecode_at(blob, offset).map_err(&blob_error)?;
+        let arg_names = self.meta.get(&message_id).cloned().unwrap_or_default();
+        let program = decode_message(
+            slice,
+            &self.string_pool,
+            &self.case_tables,
+            &self.multi_case_tables,
+            arg_names,
+        )
+        .map_err(|err| blob_error(err.at_offset(offset as usize + 4)))?;
+        verify(&program)?;
+        Ok(program)
     }
 }
 
 impl Catalog for PackCatalog {
     fn lookup(&self, id: MessageId) -> Option<&BytecodeProgram> {
-        self.messages.get(&id)
+        self.try_lookup(id).ok().flatten()
     }
 }
 
-fn map_sections<'a>(
+pub(crate) fn map_sections<'a>(
     bytes: &'a [u8],
     sections: &[SectionEntry],
 ) -> CoreResult<BTreeMap<u8, &'a [u8]>> {
     let mut map = BTreeMap::new();
     for section in sections {
-        let start = section.offset as usize;
-        let end = start + section.length as usize;
-        if end > bytes.len() {
-            return Err(CoreError::InvalidInput("section out of bounds"));
-        }
-        map.insert(section.section_type, &bytes[start..end]);
+        map.insert(section.section_type, section_bytes(bytes, section)?);
     }
     Ok(map)
 }
 
+fn section_error(
+    sections: &[SectionEntry],
+    section_type: u8,
+) -> impl Fn(CoreError) -> CoreError + '_ {
+    move |err| match sections
+        .iter()
+        .find(|section| section.section_type == section_type)
+    {
+        Some(section) => err.in_section(section_type, section.offset as usize),
+        None => err,
+    }
+}
+
 fn decode_case_tables(input: &[u8]) -> CoreResult<Vec<CaseTable>> {
     let mut cursor = 0usize;
     let count = read_u32(input, &mut cursor)? as usize;
@@ -104,25 +240,7 @@ fn decode_case_tables(input: &[u8]) -> CoreResult<Vec<CaseTable>> {
         let entry_count = read_u32(input, &mut cursor)? as usize;
         let mut entries = Vec::with_capacity(entry_count);
         for _ in 0..entry_count {
-            let key_type = read_u8(input, &mut cursor)?;
-            let key = match key_type {
-                0 => CaseKey::String(read_u32(input, &mut cursor)?),
-                1 => CaseKey::Exact(read_u32(input, &mut cursor)?),
-                2 => {
-                    let raw = read_u8(input, &mut cursor)?;
-                    let category = match raw {
-                        0 => crate::PluralCategory::Zero,
-                        1 => crate::PluralCategory::One,
-                        2 => crate::PluralCategory::Two,
-                        3 => crate::PluralCategory::Few,
-                        4 => crate::PluralCategory::Many,
-                        _ => crate::PluralCategory::Other,
-                    };
-                    CaseKey::Category(category)
-                }
-                3 => CaseKey::Other,
-                _ => return Err(CoreError::InvalidInput("unknown case key type")),
-            };
+            let key = decode_case_key(input, &mut cursor)?;
             let target = read_u32(input, &mut cursor)?;
             entries.push(CaseEntry { key, target });
         }
@@ -131,33 +249,95 @@ fn decode_case_tables(input: &[u8]) -> CoreResult<Vec<CaseTable>> {
     Ok(tables)
 }
 
+fn decode_multi_case_tables(input: &[u8]) -> CoreResult<Vec<MultiCaseTable>> {
+    let mut cursor = 0usize;
+    let count = read_u32(input, &mut cursor)? as usize;
+    let mut tables = Vec::with_capacity(count);
+    for _ in 0..count {
+        let selector_count = read_u8(input, &mut cursor)? as usize;
+        let mut selectors = Vec::with_capacity(selector_count);
+        for _ in 0..selector_count {
+            let aidx = read_u32(input, &mut cursor)?;
+            let ruleset = match read_u8(input, &mut cursor)? {
+                0xFF => None,
+                raw => Some(PluralRuleset::try_from(raw)?),
+            };
+            selectors.push(MultiSelector { aidx, ruleset });
+        }
+        let entry_count = read_u32(input, &mut cursor)? as usize;
+        let mut entries = Vec::with_capacity(entry_count);
+        for _ in 0..entry_count {
+            let mut keys = Vec::with_capacity(selector_count);
+            for _ in 0..selector_count {
+                keys.push(decode_case_key(input, &mut cursor)?);
+            }
+            let target = read_u32(input, &mut cursor)?;
+            entries.push(MultiCaseEntry { keys, target });
+        }
+        tables.push(MultiCaseTable { selectors, entries });
+    }
+    Ok(tables)
+}
+
+fn decode_case_key(input: &[u8], cursor: &mut usize) -> CoreResult<CaseKey> {
+    let key_type = read_u8(input, cursor)?;
+    let key = match key_type {
+        0 => CaseKey::String(read_u32(input, cursor)?),
+        1 => CaseKey::Exact(i64::from(read_u32(input, cursor)?)),
+        2 => {
+            let raw = read_u8(input, cursor)?;
+            let category = match raw {
+                0 => crate::PluralCategory::Zero,
+                1 => crate::PluralCategory::One,
+                2 => crate::PluralCategory::Two,
+                3 => crate::PluralCategory::Few,
+                4 => crate::PluralCategory::Many,
+                _ => crate::PluralCategory::Other,
+            };
+            CaseKey::Category(category)
+        }
+        3 => CaseKey::Other,
+        4 => CaseKey::Exact(read_i64(input, cursor)?),
+        _ => {
+            return Err(CoreError::invalid_pack(
+                *cursor - 1,
+                "unknown case key type",
+            ));
+        }
+    };
+    Ok(key)
+}
+
 fn decode_message_meta(
     input: &[u8],
     string_pool: &[String],
+    wide_ids: bool,
 ) -> CoreResult<BTreeMap<MessageId, Vec<String>>> {
     let mut cursor = 0usize;
     let count = read_u32(input, &mut cursor)? as usize;
     let mut map = BTreeMap::new();
     for _ in 0..count {
-        let id = read_u32(input, &mut cursor)?;
+        let id = read_message_id(input, &mut cursor, wide_ids)?;
         let arg_count = read_u32(input, &mut cursor)? as usize;
         let mut args = Vec::with_capacity(arg_count);
         for _ in 0..arg_count {
             let sidx = read_u32(input, &mut cursor)? as usize;
-            let name = string_pool
-                .get(sidx)
-                .ok_or(CoreError::InvalidInput("message meta string index"))?;
+            let name = string_pool.get(sidx).ok_or(CoreError::invalid_pack(
+                cursor - 4,
+                "message meta string index",
+            ))?;
             args.push(name.clone());
         }
-        map.insert(MessageId::new(id), args);
+        map.insert(id, args);
     }
     Ok(map)
 }
 
 fn decode_message(
     input: &[u8],
-    string_pool: &[String],
-    case_tables: &[CaseTable],
+    string_pool: &StringPool,
+    case_tables: &SharedTables<CaseTable>,
+    multi_case_tables: &SharedTables<MultiCaseTable>,
     arg_names: Vec<String>,
 ) -> CoreResult<BytecodeProgram> {
     let mut cursor = 0usize;
@@ -203,54 +383,83 @@ fn decode_message(
                     aidx,
                     ruleset,
                     table,
+                    offset: 0.0,
                 }
             }
             10 => crate::Opcode::Jump {
                 rel: read_i32(input, &mut cursor)?,
             },
             11 => crate::Opcode::End,
-            _ => return Err(CoreError::InvalidInput("unknown opcode tag")),
+            12 => {
+                let aidx = read_u32(input, &mut cursor)?;
+                let ruleset = PluralRuleset::try_from(read_u8(input, &mut cursor)?)?;
+                let table = read_u32(input, &mut cursor)?;
+                let offset = read_f64(input, &mut cursor)?;
+                crate::Opcode::SelectPlural {
+                    aidx,
+                    ruleset,
+                    table,
+                    offset,
+                }
+            }
+            13 => crate::Opcode::PushPluralArg {
+                aidx: read_u32(input, &mut cursor)?,
+                offset: read_f64(input, &mut cursor)?,
+            },
+            14 => crate::Opcode::SelectMulti {
+                table: read_u32(input, &mut cursor)?,
+            },
+            15 => crate::Opcode::OpenMarkup {
+                sidx: read_u32(input, &mut cursor)?,
+                opt_count: read_u8(input, &mut cursor)?,
+            },
+            16 => crate::Opcode::CloseMarkup {
+                sidx: read_u32(input, &mut cursor)?,
+                opt_count: read_u8(input, &mut cursor)?,
+            },
+            17 => crate::Opcode::StandaloneMarkup {
+                sidx: read_u32(input, &mut cursor)?,
+                opt_count: read_u8(input, &mut cursor)?,
+            },
+            _ => return Err(CoreError::invalid_pack(cursor - 1, "unknown opcode tag")),
         };
         opcodes.push(opcode);
     }
 
-    let mut pool = StringPool::new();
-    for entry in string_pool {
-        pool.push(entry.clone());
-    }
     let mut program = BytecodeProgram::new();
     program.opcodes = opcodes;
     program.number_pool = number_pool;
-    program.case_tables = case_tables.to_vec();
-    program.string_pool = pool;
+    program.case_tables = case_tables.clone();
+    program.multi_case_tables = multi_case_tables.clone();
+    program.string_pool = string_pool.clone();
     program.arg_names = arg_names;
     Ok(program)
 }
 
-fn read_u8(input: &[u8], cursor: &mut usize) -> CoreResult<u8> {
+pub(crate) fn read_u8(input: &[u8], cursor: &mut usize) -> CoreResult<u8> {
     let end = *cursor + 1;
     if end > input.len() {
-        return Err(CoreError::InvalidInput("unexpected eof"));
+        return Err(CoreError::invalid_pack(*cursor, "unexpected eof"));
     }
     let value = input[*cursor];
     *cursor = end;
     Ok(value)
 }
 
-fn read_u16(input: &[u8], cursor: &mut usize) -> CoreResult<u16> {
+pub(crate) fn read_u16(input: &[u8], cursor: &mut usize) -> CoreResult<u16> {
     let end = *cursor + 2;
     if end > input.len() {
-        return Err(CoreError::InvalidInput("unexpected eof"));
+        return Err(CoreError::invalid_pack(*cursor, "unexpected eof"));
     }
     let value = u16::from_le_bytes([input[*cursor], input[*cursor + 1]]);
     *cursor = end;
     Ok(value)
 }
 
-fn read_u32(input: &[u8], cursor: &mut usize) -> CoreResult<u32> {
+pub(crate) fn read_u32(input: &[u8], cursor: &mut usize) -> CoreResult<u32> {
     let end = *cursor + 4;
     if end > input.len() {
-        return Err(CoreError::InvalidInput("unexpected eof"));
+        return Err(CoreError::invalid_pack(*cursor, "unexpected eof"));
     }
     let value = u32::from_le_bytes([
         input[*cursor],
@@ -265,7 +474,7 @@ fn read_u32(input: &[u8], cursor: &mut usize) -> CoreResult<u32> {
 fn read_i32(input: &[u8], cursor: &mut usize) -> CoreResult<i32> {
     let end = *cursor + 4;
     if end > input.len() {
-        return Err(CoreError::InvalidInput("unexpected eof"));
+        return Err(CoreError::invalid_pack(*cursor, "unexpected eof"));
     }
     let value = i32::from_le_bytes([
         input[*cursor],
@@ -277,10 +486,29 @@ fn read_i32(input: &[u8], cursor: &mut usize) -> CoreResult<i32> {
     Ok(value)
 }
 
+fn read_i64(input: &[u8], cursor: &mut usize) -> CoreResult<i64> {
+    let end = *cursor + 8;
+    if end > input.len() {
+        return Err(CoreError::invalid_pack(*cursor, "unexpected eof"));
+    }
+    let value = i64::from_le_bytes([
+        input[*cursor],
+        input[*cursor + 1],
+        input[*cursor + 2],
+        input[*cursor + 3],
+        input[*cursor + 4],
+        input[*cursor + 5],
+        input[*cursor + 6],
+        input[*cursor + 7],
+    ]);
+    *cursor = end;
+    Ok(value)
+}
+
 fn read_f64(input: &[u8], cursor: &mut usize) -> CoreResult<f64> {
     let end = *cursor + 8;
     if end > input.len() {
-        return Err(CoreError::InvalidInput("unexpected eof"));
+        return Err(CoreError::invalid_pack(*cursor, "unexpected eof"));
     }
     let value = f64::from_le_bytes([
         input[*cursor],
@@ -319,6 +547,7 @@ impl TryFrom<u8> for PluralRuleset {
     fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
             0 => Ok(PluralRuleset::Cardinal),
+            1 => Ok(PluralRuleset::Ordinal),
             _ => Err(CoreError::InvalidInput("unknown plural ruleset")),
         }
     }
@@ -333,12 +562,12 @@ mod tests {
         PackCatalog, SECTION_BYTECODE_BLOB, SECTION_CASE_TABLES, SECTION_MESSAGE_INDEX,
         SECTION_MESSAGE_META, SECTION_STRING_POOL,
     };
-    use crate::{Catalog, MessageId, Opcode, PackKind};
+    use crate::{Catalog, CoreError, MessageId, Opcode, PACK_SCHEMA_VERSION, PackKind};
 
-    fn build_header(kind: PackKind, id_map_hash: [u8; 32]) -> Vec<u8> {
+    fn build_header(kind: PackKind, schema: u16, id_map_hash: [u8; 32]) -> Vec<u8> {
         let mut bytes = Vec::new();
         bytes.extend_from_slice(b"MF2PACK\0");
-        bytes.extend_from_slice(&0u16.to_le_bytes());
+        bytes.extend_from_slice(&schema.to_le_bytes());
         bytes.push(match kind {
             PackKind::Base => 0,
             PackKind::Overlay => 1,
@@ -352,10 +581,22 @@ mod tests {
         bytes
     }
 
-    #[test]
-    fn decodes_pack_catalog() {
-        let id_map_hash = [7u8; 32];
-        let mut bytes = build_header(PackKind::Base, id_map_hash);
+    fn build_pack(id_map_hash: [u8; 32], message: Vec<u8>) -> Vec<u8> {
+        build_pack_with_schema(0, id_map_hash, message)
+    }
+
+    fn build_pack_with_schema(schema: u16, id_map_hash: [u8; 32], message: Vec<u8>) -> Vec<u8> {
+        build_pack_with_case_tables(schema, id_map_hash, 0u32.to_le_bytes().to_vec(), message)
+    }
+
+    fn build_pack_with_case_tables(
+        schema: u16,
+        id_map_hash: [u8; 32],
+        case_tables: Vec<u8>,
+        message: Vec<u8>,
+    ) -> Vec<u8> {
+        let mut bytes = build_header(PackKind::Base, schema, id_map_hash);
+        let message_id: &[u8] = if schema >= 2 { &[0; 8] } else { &[0; 4] };
 
         let mut string_pool = Vec::new();
         string_pool.extend_from_slice(&2u32.to_le_bytes());
@@ -366,23 +607,14 @@ mod tests {
 
         let mut message_meta = Vec::new();
         message_meta.extend_from_slice(&1u32.to_le_bytes());
+        message_meta.extend_from_slice(message_id);
         message_meta.extend_from_slice(&0u32.to_le_bytes());
-        message_meta.extend_from_slice(&0u32.to_le_bytes());
-
-        let mut case_tables = Vec::new();
-        case_tables.extend_from_slice(&0u32.to_le_bytes());
 
         let mut message_index = Vec::new();
         message_index.extend_from_slice(&1u32.to_le_bytes());
-        message_index.extend_from_slice(&0u32.to_le_bytes());
+        message_index.extend_from_slice(message_id);
         message_index.extend_from_slice(&0u32.to_le_bytes());
 
-        let mut message = Vec::new();
-        message.extend_from_slice(&0u32.to_le_bytes());
-        message.extend_from_slice(&2u32.to_le_bytes());
-        message.push(0);
-        message.extend_from_slice(&0u32.to_le_bytes());
-        message.push(11);
         let mut bytecode_blob = Vec::new();
         bytecode_blob.extend_from_slice(&(message.len() as u32).to_le_bytes());
         bytecode_blob.extend_from_slice(&message);
@@ -411,6 +643,23 @@ mod tests {
             bytes.extend_from_slice(&data);
             offset += data.len() as u32;
         }
+        bytes
+    }
+
+    fn emit_hi_message() -> Vec<u8> {
+        let mut message = Vec::new();
+        message.extend_from_slice(&0u32.to_le_bytes());
+        message.extend_from_slice(&2u32.to_le_bytes());
+        message.push(0);
+        message.extend_from_slice(&0u32.to_le_bytes());
+        message.push(11);
+        message
+    }
+
+    #[test]
+    fn decodes_pack_catalog() {
+        let id_map_hash = [7u8; 32];
+        let bytes = build_pack(id_map_hash, emit_hi_message());
 
         let catalog = PackCatalog::decode(&bytes, &id_map_hash).expect("catalog");
         let program = catalog.lookup(MessageId::new(0)).expect("program");
@@ -419,4 +668,106 @@ mod tests {
             vec![Opcode::EmitText { sidx: 0 }, Opcode::End]
         );
     }
+
+    #[test]
+    fn accepts_current_schema_and_rejects_newer() {
+        let id_map_hash = [7u8; 32];
+        let mut bytes = build_pack_with_schema(PACK_SCHEMA_VERSION, id_map_hash, emit_hi_message());
+        let catalog = PackCatalog::decode(&bytes, &id_map_hash).expect("current schema");
+        assert_eq!(catalog.header().schema_version, PACK_SCHEMA_VERSION);
+        assert!(catalog.header().has_wide_message_ids());
+        assert!(catalog.lookup(MessageId::new(0)).is_some());
+
+        bytes[8..10].copy_from_slice(&(PACK_SCHEMA_VERSION + 1).to_le_bytes());
+        assert_eq!(
+            PackCatalog::decode(&bytes, &id_map_hash).err(),
+            Some(CoreError::Unsupported("pack schema too new"))
+        );
+        assert!(PackCatalog::decode_lazy(&bytes, &id_map_hash).is_err());
+    }
+
+    #[test]
+    fn rejects_unsorted_case_table() {
+        let id_map_hash = [7u8; 32];
+        let mut case_tables = Vec::new();
+        case_tables.extend_from_slice(&1u32.to_le_bytes());
+        case_tables.extend_from_slice(&2u32.to_le_bytes());
+        for sidx in [1u32, 0] {
+            case_tables.push(0);
+            case_tables.extend_from_slice(&sidx.to_le_bytes());
+            case_tables.extend_from_slice(&1u32.to_le_bytes());
+        }
+        let mut message = Vec::new();
+        message.extend_from_slice(&0u32.to_le_bytes());
+        message.extend_from_slice(&3u32.to_le_bytes());
+        message.push(8);
+        message.extend_from_slice(&0u32.to_le_bytes());
+        message.extend_from_slice(&0u32.to_le_bytes());
+        message.push(0);
+        message.extend_from_slice(&0u32.to_le_bytes());
+        message.push(11);
+        let bytes = build_pack_with_case_tables(0, id_map_hash, case_tables, message);
+
+        assert_eq!(
+            PackCatalog::decode(&bytes, &id_map_hash).err(),
+            Some(CoreError::InvalidInput("case table entries out of order"))
+        );
+    }
+
+    #[test]
+    fn truncated_string_pool_reports_offset() {
+        let id_map_hash = [7u8; 32];
+        let mut bytes = build_pack(id_map_hash, emit_hi_message());
+        let pool_start = u32::from_le_bytes([bytes[66], bytes[67], bytes[68], bytes[69]]) as usize;
+        let name_len = pool_start + 10;
+        bytes[name_len..name_len + 4].copy_from_slice(&9u32.to_le_bytes());
+
+        assert_eq!(
+            PackCatalog::decode(&bytes, &id_map_hash).err(),
+            Some(CoreError::InvalidPack {
+                section: Some(SECTION_STRING_POOL),
+                offset: name_len + 4,
+                reason: "string pool out of bounds",
+            })
+        );
+    }
+
+    #[test]
+    fn lazy_decode_caches_programs() {
+        let id_map_hash = [7u8; 32];
+        let bytes = build_pack(id_map_hash, emit_hi_message());
+
+        let catalog = PackCatalog::decode_lazy(&bytes, &id_map_hash).expect("catalog");
+        let first = catalog.lookup(MessageId::new(0)).expect("program");
+        let second = catalog.lookup(MessageId::new(0)).expect("program");
+        assert!(core::ptr::eq(first, second));
+        assert_eq!(
+            first.opcodes,
+            vec![Opcode::EmitText { sidx: 0 }, Opcode::End]
+        );
+        assert!(catalog.lookup(MessageId::new(1)).is_none());
+    }
+
+    #[test]
+    fn lazy_decode_defers_message_errors() {
+        let id_map_hash = [7u8; 32];
+        let mut message = Vec::new();
+        message.extend_from_slice(&0u32.to_le_bytes());
+        message.extend_from_slice(&1u32.to_le_bytes());
+        message.push(0xEE);
+        let bytes = build_pack(id_map_hash, message);
+
+        assert!(PackCatalog::decode(&bytes, &id_map_hash).is_err());
+        let catalog = PackCatalog::decode_lazy(&bytes, &id_map_hash).expect("catalog");
+        assert!(matches!(
+            catalog.try_lookup(MessageId::new(0)),
+            Err(CoreError::InvalidPack {
+                section: Some(SECTION_BYTECODE_BLOB),
+                reason: "unknown opcode tag",
+                ..
+            })
+        ));
+        assert!(catalog.lookup(MessageId::new(0)).is_none());
+        assert_eq!(catalog.try_lookup(MessageId::new(1)), Ok(None));
+    }
 }
@@ -11,11 +11,11 @@ pub fn decode_string_pool(input: &[u8]) -> CoreResult<Vec<String>> {
         let len = read_u32(input, &mut cursor)? as usize;
         let end = cursor + len;
         if end > input.len() {
-            return Err(CoreError::InvalidInput("string pool out of bounds"));
+            return Err(CoreError::invalid_pack(cursor, "string pool out of bounds"));
         }
         let bytes = &input[cursor..end];
         let text = core::str::from_utf8(bytes)
-            .map_err(|_| CoreError::InvalidInput("string pool invalid utf8"))?;
+            .map_err(|_| CoreError::invalid_pack(cursor, "string pool invalid utf8"))?;
         let text = String::from(text);
         entries.push(text);
         cursor = end;
@@ -33,28 +33,52 @@ pub fn decode_dense_index(input: &[u8]) -> CoreResult<Vec<u32>> {
     Ok(offsets)
 }
 
-pub fn decode_sparse_index(input: &[u8]) -> CoreResult<Vec<(MessageId, u32)>> {
+pub fn decode_sparse_index(input: &[u8], wide_ids: bool) -> CoreResult<Vec<(MessageId, u32)>> {
     let mut cursor = 0usize;
     let count = read_u32(input, &mut cursor)? as usize;
     let mut pairs = Vec::with_capacity(count);
     for _ in 0..count {
-        let id = read_u32(input, &mut cursor)?;
+        let id = read_message_id(input, &mut cursor, wide_ids)?;
         let offset = read_u32(input, &mut cursor)?;
-        pairs.push((MessageId::new(id), offset));
+        pairs.push((id, offset));
     }
     Ok(pairs)
 }
 
-pub fn read_bytecode_at<'a>(blob: &'a [u8], offset: u32) -> CoreResult<&'a [u8]> {
+pub(crate) fn read_message_id(
+    input: &[u8],
+    cursor: &mut usize,
+    wide: bool,
+) -> CoreResult<MessageId> {
+    if !wide {
+        return read_u32(input, cursor).map(|id| MessageId::new(u64::from(id)));
+    }
+    let end = *cursor + 8;
+    if end > input.len() {
+        return Err(CoreError::invalid_pack(*cursor, "unexpected eof"));
+    }
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&input[*cursor..end]);
+    *cursor = end;
+    Ok(MessageId::new(u64::from_le_bytes(bytes)))
+}
+
+pub fn read_bytecode_at(blob: &[u8], offset: u32) -> CoreResult<&[u8]> {
     let offset = offset as usize;
     if offset + 4 > blob.len() {
-        return Err(CoreError::InvalidInput("bytecode offset out of bounds"));
+        return Err(CoreError::invalid_pack(
+            offset,
+            "bytecode offset out of bounds",
+        ));
     }
     let mut cursor = offset;
     let len = read_u32(blob, &mut cursor)? as usize;
     let end = cursor + len;
     if end > blob.len() {
-        return Err(CoreError::InvalidInput("bytecode length out of bounds"));
+        return Err(CoreError::invalid_pack(
+            cursor,
+            "bytecode length out of bounds",
+        ));
     }
     Ok(&blob[cursor..end])
 }
@@ -62,7 +86,7 @@ pub fn read_bytecode_at<'a>(blob: &'a [u8], offset: u32) -> CoreResult<&'a [u8]>
 fn read_u32(input: &[u8], cursor: &mut usize) -> CoreResult<u32> {
     let end = *cursor + 4;
     if end > input.len() {
-        return Err(CoreError::InvalidInput("unexpected eof"));
+        return Err(CoreError::invalid_pack(*cursor, "unexpected eof"));
     }
     let value = u32::from_le_bytes([
         input[*cursor],
@@ -113,13 +137,25 @@ mod tests {
         bytes.extend_from_slice(&10u32.to_le_bytes());
         bytes.extend_from_slice(&2u32.to_le_bytes());
         bytes.extend_from_slice(&20u32.to_le_bytes());
-        let index = decode_sparse_index(&bytes).expect("index");
+        let index = decode_sparse_index(&bytes, false).expect("index");
         assert_eq!(
             index,
             vec![(MessageId::new(1), 10), (MessageId::new(2), 20)]
         );
     }
 
+    #[test]
+    fn decodes_sparse_index_with_wide_ids() {
+        let wide_id = 0x1_0000_0001u64;
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&wide_id.to_le_bytes());
+        bytes.extend_from_slice(&10u32.to_le_bytes());
+        let index = decode_sparse_index(&bytes, true).expect("index");
+        assert_eq!(index, vec![(MessageId::new(wide_id), 10)]);
+        assert!(decode_sparse_index(&bytes[..8], true).is_err());
+    }
+
     #[test]
     fn reads_bytecode_blob() {
         let mut bytes = Vec::new();
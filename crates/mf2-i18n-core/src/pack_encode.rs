@@ -0,0 +1,1098 @@
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{
+    BytecodeProgram, CaseEntry, CaseKey, CaseTable, MessageId, MultiCaseEntry, MultiCaseTable,
+    NumberSymbols, Opcode, PACK_FLAG_SECTION_CHECKSUMS, PACK_SCHEMA_VERSION, PackKind,
+    PluralCategory, PluralOperand, PluralRelation, PluralRules, PluralRuleset, StringPool, crc32,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PackEncodeError {
+    MissingArgName(u64, String),
+    TooManySelectors(usize),
+}
+
+impl fmt::Display for PackEncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PackEncodeError::MissingArgName(message_id, name) => write!(
+                f,
+                "arg name {name} of message {message_id} is missing from the string pool"
+            ),
+            PackEncodeError::TooManySelectors(count) => {
+                write!(
+                    f,
+                    "multi-select has {count} selectors, at most 255 fit in a pack"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PackEncodeError {}
+
+pub struct PackBuildInput {
+    pub pack_kind: PackKind,
+    pub id_map_hash: [u8; 32],
+    pub locale_tag: String,
+    pub parent_tag: Option<String>,
+    pub build_epoch_ms: u64,
+    pub messages: BTreeMap<MessageId, BytecodeProgram>,
+    pub sort_strings: bool,
+}
+
+pub struct IcuDataBuildInput {
+    pub locale_tag: String,
+    pub build_epoch_ms: u64,
+    pub cardinal: PluralRules,
+    pub ordinal: PluralRules,
+    pub symbols: NumberSymbols,
+}
+
+pub fn encode_pack(input: &PackBuildInput) -> Result<Vec<u8>, PackEncodeError> {
+    let mut interner = StringInterner::new();
+    let mut locale_tag_sidx = interner.intern(&input.locale_tag);
+    let mut parent_tag_sidx = input.parent_tag.as_ref().map(|tag| interner.intern(tag));
+
+    let mut remapped_messages = BTreeMap::new();
+    let mut case_tables = CaseTableInterner::new();
+    let mut multi_case_tables = Vec::new();
+    for (message_id, program) in &input.messages {
+        let (remapped, local_multi_tables) = remap_program(
+            program,
+            &mut interner,
+            &mut case_tables,
+            multi_case_tables.len() as u32,
+        );
+        multi_case_tables.extend(local_multi_tables);
+        remapped_messages.insert(*message_id, remapped);
+    }
+
+    let mut string_pool = interner.into_pool();
+    let mut case_tables = case_tables.into_tables();
+    if input.sort_strings {
+        let (sorted, mapping) = sort_string_pool(&string_pool);
+        string_pool = sorted;
+        locale_tag_sidx = mapping[locale_tag_sidx as usize];
+        parent_tag_sidx = parent_tag_sidx.map(|sidx| mapping[sidx as usize]);
+        for program in remapped_messages.values_mut() {
+            for opcode in &mut program.opcodes {
+                *opcode = remap_opcode_strings(*opcode, &mapping);
+            }
+        }
+        for entry in case_tables.iter_mut().flat_map(|table| &mut table.entries) {
+            entry.key = remap_case_key(&entry.key, &mapping);
+        }
+        for entry in multi_case_tables
+            .iter_mut()
+            .flat_map(|table| &mut table.entries)
+        {
+            for key in &mut entry.keys {
+                *key = remap_case_key(key, &mapping);
+            }
+        }
+    }
+
+    let string_section = encode_string_pool(&string_pool);
+    let case_section = encode_case_tables(&case_tables);
+    let meta_section = encode_message_meta(&remapped_messages, &string_pool)?;
+    let (blob_section, index_section) = encode_bytecode_blob(&remapped_messages, input.pack_kind);
+
+    let mut sections = vec![
+        (1u8, string_section),
+        (2u8, index_section),
+        (3u8, blob_section),
+        (4u8, case_section),
+        (5u8, meta_section),
+    ];
+    if !multi_case_tables.is_empty() {
+        sections.push((6u8, encode_multi_case_tables(&multi_case_tables)?));
+    }
+
+    Ok(build_pack_bytes(
+        input.pack_kind,
+        input.id_map_hash,
+        locale_tag_sidx,
+        parent_tag_sidx,
+        input.build_epoch_ms,
+        sections,
+    ))
+}
+
+pub fn encode_icu_data_pack(input: &IcuDataBuildInput) -> Vec<u8> {
+    let mut interner = StringInterner::new();
+    let locale_tag_sidx = interner.intern(&input.locale_tag);
+    let mut symbols = Vec::new();
+    for symbol in [
+        &input.symbols.decimal,
+        &input.symbols.group,
+        &input.symbols.minus,
+    ] {
+        symbols.extend_from_slice(&interner.intern(symbol).to_le_bytes());
+    }
+
+    let mut plural_rules = Vec::new();
+    plural_rules.extend_from_slice(&2u32.to_le_bytes());
+    for (ruleset, rules) in [
+        (PluralRuleset::Cardinal, &input.cardinal),
+        (PluralRuleset::Ordinal, &input.ordinal),
+    ] {
+        plural_rules.push(encode_ruleset(ruleset));
+        plural_rules.extend_from_slice(&(rules.rules.len() as u32).to_le_bytes());
+        for rule in &rules.rules {
+            plural_rules.push(encode_category(rule.category));
+            plural_rules.extend_from_slice(&(rule.conditions.len() as u32).to_le_bytes());
+            for relations in &rule.conditions {
+                plural_rules.extend_from_slice(&(relations.len() as u32).to_le_bytes());
+                for relation in relations {
+                    encode_plural_relation(&mut plural_rules, relation);
+                }
+            }
+        }
+    }
+
+    let sections = vec![
+        (1u8, encode_string_pool(&interner.into_pool())),
+        (7u8, plural_rules),
+        (8u8, symbols),
+    ];
+    build_pack_bytes(
+        PackKind::IcuData,
+        [0u8; 32],
+        locale_tag_sidx,
+        None,
+        input.build_epoch_ms,
+        sections,
+    )
+}
+
+fn encode_plural_relation(bytes: &mut Vec<u8>, relation: &PluralRelation) {
+    bytes.push(match relation.operand {
+        PluralOperand::N => 0,
+        PluralOperand::I => 1,
+        PluralOperand::V => 2,
+        PluralOperand::F => 3,
+        PluralOperand::T => 4,
+    });
+    bytes.extend_from_slice(&relation.modulus.unwrap_or(0).to_le_bytes());
+    bytes.push(u8::from(relation.negate));
+    bytes.extend_from_slice(&(relation.ranges.len() as u32).to_le_bytes());
+    for (start, end) in &relation.ranges {
+        bytes.extend_from_slice(&start.to_le_bytes());
+        bytes.extend_from_slice(&end.to_le_bytes());
+    }
+}
+
+fn remap_program(
+    program: &BytecodeProgram,
+    interner: &mut StringInterner,
+    case_tables: &mut CaseTableInterner,
+    multi_case_offset: u32,
+) -> (BytecodeProgram, Vec<MultiCaseTable>) {
+    let mut mapping = Vec::with_capacity(program.string_pool.len());
+    for idx in 0..program.string_pool.len() {
+        let value = program.string_pool.get(idx as u32).unwrap_or("");
+        let new_idx = interner.intern(value);
+        mapping.push(new_idx);
+    }
+
+    for arg in &program.arg_names {
+        interner.intern(arg);
+    }
+
+    let mut table_mapping = Vec::with_capacity(program.case_tables.len());
+    for table in &program.case_tables {
+        let mut sorted = table.clone();
+        sorted.sort_entries(&program.string_pool);
+        let entries = sorted
+            .entries
+            .iter()
+            .map(|entry| CaseEntry {
+                key: remap_case_key(&entry.key, &mapping),
+                target: entry.target,
+            })
+            .collect();
+        table_mapping.push(case_tables.intern(CaseTable { entries }));
+    }
+
+    let mut multi_tables = Vec::with_capacity(program.multi_case_tables.len());
+    for table in &program.multi_case_tables {
+        let mut entries = Vec::with_capacity(table.entries.len());
+        for entry in &table.entries {
+            entries.push(MultiCaseEntry {
+                keys: entry
+                    .keys
+                    .iter()
+                    .map(|key| remap_case_key(key, &mapping))
+                    .collect(),
+                target: entry.target,
+            });
+        }
+        multi_tables.push(MultiCaseTable {
+            selectors: table.selectors.clone(),
+            entries,
+        });
+    }
+
+    let mut opcodes = Vec::with_capacity(program.opcodes.len());
+    for opcode in &program.opcodes {
+        let remapped = match *opcode {
+            Opcode::Select { aidx, table } => Opcode::Select {
+                aidx,
+                table: table_mapping[table as usize],
+            },
+            Opcode::SelectPlural {
+                aidx,
+                ruleset,
+                table,
+                offset,
+            } => Opcode::SelectPlural {
+                aidx,
+                ruleset,
+                table: table_mapping[table as usize],
+                offset,
+            },
+            Opcode::SelectMulti { table } => Opcode::SelectMulti {
+                table: table + multi_case_offset,
+            },
+            other => remap_opcode_strings(other, &mapping),
+        };
+        opcodes.push(remapped);
+    }
+
+    let mut program_out = BytecodeProgram::new();
+    program_out.opcodes = opcodes;
+    program_out.number_pool = program.number_pool.clone();
+    program_out.string_pool = StringPool::new();
+    program_out.arg_names = program.arg_names.clone();
+
+    (program_out, multi_tables)
+}
+
+fn remap_opcode_strings(opcode: Opcode, mapping: &[u32]) -> Opcode {
+    match opcode {
+        Opcode::EmitText { sidx } => Opcode::EmitText {
+            sidx: mapping[sidx as usize],
+        },
+        Opcode::PushStr { sidx } => Opcode::PushStr {
+            sidx: mapping[sidx as usize],
+        },
+        Opcode::OpenMarkup { sidx, opt_count } => Opcode::OpenMarkup {
+            sidx: mapping[sidx as usize],
+            opt_count,
+        },
+        Opcode::CloseMarkup { sidx, opt_count } => Opcode::CloseMarkup {
+            sidx: mapping[sidx as usize],
+            opt_count,
+        },
+        Opcode::StandaloneMarkup { sidx, opt_count } => Opcode::StandaloneMarkup {
+            sidx: mapping[sidx as usize],
+            opt_count,
+        },
+        other => other,
+    }
+}
+
+fn sort_string_pool(pool: &StringPool) -> (StringPool, Vec<u32>) {
+    let mut order: Vec<u32> = (0..pool.len() as u32).collect();
+    order.sort_by_key(|idx| pool.get(*idx).unwrap_or(""));
+    let mut sorted = StringPool::new();
+    let mut mapping = vec![0u32; order.len()];
+    for old in order {
+        mapping[old as usize] = sorted.push(pool.get(old).unwrap_or(""));
+    }
+    (sorted, mapping)
+}
+
+fn remap_case_key(key: &CaseKey, mapping: &[u32]) -> CaseKey {
+    match *key {
+        CaseKey::String(old) => CaseKey::String(mapping[old as usize]),
+        CaseKey::Exact(value) => CaseKey::Exact(value),
+        CaseKey::Category(cat) => CaseKey::Category(cat),
+        CaseKey::Other => CaseKey::Other,
+    }
+}
+
+fn encode_string_pool(pool: &StringPool) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&(pool.len() as u32).to_le_bytes());
+    for idx in 0..pool.len() {
+        let value = pool.get(idx as u32).unwrap_or("");
+        let slice = value.as_bytes();
+        bytes.extend_from_slice(&(slice.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(slice);
+    }
+    bytes
+}
+
+fn encode_case_tables(tables: &[CaseTable]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&(tables.len() as u32).to_le_bytes());
+    for table in tables {
+        bytes.extend_from_slice(&(table.entries.len() as u32).to_le_bytes());
+        for entry in &table.entries {
+            encode_case_key(&mut bytes, &entry.key);
+            bytes.extend_from_slice(&entry.target.to_le_bytes());
+        }
+    }
+    bytes
+}
+
+fn encode_multi_case_tables(tables: &[MultiCaseTable]) -> Result<Vec<u8>, PackEncodeError> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&(tables.len() as u32).to_le_bytes());
+    for table in tables {
+        let selector_count = u8::try_from(table.selectors.len())
+            .map_err(|_| PackEncodeError::TooManySelectors(table.selectors.len()))?;
+        bytes.push(selector_count);
+        for selector in &table.selectors {
+            bytes.extend_from_slice(&selector.aidx.to_le_bytes());
+            bytes.push(selector.ruleset.map(encode_ruleset).unwrap_or(0xFF));
+        }
+        bytes.extend_from_slice(&(table.entries.len() as u32).to_le_bytes());
+        for entry in &table.entries {
+            for key in &entry.keys {
+                encode_case_key(&mut bytes, key);
+            }
+            bytes.extend_from_slice(&entry.target.to_le_bytes());
+        }
+    }
+    Ok(bytes)
+}
+
+fn encode_case_key(bytes: &mut Vec<u8>, key: &CaseKey) {
+    match *key {
+        CaseKey::String(sidx) => {
+            bytes.push(0);
+            bytes.extend_from_slice(&sidx.to_le_bytes());
+        }
+        CaseKey::Exact(value) => match u32::try_from(value) {
+            Ok(value) => {
+                bytes.push(1);
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+            Err(_) => {
+                bytes.push(4);
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        },
+        CaseKey::Category(cat) => {
+            bytes.push(2);
+            bytes.push(encode_category(cat));
+        }
+        CaseKey::Other => {
+            bytes.push(3);
+        }
+    }
+}
+
+fn encode_message_meta(
+    messages: &BTreeMap<MessageId, BytecodeProgram>,
+    pool: &StringPool,
+) -> Result<Vec<u8>, PackEncodeError> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&(messages.len() as u32).to_le_bytes());
+    for (message_id, program) in messages {
+        bytes.extend_from_slice(&message_id.get().to_le_bytes());
+        bytes.extend_from_slice(&(program.arg_names.len() as u32).to_le_bytes());
+        for arg in &program.arg_names {
+            let sidx = find_string(pool, arg)
+                .ok_or_else(|| PackEncodeError::MissingArgName(message_id.get(), arg.clone()))?;
+            bytes.extend_from_slice(&sidx.to_le_bytes());
+        }
+    }
+    Ok(bytes)
+}
+
+fn encode_bytecode_blob(
+    messages: &BTreeMap<MessageId, BytecodeProgram>,
+    pack_kind: PackKind,
+) -> (Vec<u8>, Vec<u8>) {
+    let mut blob = Vec::new();
+    let mut offsets = BTreeMap::new();
+    for (message_id, program) in messages {
+        let offset = blob.len() as u32;
+        let bytes = encode_message(program);
+        blob.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        blob.extend_from_slice(&bytes);
+        offsets.insert(*message_id, offset);
+    }
+
+    let index = match pack_kind {
+        PackKind::Base => encode_sparse_index(&offsets),
+        PackKind::Overlay => encode_sparse_index(&offsets),
+        PackKind::IcuData => Vec::new(),
+    };
+    (blob, index)
+}
+
+fn encode_sparse_index(offsets: &BTreeMap<MessageId, u32>) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&(offsets.len() as u32).to_le_bytes());
+    for (id, offset) in offsets {
+        bytes.extend_from_slice(&id.get().to_le_bytes());
+        bytes.extend_from_slice(&offset.to_le_bytes());
+    }
+    bytes
+}
+
+fn encode_message(program: &BytecodeProgram) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&(program.number_pool.len() as u32).to_le_bytes());
+    for value in &program.number_pool {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes.extend_from_slice(&(program.opcodes.len() as u32).to_le_bytes());
+    for opcode in &program.opcodes {
+        encode_opcode(&mut bytes, *opcode);
+    }
+    bytes
+}
+
+fn encode_opcode(bytes: &mut Vec<u8>, opcode: Opcode) {
+    match opcode {
+        Opcode::EmitText { sidx } => {
+            bytes.push(0);
+            bytes.extend_from_slice(&sidx.to_le_bytes());
+        }
+        Opcode::EmitStack => bytes.push(1),
+        Opcode::PushStr { sidx } => {
+            bytes.push(2);
+            bytes.extend_from_slice(&sidx.to_le_bytes());
+        }
+        Opcode::PushNum { nidx } => {
+            bytes.push(3);
+            bytes.extend_from_slice(&nidx.to_le_bytes());
+        }
+        Opcode::PushArg { aidx } => {
+            bytes.push(4);
+            bytes.extend_from_slice(&aidx.to_le_bytes());
+        }
+        Opcode::Dup => bytes.push(5),
+        Opcode::Pop => bytes.push(6),
+        Opcode::CallFmt { fid, opt_count } => {
+            bytes.push(7);
+            bytes.push(fid as u8);
+            bytes.push(opt_count);
+        }
+        Opcode::Select { aidx, table } => {
+            bytes.push(8);
+            bytes.extend_from_slice(&aidx.to_le_bytes());
+            bytes.extend_from_slice(&table.to_le_bytes());
+        }
+        Opcode::SelectPlural {
+            aidx,
+            ruleset,
+            table,
+            offset,
+        } => {
+            bytes.push(if offset == 0.0 { 9 } else { 12 });
+            bytes.extend_from_slice(&aidx.to_le_bytes());
+            bytes.push(encode_ruleset(ruleset));
+            bytes.extend_from_slice(&table.to_le_bytes());
+            if offset != 0.0 {
+                bytes.extend_from_slice(&offset.to_le_bytes());
+            }
+        }
+        Opcode::PushPluralArg { aidx, offset } => {
+            bytes.push(13);
+            bytes.extend_from_slice(&aidx.to_le_bytes());
+            bytes.extend_from_slice(&offset.to_le_bytes());
+        }
+        Opcode::SelectMulti { table } => {
+            bytes.push(14);
+            bytes.extend_from_slice(&table.to_le_bytes());
+        }
+        Opcode::Jump { rel } => {
+            bytes.push(10);
+            bytes.extend_from_slice(&rel.to_le_bytes());
+        }
+        Opcode::OpenMarkup { sidx, opt_count } => {
+            bytes.push(15);
+            bytes.extend_from_slice(&sidx.to_le_bytes());
+            bytes.push(opt_count);
+        }
+        Opcode::CloseMarkup { sidx, opt_count } => {
+            bytes.push(16);
+            bytes.extend_from_slice(&sidx.to_le_bytes());
+            bytes.push(opt_count);
+        }
+        Opcode::StandaloneMarkup { sidx, opt_count } => {
+            bytes.push(17);
+            bytes.extend_from_slice(&sidx.to_le_bytes());
+            bytes.push(opt_count);
+        }
+        Opcode::End => bytes.push(11),
+    }
+}
+
+fn encode_ruleset(ruleset: PluralRuleset) -> u8 {
+    match ruleset {
+        PluralRuleset::Cardinal => 0,
+        PluralRuleset::Ordinal => 1,
+    }
+}
+
+fn encode_category(category: PluralCategory) -> u8 {
+    match category {
+        PluralCategory::Zero => 0,
+        PluralCategory::One => 1,
+        PluralCategory::Two => 2,
+        PluralCategory::Few => 3,
+        PluralCategory::Many => 4,
+        PluralCategory::Other => 5,
+    }
+}
+
+fn find_string(pool: &StringPool, value: &str) -> Option<u32> {
+    (0..pool.len() as u32).find(|idx| pool.get(*idx) == Some(value))
+}
+
+fn build_pack_bytes(
+    pack_kind: PackKind,
+    id_map_hash: [u8; 32],
+    locale_tag_sidx: u32,
+    parent_tag_sidx: Option<u32>,
+    build_epoch_ms: u64,
+    sections: Vec<(u8, Vec<u8>)>,
+) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(b"MF2PACK\0");
+    bytes.extend_from_slice(&PACK_SCHEMA_VERSION.to_le_bytes());
+    bytes.push(match pack_kind {
+        PackKind::Base => 0,
+        PackKind::Overlay => 1,
+        PackKind::IcuData => 2,
+    });
+    bytes.extend_from_slice(&PACK_FLAG_SECTION_CHECKSUMS.to_le_bytes());
+    bytes.extend_from_slice(&id_map_hash);
+    bytes.extend_from_slice(&locale_tag_sidx.to_le_bytes());
+    let parent_raw = parent_tag_sidx.unwrap_or(u32::MAX);
+    bytes.extend_from_slice(&parent_raw.to_le_bytes());
+    bytes.extend_from_slice(&build_epoch_ms.to_le_bytes());
+    bytes.extend_from_slice(&(sections.len() as u16).to_le_bytes());
+
+    let directory_offset = bytes.len();
+    let section_entry_len = 1 + 4 + 4 + 4;
+    bytes.resize(directory_offset + sections.len() * section_entry_len, 0);
+
+    let mut cursor = bytes.len();
+    for (idx, (section_type, data)) in sections.into_iter().enumerate() {
+        let offset = cursor as u32;
+        let length = data.len() as u32;
+        bytes.extend_from_slice(&data);
+        let entry_offset = directory_offset + idx * section_entry_len;
+        bytes[entry_offset] = section_type;
+        bytes[entry_offset + 1..entry_offset + 5].copy_from_slice(&offset.to_le_bytes());
+        bytes[entry_offset + 5..entry_offset + 9].copy_from_slice(&length.to_le_bytes());
+        bytes[entry_offset + 9..entry_offset + 13].copy_from_slice(&crc32(&data).to_le_bytes());
+        cursor = bytes.len();
+    }
+
+    bytes
+}
+
+struct StringInterner {
+    map: BTreeMap<String, u32>,
+    pool: StringPool,
+}
+
+impl StringInterner {
+    fn new() -> Self {
+        Self {
+            map: BTreeMap::new(),
+            pool: StringPool::new(),
+        }
+    }
+
+    fn intern(&mut self, value: &str) -> u32 {
+        if let Some(idx) = self.map.get(value) {
+            return *idx;
+        }
+        let idx = self.pool.push(value.to_string());
+        self.map.insert(value.to_string(), idx);
+        idx
+    }
+
+    fn into_pool(self) -> StringPool {
+        self.pool
+    }
+}
+
+struct CaseTableInterner {
+    map: BTreeMap<Vec<u8>, u32>,
+    tables: Vec<CaseTable>,
+}
+
+impl CaseTableInterner {
+    fn new() -> Self {
+        Self {
+            map: BTreeMap::new(),
+            tables: Vec::new(),
+        }
+    }
+
+    fn intern(&mut self, table: CaseTable) -> u32 {
+        let key = encode_case_tables(core::slice::from_ref(&table));
+        if let Some(idx) = self.map.get(&key) {
+            return *idx;
+        }
+        let idx = self.tables.len() as u32;
+        self.tables.push(table);
+        self.map.insert(key, idx);
+        idx
+    }
+
+    fn into_tables(self) -> Vec<CaseTable> {
+        self.tables
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeMap;
+    use alloc::string::{String, ToString};
+    use alloc::vec;
+
+    use super::{
+        IcuDataBuildInput, PackBuildInput, PackEncodeError, encode_icu_data_pack,
+        encode_message_meta, encode_multi_case_tables, encode_pack,
+    };
+    use crate::{
+        BytecodeProgram, CaseEntry, CaseKey, CaseTable, Catalog, CoreError, IcuDataPack, MessageId,
+        MultiCaseEntry, MultiCaseTable, MultiSelector, NumberSymbols, Opcode, PACK_SCHEMA_VERSION,
+        PackCatalog, PackKind, PluralCategory, PluralOperand, PluralRelation, PluralRule,
+        PluralRules, PluralRuleset, StringPool, parse_pack_header,
+    };
+
+    #[test]
+    fn encodes_and_decodes_pack() {
+        let mut program = BytecodeProgram::new();
+        let sidx = program.string_pool.push("hello");
+        program.opcodes.push(Opcode::EmitText { sidx });
+        program.opcodes.push(Opcode::End);
+
+        let mut messages = BTreeMap::new();
+        messages.insert(MessageId::new(1), program);
+
+        let bytes = encode_pack(&PackBuildInput {
+            pack_kind: PackKind::Base,
+            id_map_hash: [7u8; 32],
+            locale_tag: "en".to_string(),
+            parent_tag: None,
+            build_epoch_ms: 0,
+            messages,
+            sort_strings: false,
+        })
+        .expect("encode");
+
+        let catalog = PackCatalog::decode(&bytes, &[7u8; 32]).expect("decode");
+        let program = catalog.lookup(MessageId::new(1)).expect("program");
+        assert_eq!(program.opcodes.len(), 2);
+        let mut found = false;
+        for idx in 0..program.string_pool.len() {
+            if program.string_pool.get(idx as u32) == Some("hello") {
+                found = true;
+                break;
+            }
+        }
+        assert!(found);
+    }
+
+    #[test]
+    fn decoded_pack_reports_locale_and_parent() {
+        let mut program = BytecodeProgram::new();
+        let sidx = program.string_pool.push("colour");
+        program.opcodes.push(Opcode::EmitText { sidx });
+        program.opcodes.push(Opcode::End);
+        let mut messages = BTreeMap::new();
+        messages.insert(MessageId::new(1), program);
+
+        let bytes = encode_pack(&PackBuildInput {
+            pack_kind: PackKind::Overlay,
+            id_map_hash: [7u8; 32],
+            locale_tag: "en".to_string(),
+            parent_tag: Some("en-001".to_string()),
+            build_epoch_ms: 0,
+            messages,
+            sort_strings: true,
+        })
+        .expect("encode");
+
+        let catalog = PackCatalog::decode(&bytes, &[7u8; 32]).expect("decode");
+        assert_eq!(catalog.locale_tag(), "en");
+        assert_eq!(catalog.parent_tag(), Some("en-001"));
+    }
+
+    #[test]
+    fn detects_corrupted_section_bytes() {
+        let mut program = BytecodeProgram::new();
+        let sidx = program.string_pool.push("hello");
+        program.opcodes.push(Opcode::EmitText { sidx });
+        program.opcodes.push(Opcode::End);
+        let mut messages = BTreeMap::new();
+        messages.insert(MessageId::new(1), program);
+
+        let mut bytes = encode_pack(&PackBuildInput {
+            pack_kind: PackKind::Base,
+            id_map_hash: [7u8; 32],
+            locale_tag: "en".to_string(),
+            parent_tag: None,
+            build_epoch_ms: 0,
+            messages,
+            sort_strings: false,
+        })
+        .expect("encode");
+        let (header, _) = parse_pack_header(&bytes).expect("header");
+        assert_eq!(header.schema_version, PACK_SCHEMA_VERSION);
+        assert!(header.has_section_checksums());
+
+        let hello = bytes
+            .windows(5)
+            .position(|window| window == b"hello")
+            .expect("string");
+        bytes[hello] ^= 0x20;
+        assert_eq!(
+            PackCatalog::decode(&bytes, &[7u8; 32]).err(),
+            Some(CoreError::InvalidInput("section checksum mismatch"))
+        );
+    }
+
+    #[test]
+    fn decoded_programs_share_string_pool_and_case_tables() {
+        let mut messages = BTreeMap::new();
+        for (id, text) in [(1, "hello"), (2, "world")] {
+            let mut program = BytecodeProgram::new();
+            let sidx = program.string_pool.push(text);
+            program.opcodes.push(Opcode::EmitText { sidx });
+            program.opcodes.push(Opcode::End);
+            messages.insert(MessageId::new(id), program);
+        }
+
+        let bytes = encode_pack(&PackBuildInput {
+            pack_kind: PackKind::Base,
+            id_map_hash: [7u8; 32],
+            locale_tag: "en".to_string(),
+            parent_tag: None,
+            build_epoch_ms: 0,
+            messages,
+            sort_strings: false,
+        })
+        .expect("encode");
+
+        let catalog = PackCatalog::decode(&bytes, &[7u8; 32]).expect("decode");
+        let first = catalog.lookup(MessageId::new(1)).expect("first");
+        let second = catalog.lookup(MessageId::new(2)).expect("second");
+        assert!(first.string_pool.shares_storage(&second.string_pool));
+        assert!(first.case_tables.shares_storage(&second.case_tables));
+        assert!(
+            first
+                .multi_case_tables
+                .shares_storage(&second.multi_case_tables)
+        );
+    }
+
+    #[test]
+    fn shares_case_tables_regardless_of_entry_order() {
+        let mut messages = BTreeMap::new();
+        for (id, keys) in [(1, ["a", "b"]), (2, ["b", "a"])] {
+            let mut program = BytecodeProgram::new();
+            let aidx = program.push_arg_name("kind");
+            let first = program.string_pool.push(keys[0]);
+            let second = program.string_pool.push(keys[1]);
+            let text = program.string_pool.push("x");
+            program.case_tables.push(CaseTable {
+                entries: vec![
+                    CaseEntry {
+                        key: CaseKey::String(first),
+                        target: if keys[0] == "a" { 1 } else { 3 },
+                    },
+                    CaseEntry {
+                        key: CaseKey::String(second),
+                        target: if keys[1] == "a" { 1 } else { 3 },
+                    },
+                ],
+            });
+            program.opcodes = vec![
+                Opcode::Select { aidx, table: 0 },
+                Opcode::EmitText { sidx: text },
+                Opcode::End,
+                Opcode::EmitText { sidx: text },
+                Opcode::End,
+            ];
+            messages.insert(MessageId::new(id), program);
+        }
+
+        let bytes = encode_pack(&PackBuildInput {
+            pack_kind: PackKind::Base,
+            id_map_hash: [7u8; 32],
+            locale_tag: "en".to_string(),
+            parent_tag: None,
+            build_epoch_ms: 0,
+            messages,
+            sort_strings: false,
+        })
+        .expect("encode");
+
+        let catalog = PackCatalog::decode(&bytes, &[7u8; 32]).expect("decode");
+        let first = catalog.lookup(MessageId::new(1)).expect("first");
+        let second = catalog.lookup(MessageId::new(2)).expect("second");
+        assert_eq!(first.case_tables.len(), 1);
+        assert_eq!(first.opcodes[0], second.opcodes[0]);
+        assert!(first.case_tables[0].is_sorted(&first.string_pool));
+    }
+
+    #[test]
+    fn rejects_arg_name_missing_from_pool() {
+        let mut program = BytecodeProgram::new();
+        program.push_arg_name("count");
+        program.opcodes.push(Opcode::End);
+        let mut messages = BTreeMap::new();
+        messages.insert(MessageId::new(4), program);
+
+        let mut pool = StringPool::new();
+        pool.push("en");
+        let err = encode_message_meta(&messages, &pool).expect_err("missing arg");
+        assert!(matches!(
+            &err,
+            PackEncodeError::MissingArgName(4, name) if name == "count"
+        ));
+        assert_eq!(
+            err.to_string(),
+            "arg name count of message 4 is missing from the string pool"
+        );
+    }
+
+    #[test]
+    fn round_trips_ordinal_ruleset() {
+        let mut program = BytecodeProgram::new();
+        let aidx = program.push_arg_name("place");
+        program.case_tables.push(CaseTable {
+            entries: vec![CaseEntry {
+                key: CaseKey::Other,
+                target: 1,
+            }],
+        });
+        program.opcodes.push(Opcode::SelectPlural {
+            aidx,
+            ruleset: PluralRuleset::Ordinal,
+            table: 0,
+            offset: 0.0,
+        });
+        program.opcodes.push(Opcode::End);
+
+        let mut messages = BTreeMap::new();
+        messages.insert(MessageId::new(1), program);
+
+        let bytes = encode_pack(&PackBuildInput {
+            pack_kind: PackKind::Base,
+            id_map_hash: [7u8; 32],
+            locale_tag: "en".to_string(),
+            parent_tag: None,
+            build_epoch_ms: 0,
+            messages,
+            sort_strings: false,
+        })
+        .expect("encode");
+
+        let catalog = PackCatalog::decode(&bytes, &[7u8; 32]).expect("decode");
+        let program = catalog.lookup(MessageId::new(1)).expect("program");
+        assert_eq!(
+            program.opcodes[0],
+            Opcode::SelectPlural {
+                aidx: 0,
+                ruleset: PluralRuleset::Ordinal,
+                table: 0,
+                offset: 0.0,
+            }
+        );
+    }
+
+    #[test]
+    fn round_trips_plural_offset() {
+        let mut program = BytecodeProgram::new();
+        let aidx = program.push_arg_name("count");
+        program.case_tables.push(CaseTable {
+            entries: vec![CaseEntry {
+                key: CaseKey::Other,
+                target: 1,
+            }],
+        });
+        program.opcodes.push(Opcode::SelectPlural {
+            aidx,
+            ruleset: PluralRuleset::Cardinal,
+            table: 0,
+            offset: 1.0,
+        });
+        program
+            .opcodes
+            .push(Opcode::PushPluralArg { aidx, offset: 1.0 });
+        program.opcodes.push(Opcode::EmitStack);
+        program.opcodes.push(Opcode::End);
+
+        let mut messages = BTreeMap::new();
+        messages.insert(MessageId::new(1), program);
+
+        let bytes = encode_pack(&PackBuildInput {
+            pack_kind: PackKind::Base,
+            id_map_hash: [7u8; 32],
+            locale_tag: "en".to_string(),
+            parent_tag: None,
+            build_epoch_ms: 0,
+            messages,
+            sort_strings: false,
+        })
+        .expect("encode");
+
+        let catalog = PackCatalog::decode(&bytes, &[7u8; 32]).expect("decode");
+        let program = catalog.lookup(MessageId::new(1)).expect("program");
+        assert_eq!(
+            program.opcodes[0],
+            Opcode::SelectPlural {
+                aidx: 0,
+                ruleset: PluralRuleset::Cardinal,
+                table: 0,
+                offset: 1.0,
+            }
+        );
+        assert_eq!(
+            program.opcodes[1],
+            Opcode::PushPluralArg {
+                aidx: 0,
+                offset: 1.0
+            }
+        );
+    }
+
+    #[test]
+    fn round_trips_multi_case_tables() {
+        let mut program = BytecodeProgram::new();
+        let count = program.push_arg_name("count");
+        let gender = program.push_arg_name("gender");
+        let female = program.string_pool.push("female");
+        program.multi_case_tables.push(MultiCaseTable {
+            selectors: vec![
+                MultiSelector {
+                    aidx: count,
+                    ruleset: Some(PluralRuleset::Cardinal),
+                },
+                MultiSelector {
+                    aidx: gender,
+                    ruleset: None,
+                },
+            ],
+            entries: vec![
+                MultiCaseEntry {
+                    keys: vec![
+                        CaseKey::Category(PluralCategory::One),
+                        CaseKey::String(female),
+                    ],
+                    target: 1,
+                },
+                MultiCaseEntry {
+                    keys: vec![CaseKey::Other, CaseKey::Other],
+                    target: 1,
+                },
+            ],
+        });
+        program.opcodes.push(Opcode::SelectMulti { table: 0 });
+        program.opcodes.push(Opcode::End);
+
+        let mut messages = BTreeMap::new();
+        messages.insert(MessageId::new(1), program);
+
+        let bytes = encode_pack(&PackBuildInput {
+            pack_kind: PackKind::Base,
+            id_map_hash: [7u8; 32],
+            locale_tag: "en".to_string(),
+            parent_tag: None,
+            build_epoch_ms: 0,
+            messages,
+            sort_strings: false,
+        })
+        .expect("encode");
+
+        let catalog = PackCatalog::decode(&bytes, &[7u8; 32]).expect("decode");
+        let program = catalog.lookup(MessageId::new(1)).expect("program");
+        assert_eq!(program.opcodes[0], Opcode::SelectMulti { table: 0 });
+        let table = &program.multi_case_tables[0];
+        assert_eq!(table.selectors[1].ruleset, None);
+        let CaseKey::String(sidx) = table.entries[0].keys[1] else {
+            panic!("expected string key");
+        };
+        assert_eq!(program.string_pool.get(sidx), Some("female"));
+    }
+
+    #[test]
+    fn rejects_multi_select_with_too_many_selectors() {
+        let selectors = vec![
+            MultiSelector {
+                aidx: 0,
+                ruleset: None,
+            };
+            256
+        ];
+        let table = MultiCaseTable {
+            selectors,
+            entries: vec![],
+        };
+        assert_eq!(
+            encode_multi_case_tables(core::slice::from_ref(&table)),
+            Err(PackEncodeError::TooManySelectors(256))
+        );
+    }
+
+    #[test]
+    fn round_trips_icu_data_pack() {
+        let one = PluralRule {
+            category: PluralCategory::One,
+            conditions: vec![vec![
+                PluralRelation {
+                    operand: PluralOperand::I,
+                    modulus: None,
+                    negate: false,
+                    ranges: vec![(1, 1)],
+                },
+                PluralRelation {
+                    operand: PluralOperand::V,
+                    modulus: None,
+                    negate: false,
+                    ranges: vec![(0, 0)],
+                },
+            ]],
+        };
+        let bytes = encode_icu_data_pack(&IcuDataBuildInput {
+            locale_tag: "de".to_string(),
+            build_epoch_ms: 0,
+            cardinal: PluralRules { rules: vec![one] },
+            ordinal: PluralRules::default(),
+            symbols: NumberSymbols {
+                decimal: String::from(","),
+                group: String::from("."),
+                minus: String::from("-"),
+            },
+        });
+
+        let pack = IcuDataPack::decode(&bytes).expect("decode");
+        assert_eq!(pack.header().pack_kind, PackKind::IcuData);
+        assert_eq!(pack.locale(), "de");
+        assert_eq!(pack.symbols().decimal, ",");
+        assert_eq!(
+            pack.plural_category(PluralRuleset::Cardinal, 1.0),
+            PluralCategory::One
+        );
+        assert_eq!(
+            pack.plural_category(PluralRuleset::Cardinal, 1.5),
+            PluralCategory::Other
+        );
+        assert_eq!(
+            pack.plural_category(PluralRuleset::Ordinal, 1.0),
+            PluralCategory::Other
+        );
+    }
+}
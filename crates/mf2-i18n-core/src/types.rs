@@ -35,14 +35,14 @@ impl TryFrom<&str> for Key {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub struct MessageId(u32);
+pub struct MessageId(u64);
 
 impl MessageId {
-    pub const fn new(value: u32) -> Self {
+    pub const fn new(value: u64) -> Self {
         Self(value)
     }
 
-    pub const fn get(self) -> u32 {
+    pub const fn get(self) -> u64 {
         self.0
     }
 }
@@ -53,13 +53,13 @@ impl fmt::Display for MessageId {
     }
 }
 
-impl From<u32> for MessageId {
-    fn from(value: u32) -> Self {
+impl From<u64> for MessageId {
+    fn from(value: u64) -> Self {
         Self::new(value)
     }
 }
 
-impl From<MessageId> for u32 {
+impl From<MessageId> for u64 {
     fn from(value: MessageId) -> Self {
         value.0
     }
@@ -87,7 +87,7 @@ mod tests {
     fn message_id_round_trips() {
         let id = MessageId::new(42);
         assert_eq!(id.get(), 42);
-        let raw: u32 = id.into();
+        let raw: u64 = id.into();
         assert_eq!(raw, 42);
         let id = MessageId::from(7);
         assert_eq!(id.get(), 7);
@@ -0,0 +1,184 @@
+use alloc::vec::Vec;
+
+use crate::{BytecodeProgram, CoreError, CoreResult, Opcode};
+
+pub fn verify(program: &BytecodeProgram) -> CoreResult<()> {
+    let len = program.opcodes.len();
+    let mut depths: Vec<Option<usize>> = alloc::vec![None; len + 1];
+    let mut pending = alloc::vec![(0usize, 0usize)];
+
+    while let Some((pc, depth)) = pending.pop() {
+        match depths[pc] {
+            Some(seen) if seen == depth => continue,
+            Some(_) => return Err(CoreError::InvalidInput("inconsistent stack depth")),
+            None => depths[pc] = Some(depth),
+        }
+        let Some(opcode) = program.opcodes.get(pc) else {
+            ensure_empty(depth)?;
+            continue;
+        };
+        let (pops, pushes) = match *opcode {
+            Opcode::EmitText { .. } | Opcode::Jump { .. } => (0, 0),
+            Opcode::EmitStack | Opcode::Pop => (1, 0),
+            Opcode::PushStr { .. }
+            | Opcode::PushNum { .. }
+            | Opcode::PushArg { .. }
+            | Opcode::PushPluralArg { .. } => (0, 1),
+            Opcode::Dup => (1, 2),
+            Opcode::CallFmt { opt_count, .. } => (usize::from(opt_count) * 2 + 1, 1),
+            Opcode::OpenMarkup { opt_count, .. }
+            | Opcode::CloseMarkup { opt_count, .. }
+            | Opcode::StandaloneMarkup { opt_count, .. } => (usize::from(opt_count) * 2, 0),
+            Opcode::Select { table, .. } | Opcode::SelectPlural { table, .. } => {
+                let table = program
+                    .case_tables
+                    .get(table as usize)
+                    .ok_or(CoreError::InvalidInput("case table index out of bounds"))?;
+                if !table.is_sorted(&program.string_pool) {
+                    return Err(CoreError::InvalidInput("case table entries out of order"));
+                }
+                for entry in &table.entries {
+                    pending.push((branch_target(len, entry.target as usize)?, depth));
+                }
+                continue;
+            }
+            Opcode::SelectMulti { table } => {
+                let table = program
+                    .multi_case_tables
+                    .get(table as usize)
+                    .ok_or(CoreError::InvalidInput("case table index out of bounds"))?;
+                for entry in &table.entries {
+                    pending.push((branch_target(len, entry.target as usize)?, depth));
+                }
+                continue;
+            }
+            Opcode::End => {
+                ensure_empty(depth)?;
+                continue;
+            }
+        };
+        let depth = depth
+            .checked_sub(pops)
+            .ok_or(CoreError::InvalidInput("stack underflow"))?;
+        let next = match *opcode {
+            Opcode::Jump { rel } => usize::try_from(pc as i64 + i64::from(rel))
+                .map_err(|_| CoreError::InvalidInput("jump underflow"))?,
+            _ => pc + 1,
+        };
+        pending.push((branch_target(len, next)?, depth + pushes));
+    }
+    Ok(())
+}
+
+fn branch_target(len: usize, target: usize) -> CoreResult<usize> {
+    if target > len {
+        return Err(CoreError::InvalidInput("jump target out of bounds"));
+    }
+    Ok(target)
+}
+
+fn ensure_empty(depth: usize) -> CoreResult<()> {
+    if depth != 0 {
+        return Err(CoreError::InvalidInput("values left on stack at end"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use super::verify;
+    use crate::{BytecodeProgram, CaseEntry, CaseKey, CaseTable, CoreError, FormatterId, Opcode};
+
+    fn select_program(other_branch: Vec<Opcode>) -> BytecodeProgram {
+        let mut program = BytecodeProgram::new();
+        let kind = program.push_arg_name("kind");
+        let text = program.string_pool.push("x");
+        program.opcodes = vec![
+            Opcode::Select {
+                aidx: kind,
+                table: 0,
+            },
+            Opcode::EmitText { sidx: text },
+            Opcode::Jump { rel: 0 },
+        ];
+        let other_target = program.opcodes.len() as u32;
+        program.opcodes.extend(other_branch);
+        let end = program.opcodes.len() as i32;
+        program.opcodes[2] = Opcode::Jump { rel: end - 2 };
+        program.opcodes.push(Opcode::End);
+        program.case_tables.push(CaseTable {
+            entries: vec![
+                CaseEntry {
+                    key: CaseKey::String(text),
+                    target: 1,
+                },
+                CaseEntry {
+                    key: CaseKey::Other,
+                    target: other_target,
+                },
+            ],
+        });
+        program
+    }
+
+    #[test]
+    fn accepts_balanced_branches() {
+        let program = select_program(vec![
+            Opcode::PushArg { aidx: 0 },
+            Opcode::CallFmt {
+                fid: FormatterId::Number,
+                opt_count: 0,
+            },
+            Opcode::EmitStack,
+        ]);
+        assert_eq!(verify(&program), Ok(()));
+    }
+
+    #[test]
+    fn flags_dangling_push_in_one_branch() {
+        let program = select_program(vec![Opcode::PushArg { aidx: 0 }]);
+        assert_eq!(
+            verify(&program),
+            Err(CoreError::InvalidInput("values left on stack at end"))
+        );
+    }
+
+    #[test]
+    fn flags_unsorted_case_table() {
+        let mut program = select_program(vec![]);
+        let later = program.string_pool.push("z");
+        program.case_tables[0].entries.insert(
+            0,
+            CaseEntry {
+                key: CaseKey::String(later),
+                target: 1,
+            },
+        );
+        assert_eq!(
+            verify(&program),
+            Err(CoreError::InvalidInput("case table entries out of order"))
+        );
+
+        program.case_tables[0].sort_entries(&program.string_pool);
+        assert_eq!(verify(&program), Ok(()));
+    }
+
+    #[test]
+    fn flags_underflow_and_bad_jumps() {
+        let mut program = BytecodeProgram::new();
+        program.opcodes = vec![Opcode::EmitStack, Opcode::End];
+        assert_eq!(
+            verify(&program),
+            Err(CoreError::InvalidInput("stack underflow"))
+        );
+
+        program.opcodes = vec![Opcode::Jump { rel: 5 }, Opcode::End];
+        assert_eq!(
+            verify(&program),
+            Err(CoreError::InvalidInput("jump target out of bounds"))
+        );
+    }
+}
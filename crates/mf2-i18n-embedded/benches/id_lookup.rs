@@ -0,0 +1,51 @@
+use std::collections::BTreeMap;
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use mf2_i18n_core::MessageId;
+use mf2_i18n_embedded::StaticIdMap;
+
+const KEY_COUNT: u32 = 2_000;
+
+fn keys() -> Vec<String> {
+    let mut keys: Vec<String> = (0..KEY_COUNT)
+        .map(|index| format!("screen{}.message{}", index % 40, index))
+        .collect();
+    keys.sort();
+    keys
+}
+
+fn id_lookup(c: &mut Criterion) {
+    let keys = keys();
+    let map: BTreeMap<String, MessageId> = keys
+        .iter()
+        .enumerate()
+        .map(|(index, key)| (key.clone(), MessageId::new(index as u64)))
+        .collect();
+    let entries: &'static [(&'static str, u64)] = keys
+        .iter()
+        .enumerate()
+        .map(|(index, key)| (&*key.clone().leak(), index as u64))
+        .collect::<Vec<_>>()
+        .leak();
+    let static_map = StaticIdMap::new(entries).expect("sorted");
+    let probes: Vec<&str> = keys.iter().step_by(7).map(String::as_str).collect();
+
+    c.bench_function("btree_map_lookup", |b| {
+        b.iter(|| {
+            for key in &probes {
+                black_box(map.get(black_box(*key)));
+            }
+        })
+    });
+    c.bench_function("static_binary_search_lookup", |b| {
+        b.iter(|| {
+            for key in &probes {
+                black_box(static_map.get(black_box(key)));
+            }
+        })
+    });
+}
+
+criterion_group!(benches, id_lookup);
+criterion_main!(benches);
@@ -0,0 +1,119 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+
+use mf2_i18n_core::{CoreError, CoreResult, MessageId, id_map_hash};
+
+#[derive(Clone, Copy, Debug)]
+pub struct StaticIdMap {
+    entries: &'static [(&'static str, u64)],
+}
+
+impl StaticIdMap {
+    pub fn new(entries: &'static [(&'static str, u64)]) -> CoreResult<Self> {
+        if entries.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+            return Err(CoreError::InvalidInput(
+                "static id map must be sorted by key",
+            ));
+        }
+        Ok(Self { entries })
+    }
+
+    pub fn get(&self, key: &str) -> Option<MessageId> {
+        self.entries
+            .binary_search_by(|(candidate, _)| (*candidate).cmp(key))
+            .ok()
+            .map(|index| MessageId::new(self.entries[index].1))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn hash(&self) -> CoreResult<[u8; 32]> {
+        id_map_hash(
+            self.entries
+                .iter()
+                .map(|(key, id)| (*key, MessageId::new(*id))),
+        )
+    }
+}
+
+pub(crate) enum IdLookup {
+    Owned(BTreeMap<String, MessageId>),
+    Static(StaticIdMap),
+}
+
+impl IdLookup {
+    pub(crate) fn get(&self, key: &str) -> Option<MessageId> {
+        match self {
+            Self::Owned(entries) => entries.get(key).copied(),
+            Self::Static(entries) => entries.get(key),
+        }
+    }
+
+    pub(crate) fn hash(&self) -> CoreResult<[u8; 32]> {
+        match self {
+            Self::Owned(entries) => {
+                id_map_hash(entries.iter().map(|(key, id)| (key.as_str(), *id)))
+            }
+            Self::Static(entries) => entries.hash(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeMap;
+    use alloc::string::ToString;
+
+    use super::{IdLookup, StaticIdMap};
+    use mf2_i18n_core::{CoreError, MessageId};
+
+    static ENTRIES: &[(&str, u64)] = &[
+        ("cart.count", 7),
+        ("cart.empty", 3),
+        ("home.subtitle", 42),
+        ("home.title", 1),
+        ("z", 9),
+    ];
+
+    #[test]
+    fn binary_search_matches_map_lookup() {
+        let map: BTreeMap<_, _> = ENTRIES
+            .iter()
+            .map(|(key, id)| (key.to_string(), MessageId::new(*id)))
+            .collect();
+        let owned = IdLookup::Owned(map);
+        let stat = IdLookup::Static(StaticIdMap::new(ENTRIES).expect("sorted"));
+        for key in [
+            "cart.count",
+            "cart.empty",
+            "home.subtitle",
+            "home.title",
+            "z",
+        ] {
+            assert_eq!(stat.get(key), owned.get(key));
+            assert!(stat.get(key).is_some());
+        }
+        for key in ["", "a", "cart", "home.titles", "zz"] {
+            assert_eq!(stat.get(key), None);
+            assert_eq!(owned.get(key), None);
+        }
+        assert_eq!(stat.hash(), owned.hash());
+    }
+
+    #[test]
+    fn rejects_unsorted_entries() {
+        static UNSORTED: &[(&str, u64)] = &[("home.title", 1), ("cart.count", 2)];
+        assert_eq!(
+            StaticIdMap::new(UNSORTED).err(),
+            Some(CoreError::InvalidInput(
+                "static id map must be sorted by key"
+            ))
+        );
+    }
+}
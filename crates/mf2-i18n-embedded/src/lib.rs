@@ -3,6 +3,8 @@
 
 extern crate alloc;
 
+mod id_map;
 mod runtime;
 
+pub use crate::id_map::StaticIdMap;
 pub use crate::runtime::{BasicFormatBackend, EmbeddedPack, EmbeddedRuntime};
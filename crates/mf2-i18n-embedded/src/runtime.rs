@@ -5,18 +5,22 @@ use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 
 use mf2_i18n_core::{
-    Args, Catalog, CoreError, CoreResult, FormatBackend, LanguageTag, PackCatalog, PluralCategory,
-    execute, negotiate_lookup,
+    Args, Catalog, CatalogChain, CoreError, CoreResult, FormatBackend, LanguageTag, MessageId,
+    PackCatalog, PluralCategory, execute, negotiate_lookup,
 };
 
+use crate::id_map::{IdLookup, StaticIdMap};
+
 pub struct EmbeddedPack<'a> {
     pub locale: &'a str,
     pub bytes: &'a [u8],
+    pub parent: Option<&'a str>,
 }
 
 pub struct EmbeddedRuntime {
-    id_map: BTreeMap<String, mf2_i18n_core::MessageId>,
+    id_map: IdLookup,
     packs: BTreeMap<String, PackCatalog>,
+    parents: BTreeMap<String, String>,
     default_locale: LanguageTag,
     supported: Vec<LanguageTag>,
 }
@@ -82,15 +86,40 @@ impl FormatBackend for BasicFormatBackend {
 
 impl EmbeddedRuntime {
     pub fn new(
-        id_map: BTreeMap<String, mf2_i18n_core::MessageId>,
+        id_map: BTreeMap<String, MessageId>,
+        id_map_hash: [u8; 32],
+        packs: &[EmbeddedPack<'_>],
+        default_locale: &str,
+    ) -> CoreResult<Self> {
+        Self::with_id_lookup(IdLookup::Owned(id_map), id_map_hash, packs, default_locale)
+    }
+
+    pub fn new_static(
+        id_map: StaticIdMap,
+        id_map_hash: [u8; 32],
+        packs: &[EmbeddedPack<'_>],
+        default_locale: &str,
+    ) -> CoreResult<Self> {
+        Self::with_id_lookup(IdLookup::Static(id_map), id_map_hash, packs, default_locale)
+    }
+
+    fn with_id_lookup(
+        id_map: IdLookup,
         id_map_hash: [u8; 32],
         packs: &[EmbeddedPack<'_>],
         default_locale: &str,
     ) -> CoreResult<Self> {
+        if id_map.hash()? != id_map_hash {
+            return Err(CoreError::InvalidInput("id map hash mismatch"));
+        }
         let mut pack_map = BTreeMap::new();
+        let mut parents = BTreeMap::new();
         let mut supported = Vec::new();
         for pack in packs {
             let catalog = PackCatalog::decode(pack.bytes, &id_map_hash)?;
+            if let Some(parent) = pack.parent.or(catalog.parent_tag()) {
+                parents.insert(pack.locale.to_string(), parent.to_string());
+            }
             pack_map.insert(pack.locale.to_string(), catalog);
             supported.push(LanguageTag::parse(pack.locale)?);
         }
@@ -98,6 +127,7 @@ impl EmbeddedRuntime {
         Ok(Self {
             id_map,
             packs: pack_map,
+            parents,
             default_locale,
             supported,
         })
@@ -119,36 +149,62 @@ impl EmbeddedRuntime {
         let negotiation = negotiate_lookup(&[locale_tag], &self.supported, &self.default_locale);
         let selected = negotiation.selected.normalized();
 
-        let catalog = self
-            .packs
-            .get(selected)
-            .ok_or(CoreError::InvalidInput("missing locale"))?;
+        let catalog = self.catalog_chain_for(selected)?;
         let message_id = self
             .id_map
             .get(key)
-            .copied()
             .ok_or(CoreError::InvalidInput("missing message"))?;
         let program = catalog
             .lookup(message_id)
             .ok_or(CoreError::InvalidInput("missing message"))?;
         execute(program, args, backend)
     }
+
+    fn catalog_chain_for(&self, locale: &str) -> CoreResult<CatalogChain<'_>> {
+        let mut catalogs = Vec::new();
+        let mut current = Some(locale);
+        let mut depth = 0usize;
+        while let Some(tag) = current {
+            if depth > self.parents.len() {
+                return Err(CoreError::InvalidInput("cyclic pack parents"));
+            }
+            depth += 1;
+            if let Some(pack) = self.packs.get(tag) {
+                catalogs.push(pack as &dyn Catalog);
+            }
+            current = self.parents.get(tag).map(String::as_str);
+        }
+        if catalogs.is_empty() {
+            return Err(CoreError::InvalidInput("missing locale"));
+        }
+        Ok(CatalogChain::new(catalogs))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{EmbeddedPack, EmbeddedRuntime};
+    use crate::StaticIdMap;
     use alloc::collections::BTreeMap;
-    use alloc::string::ToString;
+    use alloc::string::{String, ToString};
     use alloc::vec;
     use alloc::vec::Vec;
-    use mf2_i18n_core::{Args, MessageId, PackKind};
+    use mf2_i18n_core::{Args, CoreError, MessageId, PackKind, id_map_hash};
+
+    fn hash_of(id_map: &BTreeMap<String, MessageId>) -> [u8; 32] {
+        id_map_hash(id_map.iter().map(|(key, id)| (key.as_str(), *id))).expect("hash")
+    }
 
-    fn build_pack_bytes(id_map_hash: [u8; 32]) -> Vec<u8> {
+    fn build_pack_bytes(
+        id_map_hash: [u8; 32],
+        kind: PackKind,
+        parent: Option<&str>,
+        messages: &[(u32, &str)],
+    ) -> Vec<u8> {
         let mut bytes = Vec::new();
         bytes.extend_from_slice(b"MF2PACK\0");
         bytes.extend_from_slice(&0u16.to_le_bytes());
-        bytes.push(match PackKind::Base {
+        bytes.push(match kind {
             PackKind::Base => 0,
             PackKind::Overlay => 1,
             PackKind::IcuData => 2,
@@ -156,38 +212,47 @@ mod tests {
         bytes.extend_from_slice(&0u32.to_le_bytes());
         bytes.extend_from_slice(&id_map_hash);
         bytes.extend_from_slice(&0u32.to_le_bytes());
-        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        let parent_sidx = match parent {
+            Some(_) => messages.len() as u32,
+            None => u32::MAX,
+        };
+        bytes.extend_from_slice(&parent_sidx.to_le_bytes());
         bytes.extend_from_slice(&0u64.to_le_bytes());
 
+        let mut strings: Vec<&str> = messages.iter().map(|(_, text)| *text).collect();
+        strings.extend(parent);
         let mut string_pool = Vec::new();
-        string_pool.extend_from_slice(&2u32.to_le_bytes());
-        string_pool.extend_from_slice(&2u32.to_le_bytes());
-        string_pool.extend_from_slice(b"hi");
-        string_pool.extend_from_slice(&4u32.to_le_bytes());
-        string_pool.extend_from_slice(b"name");
+        string_pool.extend_from_slice(&(strings.len() as u32).to_le_bytes());
+        for value in strings {
+            string_pool.extend_from_slice(&(value.len() as u32).to_le_bytes());
+            string_pool.extend_from_slice(value.as_bytes());
+        }
 
         let mut message_meta = Vec::new();
-        message_meta.extend_from_slice(&1u32.to_le_bytes());
-        message_meta.extend_from_slice(&0u32.to_le_bytes());
-        message_meta.extend_from_slice(&0u32.to_le_bytes());
+        message_meta.extend_from_slice(&(messages.len() as u32).to_le_bytes());
+        for (id, _) in messages {
+            message_meta.extend_from_slice(&id.to_le_bytes());
+            message_meta.extend_from_slice(&0u32.to_le_bytes());
+        }
 
         let mut case_tables = Vec::new();
         case_tables.extend_from_slice(&0u32.to_le_bytes());
 
         let mut message_index = Vec::new();
-        message_index.extend_from_slice(&1u32.to_le_bytes());
-        message_index.extend_from_slice(&0u32.to_le_bytes());
-        message_index.extend_from_slice(&0u32.to_le_bytes());
-
-        let mut message = Vec::new();
-        message.extend_from_slice(&0u32.to_le_bytes());
-        message.extend_from_slice(&2u32.to_le_bytes());
-        message.push(0);
-        message.extend_from_slice(&0u32.to_le_bytes());
-        message.push(11);
         let mut bytecode_blob = Vec::new();
-        bytecode_blob.extend_from_slice(&(message.len() as u32).to_le_bytes());
-        bytecode_blob.extend_from_slice(&message);
+        message_index.extend_from_slice(&(messages.len() as u32).to_le_bytes());
+        for (sidx, (id, _)) in messages.iter().enumerate() {
+            message_index.extend_from_slice(&id.to_le_bytes());
+            message_index.extend_from_slice(&(bytecode_blob.len() as u32).to_le_bytes());
+            let mut message = Vec::new();
+            message.extend_from_slice(&0u32.to_le_bytes());
+            message.extend_from_slice(&2u32.to_le_bytes());
+            message.push(0);
+            message.extend_from_slice(&(sidx as u32).to_le_bytes());
+            message.push(11);
+            bytecode_blob.extend_from_slice(&(message.len() as u32).to_le_bytes());
+            bytecode_blob.extend_from_slice(&message);
+        }
 
         let section_count = 5u16;
         bytes.extend_from_slice(&section_count.to_le_bytes());
@@ -221,15 +286,106 @@ mod tests {
     fn formats_with_embedded_runtime() {
         let mut id_map = BTreeMap::new();
         id_map.insert("home.title".to_string(), MessageId::new(0));
-        let id_map_hash = [7u8; 32];
-        let pack_bytes = build_pack_bytes(id_map_hash);
+        let id_map_hash = hash_of(&id_map);
+        let pack_bytes = build_pack_bytes(id_map_hash, PackKind::Base, None, &[(0, "hi")]);
         let packs = [EmbeddedPack {
             locale: "en",
             bytes: &pack_bytes,
+            parent: None,
         }];
         let runtime = EmbeddedRuntime::new(id_map, id_map_hash, &packs, "en").expect("runtime");
         let args = Args::new();
         let output = runtime.format("en", "home.title", &args).expect("format");
         assert_eq!(output, "hi");
     }
+
+    #[test]
+    fn overlay_shadows_base_and_falls_back() {
+        let mut id_map = BTreeMap::new();
+        id_map.insert("home.title".to_string(), MessageId::new(0));
+        id_map.insert("home.subtitle".to_string(), MessageId::new(1));
+        let id_map_hash = hash_of(&id_map);
+        let base = build_pack_bytes(
+            id_map_hash,
+            PackKind::Base,
+            None,
+            &[(0, "hi"), (1, "welcome")],
+        );
+        let overlay = build_pack_bytes(id_map_hash, PackKind::Overlay, Some("en"), &[(0, "hiya")]);
+        let packs = [
+            EmbeddedPack {
+                locale: "en",
+                bytes: &base,
+                parent: None,
+            },
+            EmbeddedPack {
+                locale: "en-GB",
+                bytes: &overlay,
+                parent: None,
+            },
+        ];
+        let runtime = EmbeddedRuntime::new(id_map, id_map_hash, &packs, "en").expect("runtime");
+        let args = Args::new();
+        assert_eq!(
+            runtime
+                .format("en-GB", "home.title", &args)
+                .expect("overlay"),
+            "hiya"
+        );
+        assert_eq!(
+            runtime
+                .format("en-GB", "home.subtitle", &args)
+                .expect("base"),
+            "welcome"
+        );
+        assert_eq!(runtime.format("en", "home.title", &args).expect("en"), "hi");
+    }
+
+    #[test]
+    fn formats_with_static_id_map() {
+        static ID_MAP: &[(&str, u64)] = &[("home.subtitle", 1), ("home.title", 0)];
+        let id_map = StaticIdMap::new(ID_MAP).expect("sorted");
+        let id_map_hash =
+            id_map_hash(ID_MAP.iter().map(|(key, id)| (*key, MessageId::new(*id)))).expect("hash");
+        let pack_bytes = build_pack_bytes(
+            id_map_hash,
+            PackKind::Base,
+            None,
+            &[(0, "hi"), (1, "welcome")],
+        );
+        let packs = [EmbeddedPack {
+            locale: "en",
+            bytes: &pack_bytes,
+            parent: None,
+        }];
+        let runtime =
+            EmbeddedRuntime::new_static(id_map, id_map_hash, &packs, "en").expect("runtime");
+        let args = Args::new();
+        assert_eq!(
+            runtime
+                .format("en", "home.subtitle", &args)
+                .expect("format"),
+            "welcome"
+        );
+        assert!(runtime.format("en", "home.missing", &args).is_err());
+    }
+
+    #[test]
+    fn rejects_tampered_id_map() {
+        let mut id_map = BTreeMap::new();
+        id_map.insert("home.title".to_string(), MessageId::new(0));
+        let id_map_hash = hash_of(&id_map);
+        let pack_bytes = build_pack_bytes(id_map_hash, PackKind::Base, None, &[(0, "hi")]);
+        let packs = [EmbeddedPack {
+            locale: "en",
+            bytes: &pack_bytes,
+            parent: None,
+        }];
+
+        id_map.insert("home.title".to_string(), MessageId::new(1));
+        let err = EmbeddedRuntime::new(id_map, id_map_hash, &packs, "en")
+            .err()
+            .expect("error");
+        assert_eq!(err, CoreError::InvalidInput("id map hash mismatch"));
+    }
 }
@@ -1,6 +1,7 @@
 #![forbid(unsafe_code)]
 
 pub use mf2_i18n_runtime::{
-    BasicFormatBackend, IdMap, Manifest, ManifestSigning, PackEntry, Runtime, RuntimeError,
-    RuntimeResult, load_id_map, load_manifest, parse_sha256, verify_manifest_signature,
+    BasicFormatBackend, IdMap, Manifest, ManifestKey, ManifestSignatures, ManifestSigning,
+    MissingKeyPolicy, PackEntry, PreparedMessage, Runtime, RuntimeError, RuntimeResult,
+    load_id_map, load_manifest, parse_sha256, verify_manifest_signature,
 };
@@ -0,0 +1,76 @@
+use std::collections::BTreeMap;
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use mf2_i18n_core::Args;
+use mf2_i18n_runtime::{IdMap, Runtime};
+use mf2_i18n_test_support::{ManifestBuilder, pack_bytes, text_program};
+
+const KEY_COUNT: u32 = 100;
+
+fn keys() -> Vec<String> {
+    (0..KEY_COUNT)
+        .map(|index| format!("page.message{index}"))
+        .collect()
+}
+
+fn runtime(keys: &[String]) -> Runtime {
+    let id_map: BTreeMap<&str, u32> = keys
+        .iter()
+        .enumerate()
+        .map(|(index, key)| (key.as_str(), index as u32))
+        .collect();
+    let id_map_json = serde_json::to_string(&id_map).expect("id map");
+    let id_map_hash = IdMap::from_json(&id_map_json)
+        .expect("id map")
+        .hash()
+        .expect("hash");
+    let pack = pack_bytes(
+        id_map_hash,
+        "en",
+        None,
+        (0..KEY_COUNT).map(|index| (u64::from(index), text_program(&format!("text {index}")))),
+    );
+    let manifest = ManifestBuilder::new(id_map_hash)
+        .pack("en", None, &pack)
+        .build();
+    let mut packs = BTreeMap::new();
+    packs.insert("en".to_string(), pack);
+    Runtime::from_bytes(
+        &serde_json::to_vec(&manifest).expect("manifest"),
+        id_map_json.as_bytes(),
+        &packs,
+    )
+    .expect("runtime")
+}
+
+fn format_batch(c: &mut Criterion) {
+    let keys = keys();
+    let runtime = runtime(&keys);
+    let args = Args::new();
+    let entries: Vec<(&str, &Args)> = keys.iter().map(|key| (key.as_str(), &args)).collect();
+
+    c.bench_function("format_100_keys_individually", |b| {
+        b.iter(|| {
+            for (key, args) in &entries {
+                black_box(
+                    runtime
+                        .format(black_box("en-GB"), key, args)
+                        .expect("format"),
+                );
+            }
+        })
+    });
+    c.bench_function("format_100_keys_batched", |b| {
+        b.iter(|| {
+            black_box(
+                runtime
+                    .format_batch(black_box("en-GB"), &entries)
+                    .expect("batch"),
+            )
+        })
+    });
+}
+
+criterion_group!(benches, format_batch);
+criterion_main!(benches);
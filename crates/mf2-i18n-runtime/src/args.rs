@@ -0,0 +1,224 @@
+use mf2_i18n_core::{ArgType, Args, Value, parse_rfc3339};
+use serde_json::Map;
+
+use crate::error::{RuntimeError, RuntimeResult};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArgSpec {
+    pub name: String,
+    pub arg_type: ArgType,
+    pub required: bool,
+}
+
+pub fn args_from_json(value: &serde_json::Value, spec: &[ArgSpec]) -> RuntimeResult<Args> {
+    let empty = Map::new();
+    let entries = match value {
+        serde_json::Value::Object(entries) => entries,
+        serde_json::Value::Null => &empty,
+        other => {
+            return Err(RuntimeError::InvalidArgument(
+                "args".to_string(),
+                format!("expected an object, got {}", json_kind(other)),
+            ));
+        }
+    };
+    let mut args = Args::new();
+    for arg in spec {
+        match entries.get(&arg.name) {
+            None | Some(serde_json::Value::Null) if arg.required => {
+                return Err(invalid(&arg.name, "missing required argument".to_string()));
+            }
+            None | Some(serde_json::Value::Null) => {}
+            Some(value) => {
+                args.insert(arg.name.clone(), coerce(&arg.name, arg.arg_type, value)?);
+            }
+        }
+    }
+    Ok(args)
+}
+
+fn coerce(name: &str, arg_type: ArgType, value: &serde_json::Value) -> RuntimeResult<Value> {
+    let mismatch = |expected: &str| {
+        invalid(
+            name,
+            format!("expected {expected}, got {}", json_kind(value)),
+        )
+    };
+    match (arg_type, value) {
+        (ArgType::Str | ArgType::Any, serde_json::Value::String(value)) => {
+            Ok(Value::Str(value.clone()))
+        }
+        (ArgType::Num | ArgType::Any, serde_json::Value::Number(value)) => value
+            .as_i64()
+            .map(Value::Int)
+            .or_else(|| value.as_f64().map(Value::Num))
+            .ok_or_else(|| mismatch("a finite number")),
+        (ArgType::Bool | ArgType::Any, serde_json::Value::Bool(value)) => Ok(Value::Bool(*value)),
+        (ArgType::DateTime, serde_json::Value::Number(value)) => value
+            .as_i64()
+            .map(Value::DateTime)
+            .ok_or_else(|| mismatch("integer milliseconds")),
+        (ArgType::DateTime, serde_json::Value::String(value)) => parse_rfc3339(value)
+            .map(Value::DateTime)
+            .ok_or_else(|| invalid(name, format!("invalid RFC 3339 timestamp {value:?}"))),
+        (ArgType::Unit, serde_json::Value::Object(entries)) => {
+            let value = number_field(name, entries, "value")?;
+            let unit_id = entries
+                .get("unit_id")
+                .and_then(serde_json::Value::as_u64)
+                .and_then(|id| u32::try_from(id).ok())
+                .ok_or_else(|| invalid(name, "expected integer field unit_id".to_string()))?;
+            Ok(Value::Unit { value, unit_id })
+        }
+        (ArgType::Currency, serde_json::Value::Object(entries)) => {
+            let value = number_field(name, entries, "value")?;
+            let code = entries
+                .get("code")
+                .and_then(serde_json::Value::as_str)
+                .and_then(|code| <[u8; 3]>::try_from(code.as_bytes()).ok())
+                .filter(|code| code.iter().all(u8::is_ascii_alphabetic))
+                .ok_or_else(|| invalid(name, "expected a 3-letter currency code".to_string()))?;
+            Ok(Value::Currency {
+                value,
+                code: code.map(|byte| byte.to_ascii_uppercase()),
+            })
+        }
+        (ArgType::Str, _) => Err(mismatch("string")),
+        (ArgType::Num, _) => Err(mismatch("number")),
+        (ArgType::Bool, _) => Err(mismatch("bool")),
+        (ArgType::DateTime, _) => Err(mismatch("RFC 3339 string or milliseconds")),
+        (ArgType::Unit, _) => Err(mismatch("{value, unit_id} object")),
+        (ArgType::Currency, _) => Err(mismatch("{value, code} object")),
+        (ArgType::Any, _) => Err(mismatch("string, number or bool")),
+    }
+}
+
+fn number_field(
+    name: &str,
+    entries: &Map<String, serde_json::Value>,
+    field: &str,
+) -> RuntimeResult<f64> {
+    entries
+        .get(field)
+        .and_then(serde_json::Value::as_f64)
+        .ok_or_else(|| invalid(name, format!("expected number field {field}")))
+}
+
+fn invalid(name: &str, message: String) -> RuntimeError {
+    RuntimeError::InvalidArgument(name.to_string(), message)
+}
+
+fn json_kind(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "bool",
+        serde_json::Value::Number(_) => "number",
+        serde_json::Value::String(_) => "string",
+        serde_json::Value::Array(_) => "array",
+        serde_json::Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ArgSpec, args_from_json};
+    use crate::error::RuntimeError;
+    use mf2_i18n_core::{ArgType, Value};
+    use serde_json::json;
+
+    fn spec(name: &str, arg_type: ArgType) -> ArgSpec {
+        ArgSpec {
+            name: name.to_string(),
+            arg_type,
+            required: true,
+        }
+    }
+
+    #[test]
+    fn coerces_scalars() {
+        let args = args_from_json(
+            &json!({ "name": "Ada", "count": 3, "enabled": false }),
+            &[
+                spec("name", ArgType::Str),
+                spec("count", ArgType::Num),
+                spec("enabled", ArgType::Bool),
+            ],
+        )
+        .expect("args");
+        assert!(matches!(args.get("name"), Some(Value::Str(value)) if value == "Ada"));
+        assert!(matches!(args.get("count"), Some(Value::Int(3))));
+        assert!(matches!(args.get("enabled"), Some(Value::Bool(false))));
+    }
+
+    #[test]
+    fn coerces_rfc3339_and_millis_datetimes() {
+        let args = args_from_json(
+            &json!({
+                "utc": "2024-02-29T12:30:00Z",
+                "offset": "2024-02-29T14:30:00.250+02:00",
+                "millis": 1_709_209_800_000i64
+            }),
+            &[
+                spec("utc", ArgType::DateTime),
+                spec("offset", ArgType::DateTime),
+                spec("millis", ArgType::DateTime),
+            ],
+        )
+        .expect("args");
+        assert!(matches!(
+            args.get("utc"),
+            Some(Value::DateTime(1_709_209_800_000))
+        ));
+        assert!(matches!(
+            args.get("offset"),
+            Some(Value::DateTime(1_709_209_800_250))
+        ));
+        assert!(matches!(
+            args.get("millis"),
+            Some(Value::DateTime(1_709_209_800_000))
+        ));
+    }
+
+    #[test]
+    fn coerces_currency_and_unit_objects() {
+        let args = args_from_json(
+            &json!({
+                "price": { "value": 9.5, "code": "eur" },
+                "distance": { "value": 12, "unit_id": 4 }
+            }),
+            &[
+                spec("price", ArgType::Currency),
+                spec("distance", ArgType::Unit),
+            ],
+        )
+        .expect("args");
+        assert!(matches!(
+            args.get("price"),
+            Some(Value::Currency { value, code }) if *value == 9.5 && code == b"EUR"
+        ));
+        assert!(matches!(
+            args.get("distance"),
+            Some(Value::Unit { value, unit_id: 4 }) if *value == 12.0
+        ));
+    }
+
+    #[test]
+    fn skips_missing_optional_args() {
+        let mut optional = spec("name", ArgType::Str);
+        optional.required = false;
+        let args = args_from_json(&json!({}), &[optional]).expect("args");
+        assert!(args.get("name").is_none());
+    }
+
+    #[test]
+    fn rejects_type_mismatch_by_name() {
+        let err = args_from_json(&json!({ "count": "three" }), &[spec("count", ArgType::Num)])
+            .err()
+            .expect("error");
+        assert!(matches!(&err, RuntimeError::InvalidArgument(name, _) if name == "count"));
+        assert_eq!(
+            err.to_string(),
+            "invalid argument count: expected number, got string"
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use chrono::DateTime;
+use chrono_tz::Tz;
+use mf2_i18n_core::{
+    CoreError, CoreResult, FormatBackend, FormatterOption, PluralCategory, find_option,
+};
+
+pub struct ChronoDateBackend<B> {
+    inner: B,
+}
+
+impl<B: FormatBackend> ChronoDateBackend<B> {
+    pub fn new(inner: B) -> Self {
+        Self { inner }
+    }
+}
+
+impl<B: FormatBackend> FormatBackend for ChronoDateBackend<B> {
+    fn plural_category(&self, value: f64) -> CoreResult<PluralCategory> {
+        self.inner.plural_category(value)
+    }
+
+    fn ordinal_category(&self, value: f64) -> CoreResult<PluralCategory> {
+        self.inner.ordinal_category(value)
+    }
+
+    fn format_number(&self, value: f64, options: &[FormatterOption]) -> CoreResult<String> {
+        self.inner.format_number(value, options)
+    }
+
+    fn format_date(&self, value: i64, options: &[FormatterOption]) -> CoreResult<String> {
+        let style = style_option(options, "dateStyle")?.unwrap_or(Style::Medium);
+        render(value, options, Some(style), None)
+    }
+
+    fn format_time(&self, value: i64, options: &[FormatterOption]) -> CoreResult<String> {
+        let style = style_option(options, "timeStyle")?.unwrap_or(Style::Short);
+        render(value, options, None, Some(style))
+    }
+
+    fn format_datetime(&self, value: i64, options: &[FormatterOption]) -> CoreResult<String> {
+        let date = style_option(options, "dateStyle")?;
+        let time = style_option(options, "timeStyle")?;
+        match (date, time) {
+            (None, None) => render(value, options, Some(Style::Medium), Some(Style::Short)),
+            (date, time) => render(value, options, date, time),
+        }
+    }
+
+    fn format_unit(
+        &self,
+        value: f64,
+        unit_id: u32,
+        options: &[FormatterOption],
+    ) -> CoreResult<String> {
+        self.inner.format_unit(value, unit_id, options)
+    }
+
+    fn format_currency(
+        &self,
+        value: f64,
+        code: [u8; 3],
+        options: &[FormatterOption],
+    ) -> CoreResult<String> {
+        self.inner.format_currency(value, code, options)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Style {
+    Short,
+    Medium,
+    Long,
+    Full,
+}
+
+fn style_option(options: &[FormatterOption], key: &str) -> CoreResult<Option<Style>> {
+    let Some(value) = find_option(options, key) else {
+        return Ok(None);
+    };
+    match value.as_str() {
+        Some("short") => Ok(Some(Style::Short)),
+        Some("medium") => Ok(Some(Style::Medium)),
+        Some("long") => Ok(Some(Style::Long)),
+        Some("full") => Ok(Some(Style::Full)),
+        _ => Err(CoreError::InvalidInput("invalid date/time style option")),
+    }
+}
+
+fn time_zone(options: &[FormatterOption]) -> CoreResult<Tz> {
+    let Some(value) = find_option(options, "timeZone") else {
+        return Ok(Tz::UTC);
+    };
+    value
+        .as_str()
+        .and_then(|name| name.parse::<Tz>().ok())
+        .ok_or(CoreError::InvalidInput("invalid timeZone option"))
+}
+
+fn render(
+    millis: i64,
+    options: &[FormatterOption],
+    date: Option<Style>,
+    time: Option<Style>,
+) -> CoreResult<String> {
+    let zone = time_zone(options)?;
+    let instant = DateTime::from_timestamp_millis(millis)
+        .ok_or(CoreError::InvalidInput("timestamp out of range"))?
+        .with_timezone(&zone);
+    let date_pattern = date.map(|style| match style {
+        Style::Short => "%-m/%-d/%y",
+        Style::Medium => "%b %-d, %Y",
+        Style::Long => "%B %-d, %Y",
+        Style::Full => "%A, %B %-d, %Y",
+    });
+    let time_pattern = time.map(|style| match style {
+        Style::Short => "%-I:%M %p",
+        Style::Medium => "%-I:%M:%S %p",
+        Style::Long | Style::Full => "%-I:%M:%S %p %Z",
+    });
+    let pattern = match (date_pattern, time_pattern) {
+        (Some(date), Some(time)) => format!("{date}, {time}"),
+        (Some(date), None) => date.to_string(),
+        (None, Some(time)) => time.to_string(),
+        (None, None) => return Err(CoreError::InvalidInput("date/time style missing")),
+    };
+    Ok(instant.format(&pattern).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use mf2_i18n_core::{FormatBackend, FormatterOption, FormatterOptionValue};
+
+    use super::ChronoDateBackend;
+    use crate::BasicFormatBackend;
+
+    const EPOCH_MILLIS: i64 = 1_700_000_000_000;
+
+    fn option(key: &str, value: &str) -> FormatterOption {
+        FormatterOption {
+            key: key.to_string(),
+            value: FormatterOptionValue::Str(value.to_string()),
+        }
+    }
+
+    #[test]
+    fn formats_fixed_epoch_in_utc() {
+        let backend = ChronoDateBackend::new(BasicFormatBackend);
+        assert_eq!(
+            backend.format_date(EPOCH_MILLIS, &[]).expect("date"),
+            "Nov 14, 2023"
+        );
+        assert_eq!(
+            backend
+                .format_date(EPOCH_MILLIS, &[option("dateStyle", "short")])
+                .expect("date"),
+            "11/14/23"
+        );
+        assert_eq!(
+            backend
+                .format_time(EPOCH_MILLIS, &[option("timeStyle", "medium")])
+                .expect("time"),
+            "10:13:20 PM"
+        );
+        assert_eq!(
+            backend
+                .format_datetime(EPOCH_MILLIS, &[])
+                .expect("datetime"),
+            "Nov 14, 2023, 10:13 PM"
+        );
+    }
+
+    #[test]
+    fn formats_fixed_epoch_in_time_zone() {
+        let backend = ChronoDateBackend::new(BasicFormatBackend);
+        let options = [
+            option("dateStyle", "long"),
+            option("timeStyle", "long"),
+            option("timeZone", "America/New_York"),
+        ];
+        assert_eq!(
+            backend
+                .format_datetime(EPOCH_MILLIS, &options)
+                .expect("datetime"),
+            "November 14, 2023, 5:13:20 PM EST"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_options() {
+        let backend = ChronoDateBackend::new(BasicFormatBackend);
+        assert!(
+            backend
+                .format_date(EPOCH_MILLIS, &[option("dateStyle", "tiny")])
+                .is_err()
+        );
+        assert!(
+            backend
+                .format_time(EPOCH_MILLIS, &[option("timeZone", "Mars/Olympus")])
+                .is_err()
+        );
+    }
+}
@@ -14,10 +14,18 @@ pub enum RuntimeError {
     InvalidIdMap,
     #[error("hash mismatch for {0}")]
     HashMismatch(String),
+    #[error("unsupported content encoding {1} for {0}")]
+    UnsupportedEncoding(String, String),
     #[error("missing locale {0}")]
     MissingLocale(String),
+    #[error("pack for {0} declares {1}")]
+    LocaleMismatch(String, String),
     #[error("missing message key {0}")]
     MissingMessage(String),
+    #[error("prepared message {0} belongs to a replaced release")]
+    StalePrepared(String),
+    #[error("invalid argument {0}: {1}")]
+    InvalidArgument(String, String),
     #[error("invalid manifest: {0}")]
     InvalidManifest(String),
     #[error("signature verification failed")]
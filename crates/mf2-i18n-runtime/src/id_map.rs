@@ -1,7 +1,6 @@
 use std::collections::BTreeMap;
 
-use mf2_i18n_core::MessageId;
-use sha2::{Digest, Sha256};
+use mf2_i18n_core::{MessageId, id_map_hash};
 
 use crate::error::{RuntimeError, RuntimeResult};
 
@@ -12,7 +11,7 @@ pub struct IdMap {
 
 impl IdMap {
     pub fn from_json(contents: &str) -> RuntimeResult<Self> {
-        let map: BTreeMap<String, u32> = serde_json::from_str(contents)?;
+        let map: BTreeMap<String, u64> = serde_json::from_str(contents)?;
         let mut entries = BTreeMap::new();
         for (key, id) in map {
             entries.insert(key, MessageId::new(id));
@@ -24,18 +23,13 @@ impl IdMap {
         self.entries.get(key).copied()
     }
 
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
     pub fn hash(&self) -> RuntimeResult<[u8; 32]> {
-        let mut hasher = Sha256::new();
-        for (key, id) in &self.entries {
-            let len: u32 = key
-                .len()
-                .try_into()
-                .map_err(|_| RuntimeError::InvalidIdMap)?;
-            hasher.update(len.to_le_bytes());
-            hasher.update(key.as_bytes());
-            hasher.update(u32::from(*id).to_le_bytes());
-        }
-        Ok(hasher.finalize().into())
+        id_map_hash(self.entries.iter().map(|(key, id)| (key.as_str(), *id)))
+            .map_err(|_| RuntimeError::InvalidIdMap)
     }
 }
 
@@ -48,6 +42,6 @@ mod tests {
         let json = r#"{"home.title": 7}"#;
         let map = IdMap::from_json(json).expect("map");
         let id = map.get("home.title").expect("id");
-        assert_eq!(u32::from(id), 7);
+        assert_eq!(u64::from(id), 7);
     }
 }
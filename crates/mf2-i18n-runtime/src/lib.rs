@@ -1,15 +1,32 @@
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "mmap"), forbid(unsafe_code))]
+#![cfg_attr(feature = "mmap", deny(unsafe_code))]
 
+mod args;
+#[cfg(feature = "chrono")]
+mod datetime;
 mod error;
 mod id_map;
 mod loader;
 mod manifest;
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(feature = "mmap")]
+mod mmap;
 mod runtime;
 mod signing;
 
+pub use crate::args::{ArgSpec, args_from_json};
+#[cfg(feature = "chrono")]
+pub use crate::datetime::ChronoDateBackend;
 pub use crate::error::{RuntimeError, RuntimeResult};
 pub use crate::id_map::IdMap;
 pub use crate::loader::{load_id_map, load_manifest, parse_sha256};
-pub use crate::manifest::{Manifest, ManifestSigning, PackEntry};
-pub use crate::runtime::{BasicFormatBackend, Runtime};
-pub use crate::signing::verify_manifest_signature;
+pub use crate::manifest::{
+    Manifest, ManifestSignatures, ManifestSigning, PackEntry, SUPPORTED_MANIFEST_SCHEMA,
+};
+#[cfg(feature = "metrics")]
+pub use crate::metrics::MetricsSnapshot;
+pub use crate::runtime::{
+    BasicFormatBackend, IcuFormatBackend, MessageKeys, MissingKeyPolicy, PreparedMessage, Runtime,
+};
+pub use crate::signing::{ManifestKey, verify_manifest_signature};
@@ -2,6 +2,8 @@ use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
+pub const SUPPORTED_MANIFEST_SCHEMA: u32 = 1;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Manifest {
     pub schema: u32,
@@ -18,7 +20,7 @@ pub struct Manifest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub budgets: Option<BTreeMap<String, u64>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub signing: Option<ManifestSigning>,
+    pub signing: Option<ManifestSignatures>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,49 +42,71 @@ pub struct ManifestSigning {
     pub manifest_sig: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ManifestSignatures {
+    Single(ManifestSigning),
+    Multiple(Vec<ManifestSigning>),
+}
+
+impl ManifestSignatures {
+    pub fn as_slice(&self) -> &[ManifestSigning] {
+        match self {
+            Self::Single(signing) => std::slice::from_ref(signing),
+            Self::Multiple(signatures) => signatures,
+        }
+    }
+}
+
 impl Manifest {
     pub fn to_signing_bytes(&self) -> Result<Vec<u8>, serde_json::Error> {
         let mut clone = self.clone();
         clone.signing = None;
-        serde_json::to_vec(&clone)
+        clone.supported_locales.sort();
+        let value = serde_json::to_value(&clone)?;
+        Ok(mf2_i18n_core::canonical_json(&value).into_bytes())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Manifest, PackEntry};
-    use std::collections::BTreeMap;
+    use super::Manifest;
+    use mf2_i18n_test_support::ManifestBuilder;
 
     #[test]
     fn signing_bytes_are_stable() {
-        let mut mf2_packs = BTreeMap::new();
-        mf2_packs.insert(
-            "en".to_string(),
-            PackEntry {
-                kind: "base".to_string(),
-                url: "packs/en.mf2pack".to_string(),
-                hash: "sha256:abc".to_string(),
-                size: 12,
-                content_encoding: "identity".to_string(),
-                pack_schema: 0,
-                parent: None,
-            },
-        );
-        let manifest = Manifest {
-            schema: 1,
-            release_id: "r1".to_string(),
-            generated_at: "2026-02-01T00:00:00Z".to_string(),
-            default_locale: "en".to_string(),
-            supported_locales: vec!["en".to_string()],
-            id_map_hash: "sha256:dead".to_string(),
-            mf2_packs,
-            icu_packs: None,
-            micro_locales: None,
-            budgets: None,
-            signing: None,
-        };
+        let manifest: Manifest = serde_json::from_value(
+            ManifestBuilder::new([0; 32])
+                .pack("en", None, b"pack")
+                .build(),
+        )
+        .expect("manifest");
         let a = manifest.to_signing_bytes().expect("bytes");
         let b = manifest.to_signing_bytes().expect("bytes");
         assert_eq!(a, b);
+        assert!(a.starts_with(br#"{"default_locale":"en","generated_at":"#));
+    }
+
+    #[test]
+    fn signing_bytes_ignore_locale_order_only() {
+        let manifest = |locales: &[&str]| -> Manifest {
+            serde_json::from_value(
+                ManifestBuilder::new([0; 32])
+                    .supported_locales(locales)
+                    .build(),
+            )
+            .expect("manifest")
+        };
+        let sorted = manifest(&["de", "en", "fr"])
+            .to_signing_bytes()
+            .expect("bytes");
+        let shuffled = manifest(&["fr", "de", "en"])
+            .to_signing_bytes()
+            .expect("bytes");
+        let different = manifest(&["de", "en", "es"])
+            .to_signing_bytes()
+            .expect("bytes");
+        assert_eq!(sorted, shuffled);
+        assert_ne!(sorted, different);
     }
 }
@@ -0,0 +1,80 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{PoisonError, RwLock};
+
+use mf2_i18n_core::NegotiationResult;
+
+use crate::error::RuntimeError;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MetricsSnapshot {
+    pub format_calls: u64,
+    pub format_successes: u64,
+    pub missing_messages: u64,
+    pub negotiation_fallbacks: u64,
+    pub locale_hits: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Metrics {
+    format_calls: AtomicU64,
+    format_successes: AtomicU64,
+    missing_messages: AtomicU64,
+    negotiation_fallbacks: AtomicU64,
+    locale_hits: RwLock<BTreeMap<String, AtomicU64>>,
+}
+
+impl Metrics {
+    pub(crate) fn track_locales<'a>(&self, locales: impl Iterator<Item = &'a str>) {
+        let mut hits = self
+            .locale_hits
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        for locale in locales {
+            if !hits.contains_key(locale) {
+                hits.insert(locale.to_string(), AtomicU64::new(0));
+            }
+        }
+    }
+
+    pub(crate) fn record(&self, outcome: Result<&NegotiationResult, &RuntimeError>) {
+        self.format_calls.fetch_add(1, Ordering::Relaxed);
+        match outcome {
+            Ok(negotiation) => {
+                self.format_successes.fetch_add(1, Ordering::Relaxed);
+                let selected = negotiation.selected.normalized();
+                if selected != negotiation.requested.normalized() {
+                    self.negotiation_fallbacks.fetch_add(1, Ordering::Relaxed);
+                }
+                let hits = self
+                    .locale_hits
+                    .read()
+                    .unwrap_or_else(PoisonError::into_inner);
+                if let Some(counter) = hits.get(selected) {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            Err(RuntimeError::MissingMessage(_)) => {
+                self.missing_messages.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(_) => {}
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> MetricsSnapshot {
+        let hits = self
+            .locale_hits
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        MetricsSnapshot {
+            format_calls: self.format_calls.load(Ordering::Relaxed),
+            format_successes: self.format_successes.load(Ordering::Relaxed),
+            missing_messages: self.missing_messages.load(Ordering::Relaxed),
+            negotiation_fallbacks: self.negotiation_fallbacks.load(Ordering::Relaxed),
+            locale_hits: hits
+                .iter()
+                .map(|(locale, counter)| (locale.clone(), counter.load(Ordering::Relaxed)))
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+#[allow(unsafe_code)]
+pub(crate) fn map_file(path: &Path) -> io::Result<Mmap> {
+    let file = File::open(path)?;
+    // SAFETY: the map is read-only and is dropped once the pack has been hashed
+    // and decoded, since decoded catalogs copy what they keep. Pack files must
+    // not be truncated or rewritten during a load: a file shrinking under the
+    // map raises SIGBUS instead of an error.
+    unsafe { Mmap::map(&file) }
+}
@@ -1,20 +1,104 @@
 use std::collections::BTreeMap;
 use std::fs;
+use std::io::{BufReader, Read};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, PoisonError, RwLock};
 
 use mf2_i18n_core::{
-    Args, CatalogChain, FormatBackend, LanguageTag, PackCatalog, PluralCategory, execute,
-    negotiate_lookup,
+    Args, BytecodeProgram, Catalog, CatalogChain, CldrPluralBackend, CurrencyPlacement,
+    FormatBackend, IcuDataPack, LanguageTag, MessageId, NegotiationResult, PackCatalog,
+    PluralCategory, PluralRuleset, StyledNumberBackend, analyze, execute, execute_strict,
+    format_currency_value, format_decimal, negotiate_lookup, negotiate_lookup_with_trace,
+    parse_accept_language,
 };
 
+use crate::args::ArgSpec;
 use crate::error::{RuntimeError, RuntimeResult};
 use crate::id_map::IdMap;
 use crate::loader::{load_id_map, load_manifest, parse_sha256};
-use crate::manifest::PackEntry;
+use crate::manifest::{Manifest, PackEntry, SUPPORTED_MANIFEST_SCHEMA};
+#[cfg(feature = "metrics")]
+use crate::metrics::{Metrics, MetricsSnapshot};
+use crate::signing::{ManifestKey, verify_manifest_signature};
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MissingKeyPolicy {
+    #[default]
+    Error,
+    EchoKey,
+    FallbackDefault,
+}
+
+#[derive(Clone)]
 pub struct Runtime {
+    state: Arc<RwLock<Arc<RuntimeState>>>,
+    strict_args: bool,
+    missing_key_policy: MissingKeyPolicy,
+    open_pack: OpenPack,
+    verification: Option<Arc<Verification>>,
+    #[cfg(feature = "metrics")]
+    metrics: Arc<Metrics>,
+}
+
+struct Verification {
+    keyring: BTreeMap<String, ManifestKey>,
+    require_signature: bool,
+}
+
+impl Verification {
+    fn check(&self, manifest: &Manifest) -> RuntimeResult<()> {
+        if self.require_signature && manifest.signing.is_none() {
+            return Err(RuntimeError::InvalidManifest(
+                "missing signature".to_string(),
+            ));
+        }
+        verify_manifest_signature(manifest, &self.keyring)
+    }
+}
+
+pub struct MessageKeys {
+    state: Arc<RuntimeState>,
+}
+
+impl MessageKeys {
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.state.id_map.keys()
+    }
+}
+
+type OpenPack = fn(&str, &PackEntry, &Path) -> RuntimeResult<PackBytes>;
+
+enum PackBytes {
+    Owned(Vec<u8>),
+    #[cfg(feature = "mmap")]
+    Mapped(memmap2::Mmap),
+}
+
+impl std::ops::Deref for PackBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Self::Owned(bytes) => bytes,
+            #[cfg(feature = "mmap")]
+            Self::Mapped(map) => map,
+        }
+    }
+}
+
+pub struct PreparedMessage {
+    key: String,
+    locale: String,
+    message_id: MessageId,
+    strict_args: bool,
+    state: Arc<RuntimeState>,
+    current: Arc<RwLock<Arc<RuntimeState>>>,
+}
+
+struct RuntimeState {
     id_map: IdMap,
     packs: BTreeMap<String, PackCatalog>,
+    icu_data: BTreeMap<String, Arc<IcuDataPack>>,
     parents: BTreeMap<String, String>,
     default_locale: LanguageTag,
     supported: Vec<LanguageTag>,
@@ -79,25 +163,546 @@ impl FormatBackend for BasicFormatBackend {
     }
 }
 
+pub struct IcuFormatBackend<'a> {
+    data: &'a IcuDataPack,
+    placement: CurrencyPlacement,
+}
+
+impl<'a> IcuFormatBackend<'a> {
+    pub fn new(data: &'a IcuDataPack) -> Self {
+        let placement = LanguageTag::parse(data.locale())
+            .map_or(CurrencyPlacement::Prefix, |locale| {
+                CurrencyPlacement::for_locale(&locale)
+            });
+        Self { data, placement }
+    }
+}
+
+impl FormatBackend for IcuFormatBackend<'_> {
+    fn plural_category(&self, value: f64) -> mf2_i18n_core::CoreResult<PluralCategory> {
+        Ok(self.data.plural_category(PluralRuleset::Cardinal, value))
+    }
+
+    fn ordinal_category(&self, value: f64) -> mf2_i18n_core::CoreResult<PluralCategory> {
+        Ok(self.data.plural_category(PluralRuleset::Ordinal, value))
+    }
+
+    fn format_number(
+        &self,
+        value: f64,
+        options: &[mf2_i18n_core::FormatterOption],
+    ) -> mf2_i18n_core::CoreResult<String> {
+        format_decimal(value, self.data.symbols(), options)
+    }
+
+    fn format_date(
+        &self,
+        value: i64,
+        options: &[mf2_i18n_core::FormatterOption],
+    ) -> mf2_i18n_core::CoreResult<String> {
+        BasicFormatBackend.format_date(value, options)
+    }
+
+    fn format_time(
+        &self,
+        value: i64,
+        options: &[mf2_i18n_core::FormatterOption],
+    ) -> mf2_i18n_core::CoreResult<String> {
+        BasicFormatBackend.format_time(value, options)
+    }
+
+    fn format_datetime(
+        &self,
+        value: i64,
+        options: &[mf2_i18n_core::FormatterOption],
+    ) -> mf2_i18n_core::CoreResult<String> {
+        BasicFormatBackend.format_datetime(value, options)
+    }
+
+    fn format_unit(
+        &self,
+        value: f64,
+        unit_id: u32,
+        options: &[mf2_i18n_core::FormatterOption],
+    ) -> mf2_i18n_core::CoreResult<String> {
+        BasicFormatBackend.format_unit(value, unit_id, options)
+    }
+
+    fn format_currency(
+        &self,
+        value: f64,
+        code: [u8; 3],
+        options: &[mf2_i18n_core::FormatterOption],
+    ) -> mf2_i18n_core::CoreResult<String> {
+        format_currency_value(value, code, self.data.symbols(), self.placement, options)
+    }
+}
+
 impl Runtime {
+    fn from_state(state: RuntimeState, open_pack: OpenPack) -> Self {
+        let runtime = Self {
+            state: Arc::new(RwLock::new(Arc::new(state))),
+            strict_args: false,
+            missing_key_policy: MissingKeyPolicy::Error,
+            open_pack,
+            verification: None,
+            #[cfg(feature = "metrics")]
+            metrics: Arc::new(Metrics::default()),
+        };
+        #[cfg(feature = "metrics")]
+        runtime
+            .metrics
+            .track_locales(runtime.snapshot().packs.keys().map(String::as_str));
+        runtime
+    }
+
     pub fn load_from_paths(manifest_path: &Path, id_map_path: &Path) -> RuntimeResult<Self> {
+        let state = RuntimeState::load(manifest_path, id_map_path, read_pack_file)?;
+        Ok(Self::from_state(state, read_pack_file))
+    }
+
+    pub fn load_from_paths_verified(
+        manifest_path: &Path,
+        id_map_path: &Path,
+        keyring: &BTreeMap<String, ManifestKey>,
+        require_signature: bool,
+    ) -> RuntimeResult<Self> {
+        let verification = Verification {
+            keyring: keyring.clone(),
+            require_signature,
+        };
         let manifest = load_manifest(manifest_path)?;
-        let id_map = load_id_map(id_map_path)?;
-        let expected_hash = parse_sha256(&manifest.id_map_hash)?;
-        let actual_hash = id_map.hash()?;
-        if expected_hash != actual_hash {
-            return Err(RuntimeError::InvalidIdMap);
+        verification.check(&manifest)?;
+        let state =
+            RuntimeState::load_with_manifest(manifest, manifest_path, id_map_path, read_pack_file)?;
+        let mut runtime = Self::from_state(state, read_pack_file);
+        runtime.verification = Some(Arc::new(verification));
+        Ok(runtime)
+    }
+
+    #[cfg(feature = "mmap")]
+    pub fn load_from_paths_mmap(manifest_path: &Path, id_map_path: &Path) -> RuntimeResult<Self> {
+        let state = RuntimeState::load(manifest_path, id_map_path, map_pack_file)?;
+        Ok(Self::from_state(state, map_pack_file))
+    }
+
+    pub fn from_bytes(
+        manifest_bytes: &[u8],
+        id_map_bytes: &[u8],
+        pack_bytes_by_locale: &BTreeMap<String, Vec<u8>>,
+    ) -> RuntimeResult<Self> {
+        let state = RuntimeState::from_bytes(manifest_bytes, id_map_bytes, pack_bytes_by_locale)?;
+        Ok(Self::from_state(state, read_pack_file))
+    }
+
+    pub fn with_strict_args(mut self, strict: bool) -> Self {
+        self.strict_args = strict;
+        self
+    }
+
+    pub fn with_missing_key_policy(mut self, policy: MissingKeyPolicy) -> Self {
+        self.missing_key_policy = policy;
+        self
+    }
+
+    pub fn reload_from_paths(&self, manifest_path: &Path, id_map_path: &Path) -> RuntimeResult<()> {
+        let manifest = load_manifest(manifest_path)?;
+        if let Some(verification) = &self.verification {
+            verification.check(&manifest)?;
+        }
+        let state = Arc::new(RuntimeState::load_with_manifest(
+            manifest,
+            manifest_path,
+            id_map_path,
+            self.open_pack,
+        )?);
+        #[cfg(feature = "metrics")]
+        self.metrics
+            .track_locales(state.packs.keys().map(String::as_str));
+        *self.state.write().unwrap_or_else(PoisonError::into_inner) = state;
+        Ok(())
+    }
+
+    #[cfg(feature = "metrics")]
+    pub fn metrics_snapshot(&self) -> MetricsSnapshot {
+        self.metrics.snapshot()
+    }
+
+    pub fn format(&self, locale: &str, key: &str, args: &Args) -> RuntimeResult<String> {
+        let (output, _) = self.format_negotiated(locale, key, args)?;
+        Ok(output)
+    }
+
+    pub fn format_negotiated(
+        &self,
+        locale: &str,
+        key: &str,
+        args: &Args,
+    ) -> RuntimeResult<(String, NegotiationResult)> {
+        self.format_negotiated_internal(locale, key, args, false)
+    }
+
+    pub fn format_negotiated_with_trace(
+        &self,
+        locale: &str,
+        key: &str,
+        args: &Args,
+    ) -> RuntimeResult<(String, NegotiationResult)> {
+        self.format_negotiated_internal(locale, key, args, true)
+    }
+
+    fn format_negotiated_internal(
+        &self,
+        locale: &str,
+        key: &str,
+        args: &Args,
+        with_trace: bool,
+    ) -> RuntimeResult<(String, NegotiationResult)> {
+        let state = self.snapshot();
+        let result = state.negotiate(locale, with_trace).and_then(|negotiation| {
+            let output = self.format_selected(&state, &negotiation, key, args)?;
+            Ok((output, negotiation))
+        });
+        let result = self.resolve_missing(
+            &state,
+            result,
+            locale,
+            key,
+            with_trace,
+            |state, negotiation| self.format_selected(state, negotiation, key, args),
+        );
+        self.record(result.as_ref().map(|(_, negotiation)| negotiation));
+        result
+    }
+
+    pub fn format_batch(
+        &self,
+        locale: &str,
+        entries: &[(&str, &Args)],
+    ) -> RuntimeResult<Vec<String>> {
+        let state = self.snapshot();
+        let negotiation = state.negotiate(locale, false)?;
+        let chain = state.catalog_chain_for(negotiation.selected.normalized())?;
+        with_locale_backend(&state, &negotiation.selected, |backend| {
+            entries
+                .iter()
+                .map(|(key, args)| {
+                    match state.format_in(&chain, key, args, backend, self.strict_args) {
+                        Err(err @ RuntimeError::MissingMessage(_)) => {
+                            let resolved = self.resolve_missing(
+                                &state,
+                                Err(err),
+                                locale,
+                                key,
+                                false,
+                                |state, negotiation| {
+                                    self.format_selected(state, negotiation, key, args)
+                                },
+                            );
+                            self.record(resolved.as_ref().map(|(_, negotiation)| negotiation));
+                            resolved.map(|(output, _)| output)
+                        }
+                        other => {
+                            self.record(other.as_ref().map(|_| &negotiation));
+                            other
+                        }
+                    }
+                })
+                .collect()
+        })
+    }
+
+    pub fn prepare(&self, locale: &str, key: &str) -> RuntimeResult<PreparedMessage> {
+        let state = self.snapshot();
+        let negotiation = state.negotiate(locale, false)?;
+        let layers = state.layers_for(negotiation.selected.normalized())?;
+        let message_id = state
+            .id_map
+            .get(key)
+            .ok_or_else(|| RuntimeError::MissingMessage(key.to_string()))?;
+        let chain = CatalogChain::new(layers.iter().map(|(_, pack)| *pack).collect());
+        let (layer, _) = chain
+            .lookup_with_source(message_id)
+            .ok_or_else(|| RuntimeError::MissingMessage(key.to_string()))?;
+        let locale = layers[layer].0.to_string();
+        Ok(PreparedMessage {
+            key: key.to_string(),
+            locale,
+            message_id,
+            strict_args: self.strict_args,
+            state,
+            current: self.state.clone(),
+        })
+    }
+
+    fn format_selected(
+        &self,
+        state: &RuntimeState,
+        negotiation: &NegotiationResult,
+        key: &str,
+        args: &Args,
+    ) -> RuntimeResult<String> {
+        with_locale_backend(state, &negotiation.selected, |backend| {
+            state.format(
+                negotiation.selected.normalized(),
+                key,
+                args,
+                backend,
+                self.strict_args,
+            )
+        })
+    }
+
+    fn resolve_missing(
+        &self,
+        state: &RuntimeState,
+        result: RuntimeResult<(String, NegotiationResult)>,
+        locale: &str,
+        key: &str,
+        with_trace: bool,
+        retry: impl FnOnce(&RuntimeState, &NegotiationResult) -> RuntimeResult<String>,
+    ) -> RuntimeResult<(String, NegotiationResult)> {
+        if !matches!(result, Err(RuntimeError::MissingMessage(_))) {
+            return result;
+        }
+        match self.missing_key_policy {
+            MissingKeyPolicy::Error => result,
+            MissingKeyPolicy::EchoKey => {
+                Ok((key.to_string(), state.negotiate(locale, with_trace)?))
+            }
+            MissingKeyPolicy::FallbackDefault => {
+                let mut negotiation = state.negotiate(locale, with_trace)?;
+                if negotiation.selected == state.default_locale {
+                    return result;
+                }
+                negotiation.selected = state.default_locale.clone();
+                let output = retry(state, &negotiation)?;
+                Ok((output, negotiation))
+            }
+        }
+    }
+
+    pub fn negotiate_accept_language(&self, accept_language: &str) -> NegotiationResult {
+        let state = self.snapshot();
+        negotiate_lookup(
+            &parse_accept_language(accept_language),
+            &state.supported,
+            &state.default_locale,
+        )
+    }
+
+    pub fn message_arg_names(&self, locale: &str, key: &str) -> RuntimeResult<Vec<String>> {
+        let state = self.snapshot();
+        let negotiation = state.negotiate(locale, false)?;
+        let program = state.program(negotiation.selected.normalized(), key)?;
+        Ok(program.arg_names.clone())
+    }
+
+    pub fn message_arg_specs(&self, locale: &str, key: &str) -> RuntimeResult<Vec<ArgSpec>> {
+        let state = self.snapshot();
+        let negotiation = state.negotiate(locale, false)?;
+        let program = state.program(negotiation.selected.normalized(), key)?;
+        Ok(analyze(program)
+            .args
+            .into_iter()
+            .map(|arg| ArgSpec {
+                name: arg.name,
+                arg_type: arg.arg_type,
+                required: true,
+            })
+            .collect())
+    }
+
+    pub fn message_keys(&self) -> MessageKeys {
+        MessageKeys {
+            state: self.snapshot(),
+        }
+    }
+
+    pub fn has_key(&self, locale: &str, key: &str) -> bool {
+        let state = self.snapshot();
+        let Ok(negotiation) = state.negotiate(locale, false) else {
+            return false;
+        };
+        state
+            .program(negotiation.selected.normalized(), key)
+            .is_ok()
+    }
+
+    pub fn message_source(&self, locale: &str, key: &str) -> RuntimeResult<String> {
+        let state = self.snapshot();
+        let negotiation = state.negotiate(locale, false)?;
+        state.message_source(negotiation.selected.normalized(), key)
+    }
+
+    pub fn icu_data(&self, locale: &str) -> Option<Arc<IcuDataPack>> {
+        self.snapshot().icu_data_for(locale)
+    }
+
+    pub fn format_with_backend(
+        &self,
+        locale: &str,
+        key: &str,
+        args: &Args,
+        backend: &dyn FormatBackend,
+    ) -> RuntimeResult<String> {
+        let state = self.snapshot();
+        let result = state.negotiate(locale, false).and_then(|negotiation| {
+            let output = state.format(
+                negotiation.selected.normalized(),
+                key,
+                args,
+                backend,
+                self.strict_args,
+            )?;
+            Ok((output, negotiation))
+        });
+        let result =
+            self.resolve_missing(&state, result, locale, key, false, |state, negotiation| {
+                state.format(
+                    negotiation.selected.normalized(),
+                    key,
+                    args,
+                    backend,
+                    self.strict_args,
+                )
+            });
+        self.record(result.as_ref().map(|(_, negotiation)| negotiation));
+        result.map(|(output, _)| output)
+    }
+
+    #[cfg(feature = "metrics")]
+    fn record(&self, outcome: Result<&NegotiationResult, &RuntimeError>) {
+        self.metrics.record(outcome);
+    }
+
+    #[cfg(not(feature = "metrics"))]
+    fn record(&self, _outcome: Result<&NegotiationResult, &RuntimeError>) {}
+
+    fn snapshot(&self) -> Arc<RuntimeState> {
+        self.state
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+impl PreparedMessage {
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    pub fn is_stale(&self) -> bool {
+        let current = self.current.read().unwrap_or_else(PoisonError::into_inner);
+        !Arc::ptr_eq(&current, &self.state)
+    }
+
+    pub fn render(&self, args: &Args, backend: &dyn FormatBackend) -> RuntimeResult<String> {
+        if self.is_stale() {
+            return Err(RuntimeError::StalePrepared(self.key.clone()));
         }
+        let program = self
+            .state
+            .packs
+            .get(&self.locale)
+            .and_then(|pack| pack.lookup(self.message_id))
+            .ok_or_else(|| RuntimeError::MissingMessage(self.key.clone()))?;
+        let output = if self.strict_args {
+            execute_strict(program, args, backend)?
+        } else {
+            execute(program, args, backend)?
+        };
+        Ok(output)
+    }
+}
 
+impl RuntimeState {
+    fn load(manifest_path: &Path, id_map_path: &Path, open_pack: OpenPack) -> RuntimeResult<Self> {
+        Self::load_with_manifest(
+            load_manifest(manifest_path)?,
+            manifest_path,
+            id_map_path,
+            open_pack,
+        )
+    }
+
+    fn load_with_manifest(
+        manifest: Manifest,
+        manifest_path: &Path,
+        id_map_path: &Path,
+        open_pack: OpenPack,
+    ) -> RuntimeResult<Self> {
+        let id_map = load_id_map(id_map_path)?;
         let pack_root = manifest_path
             .parent()
             .map(PathBuf::from)
             .unwrap_or_else(|| PathBuf::from("."));
+        Self::from_parts(manifest, id_map, |locale, entry| {
+            open_pack(locale, entry, &pack_root.join(&entry.url))
+        })
+    }
+
+    fn from_bytes(
+        manifest_bytes: &[u8],
+        id_map_bytes: &[u8],
+        pack_bytes_by_locale: &BTreeMap<String, Vec<u8>>,
+    ) -> RuntimeResult<Self> {
+        let manifest: Manifest = serde_json::from_slice(manifest_bytes)?;
+        if manifest
+            .icu_packs
+            .as_ref()
+            .is_some_and(|packs| !packs.is_empty())
+        {
+            return Err(RuntimeError::InvalidManifest(
+                "icu data packs can only be loaded from paths".to_string(),
+            ));
+        }
+        let id_map = IdMap::from_json(
+            std::str::from_utf8(id_map_bytes).map_err(|_| RuntimeError::InvalidIdMap)?,
+        )?;
+        Self::from_parts(manifest, id_map, |locale, entry| {
+            let bytes = pack_bytes_by_locale
+                .get(locale)
+                .ok_or_else(|| RuntimeError::MissingLocale(locale.to_string()))?;
+            read_pack(locale, entry, bytes.as_slice())
+        })
+    }
+
+    fn from_parts(
+        manifest: Manifest,
+        id_map: IdMap,
+        mut fetch: impl FnMut(&str, &PackEntry) -> RuntimeResult<PackBytes>,
+    ) -> RuntimeResult<Self> {
+        if manifest.schema > SUPPORTED_MANIFEST_SCHEMA {
+            return Err(RuntimeError::InvalidManifest(format!(
+                "unsupported manifest schema {}",
+                manifest.schema
+            )));
+        }
+        let expected_hash = parse_sha256(&manifest.id_map_hash)?;
+        let actual_hash = id_map.hash()?;
+        if expected_hash != actual_hash {
+            return Err(RuntimeError::InvalidIdMap);
+        }
 
         let mut packs = BTreeMap::new();
         for (locale, entry) in &manifest.mf2_packs {
-            let pack = load_pack(&pack_root, locale, entry, &expected_hash)?;
-            packs.insert(locale.clone(), pack);
+            let bytes = fetch(locale, entry)?;
+            let catalog = PackCatalog::decode(&bytes, &expected_hash)?;
+            check_pack_locale(locale, entry, &catalog)?;
+            packs.insert(locale.clone(), catalog);
+        }
+
+        let mut icu_data = BTreeMap::new();
+        if let Some(icu_packs) = &manifest.icu_packs {
+            for (locale, entry) in icu_packs {
+                let bytes = fetch(locale, entry)?;
+                icu_data.insert(locale.clone(), Arc::new(IcuDataPack::decode(&bytes)?));
+            }
         }
 
         let mut parents = BTreeMap::new();
@@ -107,10 +712,10 @@ impl Runtime {
             }
         }
         for (locale, entry) in &manifest.mf2_packs {
-            if entry.kind == "overlay" {
-                if let Some(parent) = &entry.parent {
-                    parents.insert(locale.clone(), parent.clone());
-                }
+            if entry.kind == "overlay"
+                && let Some(parent) = &entry.parent
+            {
+                parents.insert(locale.clone(), parent.clone());
             }
         }
 
@@ -123,91 +728,237 @@ impl Runtime {
         Ok(Self {
             id_map,
             packs,
+            icu_data,
             parents,
             default_locale,
             supported,
         })
     }
 
-    pub fn format(&self, locale: &str, key: &str, args: &Args) -> RuntimeResult<String> {
-        let backend = BasicFormatBackend;
-        self.format_with_backend(locale, key, args, &backend)
+    fn format(
+        &self,
+        selected: &str,
+        key: &str,
+        args: &Args,
+        backend: &dyn FormatBackend,
+        strict_args: bool,
+    ) -> RuntimeResult<String> {
+        let chain = self.catalog_chain_for(selected)?;
+        self.format_in(&chain, key, args, backend, strict_args)
     }
 
-    pub fn format_with_backend(
+    fn format_in(
         &self,
-        locale: &str,
+        chain: &CatalogChain<'_>,
         key: &str,
         args: &Args,
         backend: &dyn FormatBackend,
+        strict_args: bool,
     ) -> RuntimeResult<String> {
-        let locale_tag = LanguageTag::parse(locale)?;
-        let negotiation = negotiate_lookup(&[locale_tag], &self.supported, &self.default_locale);
-        let selected = negotiation.selected.normalized().to_string();
-        let catalog_chain = self.catalog_chain_for(&selected)?;
+        let program = self.lookup(chain, key)?;
+        let output = if strict_args {
+            execute_strict(program, args, backend)?
+        } else {
+            execute(program, args, backend)?
+        };
+        Ok(output)
+    }
 
+    fn program(&self, selected: &str, key: &str) -> RuntimeResult<&BytecodeProgram> {
+        let catalog_chain = self.catalog_chain_for(selected)?;
+        self.lookup(&catalog_chain, key)
+    }
+
+    fn lookup<'a>(
+        &self,
+        chain: &CatalogChain<'a>,
+        key: &str,
+    ) -> RuntimeResult<&'a BytecodeProgram> {
         let message_id = self
             .id_map
             .get(key)
             .ok_or_else(|| RuntimeError::MissingMessage(key.to_string()))?;
-        let program = catalog_chain
+        chain
             .lookup(message_id)
+            .ok_or_else(|| RuntimeError::MissingMessage(key.to_string()))
+    }
+
+    fn message_source(&self, selected: &str, key: &str) -> RuntimeResult<String> {
+        let layers = self.layers_for(selected)?;
+        let message_id = self
+            .id_map
+            .get(key)
             .ok_or_else(|| RuntimeError::MissingMessage(key.to_string()))?;
-        let output = execute(program, args, backend)?;
-        Ok(output)
+        let chain = CatalogChain::new(layers.iter().map(|(_, pack)| *pack).collect());
+        let (layer, _) = chain
+            .lookup_with_source(message_id)
+            .ok_or_else(|| RuntimeError::MissingMessage(key.to_string()))?;
+        Ok(layers[layer].0.to_string())
     }
 
-    fn catalog_chain_for(&self, locale: &str) -> RuntimeResult<CatalogChain<'_>> {
-        let mut catalogs = Vec::new();
+    fn negotiate(&self, locale: &str, with_trace: bool) -> RuntimeResult<NegotiationResult> {
+        let requested = [LanguageTag::parse(locale)?];
+        Ok(if with_trace {
+            negotiate_lookup_with_trace(&requested, &self.supported, &self.default_locale)
+        } else {
+            negotiate_lookup(&requested, &self.supported, &self.default_locale)
+        })
+    }
+
+    fn icu_data_for(&self, locale: &str) -> Option<Arc<IcuDataPack>> {
         let mut current = Some(locale.to_string());
         while let Some(tag) = current {
-            if let Some(pack) = self.packs.get(&tag) {
-                catalogs.push(pack as &dyn mf2_i18n_core::Catalog);
+            if let Some(data) = self.icu_data.get(&tag) {
+                return Some(data.clone());
             }
             current = self.parents.get(&tag).cloned();
         }
-        if catalogs.is_empty() {
+        None
+    }
+
+    fn catalog_chain_for(&self, locale: &str) -> RuntimeResult<CatalogChain<'_>> {
+        let layers = self.layers_for(locale)?;
+        Ok(CatalogChain::new(
+            layers.into_iter().map(|(_, pack)| pack).collect(),
+        ))
+    }
+
+    fn layers_for(&self, locale: &str) -> RuntimeResult<Vec<(&str, &dyn Catalog)>> {
+        let mut layers = Vec::new();
+        let mut current = Some(locale);
+        while let Some(tag) = current {
+            if let Some((tag, pack)) = self.packs.get_key_value(tag) {
+                layers.push((tag.as_str(), pack as &dyn Catalog));
+            }
+            current = self.parents.get(tag).map(String::as_str);
+        }
+        if layers.is_empty() {
             return Err(RuntimeError::MissingLocale(locale.to_string()));
         }
-        Ok(CatalogChain::new(catalogs))
+        Ok(layers)
     }
 }
 
-fn load_pack(
-    root: &Path,
-    locale: &str,
-    entry: &PackEntry,
-    id_map_hash: &[u8; 32],
-) -> RuntimeResult<PackCatalog> {
-    let pack_path = root.join(&entry.url);
-    let bytes = fs::read(&pack_path)?;
-    if bytes.len() as u64 != entry.size {
-        return Err(RuntimeError::HashMismatch(locale.to_string()));
+fn with_locale_backend<T>(
+    state: &RuntimeState,
+    locale: &LanguageTag,
+    run: impl FnOnce(&dyn FormatBackend) -> T,
+) -> T {
+    match state.icu_data_for(locale.normalized()) {
+        Some(data) => {
+            let backend = IcuFormatBackend::new(&data);
+            #[cfg(feature = "chrono")]
+            let backend = crate::ChronoDateBackend::new(backend);
+            run(&backend)
+        }
+        None => {
+            let numbers = StyledNumberBackend::new(locale, BasicFormatBackend);
+            #[cfg(feature = "chrono")]
+            let numbers = crate::ChronoDateBackend::new(numbers);
+            run(&CldrPluralBackend::new(locale, numbers))
+        }
     }
-    let expected_hash = parse_sha256(&entry.hash)?;
-    let actual_hash = sha256(&bytes);
+}
+
+fn read_pack_file(locale: &str, entry: &PackEntry, path: &Path) -> RuntimeResult<PackBytes> {
+    read_pack(locale, entry, BufReader::new(fs::File::open(path)?))
+}
+
+#[cfg(feature = "mmap")]
+fn map_pack_file(locale: &str, entry: &PackEntry, path: &Path) -> RuntimeResult<PackBytes> {
+    let map = crate::mmap::map_file(path)?;
+    if map.len() as u64 != entry.size {
+        return Err(RuntimeError::HashMismatch(locale.to_string()));
+    }
+    let expected_hash = parse_sha256(&entry.hash)?;
+    let actual_hash: [u8; 32] = {
+        use sha2::{Digest, Sha256};
+        let mut hasher = Sha256::new();
+        for chunk in map.chunks(64 * 1024) {
+            hasher.update(chunk);
+        }
+        hasher.finalize().into()
+    };
     if expected_hash != actual_hash {
         return Err(RuntimeError::HashMismatch(locale.to_string()));
     }
-    Ok(PackCatalog::decode(&bytes, id_map_hash)?)
+    decode_content(locale, &entry.content_encoding, PackBytes::Mapped(map))
 }
 
-fn sha256(bytes: &[u8]) -> [u8; 32] {
+fn read_pack(locale: &str, entry: &PackEntry, source: impl Read) -> RuntimeResult<PackBytes> {
     use sha2::{Digest, Sha256};
+    let mut reader = source.take(entry.size.saturating_add(1));
+    let mut bytes = Vec::with_capacity(usize::try_from(entry.size).unwrap_or(0));
     let mut hasher = Sha256::new();
-    hasher.update(bytes);
-    hasher.finalize().into()
+    loop {
+        let start = bytes.len();
+        if (&mut reader).take(64 * 1024).read_to_end(&mut bytes)? == 0 {
+            break;
+        }
+        hasher.update(&bytes[start..]);
+    }
+    if bytes.len() as u64 != entry.size {
+        return Err(RuntimeError::HashMismatch(locale.to_string()));
+    }
+    let expected_hash = parse_sha256(&entry.hash)?;
+    let actual_hash: [u8; 32] = hasher.finalize().into();
+    if expected_hash != actual_hash {
+        return Err(RuntimeError::HashMismatch(locale.to_string()));
+    }
+    decode_content(locale, &entry.content_encoding, PackBytes::Owned(bytes))
+}
+
+fn check_pack_locale(locale: &str, entry: &PackEntry, catalog: &PackCatalog) -> RuntimeResult<()> {
+    if catalog.locale_tag() != locale {
+        return Err(RuntimeError::LocaleMismatch(
+            locale.to_string(),
+            format!("locale {}", catalog.locale_tag()),
+        ));
+    }
+    if catalog.parent_tag() != entry.parent.as_deref() {
+        return Err(RuntimeError::LocaleMismatch(
+            locale.to_string(),
+            format!("parent {}", catalog.parent_tag().unwrap_or("none")),
+        ));
+    }
+    Ok(())
+}
+
+fn decode_content(locale: &str, encoding: &str, bytes: PackBytes) -> RuntimeResult<PackBytes> {
+    match encoding {
+        "identity" => Ok(bytes),
+        "br" => {
+            let mut out = Vec::new();
+            brotli::Decompressor::new(&*bytes, 4096).read_to_end(&mut out)?;
+            Ok(PackBytes::Owned(out))
+        }
+        "gzip" => {
+            let mut out = Vec::new();
+            flate2::read::GzDecoder::new(&*bytes).read_to_end(&mut out)?;
+            Ok(PackBytes::Owned(out))
+        }
+        other => Err(RuntimeError::UnsupportedEncoding(
+            locale.to_string(),
+            other.to_string(),
+        )),
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Runtime;
+    use super::{BasicFormatBackend, IcuFormatBackend, MissingKeyPolicy, Runtime};
+    use crate::error::RuntimeError;
     use crate::id_map::IdMap;
-    use crate::manifest::{Manifest, PackEntry};
-    use mf2_i18n_core::{Args, PackKind};
+    use crate::manifest::{Manifest, ManifestSignatures, ManifestSigning};
+    use ed25519_dalek::{Signer, SigningKey};
+    use mf2_i18n_core::{
+        Args, BytecodeProgram, FormatBackend, IcuDataPack, NumberSymbols, PluralCategory,
+    };
+    use mf2_i18n_test_support::{ManifestBuilder, icu_pack_bytes, pack_bytes, text_program};
     use std::collections::BTreeMap;
     use std::fs;
-    use std::path::PathBuf;
+    use std::io::Write;
+    use std::path::{Path, PathBuf};
     use std::time::{SystemTime, UNIX_EPOCH};
 
     fn temp_dir() -> PathBuf {
@@ -221,135 +972,918 @@ mod tests {
         path
     }
 
-    fn build_pack_bytes(id_map_hash: [u8; 32]) -> Vec<u8> {
-        let mut bytes = Vec::new();
-        bytes.extend_from_slice(b"MF2PACK\0");
-        bytes.extend_from_slice(&0u16.to_le_bytes());
-        bytes.push(match PackKind::Base {
-            PackKind::Base => 0,
-            PackKind::Overlay => 1,
-            PackKind::IcuData => 2,
-        });
-        bytes.extend_from_slice(&0u32.to_le_bytes());
-        bytes.extend_from_slice(&id_map_hash);
-        bytes.extend_from_slice(&0u32.to_le_bytes());
-        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
-        bytes.extend_from_slice(&0u64.to_le_bytes());
-
-        let mut string_pool = Vec::new();
-        string_pool.extend_from_slice(&2u32.to_le_bytes());
-        string_pool.extend_from_slice(&2u32.to_le_bytes());
-        string_pool.extend_from_slice(b"hi");
-        string_pool.extend_from_slice(&4u32.to_le_bytes());
-        string_pool.extend_from_slice(b"name");
-
-        let mut message_meta = Vec::new();
-        message_meta.extend_from_slice(&1u32.to_le_bytes());
-        message_meta.extend_from_slice(&0u32.to_le_bytes());
-        message_meta.extend_from_slice(&0u32.to_le_bytes());
-
-        let mut case_tables = Vec::new();
-        case_tables.extend_from_slice(&0u32.to_le_bytes());
-
-        let mut message_index = Vec::new();
-        message_index.extend_from_slice(&1u32.to_le_bytes());
-        message_index.extend_from_slice(&0u32.to_le_bytes());
-        message_index.extend_from_slice(&0u32.to_le_bytes());
-
-        let mut message = Vec::new();
-        message.extend_from_slice(&0u32.to_le_bytes());
-        message.extend_from_slice(&2u32.to_le_bytes());
-        message.push(0);
-        message.extend_from_slice(&0u32.to_le_bytes());
-        message.push(11);
-        let mut bytecode_blob = Vec::new();
-        bytecode_blob.extend_from_slice(&(message.len() as u32).to_le_bytes());
-        bytecode_blob.extend_from_slice(&message);
-
-        let section_count = 5u16;
-        bytes.extend_from_slice(&section_count.to_le_bytes());
-        let dir_start = bytes.len();
-        let dir_len = section_count as usize * (1 + 4 + 4);
-        bytes.resize(dir_start + dir_len, 0);
-        let mut offset = bytes.len() as u32;
-
-        let sections = vec![
-            (1u8, string_pool),
-            (2u8, message_index),
-            (3u8, bytecode_blob),
-            (4u8, case_tables),
-            (5u8, message_meta),
-        ];
-
-        for (idx, (section_type, data)) in sections.into_iter().enumerate() {
-            let entry_offset = dir_start + idx * 9;
-            bytes[entry_offset] = section_type;
-            bytes[entry_offset + 1..entry_offset + 5].copy_from_slice(&offset.to_le_bytes());
-            bytes[entry_offset + 5..entry_offset + 9]
-                .copy_from_slice(&(data.len() as u32).to_le_bytes());
-            bytes.extend_from_slice(&data);
-            offset += data.len() as u32;
-        }
+    const ID_MAP_JSON: &str = r#"{"home.title": 0}"#;
 
-        bytes
+    fn write_fixture(
+        root: &Path,
+        text: &str,
+        content_encoding: &str,
+        encode: fn(&[u8]) -> Vec<u8>,
+    ) {
+        write_program_fixture(root, text_program(text), content_encoding, encode);
     }
 
-    #[test]
-    fn runtime_formats_message() {
-        let root = temp_dir();
+    fn write_program_fixture(
+        root: &Path,
+        program: BytecodeProgram,
+        content_encoding: &str,
+        encode: fn(&[u8]) -> Vec<u8>,
+    ) {
         let packs_dir = root.join("packs");
         fs::create_dir_all(&packs_dir).expect("packs");
 
-        let id_map_json = r#"{"home.title": 0}"#;
-        let id_map = IdMap::from_json(id_map_json).expect("id map");
+        let id_map = IdMap::from_json(ID_MAP_JSON).expect("id map");
         let id_map_hash = id_map.hash().expect("hash");
-        let pack_bytes = build_pack_bytes(id_map_hash);
+        let pack_bytes = encode(&pack_bytes(id_map_hash, "en", None, [(0, program)]));
         let pack_path = packs_dir.join("en.mf2pack");
         fs::write(&pack_path, &pack_bytes).expect("write pack");
 
-        let mut mf2_packs = BTreeMap::new();
-        mf2_packs.insert(
-            "en".to_string(),
-            PackEntry {
-                kind: "base".to_string(),
-                url: "packs/en.mf2pack".to_string(),
-                hash: format!("sha256:{}", hex::encode(super::sha256(&pack_bytes))),
-                size: pack_bytes.len() as u64,
-                content_encoding: "identity".to_string(),
-                pack_schema: 0,
-                parent: None,
-            },
+        let manifest = ManifestBuilder::new(id_map_hash)
+            .encoded_pack("en", None, content_encoding, &pack_bytes)
+            .build();
+
+        fs::write(
+            root.join("manifest.json"),
+            serde_json::to_string_pretty(&manifest).expect("json"),
+        )
+        .expect("write manifest");
+        fs::write(root.join("id_map.json"), ID_MAP_JSON).expect("write id map");
+    }
+
+    #[test]
+    fn runtime_formats_message() {
+        let root = temp_dir();
+        write_fixture(&root, "hi", "identity", |bytes| bytes.to_vec());
+
+        let runtime =
+            Runtime::load_from_paths(&root.join("manifest.json"), &root.join("id_map.json"))
+                .expect("runtime");
+        let args = Args::new();
+        let output = runtime.format("en", "home.title", &args).expect("format");
+        assert_eq!(output, "hi");
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn strict_runtime_rejects_unused_args() {
+        let root = temp_dir();
+        write_fixture(&root, "hi", "identity", |bytes| bytes.to_vec());
+
+        let runtime =
+            Runtime::load_from_paths(&root.join("manifest.json"), &root.join("id_map.json"))
+                .expect("runtime");
+        let mut args = Args::new();
+        args.with_str("nmae", "Nova");
+        assert_eq!(
+            runtime.format("en", "home.title", &args).expect("format"),
+            "hi"
         );
 
-        let manifest = Manifest {
-            schema: 1,
-            release_id: "r1".to_string(),
-            generated_at: "2026-02-01T00:00:00Z".to_string(),
-            default_locale: "en".to_string(),
-            supported_locales: vec!["en".to_string()],
-            id_map_hash: format!("sha256:{}", hex::encode(id_map_hash)),
-            mf2_packs,
-            icu_packs: None,
-            micro_locales: None,
-            budgets: None,
-            signing: None,
-        };
+        let err = runtime
+            .with_strict_args(true)
+            .format("en", "home.title", &args)
+            .expect_err("strict");
+        assert!(matches!(err, RuntimeError::Core(message) if message.contains("nmae")));
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn batch_formatting_matches_single_calls() {
+        let root = temp_dir();
+        write_fixture(&root, "hi", "identity", |bytes| bytes.to_vec());
+        let runtime =
+            Runtime::load_from_paths(&root.join("manifest.json"), &root.join("id_map.json"))
+                .expect("runtime");
+        let args = Args::new();
+
+        let entries = [("home.title", &args), ("home.title", &args)];
+        let batch = runtime.format_batch("en-GB", &entries).expect("batch");
+        let single: Vec<String> = entries
+            .iter()
+            .map(|(key, args)| runtime.format("en-GB", key, args).expect("format"))
+            .collect();
+        assert_eq!(batch, single);
 
+        assert!(matches!(
+            runtime.format_batch("en", &[("home.title", &args), ("home.missing", &args)]),
+            Err(RuntimeError::MissingMessage(_))
+        ));
+        let echo = runtime.with_missing_key_policy(MissingKeyPolicy::EchoKey);
+        assert_eq!(
+            echo.format_batch("en", &[("home.title", &args), ("home.missing", &args)])
+                .expect("echo"),
+            vec!["hi", "home.missing"]
+        );
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn runtime_rejects_newer_manifest_schema() {
+        let root = temp_dir();
+        write_fixture(&root, "hi", "identity", |bytes| bytes.to_vec());
         let manifest_path = root.join("manifest.json");
+        let id_map_path = root.join("id_map.json");
+        Runtime::load_from_paths(&manifest_path, &id_map_path).expect("schema 1");
+
+        let mut manifest: Manifest =
+            serde_json::from_slice(&fs::read(&manifest_path).expect("read")).expect("json");
+        manifest.schema = 999;
         fs::write(
             &manifest_path,
             serde_json::to_string_pretty(&manifest).expect("json"),
         )
         .expect("write manifest");
+        let err = Runtime::load_from_paths(&manifest_path, &id_map_path)
+            .err()
+            .expect("schema 999");
+        assert!(matches!(err, RuntimeError::InvalidManifest(message) if message.contains("999")));
 
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn verified_load_checks_manifest_signature() {
+        let root = temp_dir();
+        write_fixture(&root, "hi", "identity", |bytes| bytes.to_vec());
+        let manifest_path = root.join("manifest.json");
         let id_map_path = root.join("id_map.json");
-        fs::write(&id_map_path, id_map_json).expect("write id map");
+        let signing_key = SigningKey::from_bytes(&[5u8; 32]);
+        let keyring = BTreeMap::from([("key-1".to_string(), signing_key.verifying_key().into())]);
 
-        let runtime = Runtime::load_from_paths(&manifest_path, &id_map_path).expect("runtime");
+        let err = Runtime::load_from_paths_verified(&manifest_path, &id_map_path, &keyring, true)
+            .err()
+            .expect("unsigned");
+        assert!(matches!(err, RuntimeError::InvalidManifest(_)));
+
+        let mut manifest: Manifest =
+            serde_json::from_slice(&fs::read(&manifest_path).expect("read")).expect("json");
+        let signature = signing_key.sign(&manifest.to_signing_bytes().expect("bytes"));
+        let mut signing = ManifestSigning {
+            sig_alg: "ed25519".to_string(),
+            key_id: "key-1".to_string(),
+            manifest_sig: format!("hex:{}", hex::encode(signature.to_bytes())),
+        };
+        manifest.signing = Some(ManifestSignatures::Single(signing.clone()));
+        fs::write(&manifest_path, serde_json::to_vec(&manifest).expect("json")).expect("write");
+        let runtime =
+            Runtime::load_from_paths_verified(&manifest_path, &id_map_path, &keyring, true)
+                .expect("verified");
+        assert_eq!(
+            runtime
+                .format("en", "home.title", &Args::new())
+                .expect("format"),
+            "hi"
+        );
+
+        let corrupted = if signing.manifest_sig.ends_with('0') {
+            '1'
+        } else {
+            '0'
+        };
+        signing.manifest_sig.pop();
+        signing.manifest_sig.push(corrupted);
+        manifest.signing = Some(ManifestSignatures::Single(signing));
+        fs::write(&manifest_path, serde_json::to_vec(&manifest).expect("json")).expect("write");
+        let err = Runtime::load_from_paths_verified(&manifest_path, &id_map_path, &keyring, false)
+            .err()
+            .expect("corrupted");
+        assert!(matches!(err, RuntimeError::SignatureFailed));
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn verified_reload_rejects_unsigned_and_tampered_manifests() {
+        let signing_key = SigningKey::from_bytes(&[6u8; 32]);
+        let keyring = BTreeMap::from([("key-1".to_string(), signing_key.verifying_key().into())]);
+        let sign = |manifest_path: &Path| -> ManifestSigning {
+            let mut manifest: Manifest =
+                serde_json::from_slice(&fs::read(manifest_path).expect("read")).expect("json");
+            let signature = signing_key.sign(&manifest.to_signing_bytes().expect("bytes"));
+            let signing = ManifestSigning {
+                sig_alg: "ed25519".to_string(),
+                key_id: "key-1".to_string(),
+                manifest_sig: format!("hex:{}", hex::encode(signature.to_bytes())),
+            };
+            manifest.signing = Some(ManifestSignatures::Single(signing.clone()));
+            fs::write(manifest_path, serde_json::to_vec(&manifest).expect("json")).expect("write");
+            signing
+        };
+
+        let root = temp_dir();
+        write_fixture(&root, "hi", "identity", |bytes| bytes.to_vec());
+        sign(&root.join("manifest.json"));
+        let runtime = Runtime::load_from_paths_verified(
+            &root.join("manifest.json"),
+            &root.join("id_map.json"),
+            &keyring,
+            true,
+        )
+        .expect("verified");
+
+        let next = temp_dir();
+        write_fixture(&next, "hey", "identity", |bytes| bytes.to_vec());
+        let next_manifest = next.join("manifest.json");
+        let next_id_map = next.join("id_map.json");
+        let err = runtime
+            .reload_from_paths(&next_manifest, &next_id_map)
+            .expect_err("unsigned");
+        assert!(matches!(err, RuntimeError::InvalidManifest(_)));
+        assert_eq!(
+            runtime
+                .format("en", "home.title", &Args::new())
+                .expect("format"),
+            "hi"
+        );
+
+        sign(&next_manifest);
+        let mut manifest: Manifest =
+            serde_json::from_slice(&fs::read(&next_manifest).expect("read")).expect("json");
+        manifest.release_id = "tampered".to_string();
+        fs::write(&next_manifest, serde_json::to_vec(&manifest).expect("json")).expect("write");
+        let err = runtime
+            .reload_from_paths(&next_manifest, &next_id_map)
+            .expect_err("tampered");
+        assert!(matches!(err, RuntimeError::SignatureFailed));
+        assert_eq!(
+            runtime
+                .format("en", "home.title", &Args::new())
+                .expect("format"),
+            "hi"
+        );
+
+        sign(&next_manifest);
+        runtime
+            .reload_from_paths(&next_manifest, &next_id_map)
+            .expect("signed reload");
+        assert_eq!(
+            runtime
+                .format("en", "home.title", &Args::new())
+                .expect("format"),
+            "hey"
+        );
+
+        fs::remove_dir_all(&root).ok();
+        fs::remove_dir_all(&next).ok();
+    }
+
+    #[test]
+    fn runtime_formats_message_from_bytes() {
+        let root = temp_dir();
+        write_fixture(&root, "hi", "identity", |bytes| bytes.to_vec());
+
+        let mut packs = BTreeMap::new();
+        packs.insert(
+            "en".to_string(),
+            fs::read(root.join("packs/en.mf2pack")).expect("pack"),
+        );
+        let runtime = Runtime::from_bytes(
+            &fs::read(root.join("manifest.json")).expect("manifest"),
+            ID_MAP_JSON.as_bytes(),
+            &packs,
+        )
+        .expect("runtime");
+        let output = runtime
+            .format("en", "home.title", &Args::new())
+            .expect("format");
+        assert_eq!(output, "hi");
+
+        let err = Runtime::from_bytes(
+            &fs::read(root.join("manifest.json")).expect("manifest"),
+            ID_MAP_JSON.as_bytes(),
+            &BTreeMap::new(),
+        )
+        .err()
+        .expect("error");
+        assert!(matches!(err, RuntimeError::MissingLocale(_)));
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn runtime_from_bytes_rejects_icu_data_packs() {
+        let root = temp_dir();
+        write_fixture(&root, "hi", "identity", |bytes| bytes.to_vec());
+        write_icu_pack(&root);
+
+        let mut packs = BTreeMap::new();
+        packs.insert(
+            "en".to_string(),
+            fs::read(root.join("packs/en.mf2pack")).expect("pack"),
+        );
+        let err = Runtime::from_bytes(
+            &fs::read(root.join("manifest.json")).expect("manifest"),
+            ID_MAP_JSON.as_bytes(),
+            &packs,
+        )
+        .err()
+        .expect("error");
+        assert!(matches!(err, RuntimeError::InvalidManifest(_)));
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn mmap_runtime_formats_like_read_runtime() {
+        let encoders = [
+            (
+                "identity",
+                (|bytes: &[u8]| bytes.to_vec()) as fn(&[u8]) -> Vec<u8>,
+            ),
+            ("gzip", gzip_bytes),
+        ];
+        for (encoding, encode) in encoders {
+            let root = temp_dir();
+            write_fixture(&root, "hi", encoding, encode);
+            let manifest_path = root.join("manifest.json");
+            let id_map_path = root.join("id_map.json");
+
+            let read = Runtime::load_from_paths(&manifest_path, &id_map_path).expect("read");
+            let mapped = Runtime::load_from_paths_mmap(&manifest_path, &id_map_path).expect("mmap");
+            let args = Args::new();
+            assert_eq!(
+                mapped.format("en", "home.title", &args).expect("format"),
+                read.format("en", "home.title", &args).expect("format")
+            );
+
+            fs::write(root.join("packs/en.mf2pack"), b"MF2PACK\0").expect("truncate");
+            let err = Runtime::load_from_paths_mmap(&manifest_path, &id_map_path)
+                .err()
+                .expect("error");
+            assert!(matches!(err, RuntimeError::HashMismatch(_)));
+
+            fs::remove_dir_all(&root).ok();
+        }
+    }
+
+    #[test]
+    fn runtime_rejects_packs_longer_than_declared() {
+        let root = temp_dir();
+        write_fixture(&root, "hi", "identity", |bytes| bytes.to_vec());
+        let manifest_path = root.join("manifest.json");
+        let id_map_path = root.join("id_map.json");
+        let pack_path = root.join("packs/en.mf2pack");
+
+        let pack = fs::read(&pack_path).expect("pack");
+        let mut packs = BTreeMap::new();
+        packs.insert("en".to_string(), pack.clone());
+        let from_bytes = Runtime::from_bytes(
+            &fs::read(&manifest_path).expect("manifest"),
+            ID_MAP_JSON.as_bytes(),
+            &packs,
+        )
+        .expect("from bytes");
+        let from_paths = Runtime::load_from_paths(&manifest_path, &id_map_path).expect("paths");
         let args = Args::new();
+        assert_eq!(
+            from_paths
+                .format("en", "home.title", &args)
+                .expect("format"),
+            from_bytes
+                .format("en", "home.title", &args)
+                .expect("format")
+        );
+
+        let mut longer = pack;
+        longer.extend_from_slice(&[0; 128 * 1024]);
+        fs::write(&pack_path, &longer).expect("extend");
+        let err = Runtime::load_from_paths(&manifest_path, &id_map_path)
+            .err()
+            .expect("error");
+        assert!(matches!(err, RuntimeError::HashMismatch(_)));
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn has_key_checks_the_negotiated_catalog_chain() {
+        let id_map_json = r#"{"home.title": 0, "home.subtitle": 1}"#;
+        let id_map_hash = IdMap::from_json(id_map_json)
+            .expect("id map")
+            .hash()
+            .expect("hash");
+        let pack_bytes = pack_bytes(
+            id_map_hash,
+            "en-x-beta",
+            Some("en"),
+            [(0, text_program("hi"))],
+        );
+
+        let manifest = ManifestBuilder::new(id_map_hash)
+            .pack("en-x-beta", Some("en"), &pack_bytes)
+            .build();
+        let mut packs = BTreeMap::new();
+        packs.insert("en-x-beta".to_string(), pack_bytes);
+        let runtime = Runtime::from_bytes(
+            &serde_json::to_vec(&manifest).expect("json"),
+            id_map_json.as_bytes(),
+            &packs,
+        )
+        .expect("runtime");
+
+        let keys = runtime.message_keys();
+        assert_eq!(
+            keys.iter().collect::<Vec<_>>(),
+            vec!["home.subtitle", "home.title"]
+        );
+        assert!(runtime.has_key("en-x-beta", "home.title"));
+        assert!(!runtime.has_key("en-x-beta", "home.subtitle"));
+        assert!(!runtime.has_key("en-x-beta", "home.unknown"));
+        assert!(!runtime.has_key("en", "home.title"));
+    }
+
+    #[test]
+    fn message_source_reports_the_serving_pack() {
+        let id_map_json = r#"{"home.title": 0, "home.subtitle": 1}"#;
+        let id_map_hash = IdMap::from_json(id_map_json)
+            .expect("id map")
+            .hash()
+            .expect("hash");
+        let overlay = pack_bytes(
+            id_map_hash,
+            "en-x-beta",
+            Some("en"),
+            [(0, text_program("hiya"))],
+        );
+        let base = pack_bytes(id_map_hash, "en", None, [(1, text_program("welcome"))]);
+
+        let manifest = ManifestBuilder::new(id_map_hash)
+            .pack("en", None, &base)
+            .pack("en-x-beta", Some("en"), &overlay)
+            .build();
+        let mut packs = BTreeMap::new();
+        packs.insert("en".to_string(), base);
+        packs.insert("en-x-beta".to_string(), overlay);
+        let runtime = Runtime::from_bytes(
+            &serde_json::to_vec(&manifest).expect("json"),
+            id_map_json.as_bytes(),
+            &packs,
+        )
+        .expect("runtime");
+
+        assert_eq!(
+            runtime
+                .message_source("en-x-beta", "home.title")
+                .expect("overlay"),
+            "en-x-beta"
+        );
+        assert_eq!(
+            runtime
+                .message_source("en-x-beta", "home.subtitle")
+                .expect("base"),
+            "en"
+        );
+        assert!(matches!(
+            runtime.message_source("en", "home.title"),
+            Err(RuntimeError::MissingMessage(_))
+        ));
+    }
+
+    #[test]
+    fn missing_key_policies_apply_to_default_locale_only_keys() {
+        let id_map_json = r#"{"home.title": 0, "home.subtitle": 1}"#;
+        let id_map_hash = IdMap::from_json(id_map_json)
+            .expect("id map")
+            .hash()
+            .expect("hash");
+        let mut manifest = ManifestBuilder::new(id_map_hash);
+        let mut packs = BTreeMap::new();
+        for (locale, message_id, text) in [("en", 1, "Welcome"), ("fr", 0, "Salut")] {
+            let bytes = pack_bytes(
+                id_map_hash,
+                locale,
+                None,
+                [(message_id, text_program(text))],
+            );
+            manifest = manifest.pack(locale, None, &bytes);
+            packs.insert(locale.to_string(), bytes);
+        }
+        let manifest = manifest.build();
+        let runtime = Runtime::from_bytes(
+            &serde_json::to_vec(&manifest).expect("json"),
+            id_map_json.as_bytes(),
+            &packs,
+        )
+        .expect("runtime");
+        let args = Args::new();
+
+        assert!(matches!(
+            runtime.format("fr", "home.subtitle", &args),
+            Err(RuntimeError::MissingMessage(_))
+        ));
+
+        let echo = runtime
+            .clone()
+            .with_missing_key_policy(MissingKeyPolicy::EchoKey);
+        assert_eq!(
+            echo.format("fr", "home.subtitle", &args).expect("echo"),
+            "home.subtitle"
+        );
+
+        let fallback = runtime.with_missing_key_policy(MissingKeyPolicy::FallbackDefault);
+        let (output, negotiation) = fallback
+            .format_negotiated("fr", "home.subtitle", &args)
+            .expect("fallback");
+        assert_eq!(output, "Welcome");
+        assert_eq!(negotiation.selected.normalized(), "en");
+        assert_eq!(
+            fallback
+                .format_with_backend("fr", "home.subtitle", &args, &super::BasicFormatBackend)
+                .expect("backend fallback"),
+            "Welcome"
+        );
+        assert_eq!(
+            fallback.format("fr", "home.title", &args).expect("fr"),
+            "Salut"
+        );
+        assert_eq!(
+            fallback
+                .format_batch("fr", &[("home.subtitle", &args)])
+                .expect("batch fallback"),
+            vec!["Welcome".to_string()]
+        );
+        assert!(matches!(
+            fallback.format("fr", "home.unknown", &args),
+            Err(RuntimeError::MissingMessage(_))
+        ));
+
+        #[cfg(feature = "metrics")]
+        {
+            let metrics = fallback.metrics_snapshot();
+            assert_eq!(metrics.format_calls, 7);
+            assert_eq!(metrics.format_successes, 5);
+            assert_eq!(metrics.missing_messages, 2);
+            assert_eq!(metrics.negotiation_fallbacks, 3);
+        }
+    }
+
+    fn gzip_bytes(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(bytes).expect("gzip");
+        encoder.finish().expect("gzip")
+    }
+
+    fn brotli_bytes(bytes: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        {
+            let mut writer = brotli::CompressorWriter::new(&mut out, 4096, 9, 22);
+            writer.write_all(bytes).expect("br");
+        }
+        out
+    }
+
+    #[test]
+    fn runtime_loads_compressed_packs() {
+        let encoders = [
+            ("gzip", gzip_bytes as fn(&[u8]) -> Vec<u8>),
+            ("br", brotli_bytes),
+        ];
+        for (encoding, encode) in encoders {
+            let root = temp_dir();
+            write_fixture(&root, "hi", encoding, encode);
+
+            let runtime =
+                Runtime::load_from_paths(&root.join("manifest.json"), &root.join("id_map.json"))
+                    .expect("runtime");
+            let output = runtime
+                .format("en", "home.title", &Args::new())
+                .expect("format");
+            assert_eq!(output, "hi");
+
+            fs::remove_dir_all(&root).ok();
+        }
+    }
+
+    #[test]
+    fn runtime_rejects_unknown_encoding() {
+        let root = temp_dir();
+        write_fixture(&root, "hi", "zstd", |bytes| bytes.to_vec());
+
+        let err = Runtime::load_from_paths(&root.join("manifest.json"), &root.join("id_map.json"))
+            .err()
+            .expect("error");
+        assert!(matches!(err, RuntimeError::UnsupportedEncoding(_, _)));
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn runtime_rejects_pack_built_for_another_locale() {
+        let id_map_hash = IdMap::from_json(ID_MAP_JSON)
+            .expect("id map")
+            .hash()
+            .expect("hash");
+        let pack_bytes = pack_bytes(id_map_hash, "fr", None, [(0, text_program("salut"))]);
+
+        let manifest = ManifestBuilder::new(id_map_hash)
+            .pack("en", None, &pack_bytes)
+            .build();
+        let mut packs = BTreeMap::new();
+        packs.insert("en".to_string(), pack_bytes);
+
+        let err = Runtime::from_bytes(
+            &serde_json::to_vec(&manifest).expect("json"),
+            ID_MAP_JSON.as_bytes(),
+            &packs,
+        )
+        .err()
+        .expect("error");
+        assert!(matches!(
+            err,
+            RuntimeError::LocaleMismatch(ref locale, ref found)
+                if locale == "en" && found == "locale fr"
+        ));
+    }
+
+    fn write_icu_pack(root: &Path) {
+        let icu_bytes = icu_pack_bytes(
+            "en",
+            NumberSymbols {
+                decimal: ",".to_string(),
+                group: ".".to_string(),
+                minus: "-".to_string(),
+            },
+        );
+        fs::write(root.join("packs/en.icupack"), &icu_bytes).expect("write icu pack");
+        let id_map_hash = IdMap::from_json(ID_MAP_JSON)
+            .expect("id map")
+            .hash()
+            .expect("hash");
+        let pack_bytes = fs::read(root.join("packs/en.mf2pack")).expect("read pack");
+        let manifest = ManifestBuilder::new(id_map_hash)
+            .pack("en", None, &pack_bytes)
+            .icu_pack("en", &icu_bytes)
+            .build();
+        fs::write(
+            root.join("manifest.json"),
+            serde_json::to_string_pretty(&manifest).expect("json"),
+        )
+        .expect("write manifest");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn runtime_formats_dates_for_locales_with_icu_data() {
+        let root = temp_dir();
+        write_program_fixture(
+            &root,
+            mf2_i18n_test_support::arg_program(
+                "Due ",
+                "at",
+                Some(mf2_i18n_core::FormatterId::Date),
+            ),
+            "identity",
+            |bytes| bytes.to_vec(),
+        );
+        write_icu_pack(&root);
+
+        let runtime =
+            Runtime::load_from_paths(&root.join("manifest.json"), &root.join("id_map.json"))
+                .expect("runtime");
+        assert!(runtime.icu_data("en").is_some());
+        let mut args = Args::new();
+        args.with_datetime("at", 1_700_000_000_000);
         let output = runtime.format("en", "home.title", &args).expect("format");
+        assert_eq!(output, "Due Nov 14, 2023");
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn runtime_loads_icu_data_packs() {
+        let root = temp_dir();
+        write_fixture(&root, "hi", "identity", |bytes| bytes.to_vec());
+
+        write_icu_pack(&root);
+        let manifest_path = root.join("manifest.json");
+        let runtime =
+            Runtime::load_from_paths(&manifest_path, &root.join("id_map.json")).expect("runtime");
+        let data = runtime.icu_data("en").expect("icu data");
+        let backend = IcuFormatBackend::new(&data);
+        assert_eq!(backend.plural_category(1.0), Ok(PluralCategory::One));
+        assert_eq!(backend.plural_category(2.0), Ok(PluralCategory::Other));
+        assert_eq!(backend.format_number(-1.5, &[]).expect("number"), "-1,5");
+        let output = runtime
+            .format("en", "home.title", &Args::new())
+            .expect("format");
         assert_eq!(output, "hi");
 
         fs::remove_dir_all(&root).ok();
     }
+
+    #[test]
+    fn icu_backend_places_currency_for_the_pack_locale() {
+        let symbols = |decimal: &str, group: &str| NumberSymbols {
+            decimal: decimal.to_string(),
+            group: group.to_string(),
+            minus: "-".to_string(),
+        };
+        let de = IcuDataPack::decode(&icu_pack_bytes("de", symbols(",", "."))).expect("de");
+        assert_eq!(
+            IcuFormatBackend::new(&de)
+                .format_currency(1.0, *b"EUR", &[])
+                .expect("currency"),
+            "1,00\u{a0}€"
+        );
+        let en = IcuDataPack::decode(&icu_pack_bytes("en", symbols(".", ","))).expect("en");
+        assert_eq!(
+            IcuFormatBackend::new(&en)
+                .format_currency(1.0, *b"EUR", &[])
+                .expect("currency"),
+            "€1.00"
+        );
+    }
+
+    #[test]
+    fn runtime_formats_from_multiple_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Runtime>();
+
+        let root = temp_dir();
+        write_fixture(&root, "hi", "identity", |bytes| bytes.to_vec());
+        let runtime =
+            Runtime::load_from_paths(&root.join("manifest.json"), &root.join("id_map.json"))
+                .expect("runtime");
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let runtime = runtime.clone();
+                std::thread::spawn(move || {
+                    (0..50)
+                        .map(|_| {
+                            runtime
+                                .format("en", "home.title", &Args::new())
+                                .expect("format")
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        for handle in handles {
+            let outputs = handle.join().expect("thread");
+            assert!(outputs.iter().all(|output| output == "hi"));
+        }
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn metrics_count_concurrent_formats() {
+        let root = temp_dir();
+        write_fixture(&root, "hi", "identity", |bytes| bytes.to_vec());
+        let runtime =
+            Runtime::load_from_paths(&root.join("manifest.json"), &root.join("id_map.json"))
+                .expect("runtime");
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let runtime = runtime.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..50 {
+                        runtime
+                            .format("en", "home.title", &Args::new())
+                            .expect("format");
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().expect("thread");
+        }
+        runtime
+            .format("en-GB", "home.title", &Args::new())
+            .expect("fallback");
+        assert!(runtime.format("en", "home.missing", &Args::new()).is_err());
+
+        let metrics = runtime.metrics_snapshot();
+        assert_eq!(metrics.format_calls, 202);
+        assert_eq!(metrics.format_successes, 201);
+        assert_eq!(metrics.missing_messages, 1);
+        assert_eq!(metrics.negotiation_fallbacks, 1);
+        assert_eq!(metrics.locale_hits.get("en"), Some(&201));
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn runtime_reloads_new_release() {
+        let root = temp_dir();
+        write_fixture(&root, "hi", "identity", |bytes| bytes.to_vec());
+        let manifest_path = root.join("manifest.json");
+        let id_map_path = root.join("id_map.json");
+        let runtime = Runtime::load_from_paths(&manifest_path, &id_map_path).expect("runtime");
+        let handle = runtime.clone();
+
+        let next = temp_dir();
+        write_fixture(&next, "hey", "identity", |bytes| bytes.to_vec());
+        runtime
+            .reload_from_paths(&next.join("manifest.json"), &next.join("id_map.json"))
+            .expect("reload");
+        let output = handle
+            .format("en", "home.title", &Args::new())
+            .expect("format");
+        assert_eq!(output, "hey");
+
+        fs::remove_dir_all(&root).ok();
+        fs::remove_dir_all(&next).ok();
+    }
+
+    #[test]
+    fn prepared_message_renders_until_reload() {
+        let root = temp_dir();
+        write_fixture(&root, "hi", "identity", |bytes| bytes.to_vec());
+        let runtime =
+            Runtime::load_from_paths(&root.join("manifest.json"), &root.join("id_map.json"))
+                .expect("runtime");
+        let args = Args::new();
+
+        let prepared = runtime.prepare("en-GB", "home.title").expect("prepare");
+        assert_eq!(prepared.locale(), "en");
+        for _ in 0..1000 {
+            assert_eq!(
+                prepared.render(&args, &BasicFormatBackend).expect("render"),
+                runtime
+                    .format("en-GB", "home.title", &args)
+                    .expect("format")
+            );
+        }
+        assert!(matches!(
+            runtime.prepare("en", "home.missing"),
+            Err(RuntimeError::MissingMessage(_))
+        ));
+
+        let next = temp_dir();
+        write_fixture(&next, "hey", "identity", |bytes| bytes.to_vec());
+        runtime
+            .reload_from_paths(&next.join("manifest.json"), &next.join("id_map.json"))
+            .expect("reload");
+        assert!(prepared.is_stale());
+        assert!(matches!(
+            prepared.render(&args, &BasicFormatBackend),
+            Err(RuntimeError::StalePrepared(key)) if key == "home.title"
+        ));
+        let prepared = runtime.prepare("en", "home.title").expect("prepare");
+        assert_eq!(
+            prepared.render(&args, &BasicFormatBackend).expect("render"),
+            "hey"
+        );
+
+        fs::remove_dir_all(&root).ok();
+        fs::remove_dir_all(&next).ok();
+    }
+
+    #[test]
+    fn failed_reload_keeps_previous_release() {
+        let root = temp_dir();
+        write_fixture(&root, "hi", "identity", |bytes| bytes.to_vec());
+        let runtime =
+            Runtime::load_from_paths(&root.join("manifest.json"), &root.join("id_map.json"))
+                .expect("runtime");
+
+        let next = temp_dir();
+        write_fixture(&next, "hey", "identity", |bytes| bytes.to_vec());
+        fs::write(next.join("packs/en.mf2pack"), b"corrupt").expect("corrupt pack");
+        assert!(
+            runtime
+                .reload_from_paths(&next.join("manifest.json"), &next.join("id_map.json"))
+                .is_err()
+        );
+        let output = runtime
+            .format("en", "home.title", &Args::new())
+            .expect("format");
+        assert_eq!(output, "hi");
+
+        fs::remove_dir_all(&root).ok();
+        fs::remove_dir_all(&next).ok();
+    }
+
+    #[test]
+    fn runtime_reports_negotiated_locale() {
+        let root = temp_dir();
+        write_fixture(&root, "hi", "identity", |bytes| bytes.to_vec());
+        let runtime =
+            Runtime::load_from_paths(&root.join("manifest.json"), &root.join("id_map.json"))
+                .expect("runtime");
+
+        let (output, negotiation) = runtime
+            .format_negotiated("en-GB", "home.title", &Args::new())
+            .expect("format");
+        assert_eq!(output, "hi");
+        assert_eq!(negotiation.selected.normalized(), "en");
+        assert_eq!(negotiation.requested.normalized(), "en-GB");
+        assert!(negotiation.trace.is_none());
+
+        let (_, negotiation) = runtime
+            .format_negotiated_with_trace("en-GB", "home.title", &Args::new())
+            .expect("format");
+        let trace = negotiation.trace.expect("trace");
+        assert_eq!(trace.attempts, vec!["en-GB", "en"]);
+
+        fs::remove_dir_all(&root).ok();
+    }
 }
@@ -1,86 +1,224 @@
-use ed25519_dalek::{Signature, VerifyingKey};
+use std::collections::BTreeMap;
+
+use ed25519_dalek::Signature;
+use p256::ecdsa::signature::Verifier;
 
 use crate::error::{RuntimeError, RuntimeResult};
 use crate::manifest::Manifest;
 
+#[derive(Debug, Clone)]
+pub enum ManifestKey {
+    Ed25519(ed25519_dalek::VerifyingKey),
+    EcdsaP256(p256::ecdsa::VerifyingKey),
+}
+
+impl From<ed25519_dalek::VerifyingKey> for ManifestKey {
+    fn from(key: ed25519_dalek::VerifyingKey) -> Self {
+        Self::Ed25519(key)
+    }
+}
+
+impl From<p256::ecdsa::VerifyingKey> for ManifestKey {
+    fn from(key: p256::ecdsa::VerifyingKey) -> Self {
+        Self::EcdsaP256(key)
+    }
+}
+
 pub fn verify_manifest_signature(
     manifest: &Manifest,
-    key_id: &str,
-    verifying_key: &VerifyingKey,
+    keyring: &BTreeMap<String, ManifestKey>,
 ) -> RuntimeResult<()> {
-    let signing = match &manifest.signing {
-        Some(signing) => signing,
+    let signatures = match &manifest.signing {
+        Some(signing) => signing.as_slice(),
         None => return Ok(()),
     };
-    if signing.key_id != key_id {
-        return Err(RuntimeError::InvalidManifest("key id mismatch".to_string()));
+    let bytes = manifest.to_signing_bytes()?;
+    let mut trusted = false;
+    for signing in signatures {
+        if !matches!(signing.sig_alg.as_str(), "ed25519" | "ecdsa-p256") {
+            continue;
+        }
+        let Some(key) = keyring.get(&signing.key_id) else {
+            continue;
+        };
+        trusted = true;
+        let Some(signature) = decode_signature(&signing.manifest_sig) else {
+            continue;
+        };
+        let verified = match (signing.sig_alg.as_str(), key) {
+            ("ed25519", ManifestKey::Ed25519(key)) => Signature::from_slice(&signature)
+                .is_ok_and(|signature| key.verify_strict(&bytes, &signature).is_ok()),
+            ("ecdsa-p256", ManifestKey::EcdsaP256(key)) => {
+                p256::ecdsa::Signature::from_slice(&signature)
+                    .is_ok_and(|signature| key.verify(&bytes, &signature).is_ok())
+            }
+            _ => false,
+        };
+        if verified {
+            return Ok(());
+        }
     }
-    if signing.sig_alg != "ed25519" {
-        return Err(RuntimeError::InvalidManifest(
-            "unsupported signature".to_string(),
-        ));
+    if trusted {
+        Err(RuntimeError::SignatureFailed)
+    } else {
+        Err(RuntimeError::InvalidManifest(
+            "no signature from a trusted key".to_string(),
+        ))
     }
-    let signature = parse_signature(&signing.manifest_sig)?;
-    let bytes = manifest.to_signing_bytes()?;
-    verifying_key
-        .verify_strict(&bytes, &signature)
-        .map_err(|_| RuntimeError::SignatureFailed)
 }
 
-fn parse_signature(value: &str) -> RuntimeResult<Signature> {
+fn decode_signature(value: &str) -> Option<Vec<u8>> {
     let trimmed = value.trim();
     let hex = trimmed.strip_prefix("hex:").unwrap_or(trimmed);
-    let bytes = hex::decode(hex)
-        .map_err(|_| RuntimeError::InvalidManifest("invalid signature".to_string()))?;
-    Signature::from_slice(&bytes)
-        .map_err(|_| RuntimeError::InvalidManifest("invalid signature".to_string()))
+    hex::decode(hex).ok()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::verify_manifest_signature;
-    use crate::manifest::{Manifest, ManifestSigning, PackEntry};
+    use super::{ManifestKey, verify_manifest_signature};
+    use crate::error::RuntimeError;
+    use crate::manifest::{Manifest, ManifestSignatures, ManifestSigning};
     use ed25519_dalek::{Signer, SigningKey};
+    use mf2_i18n_test_support::ManifestBuilder;
     use std::collections::BTreeMap;
 
+    fn sample_manifest() -> Manifest {
+        let manifest = ManifestBuilder::new([0; 32])
+            .pack("en", None, b"pack")
+            .build();
+        serde_json::from_value(manifest).expect("manifest")
+    }
+
+    fn sign(manifest: &Manifest, key: &SigningKey, key_id: &str) -> ManifestSigning {
+        let signature = key.sign(&manifest.to_signing_bytes().expect("bytes"));
+        ManifestSigning {
+            sig_alg: "ed25519".to_string(),
+            key_id: key_id.to_string(),
+            manifest_sig: format!("hex:{}", hex::encode(signature.to_bytes())),
+        }
+    }
+
     #[test]
     fn verifies_manifest_signature() {
         let signing_key = SigningKey::from_bytes(&[9u8; 32]);
-        let verifying_key = signing_key.verifying_key();
-        let mut mf2_packs = BTreeMap::new();
-        mf2_packs.insert(
-            "en".to_string(),
-            PackEntry {
-                kind: "base".to_string(),
-                url: "packs/en.mf2pack".to_string(),
-                hash: "sha256:abc".to_string(),
-                size: 12,
-                content_encoding: "identity".to_string(),
-                pack_schema: 0,
-                parent: None,
-            },
-        );
-        let mut manifest = Manifest {
-            schema: 1,
-            release_id: "r1".to_string(),
-            generated_at: "2026-02-01T00:00:00Z".to_string(),
-            default_locale: "en".to_string(),
-            supported_locales: vec!["en".to_string()],
-            id_map_hash: "sha256:dead".to_string(),
-            mf2_packs,
-            icu_packs: None,
-            micro_locales: None,
-            budgets: None,
-            signing: None,
-        };
+        let mut manifest = sample_manifest();
+        manifest.signing = Some(ManifestSignatures::Single(sign(
+            &manifest,
+            &signing_key,
+            "key-1",
+        )));
+        let json = serde_json::to_string(&manifest).expect("json");
+        assert!(json.contains("\"signing\":{"));
+        let manifest: Manifest = serde_json::from_str(&json).expect("parse");
 
-        let signature = signing_key.sign(&manifest.to_signing_bytes().expect("bytes"));
-        manifest.signing = Some(ManifestSigning {
-            sig_alg: "ed25519".to_string(),
+        let keyring = BTreeMap::from([("key-1".to_string(), signing_key.verifying_key().into())]);
+        verify_manifest_signature(&manifest, &keyring).expect("verify");
+    }
+
+    #[test]
+    fn verifies_any_signature_against_a_keyring() {
+        let old_key = SigningKey::from_bytes(&[1u8; 32]);
+        let new_key = SigningKey::from_bytes(&[2u8; 32]);
+        let mut manifest = sample_manifest();
+        manifest.signing = Some(ManifestSignatures::Multiple(vec![
+            sign(&manifest, &old_key, "old"),
+            sign(&manifest, &new_key, "new"),
+        ]));
+        let manifest: Manifest =
+            serde_json::from_slice(&serde_json::to_vec(&manifest).expect("json")).expect("parse");
+
+        let both = BTreeMap::from([
+            ("old".to_string(), old_key.verifying_key().into()),
+            ("new".to_string(), new_key.verifying_key().into()),
+        ]);
+        verify_manifest_signature(&manifest, &both).expect("both");
+        let rotated = BTreeMap::from([("new".to_string(), new_key.verifying_key().into())]);
+        verify_manifest_signature(&manifest, &rotated).expect("new only");
+
+        let untrusted = BTreeMap::from([(
+            "other".to_string(),
+            SigningKey::from_bytes(&[3u8; 32]).verifying_key().into(),
+        )]);
+        assert!(matches!(
+            verify_manifest_signature(&manifest, &untrusted),
+            Err(RuntimeError::InvalidManifest(_))
+        ));
+        let wrong = BTreeMap::from([("new".to_string(), old_key.verifying_key().into())]);
+        assert!(matches!(
+            verify_manifest_signature(&manifest, &wrong),
+            Err(RuntimeError::SignatureFailed)
+        ));
+    }
+
+    #[test]
+    fn skips_signatures_with_unknown_algorithms() {
+        let signing_key = SigningKey::from_bytes(&[4u8; 32]);
+        let mut manifest = sample_manifest();
+        let future = ManifestSigning {
+            sig_alg: "ml-dsa-65".to_string(),
             key_id: "key-1".to_string(),
+            manifest_sig: "hex:00".to_string(),
+        };
+        let garbled = ManifestSigning {
+            manifest_sig: "hex:zz".to_string(),
+            ..sign(&manifest, &signing_key, "key-1")
+        };
+        let valid = sign(&manifest, &signing_key, "key-1");
+        manifest.signing = Some(ManifestSignatures::Multiple(vec![
+            future.clone(),
+            garbled.clone(),
+            valid,
+        ]));
+        let keyring = BTreeMap::from([("key-1".to_string(), signing_key.verifying_key().into())]);
+        verify_manifest_signature(&manifest, &keyring).expect("verify");
+
+        manifest.signing = Some(ManifestSignatures::Multiple(vec![future.clone()]));
+        assert!(matches!(
+            verify_manifest_signature(&manifest, &keyring),
+            Err(RuntimeError::InvalidManifest(_))
+        ));
+        manifest.signing = Some(ManifestSignatures::Multiple(vec![future, garbled]));
+        assert!(matches!(
+            verify_manifest_signature(&manifest, &keyring),
+            Err(RuntimeError::SignatureFailed)
+        ));
+    }
+
+    #[test]
+    fn verifies_ecdsa_p256_signatures() {
+        use p256::ecdsa::signature::Signer as _;
+
+        let signing_key = p256::ecdsa::SigningKey::from_slice(&[7u8; 32]).expect("key");
+        let mut manifest = sample_manifest();
+        let signature: p256::ecdsa::Signature =
+            signing_key.sign(&manifest.to_signing_bytes().expect("bytes"));
+        manifest.signing = Some(ManifestSignatures::Single(ManifestSigning {
+            sig_alg: "ecdsa-p256".to_string(),
+            key_id: "pki".to_string(),
             manifest_sig: format!("hex:{}", hex::encode(signature.to_bytes())),
-        });
+        }));
+
+        let keyring = BTreeMap::from([(
+            "pki".to_string(),
+            ManifestKey::from(*signing_key.verifying_key()),
+        )]);
+        verify_manifest_signature(&manifest, &keyring).expect("verify");
 
-        verify_manifest_signature(&manifest, "key-1", &verifying_key).expect("verify");
+        let mismatched = BTreeMap::from([(
+            "pki".to_string(),
+            ManifestKey::from(SigningKey::from_bytes(&[7u8; 32]).verifying_key()),
+        )]);
+        assert!(matches!(
+            verify_manifest_signature(&manifest, &mismatched),
+            Err(RuntimeError::SignatureFailed)
+        ));
+
+        if let Some(ManifestSignatures::Single(signing)) = &mut manifest.signing {
+            signing.sig_alg = "rsa".to_string();
+        }
+        assert!(matches!(
+            verify_manifest_signature(&manifest, &keyring),
+            Err(RuntimeError::InvalidManifest(_))
+        ));
     }
 }
@@ -0,0 +1,184 @@
+use mf2_i18n_runtime::{Runtime, RuntimeError, args_from_json};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FormatResponse {
+    Ok {
+        body: String,
+        content_language: String,
+    },
+    MissingKey(String),
+    BadArgs(String),
+    Error(String),
+}
+
+impl FormatResponse {
+    pub fn status(&self) -> u16 {
+        match self {
+            Self::Ok { .. } => 200,
+            Self::BadArgs(_) => 400,
+            Self::MissingKey(_) => 404,
+            Self::Error(_) => 500,
+        }
+    }
+
+    pub fn body(&self) -> String {
+        match self {
+            Self::Ok { body, .. } => body.clone(),
+            Self::MissingKey(key) => format!("missing message key {key}"),
+            Self::BadArgs(message) | Self::Error(message) => message.clone(),
+        }
+    }
+
+    pub fn content_language(&self) -> Option<&str> {
+        match self {
+            Self::Ok {
+                content_language, ..
+            } => Some(content_language),
+            _ => None,
+        }
+    }
+}
+
+pub fn format_request(
+    runtime: &Runtime,
+    accept_language: &str,
+    key: &str,
+    args_json: &serde_json::Value,
+) -> FormatResponse {
+    let negotiation = runtime.negotiate_accept_language(accept_language);
+    let locale = negotiation.selected.normalized();
+    let spec = match runtime.message_arg_specs(locale, key) {
+        Ok(spec) => spec,
+        Err(err) => return error_response(err),
+    };
+    let args = match args_from_json(args_json, &spec) {
+        Ok(args) => args,
+        Err(err) => return error_response(err),
+    };
+    match runtime.format(locale, key, &args) {
+        Ok(body) => FormatResponse::Ok {
+            body,
+            content_language: locale.to_string(),
+        },
+        Err(err) => error_response(err),
+    }
+}
+
+fn error_response(err: RuntimeError) -> FormatResponse {
+    match err {
+        RuntimeError::MissingMessage(key) => FormatResponse::MissingKey(key),
+        RuntimeError::InvalidArgument(..) => FormatResponse::BadArgs(err.to_string()),
+        other => FormatResponse::Error(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FormatResponse, format_request};
+    use mf2_i18n_core::FormatterId;
+    use mf2_i18n_runtime::{IdMap, Runtime};
+    use mf2_i18n_test_support::{ManifestBuilder, arg_program, pack_bytes};
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    const ID_MAP_JSON: &str = r#"{"home.greeting": 0, "home.total": 1, "home.updated": 2}"#;
+
+    fn runtime() -> Runtime {
+        let id_map_hash = IdMap::from_json(ID_MAP_JSON)
+            .expect("id map")
+            .hash()
+            .expect("hash");
+        let pack_bytes = pack_bytes(
+            id_map_hash,
+            "en",
+            None,
+            [
+                (0, arg_program("Hi ", "name", None)),
+                (
+                    1,
+                    arg_program("Total ", "total", Some(FormatterId::Currency)),
+                ),
+                (
+                    2,
+                    arg_program("Updated ", "at", Some(FormatterId::DateTime)),
+                ),
+            ],
+        );
+        let manifest = ManifestBuilder::new(id_map_hash)
+            .pack("en", None, &pack_bytes)
+            .build();
+        let mut packs = BTreeMap::new();
+        packs.insert("en".to_string(), pack_bytes);
+        Runtime::from_bytes(
+            manifest.to_string().as_bytes(),
+            ID_MAP_JSON.as_bytes(),
+            &packs,
+        )
+        .expect("runtime")
+    }
+
+    #[test]
+    fn formats_with_negotiated_content_language() {
+        let response = format_request(
+            &runtime(),
+            "fr-CA, en-GB;q=0.8",
+            "home.greeting",
+            &json!({ "name": "Ada" }),
+        );
+        assert_eq!(
+            response,
+            FormatResponse::Ok {
+                body: "Hi Ada".to_string(),
+                content_language: "en".to_string(),
+            }
+        );
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.content_language(), Some("en"));
+    }
+
+    #[test]
+    fn reports_missing_key() {
+        let response = format_request(&runtime(), "en", "home.missing", &json!({}));
+        assert_eq!(
+            response,
+            FormatResponse::MissingKey("home.missing".to_string())
+        );
+        assert_eq!(response.status(), 404);
+    }
+
+    #[test]
+    fn reports_bad_args() {
+        let runtime = runtime();
+        let response = format_request(&runtime, "en", "home.greeting", &json!({ "name": [1] }));
+        assert_eq!(response.status(), 400);
+        assert!(response.body().contains("invalid argument name"));
+
+        let response = format_request(&runtime, "en", "home.greeting", &json!({}));
+        assert!(matches!(response, FormatResponse::BadArgs(_)));
+    }
+
+    #[test]
+    fn coerces_args_to_the_message_arg_types() {
+        let runtime = runtime();
+        let response = format_request(
+            &runtime,
+            "en",
+            "home.total",
+            &json!({ "total": { "value": 12.5, "code": "usd" } }),
+        );
+        assert_eq!(response.body(), "Total $12.50");
+
+        let response = format_request(
+            &runtime,
+            "en",
+            "home.updated",
+            &json!({ "at": "2026-02-01T12:30:00Z" }),
+        );
+        assert_eq!(response.status(), 200);
+        assert!(response.body().starts_with("Updated "));
+        assert!(!response.body().contains("2026-02-01T12:30:00Z"));
+
+        let response = format_request(&runtime, "en", "home.total", &json!({ "total": 12.5 }));
+        assert_eq!(response.status(), 400);
+    }
+}
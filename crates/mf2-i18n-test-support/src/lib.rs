@@ -0,0 +1,155 @@
+#![forbid(unsafe_code)]
+
+use std::collections::BTreeMap;
+
+use mf2_i18n_core::{
+    BytecodeProgram, FormatterId, IcuDataBuildInput, MessageId, NumberSymbols, Opcode,
+    PackBuildInput, PackKind, PluralCategory, PluralOperand, PluralRelation, PluralRule,
+    PluralRules, encode_icu_data_pack, encode_pack,
+};
+use serde_json::{Map, Value, json};
+use sha2::{Digest, Sha256};
+
+pub fn text_program(text: &str) -> BytecodeProgram {
+    let mut program = BytecodeProgram::new();
+    let sidx = program.string_pool.push(text);
+    program.opcodes = vec![Opcode::EmitText { sidx }, Opcode::End];
+    program
+}
+
+pub fn arg_program(prefix: &str, arg: &str, formatter: Option<FormatterId>) -> BytecodeProgram {
+    let mut program = BytecodeProgram::new();
+    let sidx = program.string_pool.push(prefix);
+    let aidx = program.push_arg_name(arg);
+    program.opcodes = vec![Opcode::EmitText { sidx }, Opcode::PushArg { aidx }];
+    if let Some(fid) = formatter {
+        program.opcodes.push(Opcode::CallFmt { fid, opt_count: 0 });
+    }
+    program.opcodes.extend([Opcode::EmitStack, Opcode::End]);
+    program
+}
+
+pub fn pack_bytes(
+    id_map_hash: [u8; 32],
+    locale: &str,
+    parent: Option<&str>,
+    messages: impl IntoIterator<Item = (u64, BytecodeProgram)>,
+) -> Vec<u8> {
+    let messages: BTreeMap<MessageId, BytecodeProgram> = messages
+        .into_iter()
+        .map(|(id, program)| (MessageId::new(id), program))
+        .collect();
+    encode_pack(&PackBuildInput {
+        pack_kind: PackKind::Base,
+        id_map_hash,
+        locale_tag: locale.to_string(),
+        parent_tag: parent.map(str::to_string),
+        build_epoch_ms: 0,
+        messages,
+        sort_strings: false,
+    })
+    .expect("encode pack")
+}
+
+pub fn icu_pack_bytes(locale: &str, symbols: NumberSymbols) -> Vec<u8> {
+    let relation = |operand, value| PluralRelation {
+        operand,
+        modulus: None,
+        negate: false,
+        ranges: vec![(value, value)],
+    };
+    let one = PluralRule {
+        category: PluralCategory::One,
+        conditions: vec![vec![
+            relation(PluralOperand::I, 1),
+            relation(PluralOperand::V, 0),
+        ]],
+    };
+    encode_icu_data_pack(&IcuDataBuildInput {
+        locale_tag: locale.to_string(),
+        build_epoch_ms: 0,
+        cardinal: PluralRules { rules: vec![one] },
+        ordinal: PluralRules::default(),
+        symbols,
+    })
+}
+
+pub struct ManifestBuilder {
+    id_map_hash: [u8; 32],
+    supported_locales: Vec<String>,
+    mf2_packs: Map<String, Value>,
+    icu_packs: Map<String, Value>,
+}
+
+impl ManifestBuilder {
+    pub fn new(id_map_hash: [u8; 32]) -> Self {
+        Self {
+            id_map_hash,
+            supported_locales: vec!["en".to_string()],
+            mf2_packs: Map::new(),
+            icu_packs: Map::new(),
+        }
+    }
+
+    pub fn supported_locales(mut self, locales: &[&str]) -> Self {
+        self.supported_locales = locales.iter().map(|locale| locale.to_string()).collect();
+        self
+    }
+
+    pub fn pack(self, locale: &str, parent: Option<&str>, bytes: &[u8]) -> Self {
+        self.encoded_pack(locale, parent, "identity", bytes)
+    }
+
+    pub fn encoded_pack(
+        mut self,
+        locale: &str,
+        parent: Option<&str>,
+        content_encoding: &str,
+        bytes: &[u8],
+    ) -> Self {
+        let kind = if parent.is_some() { "overlay" } else { "base" };
+        let mut entry = pack_entry(kind, &format!("packs/{locale}.mf2pack"), bytes);
+        entry["content_encoding"] = json!(content_encoding);
+        if let Some(parent) = parent {
+            entry["parent"] = json!(parent);
+        }
+        self.mf2_packs.insert(locale.to_string(), entry);
+        if !self.supported_locales.iter().any(|known| known == locale) {
+            self.supported_locales.push(locale.to_string());
+        }
+        self
+    }
+
+    pub fn icu_pack(mut self, locale: &str, bytes: &[u8]) -> Self {
+        let entry = pack_entry("icu_data", &format!("packs/{locale}.icupack"), bytes);
+        self.icu_packs.insert(locale.to_string(), entry);
+        self
+    }
+
+    pub fn build(self) -> Value {
+        let mut manifest = json!({
+            "schema": 1,
+            "release_id": "r1",
+            "generated_at": "2026-02-01T00:00:00Z",
+            "default_locale": "en",
+            "supported_locales": self.supported_locales,
+            "id_map_hash": format!("sha256:{}", hex::encode(self.id_map_hash)),
+            "mf2_packs": self.mf2_packs,
+        });
+        if !self.icu_packs.is_empty() {
+            manifest["icu_packs"] = Value::Object(self.icu_packs);
+        }
+        manifest
+    }
+}
+
+fn pack_entry(kind: &str, url: &str, bytes: &[u8]) -> Value {
+    json!({
+        "kind": kind,
+        "url": url,
+        "hash": format!("sha256:{}", hex::encode(Sha256::digest(bytes))),
+        "size": bytes.len(),
+        "content_encoding": "identity",
+        "pack_schema": 0,
+    })
+}
@@ -0,0 +1,53 @@
+use std::collections::BTreeMap;
+
+use js_sys::{Array, JSON, Object, Uint8Array};
+use mf2_i18n_runtime::{Runtime, args_from_json};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub struct WasmRuntime {
+    runtime: Runtime,
+}
+
+#[wasm_bindgen]
+impl WasmRuntime {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        manifest_bytes: &[u8],
+        id_map_bytes: &[u8],
+        pack_bytes_by_locale: &Object,
+    ) -> Result<WasmRuntime, JsError> {
+        let mut packs = BTreeMap::new();
+        for entry in Object::entries(pack_bytes_by_locale).iter() {
+            let entry = Array::from(&entry);
+            let locale = entry
+                .get(0)
+                .as_string()
+                .ok_or_else(|| JsError::new("pack locale must be a string"))?;
+            packs.insert(locale, Uint8Array::new(&entry.get(1)).to_vec());
+        }
+        let runtime = Runtime::from_bytes(manifest_bytes, id_map_bytes, &packs)
+            .map_err(|err| JsError::new(&err.to_string()))?;
+        Ok(Self { runtime })
+    }
+
+    pub fn format(&self, locale: &str, key: &str, args: JsValue) -> Result<String, JsError> {
+        let args_json = if args.is_undefined() || args.is_null() {
+            serde_json::Value::Null
+        } else {
+            let text = JSON::stringify(&args)
+                .map_err(|_| JsError::new("args must be JSON-serializable"))?;
+            serde_json::from_str(&String::from(text))
+                .map_err(|err| JsError::new(&err.to_string()))?
+        };
+        let spec = self
+            .runtime
+            .message_arg_specs(locale, key)
+            .map_err(|err| JsError::new(&err.to_string()))?;
+        let args =
+            args_from_json(&args_json, &spec).map_err(|err| JsError::new(&err.to_string()))?;
+        self.runtime
+            .format(locale, key, &args)
+            .map_err(|err| JsError::new(&err.to_string()))
+    }
+}
@@ -1,6 +1,97 @@
 #![forbid(unsafe_code)]
 
+#[cfg(feature = "wasm-bindgen")]
+mod bindings;
+
+#[cfg(feature = "wasm-bindgen")]
+pub use crate::bindings::WasmRuntime;
+pub use mf2_i18n_core::Args;
 pub use mf2_i18n_runtime::{
-    BasicFormatBackend, IdMap, Manifest, ManifestSigning, PackEntry, Runtime, RuntimeError,
-    RuntimeResult, load_id_map, load_manifest, parse_sha256, verify_manifest_signature,
+    BasicFormatBackend, IdMap, Manifest, ManifestKey, ManifestSignatures, ManifestSigning,
+    MissingKeyPolicy, PackEntry, PreparedMessage, Runtime, RuntimeError, RuntimeResult,
+    load_id_map, load_manifest, parse_sha256, verify_manifest_signature,
 };
+
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TracedFormat {
+    pub text: String,
+    pub locale: String,
+    pub attempts: Vec<String>,
+}
+
+pub fn format_with_trace(
+    runtime: &Runtime,
+    locale: &str,
+    key: &str,
+    args: &Args,
+) -> RuntimeResult<TracedFormat> {
+    let (text, negotiation) = runtime.format_negotiated_with_trace(locale, key, args)?;
+    Ok(TracedFormat {
+        text,
+        locale: negotiation.selected.normalized().to_string(),
+        attempts: negotiation
+            .trace
+            .map(|trace| trace.attempts)
+            .unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Args, IdMap, Runtime, format_with_trace};
+    use mf2_i18n_test_support::{ManifestBuilder, pack_bytes, text_program};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    const ID_MAP_JSON: &str = r#"{"home.title": 0}"#;
+
+    fn temp_dir() -> PathBuf {
+        let mut path = std::env::temp_dir();
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        path.push(format!("mf2_i18n_wasm_{nanos}"));
+        fs::create_dir_all(&path).expect("dir");
+        path
+    }
+
+    fn write_fixture(root: &Path, text: &str) {
+        let id_map_hash = IdMap::from_json(ID_MAP_JSON)
+            .expect("id map")
+            .hash()
+            .expect("hash");
+        let pack_bytes = pack_bytes(id_map_hash, "en", None, [(0, text_program(text))]);
+        fs::create_dir_all(root.join("packs")).expect("packs");
+        fs::write(root.join("packs/en.mf2pack"), &pack_bytes).expect("write pack");
+        let manifest = ManifestBuilder::new(id_map_hash)
+            .pack("en", None, &pack_bytes)
+            .build();
+        fs::write(root.join("manifest.json"), manifest.to_string()).expect("write manifest");
+        fs::write(root.join("id_map.json"), ID_MAP_JSON).expect("write id map");
+    }
+
+    #[test]
+    fn format_with_trace_serializes_attempts() {
+        let root = temp_dir();
+        write_fixture(&root, "hi");
+        let runtime =
+            Runtime::load_from_paths(&root.join("manifest.json"), &root.join("id_map.json"))
+                .expect("runtime");
+
+        let traced =
+            format_with_trace(&runtime, "en-GB", "home.title", &Args::new()).expect("format");
+        assert_eq!(traced.text, "hi");
+        assert_eq!(traced.locale, "en");
+        assert_eq!(traced.attempts, vec!["en-GB", "en"]);
+
+        let json = serde_json::to_value(&traced).expect("json");
+        assert_eq!(json["attempts"], serde_json::json!(["en-GB", "en"]));
+        assert_eq!(json["text"], "hi");
+
+        fs::remove_dir_all(&root).ok();
+    }
+}